@@ -0,0 +1,160 @@
+//! Content addressing primitives
+//!
+//! [`compute_cid`] is the single place content bytes become a CIDv1. Its
+//! output is pinned by test vectors: deduplication, chunk manifests, chain
+//! verification (through [`cid_matches`]) and workflow event integrity all
+//! compare CIDs computed here against stored ones.
+
+use cid::Cid;
+
+/// IPLD codec recorded in a CID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CidCodec {
+    Raw,
+    DagPb,
+    DagCbor,
+    DagJson,
+}
+
+impl CidCodec {
+    /// Multicodec code
+    pub fn code(self) -> u64 {
+        match self {
+            Self::Raw => 0x55,
+            Self::DagPb => 0x70,
+            Self::DagCbor => 0x71,
+            Self::DagJson => 0x0129,
+        }
+    }
+
+    /// Parse an encoding name such as `raw` or `dag-json`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "raw" => Some(Self::Raw),
+            "dag-pb" => Some(Self::DagPb),
+            "dag-cbor" => Some(Self::DagCbor),
+            "dag-json" => Some(Self::DagJson),
+            _ => None,
+        }
+    }
+
+    /// Inverse of [`code`](Self::code)
+    pub fn from_code(code: u64) -> Option<Self> {
+        [Self::Raw, Self::DagPb, Self::DagCbor, Self::DagJson]
+            .into_iter()
+            .find(|codec| codec.code() == code)
+    }
+}
+
+/// Hash function used for a CID's multihash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CidHash {
+    Sha2_256,
+    Sha2_512,
+}
+
+impl CidHash {
+    /// Multihash code
+    pub fn code(self) -> u64 {
+        match self {
+            Self::Sha2_256 => 0x12,
+            Self::Sha2_512 => 0x13,
+        }
+    }
+
+    /// Parse an algorithm name such as `sha2-256`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha2-256" => Some(Self::Sha2_256),
+            "sha2-512" => Some(Self::Sha2_512),
+            _ => None,
+        }
+    }
+
+    /// Inverse of [`code`](Self::code)
+    pub fn from_code(code: u64) -> Option<Self> {
+        [Self::Sha2_256, Self::Sha2_512]
+            .into_iter()
+            .find(|hash| hash.code() == code)
+    }
+
+    fn digest(self, bytes: &[u8]) -> Vec<u8> {
+        use sha2::{Digest, Sha256, Sha512};
+
+        match self {
+            Self::Sha2_256 => Sha256::digest(bytes).to_vec(),
+            Self::Sha2_512 => Sha512::digest(bytes).to_vec(),
+        }
+    }
+}
+
+/// CIDv1 of `bytes` under `codec`, hashed with `hash`
+pub fn compute_cid(bytes: &[u8], codec: CidCodec, hash: CidHash) -> Cid {
+    let multihash = cid::multihash::Multihash::<64>::wrap(hash.code(), &hash.digest(bytes))
+        .expect("sha2 digests fit a 64-byte multihash");
+    Cid::new_v1(codec.code(), multihash)
+}
+
+/// Whether `bytes` hash to `cid` under the CID's own codec and hash function
+///
+/// CIDs with a codec or hash function [`compute_cid`] cannot produce never match.
+pub fn cid_matches(cid: &Cid, bytes: &[u8]) -> bool {
+    let (Some(codec), Some(hash)) = (CidCodec::from_code(cid.codec()), CidHash::from_code(cid.hash().code())) else {
+        return false;
+    };
+    compute_cid(bytes, codec, hash).hash() == cid.hash()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Known input → CID string, cross-checked against an independent multiformats encoder
+    const VECTORS: &[(&[u8], CidCodec, CidHash, &str)] = &[
+        (b"", CidCodec::Raw, CidHash::Sha2_256, "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"),
+        (b"hello world", CidCodec::Raw, CidHash::Sha2_256, "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"),
+        (b"hello world", CidCodec::DagCbor, CidHash::Sha2_256, "bafyreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"),
+        (b"hello world", CidCodec::DagPb, CidHash::Sha2_256, "bafybeifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"),
+        (b"hello world", CidCodec::DagJson, CidHash::Sha2_256, "baguqeeraxfgspomtju7arjjokll5u7nl7lcij37dpjjyb3uqrd32zyxpzxuq"),
+        (
+            b"hello world",
+            CidCodec::Raw,
+            CidHash::Sha2_512,
+            "bafkrgqbqt3gerhas23vuzrapkdeqf4vu2dwxp3srdj6hvg6nhsug2tgyn6mj3u23yx7utftq3i2ckw2fwdh5qmhid5qf3t35yvkc5e5ottlw6",
+        ),
+    ];
+
+    #[test]
+    fn test_sha2_256_raw_vector() {
+        assert_eq!(
+            compute_cid(b"hello world", CidCodec::Raw, CidHash::Sha2_256).to_string(),
+            "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+        );
+    }
+
+    #[test]
+    fn test_vectors() {
+        for (bytes, codec, hash, expected) in VECTORS {
+            assert_eq!(compute_cid(bytes, *codec, *hash).to_string(), *expected, "{codec:?}/{hash:?}");
+        }
+    }
+
+    #[test]
+    fn test_cid_matches_any_supported_codec_and_hash() {
+        for (bytes, codec, hash, _) in VECTORS {
+            let cid = compute_cid(bytes, *codec, *hash);
+            assert!(cid_matches(&cid, bytes), "{codec:?}/{hash:?}");
+            assert!(!cid_matches(&cid, b"tampered"), "{codec:?}/{hash:?}");
+            assert_eq!(CidCodec::from_code(codec.code()), Some(*codec));
+            assert_eq!(CidHash::from_code(hash.code()), Some(*hash));
+        }
+        assert_eq!(CidHash::from_code(0xb220), None);
+    }
+
+    #[test]
+    fn test_names_parse() {
+        assert_eq!(CidCodec::from_name("dag-json"), Some(CidCodec::DagJson));
+        assert_eq!(CidHash::from_name("sha2-512"), Some(CidHash::Sha2_512));
+        assert_eq!(CidHash::from_name("md5"), None);
+    }
+}
@@ -0,0 +1,398 @@
+//! Builders for document components with defaults for optional fields
+
+use super::{
+    AccessControlComponent, ClassificationComponent, ConfidentialityLevel, DocumentInfoComponent,
+    DocumentInfoError, DocumentStatus, LifecycleComponent, OwnershipComponent,
+};
+use crate::value_objects::normalize_tags;
+use cid::Cid;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// MIME type used when none is given
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Why a component builder could not build
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ComponentBuildError {
+    #[error("Missing required field {0}")]
+    MissingField(&'static str),
+
+    #[error(transparent)]
+    InvalidInfo(#[from] DocumentInfoError),
+}
+
+/// Builder for [`DocumentInfoComponent`]; only the title is required
+#[derive(Debug, Clone, Default)]
+pub struct DocumentInfoComponentBuilder {
+    title: Option<String>,
+    description: Option<String>,
+    mime_type: Option<String>,
+    filename: Option<String>,
+    size_bytes: u64,
+    language: Option<String>,
+}
+
+impl DocumentInfoComponentBuilder {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
+        self.filename = Some(filename.into());
+        self
+    }
+
+    pub fn with_size_bytes(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = size_bytes;
+        self
+    }
+
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Build and [`validate`](DocumentInfoComponent::validate) the component
+    ///
+    /// The MIME type defaults to [`DEFAULT_MIME_TYPE`] and the size to zero.
+    pub fn build(self) -> Result<DocumentInfoComponent, ComponentBuildError> {
+        let info = DocumentInfoComponent {
+            title: self.title.ok_or(ComponentBuildError::MissingField("title"))?,
+            description: self.description,
+            mime_type: self.mime_type.unwrap_or_else(|| DEFAULT_MIME_TYPE.to_string()),
+            filename: self.filename,
+            size_bytes: self.size_bytes,
+            language: self.language,
+        };
+        info.validate()?;
+        Ok(info)
+    }
+}
+
+impl DocumentInfoComponent {
+    pub fn builder() -> DocumentInfoComponentBuilder {
+        DocumentInfoComponentBuilder::default()
+    }
+}
+
+/// Builder for [`ClassificationComponent`]; only the document type is required
+#[derive(Debug, Clone, Default)]
+pub struct ClassificationComponentBuilder {
+    document_type: Option<String>,
+    category: String,
+    subcategories: Vec<String>,
+    tags: Vec<String>,
+    confidentiality: Option<ConfidentialityLevel>,
+}
+
+impl ClassificationComponentBuilder {
+    pub fn with_document_type(mut self, document_type: impl Into<String>) -> Self {
+        self.document_type = Some(document_type.into());
+        self
+    }
+
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = category.into();
+        self
+    }
+
+    pub fn with_subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.subcategories.push(subcategory.into());
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn with_confidentiality(mut self, confidentiality: ConfidentialityLevel) -> Self {
+        self.confidentiality = Some(confidentiality);
+        self
+    }
+
+    /// Build the component; confidentiality defaults to `Internal`
+    ///
+    /// Tags are [normalized](normalize_tags) like on every other path that sets them.
+    pub fn build(self) -> Result<ClassificationComponent, ComponentBuildError> {
+        let document_type = self
+            .document_type
+            .filter(|document_type| !document_type.trim().is_empty())
+            .ok_or(ComponentBuildError::MissingField("document_type"))?;
+        Ok(ClassificationComponent {
+            document_type,
+            category: self.category,
+            subcategories: self.subcategories,
+            tags: normalize_tags(&self.tags),
+            confidentiality: self.confidentiality.unwrap_or(ConfidentialityLevel::Internal),
+        })
+    }
+}
+
+impl ClassificationComponent {
+    pub fn builder() -> ClassificationComponentBuilder {
+        ClassificationComponentBuilder::default()
+    }
+}
+
+/// Builder for [`OwnershipComponent`]; only the owner is required
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipComponentBuilder {
+    owner_id: Option<Uuid>,
+    authors: Vec<Uuid>,
+    department: Option<String>,
+    project_id: Option<Uuid>,
+    copyright: Option<String>,
+}
+
+impl OwnershipComponentBuilder {
+    pub fn with_owner(mut self, owner_id: Uuid) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    pub fn with_author(mut self, author_id: Uuid) -> Self {
+        self.authors.push(author_id);
+        self
+    }
+
+    pub fn with_department(mut self, department: impl Into<String>) -> Self {
+        self.department = Some(department.into());
+        self
+    }
+
+    pub fn with_project(mut self, project_id: Uuid) -> Self {
+        self.project_id = Some(project_id);
+        self
+    }
+
+    pub fn with_copyright(mut self, copyright: impl Into<String>) -> Self {
+        self.copyright = Some(copyright.into());
+        self
+    }
+
+    /// Build the component; with no authors given, the owner is the author
+    pub fn build(self) -> Result<OwnershipComponent, ComponentBuildError> {
+        let owner_id = self.owner_id.ok_or(ComponentBuildError::MissingField("owner_id"))?;
+        let authors = if self.authors.is_empty() { vec![owner_id] } else { self.authors };
+        Ok(OwnershipComponent {
+            owner_id,
+            authors,
+            department: self.department,
+            project_id: self.project_id,
+            copyright: self.copyright,
+        })
+    }
+}
+
+impl OwnershipComponent {
+    pub fn builder() -> OwnershipComponentBuilder {
+        OwnershipComponentBuilder::default()
+    }
+}
+
+/// Builder for [`LifecycleComponent`]; every field has a default
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleComponentBuilder {
+    status: Option<DocumentStatus>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    version_number: Option<String>,
+    previous_version_cid: Option<Cid>,
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    retention_policy: Option<String>,
+}
+
+impl LifecycleComponentBuilder {
+    pub fn with_status(mut self, status: DocumentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn with_created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_version_number(mut self, version_number: impl Into<String>) -> Self {
+        self.version_number = Some(version_number.into());
+        self
+    }
+
+    pub fn with_previous_version(mut self, cid: Cid) -> Self {
+        self.previous_version_cid = Some(cid);
+        self
+    }
+
+    pub fn with_expiry(mut self, expires_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub fn with_retention_policy(mut self, policy: impl Into<String>) -> Self {
+        self.retention_policy = Some(policy.into());
+        self
+    }
+
+    /// Build a `Draft` at version `1.0`, created and modified now unless set
+    pub fn build(self) -> Result<LifecycleComponent, ComponentBuildError> {
+        let created_at = self.created_at.unwrap_or_else(chrono::Utc::now);
+        Ok(LifecycleComponent {
+            status: self.status.unwrap_or(DocumentStatus::Draft),
+            created_at,
+            modified_at: created_at,
+            version_number: self.version_number.unwrap_or_else(|| "1.0".to_string()),
+            previous_version_cid: self.previous_version_cid,
+            expires_at: self.expires_at,
+            retention_policy: self.retention_policy,
+        })
+    }
+}
+
+impl LifecycleComponent {
+    pub fn builder() -> LifecycleComponentBuilder {
+        LifecycleComponentBuilder::default()
+    }
+}
+
+/// Builder for [`AccessControlComponent`]; starts with no grants and auditing off
+#[derive(Debug, Clone, Default)]
+pub struct AccessControlComponentBuilder {
+    read_access: Vec<Uuid>,
+    write_access: Vec<Uuid>,
+    share_access: Vec<Uuid>,
+    audit_access: bool,
+    encryption_key_id: Option<String>,
+    block_access: HashMap<String, ConfidentialityLevel>,
+}
+
+impl AccessControlComponentBuilder {
+    pub fn with_reader(mut self, user: Uuid) -> Self {
+        self.read_access.push(user);
+        self
+    }
+
+    pub fn with_writer(mut self, user: Uuid) -> Self {
+        self.write_access.push(user);
+        self
+    }
+
+    pub fn with_sharer(mut self, user: Uuid) -> Self {
+        self.share_access.push(user);
+        self
+    }
+
+    pub fn with_audit(mut self, audit_access: bool) -> Self {
+        self.audit_access = audit_access;
+        self
+    }
+
+    pub fn with_encryption_key(mut self, key_id: impl Into<String>) -> Self {
+        self.encryption_key_id = Some(key_id.into());
+        self
+    }
+
+    pub fn with_block_level(mut self, block_id: impl Into<String>, level: ConfidentialityLevel) -> Self {
+        self.block_access.insert(block_id.into(), level);
+        self
+    }
+
+    pub fn build(self) -> Result<AccessControlComponent, ComponentBuildError> {
+        Ok(AccessControlComponent {
+            read_access: self.read_access,
+            write_access: self.write_access,
+            share_access: self.share_access,
+            audit_access: self.audit_access,
+            encryption_key_id: self.encryption_key_id,
+            block_access: self.block_access,
+        })
+    }
+}
+
+impl AccessControlComponent {
+    pub fn builder() -> AccessControlComponentBuilder {
+        AccessControlComponentBuilder::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_info_component_uses_defaults() {
+        let info = DocumentInfoComponent::builder().with_title("Q3 Report").build().unwrap();
+
+        assert_eq!(info.title, "Q3 Report");
+        assert_eq!(info.mime_type, DEFAULT_MIME_TYPE);
+        assert_eq!(info.size_bytes, 0);
+        assert_eq!(info.description, None);
+        assert_eq!(info.filename, None);
+        assert_eq!(info.language, None);
+    }
+
+    #[test]
+    fn test_builders_validate_required_fields() {
+        assert_eq!(
+            DocumentInfoComponent::builder().build(),
+            Err(ComponentBuildError::MissingField("title"))
+        );
+        assert_eq!(
+            DocumentInfoComponent::builder().with_title("Report").with_mime_type("pdf").build(),
+            Err(ComponentBuildError::InvalidInfo(DocumentInfoError::InvalidMimeType("pdf".to_string())))
+        );
+        assert_eq!(
+            ClassificationComponent::builder().with_document_type(" ").build(),
+            Err(ComponentBuildError::MissingField("document_type"))
+        );
+        assert_eq!(
+            OwnershipComponent::builder().with_department("Finance").build(),
+            Err(ComponentBuildError::MissingField("owner_id"))
+        );
+    }
+
+    #[test]
+    fn test_component_builder_defaults() {
+        let owner = Uuid::new_v4();
+        let ownership = OwnershipComponent::builder().with_owner(owner).build().unwrap();
+        assert_eq!(ownership.authors, vec![owner]);
+
+        let classification = ClassificationComponent::builder()
+            .with_document_type("Report")
+            .with_tag("finance")
+            .build()
+            .unwrap();
+        assert_eq!(classification.confidentiality, ConfidentialityLevel::Internal);
+        assert_eq!(classification.tags, vec!["finance".to_string()]);
+        assert!(classification.category.is_empty());
+
+        let classification = ClassificationComponent::builder()
+            .with_document_type("Report")
+            .with_tag(" Finance ")
+            .with_tag("finance")
+            .with_tag("Q3")
+            .build()
+            .unwrap();
+        assert_eq!(classification.tags, vec!["finance".to_string(), "q3".to_string()]);
+
+        let lifecycle = LifecycleComponent::builder().build().unwrap();
+        assert_eq!(lifecycle.status, DocumentStatus::Draft);
+        assert_eq!(lifecycle.version_number, "1.0");
+        assert_eq!(lifecycle.created_at, lifecycle.modified_at);
+
+        let access = AccessControlComponent::builder().with_reader(owner).build().unwrap();
+        assert!(access.can(owner, crate::value_objects::AccessLevel::Read));
+        assert!(!access.audit_access);
+    }
+}
@@ -5,11 +5,12 @@ use crate::events::*;
 use crate::value_objects::*;
 use crate::{
     DocumentInfoComponent, ContentAddressComponent, ClassificationComponent,
-    LifecycleComponent, AccessControlComponent, DocumentStatus, ConfidentialityLevel
+    LifecycleComponent, AccessControlComponent, DocumentStatus, ConfidentialityLevel,
+    RelationshipsComponent, DocumentRelation, RelationType, CustomAttributesComponent,
 };
 use cim_domain::{DomainResult, DomainError, EntityId, AggregateRoot};
 use cid::Cid;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Wrapper aggregate for command processing
@@ -34,7 +35,7 @@ impl DocumentAggregate {
         let placeholder_cid = Cid::default();
         
         Self {
-            document: Document::new(entity_id, info, placeholder_cid),
+            document: Document::new_unchecked(entity_id, info, placeholder_cid),
         }
     }
     
@@ -63,6 +64,8 @@ impl DocumentAggregate {
             size_bytes: metadata.size_bytes.unwrap_or(0),
             language: metadata.language.clone(),
         };
+        info.validate()
+            .map_err(|e| DomainError::ValidationError(e.to_string()))?;
         
         // Update content address
         let content_address = ContentAddressComponent {
@@ -75,12 +78,10 @@ impl DocumentAggregate {
         };
         
         // Update or add document info component
-        self.document.remove_component::<DocumentInfoComponent>().ok(); // Remove existing if present
-        self.document.add_component(info, &uploaded_by, Some("Document upload".to_string()))?;
+        self.document.replace_component(info, &uploaded_by, Some("Document upload".to_string()))?;
         
         // Update or add content address component  
-        self.document.remove_component::<ContentAddressComponent>().ok(); // Remove existing if present
-        self.document.add_component(content_address, &uploaded_by, Some("Content address".to_string()))?;
+        self.document.replace_component(content_address, &uploaded_by, Some("Content address".to_string()))?;
         
         // Create classification
         let classification = ClassificationComponent {
@@ -91,6 +92,12 @@ impl DocumentAggregate {
             confidentiality: ConfidentialityLevel::Internal,
         };
         self.document.add_component(classification, &uploaded_by, Some("Initial classification".to_string()))?;
+
+        self.document.remove_component::<CustomAttributesComponent>().ok();
+        if !metadata.custom_attributes.is_empty() {
+            let attributes = CustomAttributesComponent { attributes: metadata.custom_attributes.clone() };
+            self.document.add_component(attributes, &uploaded_by, Some("Document upload".to_string()))?;
+        }
         
         // Create lifecycle
         let lifecycle = LifecycleComponent {
@@ -125,8 +132,7 @@ impl DocumentAggregate {
         updated_by: String,
     ) -> DomainResult<Vec<DocumentMetadataUpdated>> {
         // Get current info
-        let current_info = self.document.get_component::<DocumentInfoComponent>()
-            .ok_or_else(|| DomainError::generic("Document info not found"))?;
+        let current_info = self.document.require_component::<DocumentInfoComponent>()?;
         
         // Create updated info
         let updated_info = DocumentInfoComponent {
@@ -139,15 +145,19 @@ impl DocumentAggregate {
         };
         
         // Update component
-        self.document.remove_component::<DocumentInfoComponent>()?;
-        self.document.add_component(updated_info, &updated_by, Some("Metadata update".to_string()))?;
+        self.document.replace_component(updated_info, &updated_by, Some("Metadata update".to_string()))?;
+
+        self.document.remove_component::<CustomAttributesComponent>().ok();
+        if !metadata.custom_attributes.is_empty() {
+            let attributes = CustomAttributesComponent { attributes: metadata.custom_attributes.clone() };
+            self.document.add_component(attributes, &updated_by, Some("Metadata update".to_string()))?;
+        }
         
         // Update lifecycle
         if let Some(lifecycle) = self.document.get_component::<LifecycleComponent>() {
             let mut updated_lifecycle = lifecycle.clone();
             updated_lifecycle.modified_at = chrono::Utc::now();
-            self.document.remove_component::<LifecycleComponent>()?;
-            self.document.add_component(updated_lifecycle, &updated_by, Some("Update timestamp".to_string()))?;
+            self.document.replace_component(updated_lifecycle, &updated_by, Some("Update timestamp".to_string()))?;
         }
         
         // Create event
@@ -161,6 +171,68 @@ impl DocumentAggregate {
         Ok(vec![event])
     }
     
+    /// Current metadata as held by the info, classification and custom attribute components
+    pub fn metadata(&self) -> DocumentMetadata {
+        let info = self.document.get_component::<DocumentInfoComponent>();
+        let classification = self.document.get_component::<ClassificationComponent>();
+        DocumentMetadata {
+            title: info.map(|i| i.title.clone()).unwrap_or_default(),
+            description: info.and_then(|i| i.description.clone()),
+            tags: classification.map(|c| c.tags.clone()).unwrap_or_default(),
+            custom_attributes: self.document
+                .get_component::<CustomAttributesComponent>()
+                .map(|c| c.attributes.clone())
+                .unwrap_or_default(),
+            mime_type: info.map(|i| i.mime_type.clone()),
+            size_bytes: info.map(|i| i.size_bytes),
+            language: info.and_then(|i| i.language.clone()),
+            category: classification.map(|c| c.category.clone()),
+            subcategories: classification.map(|c| c.subcategories.clone()),
+            filename: info.and_then(|i| i.filename.clone()),
+        }
+    }
+
+    /// Update only the metadata fields set in `patch`
+    ///
+    /// Tags live on the classification, so patching tags of an unclassified
+    /// document is rejected rather than dropped. Patched tags are normalized
+    /// like on the tagging path.
+    pub fn patch_metadata(
+        &mut self,
+        mut patch: DocumentMetadataPatch,
+        updated_by: String,
+    ) -> DomainResult<Vec<DocumentMetadataUpdated>> {
+        if let Some(tags) = &mut patch.tags {
+            if tags.iter().any(|tag| tag.trim().is_empty()) {
+                return Err(DomainError::ValidationError("Tags cannot be empty".to_string()));
+            }
+            *tags = normalize_tags(tags);
+        }
+
+        let classification = self.document.get_component::<ClassificationComponent>().cloned();
+        if patch.tags.is_some() && classification.is_none() {
+            return Err(DomainError::ValidationError(
+                "Cannot set tags on a document without a classification".to_string(),
+            ));
+        }
+
+        let mut metadata = self.metadata();
+        let tags = patch.tags.clone();
+        metadata.merge(patch);
+
+        let events = self.update_metadata(metadata, updated_by.clone())?;
+
+        if let (Some(tags), Some(classification)) = (tags, classification) {
+            let updated = ClassificationComponent {
+                tags,
+                ..classification
+            };
+            self.document.replace_component(updated, &updated_by, Some("Tag update".to_string()))?;
+        }
+
+        Ok(events)
+    }
+
     /// Share document with users
     pub fn share(
         &mut self,
@@ -168,6 +240,15 @@ impl DocumentAggregate {
         permissions: Vec<String>,
         shared_by: String,
     ) -> DomainResult<Vec<DocumentShared>> {
+        let user_ids = shared_with
+            .iter()
+            .map(|user| {
+                Uuid::parse_str(user).map_err(|_| {
+                    DomainError::ValidationError(format!("Cannot share with {user:?}: not a user ID"))
+                })
+            })
+            .collect::<DomainResult<Vec<Uuid>>>()?;
+
         // Get or create access control
         let access_control = if let Some(ac) = self.document.get_component::<AccessControlComponent>() {
             ac.clone()
@@ -178,14 +259,13 @@ impl DocumentAggregate {
                 share_access: vec![],
                 audit_access: false,
                 encryption_key_id: None,
+                block_access: HashMap::new(),
             }
         };
         
         // Update access control based on permissions
         let mut updated_ac = access_control;
-        for user in &shared_with {
-            let user_id = Uuid::parse_str(user).unwrap_or_else(|_| Uuid::new_v4());
-            
+        for user_id in user_ids {
             if permissions.contains(&"read".to_string()) && !updated_ac.read_access.contains(&user_id) {
                 updated_ac.read_access.push(user_id);
             }
@@ -198,8 +278,7 @@ impl DocumentAggregate {
         }
         
         // Update component
-        self.document.remove_component::<AccessControlComponent>().ok();
-        self.document.add_component(updated_ac, &shared_by, Some("Share document".to_string()))?;
+        self.document.replace_component(updated_ac, &shared_by, Some("Share document".to_string()))?;
         
         // Create event
         let event = DocumentShared {
@@ -220,15 +299,13 @@ impl DocumentAggregate {
         archived_by: String,
     ) -> DomainResult<Vec<DocumentArchived>> {
         // Update lifecycle status
-        let lifecycle = self.document.get_component::<LifecycleComponent>()
-            .ok_or_else(|| DomainError::generic("Lifecycle component not found"))?;
+        let lifecycle = self.document.require_component::<LifecycleComponent>()?;
         
         let mut updated_lifecycle = lifecycle.clone();
         updated_lifecycle.status = DocumentStatus::Archived;
         updated_lifecycle.modified_at = chrono::Utc::now();
         
-        self.document.remove_component::<LifecycleComponent>()?;
-        self.document.add_component(updated_lifecycle, &archived_by, Some("Archive document".to_string()))?;
+        self.document.replace_component(updated_lifecycle, &archived_by, Some("Archive document".to_string()))?;
         
         // Parse archived_by as UUID
         let archived_by_uuid = Uuid::parse_str(&archived_by).unwrap_or_else(|_| Uuid::new_v4());
@@ -245,20 +322,166 @@ impl DocumentAggregate {
         Ok(vec![event])
     }
     
+    /// Change lifecycle state, rejecting transitions the lifecycle does not allow
+    pub fn change_state(
+        &mut self,
+        new_state: DocumentState,
+        reason: String,
+        changed_by: Uuid,
+    ) -> DomainResult<Vec<StateChanged>> {
+        let lifecycle = self.document.require_component::<LifecycleComponent>()?;
+
+        let old_state = DocumentState::try_from(lifecycle.status)
+            .map_err(|e| DomainError::ValidationError(e.to_string()))?;
+        if !old_state.can_transition_to(&new_state) {
+            return Err(DomainError::ValidationError(format!(
+                "Illegal state transition from {old_state:?} to {new_state:?}"
+            )));
+        }
+
+        let mut updated_lifecycle = lifecycle.clone();
+        updated_lifecycle.status = DocumentStatus::from(new_state.clone());
+        updated_lifecycle.modified_at = chrono::Utc::now();
+
+        self.document.replace_component(updated_lifecycle, &changed_by.to_string(), Some(reason.clone()))?;
+
+        let event = StateChanged {
+            document_id: self.document.id().into(),
+            old_state,
+            new_state,
+            reason,
+            changed_by,
+            changed_at: chrono::Utc::now(),
+        };
+
+        Ok(vec![event])
+    }
+
+    /// Remove a user from every access list
+    pub fn revoke_share(
+        &mut self,
+        revoke_from: Uuid,
+        revoked_by: String,
+    ) -> DomainResult<Vec<ShareRevoked>> {
+        let mut access_control = self.document.get_component::<AccessControlComponent>()
+            .cloned()
+            .ok_or_else(|| DomainError::ValidationError("Document has no shares to revoke".to_string()))?;
+
+        if access_control.level(revoke_from).is_none() {
+            return Err(DomainError::ValidationError(format!(
+                "User {revoke_from} has no shared access"
+            )));
+        }
+        access_control.read_access.retain(|user| *user != revoke_from);
+        access_control.write_access.retain(|user| *user != revoke_from);
+        access_control.share_access.retain(|user| *user != revoke_from);
+
+        self.document.replace_component(access_control, &revoked_by, Some("Revoke share".to_string()))?;
+
+        let event = ShareRevoked {
+            document_id: self.document.id().into(),
+            revoked_from,
+            revoked_by,
+            revoked_at: chrono::Utc::now(),
+        };
+
+        Ok(vec![event])
+    }
+
+    /// Link this document to `target`, recording the relation on both ends
+    ///
+    /// Link types with a [`RelationType`] add it here and its inverse on the
+    /// target, e.g. `Supersedes` here and `SupersededBy` there.
+    pub fn link(
+        &mut self,
+        target: &mut DocumentAggregate,
+        link_type: LinkType,
+        description: Option<String>,
+        linked_by: Uuid,
+    ) -> DomainResult<Vec<DocumentsLinked>> {
+        let source_id: Uuid = self.document.id().into();
+        let target_id: Uuid = target.document.id().into();
+        if source_id == target_id {
+            return Err(DomainError::ValidationError(
+                "Cannot link a document to itself".to_string(),
+            ));
+        }
+
+        if let Some(relation_type) = RelationType::from_link_type(&link_type) {
+            if let Some(existing) = self.relations_to(target_id).find(|r| r.contradicts(relation_type)) {
+                return Err(DomainError::ValidationError(format!(
+                    "Cannot add {relation_type:?} to document {target_id}: it already has {existing:?}"
+                )));
+            }
+            // Validate both ends before changing either
+            let source_relations = self.with_relation(DocumentRelation {
+                document_id: target_id,
+                relation_type,
+                description: description.clone(),
+            })?;
+            let target_relations = target.with_relation(DocumentRelation {
+                document_id: source_id,
+                relation_type: relation_type.inverse(),
+                description: description.clone(),
+            })?;
+
+            let by = linked_by.to_string();
+            if let Some(relationships) = source_relations {
+                self.store_relations(relationships, &by)?;
+            }
+            if let Some(relationships) = target_relations {
+                target.store_relations(relationships, &by)?;
+            }
+        }
+
+        let event = DocumentsLinked {
+            source_id: source_id.into(),
+            target_id: target_id.into(),
+            link_type,
+            description,
+            linked_by,
+            linked_at: chrono::Utc::now(),
+        };
+
+        Ok(vec![event])
+    }
+
+    fn relations_to(&self, document_id: Uuid) -> impl Iterator<Item = RelationType> + '_ {
+        self.document.get_component::<RelationshipsComponent>()
+            .into_iter()
+            .flat_map(|r| r.related_documents.iter())
+            .filter(move |r| r.document_id == document_id)
+            .map(|r| r.relation_type)
+    }
+
+    /// Relationships with `relation` added, or `None` if it is already recorded
+    fn with_relation(&self, relation: DocumentRelation) -> DomainResult<Option<RelationshipsComponent>> {
+        let mut relationships = self.document.get_component::<RelationshipsComponent>()
+            .cloned()
+            .unwrap_or(RelationshipsComponent {
+                parent_document_id: None,
+                related_documents: vec![],
+                external_references: vec![],
+            });
+        Ok(relationships.insert_relation(relation)?.then_some(relationships))
+    }
+
+    fn store_relations(&mut self, relationships: RelationshipsComponent, added_by: &str) -> DomainResult<()> {
+        self.document.replace_component(relationships, added_by, Some("Link documents".to_string()))
+    }
+
     /// Apply document successor to update CID chain
     pub fn apply_successor(&mut self, successor: crate::value_objects::DocumentSuccessor) -> DomainResult<()> {
         // Update content address with new CID
         if let Some(mut content_address) = self.document.get_component::<ContentAddressComponent>() {
             content_address.content_cid = successor.new_cid;
-            self.document.remove_component::<ContentAddressComponent>().ok();
-            self.document.add_component(content_address, &successor.edited_by.to_string(), Some("CID chain update".to_string()))?;
+            self.document.replace_component(content_address, &successor.edited_by.to_string(), Some("CID chain update".to_string()))?;
         }
         
         // Update lifecycle timestamp
         if let Some(mut lifecycle) = self.document.get_component::<LifecycleComponent>() {
             lifecycle.modified_at = chrono::Utc::now();
-            self.document.remove_component::<LifecycleComponent>().ok();
-            self.document.add_component(lifecycle, &successor.edited_by.to_string(), Some("Successor applied".to_string()))?;
+            self.document.replace_component(lifecycle, &successor.edited_by.to_string(), Some("Successor applied".to_string()))?;
         }
         
         Ok(())
@@ -304,6 +527,119 @@ mod tests {
         Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap()
     }
 
+    #[test]
+    fn test_share_then_revoke_updates_access_checks() {
+        // US-004: Test shares grant access that revocation removes
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let user = Uuid::new_v4();
+        let mut shared_with = HashSet::new();
+        shared_with.insert(user.to_string());
+
+        aggregate.share(shared_with, vec!["read".to_string(), "write".to_string()], "owner".to_string()).unwrap();
+        let access = aggregate.document.get_component::<AccessControlComponent>().unwrap();
+        assert!(access.can(user, AccessLevel::Write));
+        assert!(!access.can(user, AccessLevel::Admin));
+
+        let events = aggregate.revoke_share(user, "owner".to_string()).unwrap();
+        assert_eq!(events[0].revoked_from, user);
+        let access = aggregate.document.get_component::<AccessControlComponent>().unwrap();
+        assert!(!access.can(user, AccessLevel::Write));
+        assert!(!access.can(user, AccessLevel::Read));
+
+        assert!(aggregate.revoke_share(user, "owner".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_link_records_inverse_relation_on_target() {
+        // US-005: Test linking documents records the paired relation on both ends
+        let (a_id, b_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut a = DocumentAggregate::new(a_id);
+        let mut b = DocumentAggregate::new(b_id);
+
+        let events = a.link(&mut b, LinkType::Supersedes, Some("v2".to_string()), Uuid::new_v4()).unwrap();
+        assert_eq!(events[0].source_id, DocumentId::from(a_id));
+        assert_eq!(events[0].target_id, DocumentId::from(b_id));
+
+        let a_relations = &a.document.get_component::<RelationshipsComponent>().unwrap().related_documents;
+        assert_eq!(a_relations.len(), 1);
+        assert_eq!(a_relations[0].document_id, b_id);
+        assert_eq!(a_relations[0].relation_type, RelationType::Supersedes);
+
+        let b_relations = &b.document.get_component::<RelationshipsComponent>().unwrap().related_documents;
+        assert_eq!(b_relations.len(), 1);
+        assert_eq!(b_relations[0].document_id, a_id);
+        assert_eq!(b_relations[0].relation_type, RelationType::SupersededBy);
+        assert_eq!(b_relations[0].description.as_deref(), Some("v2"));
+
+        let (fork_id, origin_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut fork = DocumentAggregate::new(fork_id);
+        let mut origin = DocumentAggregate::new(origin_id);
+        fork.link(&mut origin, LinkType::DerivedFrom, None, Uuid::new_v4()).unwrap();
+        let fork_relations = &fork.document.get_component::<RelationshipsComponent>().unwrap().related_documents;
+        assert_eq!((fork_relations[0].document_id, fork_relations[0].relation_type), (origin_id, RelationType::DerivedFrom));
+        let origin_relations = &origin.document.get_component::<RelationshipsComponent>().unwrap().related_documents;
+        assert_eq!((origin_relations[0].document_id, origin_relations[0].relation_type), (fork_id, RelationType::HasDerivative));
+
+        // Links without a relation type only emit the event
+        let mut c = DocumentAggregate::new(Uuid::new_v4());
+        a.link(&mut c, LinkType::Related, None, Uuid::new_v4()).unwrap();
+        assert!(c.document.get_component::<RelationshipsComponent>().is_none());
+
+        assert!(a.link(&mut DocumentAggregate::new(a_id), LinkType::References, None, Uuid::new_v4()).is_err());
+    }
+
+    #[test]
+    fn test_link_dedups_repeats_and_rejects_contradictions() {
+        let (a_id, b_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut a = DocumentAggregate::new(a_id);
+        let mut b = DocumentAggregate::new(b_id);
+
+        a.link(&mut b, LinkType::Supersedes, None, Uuid::new_v4()).unwrap();
+        a.link(&mut b, LinkType::Supersedes, None, Uuid::new_v4()).unwrap();
+        assert_eq!(a.document.get_component::<RelationshipsComponent>().unwrap().related_documents.len(), 1);
+        assert_eq!(b.document.get_component::<RelationshipsComponent>().unwrap().related_documents.len(), 1);
+
+        // b superseding a would make a SupersededBy b, which it already supersedes
+        assert!(b.link(&mut a, LinkType::Supersedes, None, Uuid::new_v4()).is_err());
+        assert_eq!(a.document.get_component::<RelationshipsComponent>().unwrap().related_documents.len(), 1);
+    }
+
+    #[test]
+    fn test_link_rejected_by_target_leaves_source_unchanged() {
+        let (a_id, b_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut a = DocumentAggregate::new(a_id);
+        let mut b = DocumentAggregate::new(b_id);
+        // b records that it supersedes a, but a does not know
+        b.store_relations(RelationshipsComponent {
+            parent_document_id: None,
+            related_documents: vec![DocumentRelation {
+                document_id: a_id,
+                relation_type: RelationType::Supersedes,
+                description: None,
+            }],
+            external_references: vec![],
+        }, "test").unwrap();
+
+        assert!(a.link(&mut b, LinkType::Supersedes, None, Uuid::new_v4()).is_err());
+        assert!(a.document.get_component::<RelationshipsComponent>().is_none());
+        assert_eq!(b.document.get_component::<RelationshipsComponent>().unwrap().related_documents.len(), 1);
+    }
+
+    #[test]
+    fn test_relation_type_inverse_is_involution() {
+        // US-005: Test every relation type pairs with its inverse
+        for relation in [
+            RelationType::Supersedes, RelationType::SupersededBy,
+            RelationType::References, RelationType::ReferencedBy,
+            RelationType::AttachmentOf, RelationType::HasAttachment,
+            RelationType::TranslationOf, RelationType::HasTranslation,
+            RelationType::DerivedFrom, RelationType::HasDerivative,
+        ] {
+            assert_ne!(relation.inverse(), relation);
+            assert_eq!(relation.inverse().inverse(), relation);
+        }
+    }
+
     #[test]
     fn test_new_document_aggregate_creation() {
         // US-001: Test document aggregate creation
@@ -328,16 +664,17 @@ mod tests {
     fn test_from_existing_document() {
         // US-001: Test creating aggregate from existing document
         let entity_id = EntityId::<DocumentMarker>::new();
-        let info = DocumentInfoComponent {
-            title: "Existing Document".to_string(),
-            description: Some("An existing document".to_string()),
-            mime_type: "text/plain".to_string(),
-            filename: Some("existing.txt".to_string()),
-            size_bytes: 512,
-            language: Some("en".to_string()),
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Existing Document")
+            .with_description("An existing document")
+            .with_mime_type("text/plain")
+            .with_filename("existing.txt")
+            .with_size_bytes(512)
+            .with_language("en")
+            .build()
+            .unwrap();
         let cid = create_test_cid();
-        let document = Document::new(entity_id, info.clone(), cid);
+        let document = Document::new(entity_id, info.clone(), cid).unwrap();
         
         let aggregate = DocumentAggregate::from(document);
         
@@ -433,6 +770,100 @@ mod tests {
         assert_eq!(doc_info.size_bytes, 2048);
     }
 
+    #[test]
+    fn test_patch_metadata_changes_only_patched_fields() {
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        aggregate.upload(path, create_test_cid(), create_test_metadata(), DocumentType::Text, "user123".to_string()).unwrap();
+
+        let events = aggregate.patch_metadata(
+            DocumentMetadataPatch {
+                tags: Some(vec!["final".to_string()]),
+                custom_attributes: HashMap::from([("reviewed".to_string(), serde_json::json!(true))]),
+                ..DocumentMetadataPatch::default()
+            },
+            "user456".to_string(),
+        ).unwrap();
+
+        let metadata = &events[0].metadata;
+        assert_eq!(metadata.tags, vec!["final"]);
+        assert_eq!(metadata.custom_attributes["reviewed"], serde_json::json!(true));
+        assert_eq!(metadata.title, "Test Document");
+        assert_eq!(metadata.description, Some("A test document".to_string()));
+        assert_eq!(metadata.language, Some("en".to_string()));
+        assert_eq!(aggregate.metadata().tags, vec!["final"]);
+        assert_eq!(aggregate.metadata().size_bytes, Some(1024));
+    }
+
+    #[test]
+    fn test_patch_metadata_keeps_existing_custom_attributes() {
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        let mut metadata = create_test_metadata();
+        metadata.set("owner", "finance");
+        aggregate.upload(path, create_test_cid(), metadata, DocumentType::Text, "user123".to_string()).unwrap();
+
+        let events = aggregate.patch_metadata(
+            DocumentMetadataPatch {
+                custom_attributes: HashMap::from([("reviewed".to_string(), serde_json::json!(true))]),
+                ..DocumentMetadataPatch::default()
+            },
+            "user456".to_string(),
+        ).unwrap();
+
+        let attributes = &events[0].metadata.custom_attributes;
+        assert_eq!(attributes["owner"], serde_json::json!("finance"));
+        assert_eq!(attributes["reviewed"], serde_json::json!(true));
+        assert_eq!(aggregate.metadata().custom_attributes, *attributes);
+    }
+
+    #[test]
+    fn test_patch_metadata_normalizes_tags() {
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        aggregate.upload(path, create_test_cid(), create_test_metadata(), DocumentType::Text, "user123".to_string()).unwrap();
+
+        let events = aggregate.patch_metadata(
+            DocumentMetadataPatch {
+                tags: Some(vec!["Final".to_string(), " final ".to_string(), "Q3".to_string()]),
+                ..DocumentMetadataPatch::default()
+            },
+            "user456".to_string(),
+        ).unwrap();
+
+        assert_eq!(events[0].metadata.tags, vec!["final", "q3"]);
+        assert_eq!(aggregate.metadata().tags, vec!["final", "q3"]);
+
+        let result = aggregate.patch_metadata(
+            DocumentMetadataPatch {
+                tags: Some(vec!["draft".to_string(), "  ".to_string()]),
+                ..DocumentMetadataPatch::default()
+            },
+            "user456".to_string(),
+        );
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        assert_eq!(aggregate.metadata().tags, vec!["final", "q3"]);
+    }
+
+    #[test]
+    fn test_patch_tags_requires_classification() {
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        aggregate.upload(path, create_test_cid(), create_test_metadata(), DocumentType::Text, "user123".to_string()).unwrap();
+        aggregate.document.remove_component::<ClassificationComponent>().unwrap();
+
+        let result = aggregate.patch_metadata(
+            DocumentMetadataPatch {
+                tags: Some(vec!["final".to_string()]),
+                ..DocumentMetadataPatch::default()
+            },
+            "user456".to_string(),
+        );
+
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        assert_eq!(aggregate.metadata().title, "Test Document");
+    }
+
     #[test]
     fn test_metadata_update_without_initial_info() {
         // US-007: Test edge case - update metadata on document without info component
@@ -461,8 +892,8 @@ mod tests {
 
         // Share with users
         let mut shared_with = HashSet::new();
-        shared_with.insert("user1".to_string());
-        shared_with.insert("user2".to_string());
+        shared_with.insert(Uuid::new_v4().to_string());
+        shared_with.insert(Uuid::new_v4().to_string());
         let permissions = vec!["read".to_string(), "write".to_string()];
 
         let result = aggregate.share(shared_with.clone(), permissions.clone(), "owner123".to_string());
@@ -497,12 +928,12 @@ mod tests {
 
         // First share
         let mut first_share = HashSet::new();
-        first_share.insert("user1".to_string());
+        first_share.insert(Uuid::new_v4().to_string());
         aggregate.share(first_share, vec!["read".to_string()], "owner123".to_string()).unwrap();
 
         // Second share with additional users
         let mut second_share = HashSet::new();
-        second_share.insert("user2".to_string());
+        second_share.insert(Uuid::new_v4().to_string());
         let result = aggregate.share(second_share, vec!["write".to_string()], "owner123".to_string());
 
         assert!(result.is_ok());
@@ -545,6 +976,74 @@ mod tests {
         assert_eq!(lifecycle.status, DocumentStatus::Archived);
     }
 
+    #[test]
+    fn test_lifecycle_commands_name_missing_component() {
+        // Nothing uploaded yet, so there is no lifecycle to change
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let missing_lifecycle = |err: DomainError| matches!(err, DomainError::ComponentNotFound(name) if name == "LifecycleComponent");
+
+        assert!(missing_lifecycle(aggregate.archive("Retired".to_string(), "admin".to_string()).unwrap_err()));
+        assert!(missing_lifecycle(aggregate.change_state(DocumentState::Archived, "Retired".to_string(), Uuid::new_v4()).unwrap_err()));
+    }
+
+    #[test]
+    fn test_change_state_follows_lifecycle() {
+        // US-005: Test state changes are validated against the lifecycle
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        aggregate.upload(path, create_test_cid(), create_test_metadata(), DocumentType::Text, "user123".to_string()).unwrap();
+        let actor = Uuid::new_v4();
+
+        // Uploaded documents are published, so they may only be archived
+        let err = aggregate.change_state(DocumentState::InReview, "Review".to_string(), actor).unwrap_err();
+        assert!(err.to_string().contains("Approved to InReview"));
+
+        let events = aggregate.change_state(DocumentState::Archived, "Retired".to_string(), actor).unwrap();
+        assert_eq!(events[0].old_state, DocumentState::Approved);
+        assert_eq!(events[0].new_state, DocumentState::Archived);
+
+        // Archived documents only come back by being restored to draft
+        assert!(aggregate.change_state(DocumentState::InReview, "Reopen".to_string(), actor).is_err());
+        let events = aggregate.change_state(DocumentState::Draft, "Restore".to_string(), actor).unwrap();
+        assert_eq!(events[0].old_state, DocumentState::Archived);
+        assert_eq!(aggregate.document.get_component::<LifecycleComponent>().unwrap().status, DocumentStatus::Draft);
+    }
+
+    #[test]
+    fn test_change_state_rejected_path() {
+        // US-005: Test a rejected document goes back to draft before another review
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+        let path = std::path::PathBuf::from("/test/document.txt");
+        aggregate.upload(path, create_test_cid(), create_test_metadata(), DocumentType::Text, "user123".to_string()).unwrap();
+        aggregate.document
+            .replace_component(LifecycleComponent::builder().build().unwrap(), "test", None)
+            .unwrap();
+        let actor = Uuid::new_v4();
+
+        aggregate.change_state(DocumentState::InReview, "Review".to_string(), actor).unwrap();
+        aggregate.change_state(DocumentState::Rejected, "Needs work".to_string(), actor).unwrap();
+
+        // The rejection is stored, so it still governs the next transition
+        let lifecycle = aggregate.document.get_component::<LifecycleComponent>().unwrap();
+        assert_eq!(lifecycle.status, DocumentStatus::Rejected);
+        let err = aggregate.change_state(DocumentState::InReview, "Resubmit".to_string(), actor).unwrap_err();
+        assert!(err.to_string().contains("Rejected to InReview"));
+        assert!(aggregate.change_state(DocumentState::Approved, "Approve".to_string(), actor).is_err());
+
+        let events = aggregate.change_state(DocumentState::Draft, "Rework".to_string(), actor).unwrap();
+        assert_eq!(events[0].old_state, DocumentState::Rejected);
+        aggregate.change_state(DocumentState::InReview, "Resubmit".to_string(), actor).unwrap();
+        assert_eq!(aggregate.document.get_component::<LifecycleComponent>().unwrap().status, DocumentStatus::UnderReview);
+    }
+
+    #[test]
+    fn test_change_state_without_lifecycle() {
+        // US-005: Test state change requires a lifecycle component
+        let mut aggregate = DocumentAggregate::new(Uuid::new_v4());
+
+        assert!(aggregate.change_state(DocumentState::InReview, "Review".to_string(), Uuid::new_v4()).is_err());
+    }
+
     #[test]
     fn test_archiving_without_lifecycle() {
         // US-007: Test edge case - archive document without lifecycle component
@@ -567,19 +1066,17 @@ mod tests {
         let path = std::path::PathBuf::from("/test/document.txt");
         aggregate.upload(path, content_cid, metadata, DocumentType::Text, "user123".to_string()).unwrap();
 
-        // Share with invalid user ID (should generate new UUID)
+        // Share with an invalid user ID alongside a valid one
         let mut shared_with = HashSet::new();
         shared_with.insert("invalid-uuid".to_string());
+        shared_with.insert(Uuid::new_v4().to_string());
         let permissions = vec!["read".to_string()];
 
         let result = aggregate.share(shared_with, permissions, "owner123".to_string());
 
-        // Should succeed by generating a new UUID
-        assert!(result.is_ok());
-        
-        // Verify access control was still created
-        let access_control = aggregate.document.get_component::<AccessControlComponent>().unwrap();
-        assert_eq!(access_control.read_access.len(), 1);
+        // The whole share is rejected and nobody gains access
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        assert!(aggregate.document.get_component::<AccessControlComponent>().is_none());
     }
 
     #[test]
@@ -3,9 +3,21 @@
 //! A Document is an aggregate that represents business documents stored in a
 //! content-addressed object store using CIDs (Content Identifiers).
 
+mod addressing;
+mod builders;
 mod document_aggregate;
-
+mod relationships;
+mod replay;
+
+pub use addressing::{cid_matches, compute_cid, CidCodec, CidHash};
+pub use builders::{
+    AccessControlComponentBuilder, ClassificationComponentBuilder, ComponentBuildError,
+    DocumentInfoComponentBuilder, LifecycleComponentBuilder, OwnershipComponentBuilder,
+    DEFAULT_MIME_TYPE,
+};
 pub use document_aggregate::DocumentAggregate;
+pub use relationships::detect_relationship_cycle;
+pub use replay::ReplaySummary;
 
 use cim_domain::{
     AggregateRoot, Entity, EntityId, DomainError, DomainResult, Component, ComponentStorage,
@@ -15,6 +27,9 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 use std::any::Any;
+use crate::events::Classification;
+use crate::services::{AccessAction, AccessAuditEntry, AuditSink, CidResolver};
+use crate::value_objects::{AccessLevel, ContentBlock, DocumentId, DocumentState, DocumentVersion, LinkType, VersionBump, VersionParseError};
 
 /// Document aggregate - represents a business document with CID-based storage
 #[derive(Debug, Clone)]
@@ -73,6 +88,58 @@ pub struct DocumentInfoComponent {
     pub language: Option<String>,
 }
 
+/// Why a `DocumentInfoComponent` is invalid
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DocumentInfoError {
+    #[error("Document title must not be empty")]
+    EmptyTitle,
+
+    #[error("Invalid MIME type: {0:?}")]
+    InvalidMimeType(String),
+
+    #[error("Invalid language code: {0:?}")]
+    InvalidLanguage(String),
+}
+
+impl DocumentInfoComponent {
+    /// Check the title, MIME type and language code are well-formed
+    pub fn validate(&self) -> Result<(), DocumentInfoError> {
+        if self.title.trim().is_empty() {
+            return Err(DocumentInfoError::EmptyTitle);
+        }
+        if !is_valid_mime_type(&self.mime_type) {
+            return Err(DocumentInfoError::InvalidMimeType(self.mime_type.clone()));
+        }
+        match &self.language {
+            Some(language) if !is_valid_language(language) => {
+                Err(DocumentInfoError::InvalidLanguage(language.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `type/subtype`, optionally followed by `;` parameters
+fn is_valid_mime_type(mime_type: &str) -> bool {
+    let is_token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+    };
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    matches!(essence.split_once('/'), Some((kind, subtype)) if is_token(kind) && is_token(subtype))
+}
+
+/// ISO 639 code, optionally followed by subtags such as a region (`en-US`)
+fn is_valid_language(language: &str) -> bool {
+    let mut subtags = language.split(['-', '_']);
+    let primary = subtags.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|tag| (2..=8).contains(&tag.len()) && tag.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
 /// Content addressing information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ContentAddressComponent {
@@ -95,6 +162,60 @@ pub struct ContentAddressComponent {
     pub chunk_cids: Vec<Cid>,
 }
 
+impl ContentAddressComponent {
+    /// Build an unchunked, raw-encoded address by hashing `content`
+    ///
+    /// Fails with `UnsupportedAlgorithm` when `algorithm` is not a known hash.
+    pub fn from_content(content: &[u8], algorithm: &str) -> Result<Self, CidMismatch> {
+        let content_cid = Self::compute_cid(content, algorithm, "raw")?;
+
+        Ok(Self {
+            content_cid,
+            metadata_cid: None,
+            hash_algorithm: algorithm.to_string(),
+            encoding: "raw".to_string(),
+            is_chunked: false,
+            chunk_cids: vec![],
+        })
+    }
+
+    /// Recompute the CID of `content` with the stored algorithm and encoding and compare
+    pub fn verify(&self, content: &[u8]) -> Result<(), CidMismatch> {
+        let computed = Self::compute_cid(content, &self.hash_algorithm, &self.encoding)?;
+        if computed == self.content_cid {
+            Ok(())
+        } else {
+            Err(CidMismatch::Mismatch {
+                expected: self.content_cid,
+                computed,
+            })
+        }
+    }
+
+    /// CIDv1 of `content` for a hash algorithm and IPLD encoding name
+    pub fn compute_cid(content: &[u8], algorithm: &str, encoding: &str) -> Result<Cid, CidMismatch> {
+        let codec = CidCodec::from_name(encoding)
+            .ok_or_else(|| CidMismatch::UnsupportedEncoding(encoding.to_string()))?;
+        let hash = CidHash::from_name(algorithm)
+            .ok_or_else(|| CidMismatch::UnsupportedAlgorithm(algorithm.to_string()))?;
+        Ok(compute_cid(content, codec, hash))
+    }
+}
+
+/// Content does not match its recorded CID
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CidMismatch {
+    /// Content hashes to a different CID
+    #[error("content hashes to {computed}, expected {expected}")]
+    Mismatch { expected: Cid, computed: Cid },
+    /// Hash algorithm cannot be recomputed
+    #[error("unsupported hash algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
+    /// Encoding has no known codec
+    #[error("unsupported encoding '{0}'")]
+    UnsupportedEncoding(String),
+}
+
 /// Document classification and categorization
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ClassificationComponent {
@@ -114,6 +235,18 @@ pub struct ClassificationComponent {
     pub confidentiality: ConfidentialityLevel,
 }
 
+impl ClassificationComponent {
+    /// Apply a classifier result: adopt its category and add its labels as tags
+    pub fn apply(&mut self, classification: &Classification) {
+        self.category = classification.category.clone();
+        for label in &classification.labels {
+            if !self.tags.contains(label) {
+                self.tags.push(label.clone());
+            }
+        }
+    }
+}
+
 /// Confidentiality levels for documents
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConfidentialityLevel {
@@ -129,6 +262,36 @@ pub enum ConfidentialityLevel {
     Restricted,
 }
 
+impl ConfidentialityLevel {
+    /// Whether someone cleared for `user_clearance` may see a document at `doc_level`
+    pub fn can_access(user_clearance: Self, doc_level: Self) -> bool {
+        doc_level <= user_clearance
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            ConfidentialityLevel::Public => 0,
+            ConfidentialityLevel::Internal => 1,
+            ConfidentialityLevel::Confidential => 2,
+            ConfidentialityLevel::HighlyConfidential => 3,
+            ConfidentialityLevel::Restricted => 4,
+        }
+    }
+}
+
+/// Levels order from `Public` up to `Restricted`
+impl Ord for ConfidentialityLevel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for ConfidentialityLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Document ownership and authorship
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OwnershipComponent {
@@ -173,6 +336,15 @@ pub struct LifecycleComponent {
     pub retention_policy: Option<String>,
 }
 
+impl LifecycleComponent {
+    /// Advance `version_number` by `bump`, returning the new version
+    pub fn bump_version(&mut self, bump: VersionBump) -> Result<DocumentVersion, VersionParseError> {
+        let next = self.version_number.parse::<DocumentVersion>()?.bump(bump);
+        self.version_number = next.to_string();
+        Ok(next)
+    }
+}
+
 /// Document status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DocumentStatus {
@@ -180,6 +352,8 @@ pub enum DocumentStatus {
     Draft,
     /// Under review
     UnderReview,
+    /// Rejected in review, awaiting rework
+    Rejected,
     /// Approved and published
     Published,
     /// Archived
@@ -190,6 +364,53 @@ pub enum DocumentStatus {
     Superseded,
 }
 
+// `DocumentState` (workflow, carried by `StateChanged`) and `DocumentStatus`
+// (stored in `LifecycleComponent`) overlap as follows:
+//
+// | DocumentState | DocumentStatus    |
+// |---------------|-------------------|
+// | Draft         | Draft             |
+// | InReview      | UnderReview       |
+// | Approved      | Published         |
+// | Archived      | Archived          |
+// | Rejected      | Rejected          |
+// | -             | MarkedForDeletion |
+// | -             | Superseded        |
+//
+// These conversions are the single mapping between the two.
+
+impl From<DocumentState> for DocumentStatus {
+    fn from(state: DocumentState) -> Self {
+        match state {
+            DocumentState::Draft => DocumentStatus::Draft,
+            DocumentState::InReview => DocumentStatus::UnderReview,
+            DocumentState::Rejected => DocumentStatus::Rejected,
+            DocumentState::Approved => DocumentStatus::Published,
+            DocumentState::Archived => DocumentStatus::Archived,
+        }
+    }
+}
+
+impl TryFrom<DocumentStatus> for DocumentState {
+    type Error = NoWorkflowState;
+
+    fn try_from(status: DocumentStatus) -> Result<Self, Self::Error> {
+        match status {
+            DocumentStatus::Draft => Ok(DocumentState::Draft),
+            DocumentStatus::UnderReview => Ok(DocumentState::InReview),
+            DocumentStatus::Rejected => Ok(DocumentState::Rejected),
+            DocumentStatus::Published => Ok(DocumentState::Approved),
+            DocumentStatus::Archived => Ok(DocumentState::Archived),
+            DocumentStatus::MarkedForDeletion | DocumentStatus::Superseded => Err(NoWorkflowState(status)),
+        }
+    }
+}
+
+/// Lifecycle status with no workflow state counterpart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("document status {0:?} has no workflow state")]
+pub struct NoWorkflowState(pub DocumentStatus);
+
 /// Access control for documents
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccessControlComponent {
@@ -207,6 +428,42 @@ pub struct AccessControlComponent {
 
     /// Encryption key ID (if encrypted)
     pub encryption_key_id: Option<String>,
+
+    /// Per-block confidentiality overrides keyed by `ContentBlock.id`
+    #[serde(default)]
+    pub block_access: HashMap<String, ConfidentialityLevel>,
+}
+
+impl AccessControlComponent {
+    /// Effective level of a block; blocks without an override inherit the document level
+    pub fn block_level(
+        &self,
+        block_id: &str,
+        document_level: ConfidentialityLevel,
+    ) -> ConfidentialityLevel {
+        self.block_access
+            .get(block_id)
+            .copied()
+            .unwrap_or(document_level)
+    }
+
+    /// Highest level granted to a user: share is `Admin`, write is `Write`
+    pub fn level(&self, user: Uuid) -> Option<AccessLevel> {
+        if self.share_access.contains(&user) {
+            Some(AccessLevel::Admin)
+        } else if self.write_access.contains(&user) {
+            Some(AccessLevel::Write)
+        } else if self.read_access.contains(&user) {
+            Some(AccessLevel::Read)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a user holds `level` or a higher one
+    pub fn can(&self, user: Uuid, level: AccessLevel) -> bool {
+        self.level(user).is_some_and(|granted| granted >= level)
+    }
 }
 
 /// Document relationships
@@ -222,6 +479,28 @@ pub struct RelationshipsComponent {
     pub external_references: Vec<ExternalReference>,
 }
 
+impl RelationshipsComponent {
+    /// Add `relation`, returning `false` if the same relation to that document already exists
+    ///
+    /// Fails if it contradicts an existing relation to the same document,
+    /// e.g. `SupersededBy` a document this one already `Supersedes`.
+    pub fn insert_relation(&mut self, relation: DocumentRelation) -> DomainResult<bool> {
+        for existing in self.related_documents.iter().filter(|r| r.document_id == relation.document_id) {
+            if existing.relation_type == relation.relation_type {
+                return Ok(false);
+            }
+            if existing.relation_type.contradicts(relation.relation_type) {
+                return Err(DomainError::ValidationError(format!(
+                    "Cannot add {:?} to document {}: it already has {:?}",
+                    relation.relation_type, relation.document_id, existing.relation_type
+                )));
+            }
+        }
+        self.related_documents.push(relation);
+        Ok(true)
+    }
+}
+
 /// Relation to another document
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DocumentRelation {
@@ -254,6 +533,47 @@ pub enum RelationType {
     TranslationOf,
     /// The related document is a translation of this one
     HasTranslation,
+    /// This document was forked or derived from the related one
+    DerivedFrom,
+    /// The related document was forked or derived from this one
+    HasDerivative,
+}
+
+impl RelationType {
+    /// The same relation as seen from the related document
+    pub fn inverse(&self) -> RelationType {
+        match self {
+            RelationType::Supersedes => RelationType::SupersededBy,
+            RelationType::SupersededBy => RelationType::Supersedes,
+            RelationType::References => RelationType::ReferencedBy,
+            RelationType::ReferencedBy => RelationType::References,
+            RelationType::AttachmentOf => RelationType::HasAttachment,
+            RelationType::HasAttachment => RelationType::AttachmentOf,
+            RelationType::TranslationOf => RelationType::HasTranslation,
+            RelationType::HasTranslation => RelationType::TranslationOf,
+            RelationType::DerivedFrom => RelationType::HasDerivative,
+            RelationType::HasDerivative => RelationType::DerivedFrom,
+        }
+    }
+
+    /// Whether one document cannot hold both relations to the same target
+    ///
+    /// Directional relations contradict their inverse; documents may still
+    /// reference each other.
+    pub fn contradicts(self, other: RelationType) -> bool {
+        other == self.inverse()
+            && !matches!(self, RelationType::References | RelationType::ReferencedBy)
+    }
+
+    /// Relation recorded for a document link, if the link type has one
+    pub fn from_link_type(link_type: &LinkType) -> Option<RelationType> {
+        match link_type {
+            LinkType::Supersedes => Some(RelationType::Supersedes),
+            LinkType::References => Some(RelationType::References),
+            LinkType::DerivedFrom => Some(RelationType::DerivedFrom),
+            LinkType::Related | LinkType::PartOf => None,
+        }
+    }
 }
 
 /// External reference
@@ -270,7 +590,7 @@ pub struct ExternalReference {
 }
 
 /// Document processing metadata
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ProcessingComponent {
     /// Text extraction status
     pub text_extracted: bool,
@@ -294,6 +614,12 @@ pub struct ProcessingComponent {
     pub processing_errors: Vec<String>,
 }
 
+/// User-defined attributes carried by `DocumentMetadata::custom_attributes`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomAttributesComponent {
+    pub attributes: HashMap<String, serde_json::Value>,
+}
+
 /// Thumbnail information
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ThumbnailInfo {
@@ -311,11 +637,23 @@ pub struct ThumbnailInfo {
 }
 
 impl Document {
-    /// Create a new document with basic info and content CID
+    /// Create a new document with validated info and content CID
     pub fn new(
         id: EntityId<DocumentMarker>,
         info: DocumentInfoComponent,
         content_cid: Cid,
+    ) -> Result<Self, DocumentInfoError> {
+        info.validate()?;
+        Ok(Self::new_unchecked(id, info, content_cid))
+    }
+
+    /// Create a document without validating its info
+    ///
+    /// Used by replay and placeholder paths where info is filled in later.
+    pub fn new_unchecked(
+        id: EntityId<DocumentMarker>,
+        info: DocumentInfoComponent,
+        content_cid: Cid,
     ) -> Self {
         let mut components = ComponentStorage::new();
         components.add(info).unwrap();
@@ -357,6 +695,77 @@ impl Document {
         }
     }
 
+    /// Independent copy of this document under `new_id`
+    ///
+    /// Content and descriptive components are copied and the version restarts
+    /// at 0. Access grants, relations and lifecycle status are not inherited:
+    /// only `forked_by` is granted access (read, write and share), the
+    /// lifecycle is a fresh `Draft` and the fork holds a single `DerivedFrom`
+    /// relation to this document. Encryption and per-block overrides follow
+    /// the copied content.
+    pub fn fork(&self, new_id: EntityId<DocumentMarker>, forked_by: Uuid) -> Document {
+        let mut relationships = RelationshipsComponent {
+            parent_document_id: None,
+            related_documents: vec![],
+            external_references: vec![],
+        };
+        relationships
+            .insert_relation(DocumentRelation {
+                document_id: *self.id().as_uuid(),
+                relation_type: RelationType::DerivedFrom,
+                description: Some("Forked".to_string()),
+            })
+            .expect("an empty component holds no contradicting relation");
+
+        let mut components = self.components.clone();
+        let mut component_metadata = self.component_metadata.clone();
+        let inherited = self.get_component::<AccessControlComponent>();
+        let access_control = AccessControlComponent {
+            read_access: vec![forked_by],
+            write_access: vec![forked_by],
+            share_access: vec![forked_by],
+            audit_access: inherited.is_some_and(|ac| ac.audit_access),
+            encryption_key_id: inherited.and_then(|ac| ac.encryption_key_id.clone()),
+            block_access: inherited.map(|ac| ac.block_access.clone()).unwrap_or_default(),
+        };
+        components.remove::<AccessControlComponent>();
+        components.remove::<RelationshipsComponent>();
+        let mut fresh: Vec<&'static str> = vec![relationships.type_name(), access_control.type_name()];
+        components
+            .add(relationships)
+            .expect("relationships slot was just cleared");
+        components
+            .add(access_control)
+            .expect("access control slot was just cleared");
+        if components.remove::<LifecycleComponent>().is_some() {
+            let lifecycle = LifecycleComponent::builder()
+                .build()
+                .expect("the default lifecycle is valid");
+            fresh.push(lifecycle.type_name());
+            components
+                .add(lifecycle)
+                .expect("lifecycle slot was just cleared");
+        }
+
+        for component_type in fresh {
+            component_metadata.insert(
+                component_type.to_string(),
+                ComponentMetadata {
+                    added_at: std::time::SystemTime::now(),
+                    added_by: "system".to_string(),
+                    reason: Some(format!("Forked from {}", self.id())),
+                },
+            );
+        }
+
+        Self {
+            entity: Entity::with_id(new_id),
+            version: 0,
+            components,
+            component_metadata,
+        }
+    }
+
     /// Create a chunked document with multiple content blocks
     pub fn new_chunked(
         id: EntityId<DocumentMarker>,
@@ -448,11 +857,30 @@ impl Document {
         }
     }
 
+    /// Add a component, first removing any existing one of the same type
+    pub fn replace_component<C: Component + 'static>(
+        &mut self,
+        component: C,
+        added_by: &str,
+        reason: Option<String>,
+    ) -> DomainResult<()> {
+        self.remove_component::<C>().ok();
+        self.add_component(component, added_by, reason)
+    }
+
     /// Get a component
     pub fn get_component<C: Component + 'static>(&self) -> Option<&C> {
         self.components.get::<C>()
     }
 
+    /// Get a component, failing with `ComponentNotFound` naming its type
+    pub fn require_component<C: Component + 'static>(&self) -> DomainResult<&C> {
+        self.get_component::<C>().ok_or_else(|| {
+            let name = std::any::type_name::<C>().rsplit("::").next().unwrap_or_default();
+            DomainError::ComponentNotFound(name.to_string())
+        })
+    }
+
     /// Check if document has a component
     pub fn has_component<C: Component + 'static>(&self) -> bool {
         self.components.has::<C>()
@@ -469,6 +897,13 @@ impl Document {
             .map(|c| c.content_cid)
     }
 
+    /// Confidentiality from the classification, `Internal` when unclassified
+    pub fn confidentiality(&self) -> ConfidentialityLevel {
+        self.get_component::<ClassificationComponent>()
+            .map(|c| c.confidentiality)
+            .unwrap_or(ConfidentialityLevel::Internal)
+    }
+
     /// Check if document is chunked
     pub fn is_chunked(&self) -> bool {
         self.get_component::<ContentAddressComponent>()
@@ -476,12 +911,160 @@ impl Document {
             .unwrap_or(false)
     }
 
+    /// Require that `user` holds `level` on this document
+    ///
+    /// The attempt is recorded to `sink` when the document is audited.
+    pub fn check_access(
+        &self,
+        user: Uuid,
+        level: AccessLevel,
+        sink: &dyn AuditSink,
+    ) -> DomainResult<()> {
+        let result = self.access_result(user, level.clone());
+        if self.is_audited() {
+            sink.record(
+                self.id().into(),
+                AccessAuditEntry::new(user, AccessAction::Check(level), result.is_ok()),
+            );
+        }
+        result
+    }
+
+    fn access_result(&self, user: Uuid, level: AccessLevel) -> DomainResult<()> {
+        let allowed = self.get_component::<AccessControlComponent>()
+            .is_some_and(|access| access.can(user, level.clone()));
+        if allowed {
+            Ok(())
+        } else {
+            Err(DomainError::PermissionDenied(format!(
+                "User {user} lacks {level:?} access to document {}",
+                self.id()
+            )))
+        }
+    }
+
+    /// Whether access to this document is audited
+    pub fn is_audited(&self) -> bool {
+        self.get_component::<AccessControlComponent>()
+            .is_some_and(|access| access.audit_access)
+    }
+
+    /// Advance the document's version by `bump`
+    pub fn bump_version(&mut self, bump: VersionBump, bumped_by: &str) -> DomainResult<DocumentVersion> {
+        let mut lifecycle = self.require_component::<LifecycleComponent>()?.clone();
+        let version = lifecycle.bump_version(bump)
+            .map_err(|e| DomainError::ValidationError(e.to_string()))?;
+
+        self.replace_component(lifecycle, bumped_by, Some(format!("Version bump to {version}")))?;
+        Ok(version)
+    }
+
+    /// Whether the document has an expiry date at or before `now`
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.get_component::<LifecycleComponent>()
+            .and_then(|lifecycle| lifecycle.expires_at)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// Get chunk CIDs if document is chunked
     pub fn chunk_cids(&self) -> Vec<Cid> {
         self.get_component::<ContentAddressComponent>()
             .map(|c| c.chunk_cids.clone())
             .unwrap_or_default()
     }
+
+    /// Fetch and concatenate the chunks of a chunked document
+    ///
+    /// Chunks must match the order declared in the [`ChunkManifest`] stored at the
+    /// metadata CID, and the combined bytes must hash to the manifest's root.
+    pub fn reassemble(&self, resolver: &dyn CidResolver) -> Result<Vec<u8>, ReassemblyError> {
+        let address = self.get_component::<ContentAddressComponent>()
+            .filter(|address| address.is_chunked)
+            .ok_or(ReassemblyError::NotChunked)?;
+        let metadata_cid = address.metadata_cid.ok_or(ReassemblyError::NotChunked)?;
+
+        let manifest_bytes = resolver.resolve(&metadata_cid)
+            .ok_or(ReassemblyError::MissingManifest(metadata_cid))?;
+        let manifest = ChunkManifest::from_bytes(&manifest_bytes)?;
+
+        if manifest.chunks.len() != address.chunk_cids.len() {
+            return Err(ReassemblyError::ChunkCountMismatch {
+                declared: manifest.chunks.len(),
+                stored: address.chunk_cids.len(),
+            });
+        }
+
+        let mut content = Vec::new();
+        for (position, (cid, expected)) in address.chunk_cids.iter().zip(&manifest.chunks).enumerate() {
+            if cid != expected {
+                return Err(ReassemblyError::OutOfOrder {
+                    position,
+                    expected: *expected,
+                    found: *cid,
+                });
+            }
+            let chunk = resolver.resolve(cid)
+                .ok_or(ReassemblyError::MissingChunk { position, cid: *cid })?;
+            content.extend_from_slice(&chunk);
+        }
+
+        let computed = ContentAddressComponent::compute_cid(&content, &address.hash_algorithm, "raw")?;
+        if computed != manifest.root {
+            return Err(ReassemblyError::RootMismatch(CidMismatch::Mismatch {
+                expected: manifest.root,
+                computed,
+            }));
+        }
+
+        Ok(content)
+    }
+}
+
+/// Metadata DAG block of a chunked document: the whole-content root and chunk order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    /// CID of the complete, raw-encoded content
+    pub root: Cid,
+    /// Chunk CIDs in content order
+    pub chunks: Vec<Cid>,
+}
+
+impl ChunkManifest {
+    /// Encode as the JSON block stored at the metadata CID
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("manifest serialization cannot fail")
+    }
+
+    /// Decode from a metadata block
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReassemblyError> {
+        serde_json::from_slice(bytes).map_err(|e| ReassemblyError::InvalidManifest(e.to_string()))
+    }
+}
+
+/// Failure to reassemble a chunked document
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReassemblyError {
+    /// Document has no chunked content address
+    #[error("document is not chunked")]
+    NotChunked,
+    /// Metadata DAG block could not be resolved
+    #[error("chunk manifest {0} not found")]
+    MissingManifest(Cid),
+    /// Metadata DAG block is not a valid manifest
+    #[error("invalid chunk manifest: {0}")]
+    InvalidManifest(String),
+    /// Manifest and document disagree on the number of chunks
+    #[error("manifest declares {declared} chunks but document stores {stored}")]
+    ChunkCountMismatch { declared: usize, stored: usize },
+    /// A chunk is not where the manifest places it
+    #[error("chunk {position} is {found}, manifest expects {expected}")]
+    OutOfOrder { position: usize, expected: Cid, found: Cid },
+    /// A chunk could not be resolved
+    #[error("chunk {position} ({cid}) not found")]
+    MissingChunk { position: usize, cid: Cid },
+    /// Combined content does not hash to the declared root
+    #[error("reassembled content does not match root: {0}")]
+    RootMismatch(#[from] CidMismatch),
 }
 
 impl AggregateRoot for Document {
@@ -615,42 +1198,153 @@ impl Component for ProcessingComponent {
     }
 }
 
+impl Component for CustomAttributesComponent {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Component> {
+        Box::new(self.clone())
+    }
+
+    fn type_name(&self) -> &'static str {
+        "CustomAttributes"
+    }
+}
+
+/// Filter content blocks down to those a viewer with `viewer_clearance` may see
+///
+/// The document level comes from the classification component (Internal if
+/// unclassified); the access control overlay may raise or lower it per block.
+pub fn visible_blocks<'a>(
+    document: &Document,
+    blocks: &'a [ContentBlock],
+    viewer_clearance: ConfidentialityLevel,
+) -> Vec<&'a ContentBlock> {
+    let document_level = document.confidentiality();
+    let access = document.get_component::<AccessControlComponent>();
+
+    blocks
+        .iter()
+        .filter(|block| {
+            let level = access
+                .map(|a| a.block_level(&block.id, document_level))
+                .unwrap_or(document_level);
+            ConfidentialityLevel::can_access(viewer_clearance, level)
+        })
+        .collect()
+}
+
 // View projections
 
 /// Public document view (for external sharing)
 pub struct PublicDocumentView {
     /// The document's unique identifier
     pub document_id: EntityId<DocumentMarker>,
-    /// Basic document information
+    /// Basic document information, with sensitive fields removed when `redacted`
     pub info: DocumentInfoComponent,
-    /// Classification information if available
+    /// Classification information if available; category, subcategories and tags are cleared when `redacted`
     pub classification: Option<ClassificationComponent>,
-    /// Lifecycle information if available
+    /// Lifecycle information if available; the previous version CID is withheld with `content_cid`
     pub lifecycle: Option<LifecycleComponent>,
-    /// Content identifier for retrieval
-    pub content_cid: Cid,
+    /// Authors from the ownership component; empty when `redacted`
+    pub authors: Vec<Uuid>,
+    /// Content identifier for retrieval, present only when the level may be shared externally
+    pub content_cid: Option<Cid>,
+    /// Whether the policy removed filename, description, size, authors and classification detail
+    pub redacted: bool,
+}
+
+/// How much of a document a [`PublicDocumentView`] exposes, by confidentiality level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicViewPolicy {
+    /// Highest level that can be viewed at all
+    pub max_viewable: ConfidentialityLevel,
+    /// Highest level whose filename, description, size, authors and classification detail are shown
+    pub max_unredacted: ConfidentialityLevel,
+    /// Highest level whose content CIDs are shared externally
+    pub max_shareable: ConfidentialityLevel,
+}
+
+impl PublicViewPolicy {
+    /// Reject policies that unredact or share levels they do not let anyone view
+    pub fn validate(&self) -> DomainResult<()> {
+        for (name, level) in [("max_unredacted", self.max_unredacted), ("max_shareable", self.max_shareable)] {
+            if !ConfidentialityLevel::can_access(self.max_viewable, level) {
+                return Err(DomainError::ValidationError(format!(
+                    "{name} {level:?} exceeds max_viewable {:?}",
+                    self.max_viewable
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for PublicViewPolicy {
+    /// Only `Public` documents, shown in full
+    fn default() -> Self {
+        Self {
+            max_viewable: ConfidentialityLevel::Public,
+            max_unredacted: ConfidentialityLevel::Public,
+            max_shareable: ConfidentialityLevel::Public,
+        }
+    }
 }
 
 impl PublicDocumentView {
-    /// Create public view from document
+    /// Create public view from document under the default [`PublicViewPolicy`]
+    ///
+    /// Only `Public` documents may be viewed; anything else is `PermissionDenied`.
     pub fn from_document(document: &Document) -> DomainResult<Self> {
-        let info = document.get_component::<DocumentInfoComponent>()
-            .ok_or_else(|| DomainError::ValidationError(
-                "Document missing info component".to_string()
-            ))?
-            .clone();
+        Self::from_document_with_policy(document, &PublicViewPolicy::default())
+    }
 
-        let content_cid = document.content_cid()
-            .ok_or_else(|| DomainError::ValidationError(
-                "Document missing content address".to_string()
-            ))?;
+    /// Create a view, redacting what `policy` withholds at the document's level
+    pub fn from_document_with_policy(document: &Document, policy: &PublicViewPolicy) -> DomainResult<Self> {
+        policy.validate()?;
+        let mut info = document.require_component::<DocumentInfoComponent>()?.clone();
+        let content_cid = document.require_component::<ContentAddressComponent>()?.content_cid;
+        let level = document.confidentiality();
+        if !ConfidentialityLevel::can_access(policy.max_viewable, level) {
+            return Err(DomainError::PermissionDenied(format!(
+                "Document {} is {level:?} and cannot be viewed publicly",
+                document.id()
+            )));
+        }
+
+        let redacted = !ConfidentialityLevel::can_access(policy.max_unredacted, level);
+        let shareable = ConfidentialityLevel::can_access(policy.max_shareable, level);
+        let mut authors = document.get_component::<OwnershipComponent>()
+            .map(|ownership| ownership.authors.clone())
+            .unwrap_or_default();
+        let mut classification = document.get_component::<ClassificationComponent>().cloned();
+        let mut lifecycle = document.get_component::<LifecycleComponent>().cloned();
+        if redacted {
+            info.filename = None;
+            info.description = None;
+            info.size_bytes = 0;
+            authors.clear();
+            if let Some(classification) = classification.as_mut() {
+                classification.category.clear();
+                classification.subcategories.clear();
+                classification.tags.clear();
+            }
+        }
+        if !shareable {
+            if let Some(lifecycle) = lifecycle.as_mut() {
+                lifecycle.previous_version_cid = None;
+            }
+        }
 
         Ok(Self {
             document_id: document.id(),
             info,
-            classification: document.get_component::<ClassificationComponent>().cloned(),
-            lifecycle: document.get_component::<LifecycleComponent>().cloned(),
-            content_cid,
+            classification,
+            lifecycle,
+            authors,
+            content_cid: shareable.then_some(content_cid),
+            redacted,
         })
     }
 }
@@ -682,15 +1376,8 @@ pub struct SearchIndexProjection {
 impl SearchIndexProjection {
     /// Create search projection from document
     pub fn from_document(document: &Document) -> DomainResult<Self> {
-        let info = document.get_component::<DocumentInfoComponent>()
-            .ok_or_else(|| DomainError::ValidationError(
-                "Document missing info component".to_string()
-            ))?;
-
-        let content_cid = document.content_cid()
-            .ok_or_else(|| DomainError::ValidationError(
-                "Document missing content address".to_string()
-            ))?;
+        let info = document.require_component::<DocumentInfoComponent>()?;
+        let content_cid = document.require_component::<ContentAddressComponent>()?.content_cid;
 
         let classification = document.get_component::<ClassificationComponent>();
         let ownership = document.get_component::<OwnershipComponent>();
@@ -709,28 +1396,217 @@ impl SearchIndexProjection {
             size_bytes: info.size_bytes,
         })
     }
+
+    /// Project many documents, collecting failures instead of stopping at the first
+    pub fn from_documents(docs: &[Document]) -> (Vec<Self>, Vec<(DocumentId, DomainError)>) {
+        let mut projections = Vec::with_capacity(docs.len());
+        let mut errors = Vec::new();
+        for document in docs {
+            match Self::from_document(document) {
+                Ok(projection) => projections.push(projection),
+                Err(error) => errors.push((DocumentId::from(document.id()), error)),
+            }
+        }
+        (projections, errors)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn valid_info() -> DocumentInfoComponent {
+        DocumentInfoComponent::builder()
+            .with_title("Quarterly Report")
+            .with_mime_type("text/html; charset=utf-8")
+            .with_language("en-US")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_document_info_validation() {
+        assert_eq!(valid_info().validate(), Ok(()));
+
+        let untitled = DocumentInfoComponent { title: "  ".to_string(), ..valid_info() };
+        assert_eq!(untitled.validate(), Err(DocumentInfoError::EmptyTitle));
+
+        for mime_type in ["pdf", "text/", "/plain", "text/plain/extra", "text/pl ain"] {
+            let info = DocumentInfoComponent { mime_type: mime_type.to_string(), ..valid_info() };
+            assert_eq!(info.validate(), Err(DocumentInfoError::InvalidMimeType(mime_type.to_string())));
+        }
+
+        let info = DocumentInfoComponent { language: Some("english".to_string()), ..valid_info() };
+        assert_eq!(info.validate(), Err(DocumentInfoError::InvalidLanguage("english".to_string())));
+    }
+
+    #[test]
+    fn test_document_new_rejects_invalid_info() {
+        let untitled = DocumentInfoComponent { title: String::new(), ..valid_info() };
+
+        assert!(Document::new(EntityId::new(), valid_info(), Cid::default()).is_ok());
+        assert_eq!(
+            Document::new(EntityId::new(), untitled.clone(), Cid::default()).err(),
+            Some(DocumentInfoError::EmptyTitle)
+        );
+
+        let placeholder = Document::new_unchecked(EntityId::new(), untitled, Cid::default());
+        assert!(placeholder.get_component::<DocumentInfoComponent>().unwrap().title.is_empty());
+    }
+
+    #[test]
+    fn test_fork_is_independent_with_provenance() {
+        let mut original = Document::new(EntityId::new(), valid_info(), Cid::default()).unwrap();
+        original.add_component(ClassificationComponent {
+            document_type: "Report".to_string(),
+            category: "Finance".to_string(),
+            subcategories: vec![],
+            tags: vec!["q3".to_string()],
+            confidentiality: ConfidentialityLevel::Internal,
+        }, "test", None).unwrap();
+
+        let fork_id = EntityId::new();
+        let mut fork = original.fork(fork_id, Uuid::new_v4());
+
+        assert_eq!(fork.id(), fork_id);
+        assert_eq!(fork.version(), 0);
+        let relations = &fork.get_component::<RelationshipsComponent>().unwrap().related_documents;
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].document_id, *original.id().as_uuid());
+        assert_eq!(relations[0].relation_type, RelationType::DerivedFrom);
+
+        let mut classification = fork.get_component::<ClassificationComponent>().cloned().unwrap();
+        classification.tags.push("forked".to_string());
+        fork.replace_component(classification, "editor", None).unwrap();
+        fork.remove_component::<DocumentInfoComponent>().unwrap();
+
+        assert_eq!(original.get_component::<ClassificationComponent>().unwrap().tags, vec!["q3".to_string()]);
+        assert!(original.get_component::<DocumentInfoComponent>().is_some());
+        assert!(original.get_component::<RelationshipsComponent>().is_none());
+    }
+
+    #[test]
+    fn test_fork_grants_only_the_forker_and_drops_relations_and_status() {
+        let mut original = Document::new(EntityId::new(), valid_info(), Cid::default()).unwrap();
+        let reader = Uuid::new_v4();
+        original.add_component(AccessControlComponent {
+            read_access: vec![reader],
+            write_access: vec![reader],
+            share_access: vec![],
+            audit_access: true,
+            encryption_key_id: None,
+            block_access: HashMap::new(),
+        }, "test", None).unwrap();
+        original.add_component(LifecycleComponent::builder()
+            .with_status(DocumentStatus::Published)
+            .with_version_number("3.2")
+            .build()
+            .unwrap(), "test", None).unwrap();
+        let mut relationships = RelationshipsComponent {
+            parent_document_id: Some(Uuid::new_v4()),
+            related_documents: vec![],
+            external_references: vec![],
+        };
+        relationships.insert_relation(DocumentRelation {
+            document_id: Uuid::new_v4(),
+            relation_type: RelationType::Supersedes,
+            description: None,
+        }).unwrap();
+        original.add_component(relationships, "test", None).unwrap();
+
+        let forker = Uuid::new_v4();
+        let fork = original.fork(EntityId::new(), forker);
+
+        let access = fork.get_component::<AccessControlComponent>().unwrap();
+        assert!(access.can(forker, AccessLevel::Read));
+        assert!(access.can(forker, AccessLevel::Write));
+        assert_eq!(access.level(reader), None);
+        assert!(access.audit_access);
+        let lifecycle = fork.get_component::<LifecycleComponent>().unwrap();
+        assert_eq!(lifecycle.status, DocumentStatus::Draft);
+        assert_eq!(lifecycle.version_number, "1.0");
+        let relationships = fork.get_component::<RelationshipsComponent>().unwrap();
+        assert_eq!(relationships.parent_document_id, None);
+        assert_eq!(relationships.related_documents, vec![DocumentRelation {
+            document_id: *original.id().as_uuid(),
+            relation_type: RelationType::DerivedFrom,
+            description: Some("Forked".to_string()),
+        }]);
+        assert!(original.get_component::<AccessControlComponent>().unwrap().can(reader, AccessLevel::Write));
+    }
+
+    #[test]
+    fn test_insert_relation_dedups_and_rejects_contradictions() {
+        let target = Uuid::new_v4();
+        let relation = |relation_type| DocumentRelation {
+            document_id: target,
+            relation_type,
+            description: None,
+        };
+        let mut relationships = RelationshipsComponent {
+            parent_document_id: None,
+            related_documents: vec![],
+            external_references: vec![],
+        };
+
+        assert!(relationships.insert_relation(relation(RelationType::Supersedes)).unwrap());
+        assert!(!relationships.insert_relation(relation(RelationType::Supersedes)).unwrap());
+        assert_eq!(relationships.related_documents.len(), 1);
+
+        let error = relationships.insert_relation(relation(RelationType::SupersededBy)).unwrap_err();
+        assert!(error.to_string().contains("already has Supersedes"), "{error}");
+        assert_eq!(relationships.related_documents.len(), 1);
+
+        // Mutual references are allowed
+        assert!(relationships.insert_relation(relation(RelationType::References)).unwrap());
+        assert!(relationships.insert_relation(relation(RelationType::ReferencedBy)).unwrap());
+        assert_eq!(relationships.related_documents.len(), 3);
+    }
+
+    #[test]
+    fn test_version_bump_policies() {
+        let mut document = Document::new(EntityId::new(), valid_info(), Cid::default()).unwrap();
+        document.add_component(LifecycleComponent {
+            status: DocumentStatus::Draft,
+            created_at: chrono::Utc::now(),
+            modified_at: chrono::Utc::now(),
+            version_number: "1.2.3".to_string(),
+            previous_version_cid: None,
+            expires_at: None,
+            retention_policy: None,
+        }, "test", None).unwrap();
+
+        for (bump, expected) in [
+            (VersionBump::Patch, "1.2.4"),
+            (VersionBump::Minor, "1.3.0"),
+            (VersionBump::Major, "2.0.0"),
+        ] {
+            let mut base = document.clone();
+            let version = base.bump_version(bump, "editor").unwrap();
+
+            assert_eq!(version.to_string(), expected);
+            assert_eq!(base.get_component::<LifecycleComponent>().unwrap().version_number, expected);
+        }
+        assert_eq!(VersionBump::default(), VersionBump::Patch);
+    }
+
     #[test]
     fn test_document_creation() {
         let id = EntityId::new();
-        let info = DocumentInfoComponent {
-            title: "Test Document".to_string(),
-            description: Some("A test document".to_string()),
-            mime_type: "application/pdf".to_string(),
-            filename: Some("test.pdf".to_string()),
-            size_bytes: 1024,
-            language: Some("en".to_string()),
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Test Document")
+            .with_description("A test document")
+            .with_mime_type("application/pdf")
+            .with_filename("test.pdf")
+            .with_size_bytes(1024)
+            .with_language("en")
+            .build()
+            .unwrap();
 
         // Create a test CID
         let content_cid = Cid::default();
 
-        let document = Document::new(id, info.clone(), content_cid);
+        let document = Document::new(id, info.clone(), content_cid).unwrap();
 
         assert_eq!(document.id(), id);
         assert_eq!(document.version(), 0);
@@ -747,14 +1623,14 @@ mod tests {
     #[test]
     fn test_chunked_document() {
         let id = EntityId::new();
-        let info = DocumentInfoComponent {
-            title: "Large Document".to_string(),
-            description: Some("A large chunked document".to_string()),
-            mime_type: "video/mp4".to_string(),
-            filename: Some("video.mp4".to_string()),
-            size_bytes: 1_000_000_000, // 1GB
-            language: None,
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Large Document")
+            .with_description("A large chunked document")
+            .with_mime_type("video/mp4")
+            .with_filename("video.mp4")
+            .with_size_bytes(1_000_000_000) // 1GB
+            .build()
+            .unwrap();
 
         // Create test CIDs for chunks
         let chunk_cids = vec![Cid::default(), Cid::default(), Cid::default()];
@@ -773,16 +1649,16 @@ mod tests {
     #[test]
     fn test_document_components() {
         let id = EntityId::new();
-        let info = DocumentInfoComponent {
-            title: "Component Test".to_string(),
-            description: None,
-            mime_type: "text/plain".to_string(),
-            filename: Some("test.txt".to_string()),
-            size_bytes: 100,
-            language: Some("en".to_string()),
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Component Test")
+            .with_mime_type("text/plain")
+            .with_filename("test.txt")
+            .with_size_bytes(100)
+            .with_language("en")
+            .build()
+            .unwrap();
 
-        let mut document = Document::new(id, info, Cid::default());
+        let mut document = Document::new(id, info, Cid::default()).unwrap();
 
         // Add classification
         let classification = ClassificationComponent {
@@ -812,6 +1688,13 @@ mod tests {
         document.add_component(ownership, "test_user", None).unwrap();
         assert!(document.has_component::<OwnershipComponent>());
 
+        // Replacing swaps an existing component and adds a missing one
+        let retagged = ClassificationComponent { tags: vec!["cim".to_string()], ..classification };
+        document.replace_component(retagged, "editor", None).unwrap();
+        assert_eq!(document.get_component::<ClassificationComponent>().unwrap().tags, vec!["cim".to_string()]);
+        document.replace_component(LifecycleComponent::builder().build().unwrap(), "editor", None).unwrap();
+        assert!(document.has_component::<LifecycleComponent>());
+
         // Test component removal
         document.remove_component::<ClassificationComponent>().unwrap();
         assert!(!document.has_component::<ClassificationComponent>());
@@ -820,17 +1703,18 @@ mod tests {
     #[test]
     fn test_public_view() {
         let id = EntityId::new();
-        let info = DocumentInfoComponent {
-            title: "Public Document".to_string(),
-            description: Some("A document for public viewing".to_string()),
-            mime_type: "application/pdf".to_string(),
-            filename: Some("public.pdf".to_string()),
-            size_bytes: 2048,
-            language: Some("en".to_string()),
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Public Document")
+            .with_description("A document for public viewing")
+            .with_mime_type("application/pdf")
+            .with_filename("public.pdf")
+            .with_size_bytes(2048)
+            .with_language("en")
+            .build()
+            .unwrap();
 
         let content_cid = Cid::default();
-        let mut document = Document::new(id, info, content_cid);
+        let mut document = Document::new(id, info, content_cid).unwrap();
 
         // Add classification
         let classification = ClassificationComponent {
@@ -848,24 +1732,162 @@ mod tests {
 
         assert_eq!(public_view.document_id, id);
         assert_eq!(public_view.info.title, "Public Document");
-        assert_eq!(public_view.content_cid, content_cid);
+        assert_eq!(public_view.content_cid, Some(content_cid));
         assert!(public_view.classification.is_some());
+        assert!(!public_view.redacted);
+    }
+
+    #[test]
+    fn test_public_view_redacts_by_policy() {
+        let author = Uuid::new_v4();
+        let previous_cid = compute_cid(b"previous", CidCodec::Raw, CidHash::Sha2_256);
+        let document_at = |level| {
+            let info = DocumentInfoComponent::builder()
+                .with_title("Supplier Review")
+                .with_description("Pricing and terms")
+                .with_mime_type("application/pdf")
+                .with_filename("suppliers.pdf")
+                .with_size_bytes(2048)
+                .build()
+                .unwrap();
+            let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+            document.add_component(ClassificationComponent {
+                document_type: "review".to_string(),
+                category: "procurement".to_string(),
+                subcategories: vec!["suppliers".to_string()],
+                tags: vec!["acme-bid".to_string()],
+                confidentiality: level,
+            }, "system", None).unwrap();
+            document.add_component(LifecycleComponent::builder()
+                .with_previous_version(previous_cid)
+                .build()
+                .unwrap(), "system", None).unwrap();
+            document.add_component(OwnershipComponent {
+                owner_id: author,
+                authors: vec![author],
+                department: None,
+                project_id: None,
+                copyright: None,
+            }, "system", None).unwrap();
+            document
+        };
+        let policy = PublicViewPolicy {
+            max_viewable: ConfidentialityLevel::Confidential,
+            ..PublicViewPolicy::default()
+        };
+        let lifecycle_cid = |view: &PublicDocumentView| view.lifecycle.as_ref().unwrap().previous_version_cid;
+
+        let public = PublicDocumentView::from_document_with_policy(&document_at(ConfidentialityLevel::Public), &policy).unwrap();
+        assert!(!public.redacted);
+        assert_eq!(public.info.filename.as_deref(), Some("suppliers.pdf"));
+        assert_eq!(public.info.description.as_deref(), Some("Pricing and terms"));
+        assert_eq!(public.authors, vec![author]);
+        assert_eq!(public.content_cid, Some(Cid::default()));
+        assert_eq!(public.info.size_bytes, 2048);
+        assert_eq!(public.classification.as_ref().unwrap().tags, vec!["acme-bid"]);
+        assert_eq!(lifecycle_cid(&public), Some(previous_cid));
+
+        let confidential = document_at(ConfidentialityLevel::Confidential);
+        let view = PublicDocumentView::from_document_with_policy(&confidential, &policy).unwrap();
+        assert!(view.redacted);
+        assert_eq!(view.info.title, "Supplier Review");
+        assert_eq!(view.info.filename, None);
+        assert_eq!(view.info.description, None);
+        assert!(view.authors.is_empty());
+        assert_eq!(view.content_cid, None);
+        assert_eq!(view.info.size_bytes, 0);
+        let classification = view.classification.as_ref().unwrap();
+        assert!(classification.tags.is_empty() && classification.subcategories.is_empty());
+        assert_eq!(classification.category, "");
+        assert_eq!(lifecycle_cid(&view), None);
+
+        assert!(PublicDocumentView::from_document(&confidential).is_err());
+        assert!(PublicDocumentView::from_document_with_policy(
+            &document_at(ConfidentialityLevel::Restricted),
+            &policy
+        ).is_err());
+    }
+
+    #[test]
+    fn test_public_view_policy_cannot_exceed_viewable_level() {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Notice")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+
+        for policy in [
+            PublicViewPolicy { max_unredacted: ConfidentialityLevel::Internal, ..PublicViewPolicy::default() },
+            PublicViewPolicy { max_shareable: ConfidentialityLevel::Restricted, ..PublicViewPolicy::default() },
+        ] {
+            assert!(matches!(policy.validate(), Err(DomainError::ValidationError(_))));
+            assert!(matches!(
+                PublicDocumentView::from_document_with_policy(&document, &policy),
+                Err(DomainError::ValidationError(_))
+            ));
+        }
+        assert!(PublicViewPolicy::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_confidentiality_clearance_ordering() {
+        use ConfidentialityLevel::*;
+        let levels = [Public, Internal, Confidential, HighlyConfidential, Restricted];
+        assert!(levels.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert!(ConfidentialityLevel::can_access(Confidential, Internal));
+        assert!(ConfidentialityLevel::can_access(Confidential, Public));
+        assert!(ConfidentialityLevel::can_access(Confidential, Confidential));
+        assert!(!ConfidentialityLevel::can_access(Confidential, HighlyConfidential));
+        assert!(!ConfidentialityLevel::can_access(Public, Internal));
+        assert_eq!(levels.iter().max(), Some(&Restricted));
+    }
+
+    #[test]
+    fn test_public_view_excludes_confidential_documents() {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Merger Plan")
+            .with_mime_type("application/pdf")
+            .with_size_bytes(2048)
+            .build()
+            .unwrap();
+        let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+        assert!(matches!(
+            PublicDocumentView::from_document(&document),
+            Err(DomainError::PermissionDenied(_))
+        ));
+
+        document.add_component(ClassificationComponent {
+            document_type: "plan".to_string(),
+            category: "strategy".to_string(),
+            subcategories: vec![],
+            tags: vec![],
+            confidentiality: ConfidentialityLevel::Confidential,
+        }, "system", None).unwrap();
+
+        assert_eq!(document.confidentiality(), ConfidentialityLevel::Confidential);
+        assert!(matches!(
+            PublicDocumentView::from_document(&document),
+            Err(DomainError::PermissionDenied(_))
+        ));
     }
 
     #[test]
     fn test_search_projection() {
         let id = EntityId::new();
-        let info = DocumentInfoComponent {
-            title: "Research Paper".to_string(),
-            description: Some("AI research".to_string()),
-            mime_type: "application/pdf".to_string(),
-            filename: Some("paper.pdf".to_string()),
-            size_bytes: 2048,
-            language: Some("en".to_string()),
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Research Paper")
+            .with_description("AI research")
+            .with_mime_type("application/pdf")
+            .with_filename("paper.pdf")
+            .with_size_bytes(2048)
+            .with_language("en")
+            .build()
+            .unwrap();
 
         let content_cid = Cid::default();
-        let mut document = Document::new(id, info, content_cid);
+        let mut document = Document::new(id, info, content_cid).unwrap();
 
         // Add classification
         let classification = ClassificationComponent {
@@ -885,6 +1907,58 @@ mod tests {
         assert!(projection.tags.contains(&"neural networks".to_string()));
     }
 
+    #[test]
+    fn test_projections_name_the_missing_component() {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Research Paper")
+            .with_mime_type("application/pdf")
+            .with_size_bytes(2048)
+            .build()
+            .unwrap();
+        let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+        let missing = |result: DomainResult<()>| match result {
+            Err(DomainError::ComponentNotFound(component)) => component,
+            other => panic!("expected ComponentNotFound, got {other:?}"),
+        };
+
+        document.remove_component::<ContentAddressComponent>().unwrap();
+        assert_eq!(missing(PublicDocumentView::from_document(&document).map(|_| ())), "ContentAddressComponent");
+        assert_eq!(missing(SearchIndexProjection::from_document(&document).map(|_| ())), "ContentAddressComponent");
+
+        document.remove_component::<DocumentInfoComponent>().unwrap();
+        assert_eq!(missing(PublicDocumentView::from_document(&document).map(|_| ())), "DocumentInfoComponent");
+        assert_eq!(missing(SearchIndexProjection::from_document(&document).map(|_| ())), "DocumentInfoComponent");
+        assert_eq!(missing(document.require_component::<LifecycleComponent>().map(|_| ())), "LifecycleComponent");
+    }
+
+    #[test]
+    fn test_search_projection_batch_collects_errors() {
+        let document = |title: &str| {
+            let info = DocumentInfoComponent::builder()
+                .with_title(title)
+                .with_mime_type("text/plain")
+                .with_size_bytes(12)
+                .build()
+                .unwrap();
+            Document::new(EntityId::new(), info, Cid::default()).unwrap()
+        };
+        let mut no_content = document("Orphaned");
+        no_content.remove_component::<ContentAddressComponent>().unwrap();
+        let mut no_info = document("Untitled");
+        no_info.remove_component::<DocumentInfoComponent>().unwrap();
+        let docs = vec![document("Alpha"), no_content.clone(), document("Beta"), no_info.clone()];
+
+        let (projections, errors) = SearchIndexProjection::from_documents(&docs);
+
+        let titles: Vec<&str> = projections.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alpha", "Beta"]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, DocumentId::from(no_content.id()));
+        assert!(matches!(&errors[0].1, DomainError::ComponentNotFound(name) if name == "ContentAddressComponent"));
+        assert_eq!(errors[1].0, DocumentId::from(no_info.id()));
+        assert!(matches!(&errors[1].1, DomainError::ComponentNotFound(name) if name == "DocumentInfoComponent"));
+    }
+
     #[test]
     fn test_document_versioning() {
         use crate::value_objects::{DocumentVersion, VersionTag};
@@ -1072,6 +2146,8 @@ mod tests {
             target_content: "Version A content".to_string(),
             source_content: "Version B content".to_string(),
             base_content: Some("Original content".to_string()),
+            target_block: None,
+            source_block: None,
             conflict_type: ConflictType::ContentModified,
         };
 
@@ -1079,4 +2155,274 @@ mod tests {
         assert!(conflict.base_content.is_some());
         assert!(matches!(conflict.conflict_type, ConflictType::ContentModified));
     }
+
+    fn block(id: &str) -> ContentBlock {
+        ContentBlock {
+            id: id.to_string(),
+            block_type: "paragraph".to_string(),
+            title: None,
+            content: format!("{id} content"),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn classified_document(level: ConfidentialityLevel) -> Document {
+        let info = DocumentInfoComponent::builder()
+            .with_title("HR Record")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+        document
+            .add_component(
+                ClassificationComponent {
+                    document_type: "Record".to_string(),
+                    category: "HR".to_string(),
+                    subcategories: vec![],
+                    tags: vec![],
+                    confidentiality: level,
+                },
+                "test",
+                None,
+            )
+            .unwrap();
+        document
+    }
+
+    #[test]
+    fn test_visible_blocks_hides_confidential_block() {
+        let mut document = classified_document(ConfidentialityLevel::Public);
+        let mut block_access = HashMap::new();
+        block_access.insert("salary".to_string(), ConfidentialityLevel::Confidential);
+        document
+            .add_component(
+                AccessControlComponent {
+                    read_access: vec![],
+                    write_access: vec![],
+                    share_access: vec![],
+                    audit_access: false,
+                    encryption_key_id: None,
+                    block_access,
+                },
+                "test",
+                None,
+            )
+            .unwrap();
+
+        let blocks = vec![block("name"), block("salary"), block("role")];
+
+        let public: Vec<&str> = visible_blocks(&document, &blocks, ConfidentialityLevel::Public)
+            .iter()
+            .map(|b| b.id.as_str())
+            .collect();
+        assert_eq!(public, vec!["name", "role"]);
+
+        let cleared = visible_blocks(&document, &blocks, ConfidentialityLevel::Confidential);
+        assert_eq!(cleared.len(), 3);
+    }
+
+    #[test]
+    fn test_access_levels_imply_lower_levels() {
+        let (reader, writer, sharer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let sink = crate::services::InMemoryAuditSink::new();
+        let mut document = classified_document(ConfidentialityLevel::Internal);
+        assert!(document.check_access(reader, AccessLevel::Read, &sink).is_err());
+
+        document
+            .add_component(
+                AccessControlComponent {
+                    read_access: vec![reader],
+                    write_access: vec![writer],
+                    share_access: vec![sharer],
+                    audit_access: false,
+                    encryption_key_id: None,
+                    block_access: HashMap::new(),
+                },
+                "test",
+                None,
+            )
+            .unwrap();
+        let access = document.get_component::<AccessControlComponent>().unwrap();
+
+        assert!(access.can(writer, AccessLevel::Read));
+        assert!(access.can(writer, AccessLevel::Write));
+        assert!(!access.can(writer, AccessLevel::Admin));
+        assert!(access.can(reader, AccessLevel::Read));
+        assert!(!access.can(reader, AccessLevel::Write));
+        assert!(access.can(sharer, AccessLevel::Write));
+        assert!(!access.can(Uuid::new_v4(), AccessLevel::Read));
+
+        assert!(document.check_access(writer, AccessLevel::Read, &sink).is_ok());
+        assert!(matches!(
+            document.check_access(reader, AccessLevel::Write, &sink),
+            Err(DomainError::PermissionDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_visible_blocks_inherit_document_level() {
+        let document = classified_document(ConfidentialityLevel::Confidential);
+        let blocks = vec![block("intro"), block("body")];
+
+        assert!(visible_blocks(&document, &blocks, ConfidentialityLevel::Internal).is_empty());
+        assert_eq!(
+            visible_blocks(&document, &blocks, ConfidentialityLevel::Restricted).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_content_address_from_content_verifies() {
+        let content = b"Quarterly figures";
+        let address = ContentAddressComponent::from_content(content, "sha2-256").unwrap();
+
+        assert_eq!(address.hash_algorithm, "sha2-256");
+        assert_eq!(address.encoding, "raw");
+        assert_eq!(address.content_cid.codec(), 0x55);
+        assert!(address.verify(content).is_ok());
+    }
+
+    #[test]
+    fn test_content_address_detects_tampered_byte() {
+        let address = ContentAddressComponent::from_content(b"Quarterly figures", "sha2-512").unwrap();
+        assert!(address.verify(b"Quarterly figures").is_ok());
+
+        let err = address.verify(b"Quarterly figurez").unwrap_err();
+        assert!(matches!(err, CidMismatch::Mismatch { expected, .. } if expected == address.content_cid));
+    }
+
+    #[test]
+    fn test_content_address_default_cid_does_not_verify() {
+        let mut address = ContentAddressComponent::from_content(b"content", "sha2-256").unwrap();
+        address.content_cid = Cid::default();
+
+        assert!(address.verify(b"content").is_err());
+    }
+
+    #[test]
+    fn test_content_address_unsupported_algorithm() {
+        assert_eq!(
+            ContentAddressComponent::from_content(b"content", "md5"),
+            Err(CidMismatch::UnsupportedAlgorithm("md5".to_string()))
+        );
+
+        let mut address = ContentAddressComponent::from_content(b"content", "sha2-256").unwrap();
+        address.hash_algorithm = "md5".to_string();
+        assert_eq!(
+            address.verify(b"content"),
+            Err(CidMismatch::UnsupportedAlgorithm("md5".to_string()))
+        );
+    }
+
+    struct ChunkStore(HashMap<Cid, Vec<u8>>);
+
+    impl CidResolver for ChunkStore {
+        fn resolve(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.0.get(cid).cloned()
+        }
+    }
+
+    fn raw_cid(data: &[u8]) -> Cid {
+        ContentAddressComponent::compute_cid(data, "sha2-256", "raw").unwrap()
+    }
+
+    /// Chunked document over three chunks, with its manifest and chunks in the store
+    fn chunked_document() -> (Document, ChunkStore, Vec<Cid>) {
+        let chunks: [&[u8]; 3] = [b"The quick ", b"brown fox ", b"jumps."];
+        let chunk_cids: Vec<Cid> = chunks.iter().map(|c| raw_cid(c)).collect();
+
+        let manifest = ChunkManifest {
+            root: raw_cid(b"The quick brown fox jumps."),
+            chunks: chunk_cids.clone(),
+        };
+        let manifest_bytes = manifest.to_bytes();
+        let metadata_cid = ContentAddressComponent::compute_cid(&manifest_bytes, "sha2-256", "dag-json").unwrap();
+
+        let mut blocks: HashMap<Cid, Vec<u8>> = chunk_cids.iter().copied()
+            .zip(chunks.iter().map(|c| c.to_vec()))
+            .collect();
+        blocks.insert(metadata_cid, manifest_bytes);
+
+        let info = DocumentInfoComponent::builder()
+            .with_title("Chunked")
+            .with_mime_type("text/plain")
+            .with_size_bytes(26)
+            .build()
+            .unwrap();
+        let document = Document::new_chunked(EntityId::new(), info, chunk_cids.clone(), metadata_cid);
+        (document, ChunkStore(blocks), chunk_cids)
+    }
+
+    #[test]
+    fn test_reassemble_chunked_document() {
+        let (document, store, _) = chunked_document();
+
+        let content = document.reassemble(&store).unwrap();
+
+        assert_eq!(content, b"The quick brown fox jumps.");
+    }
+
+    #[test]
+    fn test_reassemble_missing_chunk() {
+        let (document, mut store, chunk_cids) = chunked_document();
+        store.0.remove(&chunk_cids[1]);
+
+        let err = document.reassemble(&store).unwrap_err();
+
+        assert_eq!(err, ReassemblyError::MissingChunk { position: 1, cid: chunk_cids[1] });
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order_chunk() {
+        let (document, store, chunk_cids) = chunked_document();
+        let metadata_cid = document.content_cid().unwrap();
+        let info = document.get_component::<DocumentInfoComponent>().unwrap().clone();
+        let swapped = vec![chunk_cids[1], chunk_cids[0], chunk_cids[2]];
+        let document = Document::new_chunked(EntityId::new(), info, swapped, metadata_cid);
+
+        let err = document.reassemble(&store).unwrap_err();
+
+        assert_eq!(err, ReassemblyError::OutOfOrder {
+            position: 0,
+            expected: chunk_cids[0],
+            found: chunk_cids[1],
+        });
+    }
+
+    #[test]
+    fn test_reassemble_unchunked_document() {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Plain")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+
+        assert_eq!(document.reassemble(&ChunkStore(HashMap::new())), Err(ReassemblyError::NotChunked));
+    }
+
+    #[test]
+    fn test_state_status_round_trip() {
+        let overlapping = [
+            (DocumentState::Draft, DocumentStatus::Draft),
+            (DocumentState::InReview, DocumentStatus::UnderReview),
+            (DocumentState::Rejected, DocumentStatus::Rejected),
+            (DocumentState::Approved, DocumentStatus::Published),
+            (DocumentState::Archived, DocumentStatus::Archived),
+        ];
+
+        for (state, status) in overlapping {
+            assert_eq!(DocumentStatus::from(state.clone()), status);
+            assert_eq!(DocumentState::try_from(status), Ok(state));
+        }
+    }
+
+    #[test]
+    fn test_state_status_without_counterpart() {
+        assert_eq!(
+            DocumentState::try_from(DocumentStatus::Superseded),
+            Err(NoWorkflowState(DocumentStatus::Superseded))
+        );
+        assert!(DocumentState::try_from(DocumentStatus::MarkedForDeletion).is_err());
+    }
 }
@@ -0,0 +1,101 @@
+//! Cycle detection over document relationship graphs
+
+use super::RelationType;
+use crate::value_objects::DocumentId;
+use std::collections::{HashMap, HashSet};
+
+/// Find a cycle reachable from `start` made of edges sharing one relation type
+///
+/// Edges are `(from, to, relation)` triples. The returned path begins and
+/// ends with the same document, e.g. `[A, B, C, A]`.
+pub fn detect_relationship_cycle(
+    start: DocumentId,
+    edges: &[(DocumentId, DocumentId, RelationType)],
+) -> Option<Vec<DocumentId>> {
+    let mut relation_types: Vec<RelationType> = Vec::new();
+    for (_, _, relation) in edges {
+        if !relation_types.contains(relation) {
+            relation_types.push(*relation);
+        }
+    }
+
+    relation_types.into_iter().find_map(|relation| {
+        let mut adjacency: HashMap<DocumentId, Vec<DocumentId>> = HashMap::new();
+        for (from, to, _) in edges.iter().filter(|(_, _, r)| *r == relation) {
+            adjacency.entry(*from).or_default().push(*to);
+        }
+        let mut path = Vec::new();
+        let mut finished = HashSet::new();
+        find_cycle(start, &adjacency, &mut path, &mut finished)
+    })
+}
+
+/// Depth-first search keeping the current path to report back edges
+fn find_cycle(
+    node: DocumentId,
+    adjacency: &HashMap<DocumentId, Vec<DocumentId>>,
+    path: &mut Vec<DocumentId>,
+    finished: &mut HashSet<DocumentId>,
+) -> Option<Vec<DocumentId>> {
+    if let Some(position) = path.iter().position(|visited| *visited == node) {
+        let mut cycle = path[position..].to_vec();
+        cycle.push(node);
+        return Some(cycle);
+    }
+    if finished.contains(&node) {
+        return None;
+    }
+
+    path.push(node);
+    for next in adjacency.get(&node).into_iter().flatten() {
+        if let Some(cycle) = find_cycle(*next, adjacency, path, finished) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    finished.insert(node);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_three_node_supersedes_cycle() {
+        let (a, b, c) = (DocumentId::new(), DocumentId::new(), DocumentId::new());
+        let edges = vec![
+            (a, b, RelationType::Supersedes),
+            (b, c, RelationType::Supersedes),
+            (c, a, RelationType::Supersedes),
+        ];
+
+        assert_eq!(detect_relationship_cycle(a, &edges), Some(vec![a, b, c, a]));
+        assert_eq!(detect_relationship_cycle(b, &edges), Some(vec![b, c, a, b]));
+    }
+
+    #[test]
+    fn test_dag_has_no_cycle() {
+        let (a, b, c) = (DocumentId::new(), DocumentId::new(), DocumentId::new());
+        let edges = vec![
+            (a, b, RelationType::Supersedes),
+            (a, c, RelationType::Supersedes),
+            (b, c, RelationType::Supersedes),
+        ];
+
+        for start in [a, b, c] {
+            assert_eq!(detect_relationship_cycle(start, &edges), None);
+        }
+    }
+
+    #[test]
+    fn test_cycle_must_share_one_relation_type() {
+        let (a, b) = (DocumentId::new(), DocumentId::new());
+        let edges = vec![
+            (a, b, RelationType::Supersedes),
+            (b, a, RelationType::References),
+        ];
+
+        assert_eq!(detect_relationship_cycle(a, &edges), None);
+    }
+}
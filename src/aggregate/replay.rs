@@ -0,0 +1,414 @@
+//! Rebuilding a document by folding its domain events
+
+use super::{
+    ClassificationComponent, ConfidentialityLevel, ComponentMetadata, ContentAddressComponent,
+    Document, DocumentInfoComponent, DocumentMarker, DocumentStatus, LifecycleComponent,
+};
+use crate::events::{DocumentDomainEvent, EventEnvelope};
+use cim_domain::{AggregateRoot, Component, DomainError, DomainResult, EntityId};
+
+/// Outcome of folding an event sequence into a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+    /// Events that changed the document
+    pub applied: usize,
+    /// Events with no effect on the component model
+    pub skipped: usize,
+}
+
+impl Document {
+    /// Rebuild a document from its event history
+    ///
+    /// The first event must be the `DocumentUploaded` that created the
+    /// document. Each applied event increments the version.
+    pub fn from_events(events: &[DocumentDomainEvent]) -> DomainResult<(Self, ReplaySummary)> {
+        Self::fold(events.iter().collect())
+    }
+
+    /// Rebuild a document from appended events, in sequence order
+    ///
+    /// Timestamps are ignored for ordering; repeated sequence numbers are rejected.
+    pub fn replay(envelopes: &[EventEnvelope]) -> DomainResult<(Self, ReplaySummary)> {
+        let events = EventEnvelope::in_sequence(envelopes);
+        let mut sequences: Vec<u64> = envelopes.iter().map(|envelope| envelope.sequence).collect();
+        sequences.sort_unstable();
+        if let Some(pair) = sequences.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(DomainError::ValidationError(format!(
+                "Event sequence {} appears more than once",
+                pair[0]
+            )));
+        }
+        Self::fold(events)
+    }
+
+    fn fold(events: Vec<&DocumentDomainEvent>) -> DomainResult<(Self, ReplaySummary)> {
+        let Some(DocumentDomainEvent::DocumentUploaded(uploaded)) = events.first() else {
+            return Err(DomainError::ValidationError(
+                "Event history must start with DocumentUploaded".to_string(),
+            ));
+        };
+
+        let info = DocumentInfoComponent {
+            title: String::new(),
+            description: None,
+            mime_type: "application/octet-stream".to_string(),
+            filename: None,
+            size_bytes: 0,
+            language: None,
+        };
+        let mut document = Document::new_unchecked(
+            EntityId::<DocumentMarker>::from_uuid(*uploaded.document_id.as_uuid()),
+            info,
+            uploaded.content_cid,
+        );
+
+        let mut summary = ReplaySummary::default();
+        for event in events {
+            if document.apply(event) {
+                summary.applied += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+        Ok((document, summary))
+    }
+
+    /// Fold one event into the components, returning whether it was applied
+    ///
+    /// Only the core lifecycle events are supported; others are ignored.
+    /// Component metadata is stamped with the event's own timestamp, so
+    /// replaying the same history always yields the same components.
+    pub fn apply(&mut self, event: &DocumentDomainEvent) -> bool {
+        match event {
+            DocumentDomainEvent::DocumentUploaded(e) => {
+                self.set_component(DocumentInfoComponent {
+                    title: e.metadata.title.clone(),
+                    description: e.metadata.description.clone(),
+                    mime_type: e.metadata.mime_type.clone()
+                        .unwrap_or("application/octet-stream".to_string()),
+                    filename: e.path.file_name().map(|name| name.to_string_lossy().to_string()),
+                    size_bytes: e.metadata.size_bytes.unwrap_or(0),
+                    language: e.metadata.language.clone(),
+                }, e.uploaded_at, &e.uploaded_by, "Document upload");
+                self.set_component(ContentAddressComponent {
+                    content_cid: e.content_cid,
+                    metadata_cid: None,
+                    hash_algorithm: "sha2-256".to_string(),
+                    encoding: "raw".to_string(),
+                    is_chunked: false,
+                    chunk_cids: vec![],
+                }, e.uploaded_at, &e.uploaded_by, "Content address");
+                self.set_component(ClassificationComponent {
+                    document_type: format!("{:?}", e.document_type),
+                    category: e.metadata.category.clone().unwrap_or_default(),
+                    subcategories: e.metadata.subcategories.clone().unwrap_or_default(),
+                    tags: e.metadata.tags.clone(),
+                    confidentiality: ConfidentialityLevel::Internal,
+                }, e.uploaded_at, &e.uploaded_by, "Initial classification");
+                self.set_component(LifecycleComponent {
+                    status: DocumentStatus::Published,
+                    created_at: e.uploaded_at,
+                    modified_at: e.uploaded_at,
+                    version_number: "1.0".to_string(),
+                    previous_version_cid: None,
+                    expires_at: None,
+                    retention_policy: None,
+                }, e.uploaded_at, &e.uploaded_by, "Initial lifecycle");
+            }
+            DocumentDomainEvent::DocumentMetadataUpdated(e) => {
+                let Some(current) = self.get_component::<DocumentInfoComponent>() else {
+                    return false;
+                };
+                let info = DocumentInfoComponent {
+                    title: e.metadata.title.clone(),
+                    description: e.metadata.description.clone(),
+                    mime_type: e.metadata.mime_type.clone().unwrap_or(current.mime_type.clone()),
+                    filename: current.filename.clone(),
+                    size_bytes: e.metadata.size_bytes.unwrap_or(current.size_bytes),
+                    language: e.metadata.language.clone(),
+                };
+                self.set_component(info, e.updated_at, &e.updated_by, "Metadata update");
+                if let Some(mut classification) = self.get_component::<ClassificationComponent>().cloned() {
+                    classification.tags = e.metadata.tags.clone();
+                    self.set_component(classification, e.updated_at, &e.updated_by, "Metadata update");
+                }
+                self.touch_lifecycle(e.updated_at, &e.updated_by, None);
+            }
+            DocumentDomainEvent::DocumentClassified(e) => {
+                let (tags, confidentiality) = self
+                    .get_component::<ClassificationComponent>()
+                    .map(|c| (c.tags.clone(), c.confidentiality))
+                    .unwrap_or((Vec::new(), ConfidentialityLevel::Internal));
+                self.set_component(ClassificationComponent {
+                    document_type: format!("{:?}", e.document_type),
+                    category: e.category.clone(),
+                    subcategories: e.subcategories.clone(),
+                    tags,
+                    confidentiality,
+                }, e.classified_at, &e.classified_by, "Classification");
+            }
+            DocumentDomainEvent::DocumentContentUpdated(e) => {
+                let Some(current) = self.get_component::<ContentAddressComponent>() else {
+                    return false;
+                };
+                let address = ContentAddressComponent {
+                    content_cid: e.new_content_cid,
+                    ..current.clone()
+                };
+                self.set_component(address, e.updated_at, &e.updated_by, "Content update");
+                if let Some(mut lifecycle) = self.get_component::<LifecycleComponent>().cloned() {
+                    lifecycle.previous_version_cid = Some(e.previous_content_cid);
+                    lifecycle.modified_at = e.updated_at;
+                    self.set_component(lifecycle, e.updated_at, &e.updated_by, "Content update");
+                }
+            }
+            DocumentDomainEvent::DocumentArchived(e) => {
+                let by = e.archived_by.to_string();
+                self.touch_lifecycle(e.archived_at, &by, Some(DocumentStatus::Archived));
+            }
+            DocumentDomainEvent::DocumentDeleted(e) => {
+                let by = e.deleted_by.to_string();
+                self.touch_lifecycle(e.deleted_at, &by, Some(DocumentStatus::MarkedForDeletion));
+            }
+            DocumentDomainEvent::DocumentRestored(e) => {
+                let by = e.restored_by.to_string();
+                self.touch_lifecycle(e.restored_at, &by, Some(DocumentStatus::Draft));
+            }
+            _ => return false,
+        }
+
+        self.increment_version();
+        true
+    }
+
+    /// Insert or replace a component, recorded as added `at`, without bumping the version
+    fn set_component<C: Component + 'static>(
+        &mut self,
+        component: C,
+        at: chrono::DateTime<chrono::Utc>,
+        by: &str,
+        reason: &str,
+    ) {
+        let component_type = component.type_name().to_string();
+        self.components.remove::<C>();
+        self.components
+            .add(component)
+            .expect("component slot was just cleared");
+        self.component_metadata.insert(
+            component_type,
+            ComponentMetadata {
+                added_at: at.into(),
+                added_by: by.to_string(),
+                reason: Some(reason.to_string()),
+            },
+        );
+    }
+
+    /// Update the lifecycle's modification time and optionally its status
+    fn touch_lifecycle(
+        &mut self,
+        at: chrono::DateTime<chrono::Utc>,
+        by: &str,
+        status: Option<DocumentStatus>,
+    ) {
+        let mut lifecycle = self
+            .get_component::<LifecycleComponent>()
+            .cloned()
+            .unwrap_or(LifecycleComponent {
+                status: DocumentStatus::Draft,
+                created_at: at,
+                modified_at: at,
+                version_number: "1.0".to_string(),
+                previous_version_cid: None,
+                expires_at: None,
+                retention_policy: None,
+            });
+        lifecycle.modified_at = at;
+        if let Some(status) = status {
+            lifecycle.status = status;
+        }
+        self.set_component(lifecycle, at, by, "Lifecycle update");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{
+        DocumentArchived, DocumentClassified, DocumentContentUpdated, DocumentMetadataUpdated,
+        DocumentTagged, DocumentUploaded, EventEnvelope,
+    };
+    use crate::value_objects::{DocumentId, DocumentMetadata, DocumentType};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn metadata(title: &str) -> DocumentMetadata {
+        DocumentMetadata {
+            title: title.to_string(),
+            description: None,
+            tags: vec!["finance".to_string()],
+            custom_attributes: HashMap::new(),
+            mime_type: Some("application/pdf".to_string()),
+            size_bytes: Some(2048),
+            language: Some("en".to_string()),
+            category: None,
+            subcategories: None,
+            filename: None,
+        }
+    }
+
+    #[test]
+    fn test_from_events_folds_lifecycle_events() {
+        let document_id = DocumentId::new();
+        let first_cid = ContentAddressComponent::from_content(b"v1", "sha2-256").unwrap().content_cid;
+        let second_cid = ContentAddressComponent::from_content(b"v2", "sha2-256").unwrap().content_cid;
+        let start = chrono::Utc::now();
+        let at = |minutes| start + chrono::Duration::minutes(minutes);
+
+        let events = vec![
+            DocumentDomainEvent::DocumentUploaded(DocumentUploaded {
+                document_id,
+                path: "reports/q3.pdf".into(),
+                content_cid: first_cid,
+                metadata: metadata("Q3 Report"),
+                document_type: DocumentType::Report,
+                uploaded_by: "alice".to_string(),
+                uploaded_at: at(0),
+            }),
+            DocumentDomainEvent::DocumentMetadataUpdated(DocumentMetadataUpdated {
+                document_id,
+                metadata: DocumentMetadata {
+                    tags: vec!["finance".to_string(), "final".to_string()],
+                    ..metadata("Q3 Report (final)")
+                },
+                updated_by: "alice".to_string(),
+                updated_at: at(1),
+            }),
+            DocumentDomainEvent::DocumentTagged(DocumentTagged {
+                document_id,
+                tags: vec!["q3".to_string()],
+                all_tags: vec!["finance".to_string(), "q3".to_string()],
+                tagged_by: "alice".to_string(),
+                tagged_at: at(2),
+            }),
+            DocumentDomainEvent::DocumentClassified(DocumentClassified {
+                document_id,
+                document_type: DocumentType::Report,
+                category: "Finance".to_string(),
+                subcategories: vec!["Quarterly".to_string()],
+                classified_by: "classifier".to_string(),
+                classified_at: at(3),
+            }),
+            DocumentDomainEvent::DocumentContentUpdated(DocumentContentUpdated {
+                document_id,
+                new_content_cid: second_cid,
+                previous_content_cid: first_cid,
+                updated_by: "bob".to_string(),
+                updated_at: at(4),
+                update_reason: None,
+            }),
+            DocumentDomainEvent::DocumentArchived(DocumentArchived {
+                document_id,
+                reason: "Quarter closed".to_string(),
+                archived_by: Uuid::new_v4(),
+                archived_at: at(5),
+                metadata: HashMap::new(),
+            }),
+        ];
+
+        let (document, summary) = Document::from_events(&events).unwrap();
+
+        assert_eq!(summary, ReplaySummary { applied: 5, skipped: 1 });
+        assert_eq!(document.version(), 5);
+        assert_eq!(DocumentId::from(document.id()), document_id);
+
+        let info = document.get_component::<DocumentInfoComponent>().unwrap();
+        assert_eq!(info.title, "Q3 Report (final)");
+        assert_eq!(info.filename.as_deref(), Some("q3.pdf"));
+
+        let classification = document.get_component::<ClassificationComponent>().unwrap();
+        assert_eq!(classification.category, "Finance");
+        assert_eq!(classification.subcategories, vec!["Quarterly".to_string()]);
+        assert_eq!(classification.tags, vec!["finance".to_string(), "final".to_string()]);
+
+        assert_eq!(document.content_cid(), Some(second_cid));
+        let lifecycle = document.get_component::<LifecycleComponent>().unwrap();
+        assert_eq!(lifecycle.status, DocumentStatus::Archived);
+        assert_eq!(lifecycle.previous_version_cid, Some(first_cid));
+        assert_eq!(lifecycle.created_at, at(0));
+        assert_eq!(lifecycle.modified_at, at(5));
+
+        // Component metadata carries event time, so a second replay is identical
+        let stamped = |document: &Document| {
+            let mut stamps: Vec<(String, std::time::SystemTime, String)> = document
+                .component_metadata
+                .iter()
+                .map(|(name, meta)| (name.clone(), meta.added_at, meta.added_by.clone()))
+                .collect();
+            stamps.sort();
+            stamps
+        };
+        let (again, _) = Document::from_events(&events).unwrap();
+        assert_eq!(stamped(&again), stamped(&document));
+        assert_eq!(
+            document.component_metadata["DocumentInfo"].added_at,
+            std::time::SystemTime::from(at(1))
+        );
+    }
+
+    #[test]
+    fn test_from_events_requires_upload_first() {
+        assert!(Document::from_events(&[]).is_err());
+
+        let archived = DocumentDomainEvent::DocumentArchived(DocumentArchived {
+            document_id: DocumentId::new(),
+            reason: "n/a".to_string(),
+            archived_by: Uuid::new_v4(),
+            archived_at: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        });
+        assert!(Document::from_events(&[archived]).is_err());
+    }
+
+    #[test]
+    fn test_replay_orders_tied_timestamps_by_sequence() {
+        let document_id = DocumentId::new();
+        let at = chrono::Utc::now();
+        let renamed = |title: &str| DocumentDomainEvent::DocumentMetadataUpdated(DocumentMetadataUpdated {
+            document_id,
+            metadata: metadata(title),
+            updated_by: "alice".to_string(),
+            updated_at: at,
+        });
+        let envelope = |sequence, event| EventEnvelope {
+            sequence,
+            stream_position: sequence + 10,
+            event,
+        };
+        let uploaded = DocumentDomainEvent::DocumentUploaded(DocumentUploaded {
+            document_id,
+            path: "reports/q3.pdf".into(),
+            content_cid: ContentAddressComponent::from_content(b"v1", "sha2-256").unwrap().content_cid,
+            metadata: metadata("Draft"),
+            document_type: DocumentType::Report,
+            uploaded_by: "alice".to_string(),
+            uploaded_at: at,
+        });
+        let envelopes = vec![
+            envelope(3, renamed("Final")),
+            envelope(1, uploaded),
+            envelope(2, renamed("Reviewed")),
+        ];
+
+        for _ in 0..3 {
+            let (document, summary) = Document::replay(&envelopes).unwrap();
+            assert_eq!(summary, ReplaySummary { applied: 3, skipped: 0 });
+            let info = document.get_component::<DocumentInfoComponent>().unwrap();
+            assert_eq!(info.title, "Final");
+        }
+
+        let mut duplicated = envelopes.clone();
+        duplicated[0].sequence = 2;
+        assert!(Document::replay(&duplicated).is_err());
+    }
+}
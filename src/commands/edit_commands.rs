@@ -48,6 +48,9 @@ pub struct EditDocumentDirect {
     pub description: Option<String>,
     /// Editor used for the edit
     pub editor_info: Option<EditorInfo>,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for EditDocumentDirect {
@@ -58,7 +61,11 @@ impl DomainCommand for EditDocumentDirect {
     }
 }
 
-impl crate::commands::Command for EditDocumentDirect {}
+impl crate::commands::Command for EditDocumentDirect {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 impl EditDocumentDirect {
     pub fn new(
@@ -107,6 +114,9 @@ pub struct EditDocumentPatch {
     pub description: Option<String>,
     /// Editor information
     pub editor_info: Option<EditorInfo>,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for EditDocumentPatch {
@@ -117,7 +127,11 @@ impl DomainCommand for EditDocumentPatch {
     }
 }
 
-impl crate::commands::Command for EditDocumentPatch {}
+impl crate::commands::Command for EditDocumentPatch {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 impl EditDocumentPatch {
     pub fn new(
@@ -154,6 +168,9 @@ pub struct EditDocumentStructured {
     pub edited_by: Uuid,
     /// Editor information
     pub editor_info: Option<EditorInfo>,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for EditDocumentStructured {
@@ -164,7 +181,11 @@ impl DomainCommand for EditDocumentStructured {
     }
 }
 
-impl crate::commands::Command for EditDocumentStructured {}
+impl crate::commands::Command for EditDocumentStructured {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Request content for external editing
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -229,6 +250,9 @@ pub struct TransformDocument {
     pub processor: String,
     /// Description of transformation
     pub description: Option<String>,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for TransformDocument {
@@ -239,7 +263,11 @@ impl DomainCommand for TransformDocument {
     }
 }
 
-impl crate::commands::Command for TransformDocument {}
+impl crate::commands::Command for TransformDocument {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Types of automated transformations
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -277,6 +305,9 @@ pub struct MergeDocumentEdits {
     pub merged_by: Uuid,
     /// Merge description
     pub description: Option<String>,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for MergeDocumentEdits {
@@ -287,7 +318,11 @@ impl DomainCommand for MergeDocumentEdits {
     }
 }
 
-impl crate::commands::Command for MergeDocumentEdits {}
+impl crate::commands::Command for MergeDocumentEdits {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Merge strategies for resolving conflicts
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -319,6 +354,9 @@ pub struct RollbackDocument {
     pub reason: String,
     /// Whether to create new successor or restore directly
     pub create_successor: bool,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for RollbackDocument {
@@ -329,7 +367,11 @@ impl DomainCommand for RollbackDocument {
     }
 }
 
-impl crate::commands::Command for RollbackDocument {}
+impl crate::commands::Command for RollbackDocument {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -412,6 +454,7 @@ mod tests {
             change_summary: "Updated paragraph 5".to_string(),
             edited_by,
             editor_info: None,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, document_id);
@@ -433,6 +476,7 @@ mod tests {
             parameters: std::collections::HashMap::new(),
             processor: "translation_service_v1".to_string(),
             description: Some("Spanish translation".to_string()),
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, document_id);
@@ -457,6 +501,7 @@ mod tests {
             merge_strategy: MergeStrategy::AutoMerge,
             merged_by,
             description: Some("Merging concurrent edits".to_string()),
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, document_id);
@@ -478,6 +523,7 @@ mod tests {
             rolled_back_by,
             reason: "Reverting problematic changes".to_string(),
             create_successor: true,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, document_id);
@@ -698,6 +744,7 @@ mod tests {
             change_summary: "Test changes".to_string(),
             edited_by: Uuid::new_v4(),
             editor_info: None,
+            idempotency_key: None,
         };
         
         // All should have the same aggregate ID
@@ -17,7 +17,24 @@ use serde::{Deserialize, Serialize};
 use crate::value_objects::DocumentMetadata;
 
 /// Trait for document commands
-pub trait Command: Send + Sync + std::fmt::Debug {}
+pub trait Command: IntoAny + Send + Sync + std::fmt::Debug {
+    /// Key identifying redeliveries of the same command
+    fn idempotency_key(&self) -> Option<Uuid> {
+        None
+    }
+}
+
+/// Conversion of a boxed command back to its concrete type
+pub trait IntoAny {
+    /// Box as `Any` for downcasting
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any + Send>;
+}
+
+impl<T: std::any::Any + Send> IntoAny for T {
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any + Send> {
+        self
+    }
+}
 
 /// Upload a new document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +51,9 @@ pub struct UploadDocument {
     pub chunk_cids: Vec<Cid>,
     /// Who is uploading the document
     pub uploaded_by: Uuid,
+    /// Key for deduplicating redelivered uploads
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for UploadDocument {
@@ -44,7 +64,11 @@ impl DomainCommand for UploadDocument {
     }
 }
 
-impl Command for UploadDocument {}
+impl Command for UploadDocument {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Classify a document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -111,6 +135,12 @@ pub struct UpdateContent {
     pub change_summary: String,
     /// Updated by
     pub updated_by: Uuid,
+    /// How the document version advances
+    #[serde(default)]
+    pub version_bump: crate::value_objects::VersionBump,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for UpdateContent {
@@ -121,7 +151,11 @@ impl DomainCommand for UpdateContent {
     }
 }
 
-impl Command for UpdateContent {}
+impl Command for UpdateContent {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Share document with users
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,6 +168,9 @@ pub struct ShareDocument {
     pub access_level: crate::value_objects::AccessLevel,
     /// Shared by
     pub shared_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for ShareDocument {
@@ -144,7 +181,39 @@ impl DomainCommand for ShareDocument {
     }
 }
 
-impl Command for ShareDocument {}
+impl Command for ShareDocument {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
+
+/// Revoke a user's shared access to a document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeShare {
+    /// Document ID
+    pub document_id: crate::value_objects::DocumentId,
+    /// User whose access is revoked
+    pub revoke_from: Uuid,
+    /// Revoked by
+    pub revoked_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
+}
+
+impl DomainCommand for RevokeShare {
+    type Aggregate = crate::Document;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(*self.document_id.as_uuid()))
+    }
+}
+
+impl Command for RevokeShare {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Change document state
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -157,6 +226,9 @@ pub struct ChangeState {
     pub reason: String,
     /// Changed by
     pub changed_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for ChangeState {
@@ -167,7 +239,11 @@ impl DomainCommand for ChangeState {
     }
 }
 
-impl Command for ChangeState {}
+impl Command for ChangeState {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Update document metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -178,6 +254,9 @@ pub struct UpdateDocumentMetadata {
     pub metadata: DocumentMetadata,
     /// Who is updating
     pub updated_by: String,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for UpdateDocumentMetadata {
@@ -188,7 +267,39 @@ impl DomainCommand for UpdateDocumentMetadata {
     }
 }
 
-impl Command for UpdateDocumentMetadata {}
+impl Command for UpdateDocumentMetadata {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
+
+/// Change only the given metadata fields
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchDocumentMetadata {
+    /// The ID of the document to update
+    pub document_id: Uuid,
+    /// Fields to change
+    pub patch: crate::value_objects::DocumentMetadataPatch,
+    /// Who is updating
+    pub updated_by: String,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
+}
+
+impl DomainCommand for PatchDocumentMetadata {
+    type Aggregate = crate::Document;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.document_id))
+    }
+}
+
+impl Command for PatchDocumentMetadata {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Archive a document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -201,6 +312,9 @@ pub struct ArchiveDocument {
     pub retention_days: Option<u32>,
     /// Who is archiving
     pub archived_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for ArchiveDocument {
@@ -211,7 +325,11 @@ impl DomainCommand for ArchiveDocument {
     }
 }
 
-impl Command for ArchiveDocument {}
+impl Command for ArchiveDocument {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Fork a document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -297,6 +415,9 @@ pub struct LinkDocuments {
     pub description: Option<String>,
     /// Who is creating the link
     pub linked_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
 }
 
 impl DomainCommand for LinkDocuments {
@@ -307,7 +428,11 @@ impl DomainCommand for LinkDocuments {
     }
 }
 
-impl Command for LinkDocuments {}
+impl Command for LinkDocuments {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
 
 /// Merge changes from a forked document
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -472,6 +597,34 @@ impl DomainCommand for AddToCollection {
 
 impl Command for AddToCollection {}
 
+/// Remove document from collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveDocumentFromCollection {
+    /// Document ID
+    pub document_id: crate::value_objects::DocumentId,
+    /// Collection ID
+    pub collection_id: Uuid,
+    /// Who is removing from collection
+    pub removed_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
+}
+
+impl DomainCommand for RemoveDocumentFromCollection {
+    type Aggregate = crate::Document;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(*self.document_id.as_uuid()))
+    }
+}
+
+impl Command for RemoveDocumentFromCollection {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
+
 /// Import external document
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportDocument {
@@ -520,6 +673,152 @@ impl DomainCommand for ExportDocument {
 
 impl Command for ExportDocument {}
 
+/// Save a search query under a name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSearch {
+    /// Saved search ID
+    pub search_id: Uuid,
+    /// Display name
+    pub name: String,
+    /// Query to re-run
+    pub query: crate::value_objects::SearchQuery,
+    /// Who owns the saved search
+    pub owner: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
+}
+
+impl DomainCommand for SaveSearch {
+    type Aggregate = crate::Document;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        None // Saved searches are not tied to a document
+    }
+}
+
+impl Command for SaveSearch {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
+
+/// Delete a saved search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteSavedSearch {
+    /// Saved search ID
+    pub search_id: Uuid,
+    /// Who is deleting it
+    pub deleted_by: Uuid,
+    /// Key for deduplicating redelivered commands
+    #[serde(default)]
+    pub idempotency_key: Option<Uuid>,
+}
+
+impl DomainCommand for DeleteSavedSearch {
+    type Aggregate = crate::Document;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        None
+    }
+}
+
+impl Command for DeleteSavedSearch {
+    fn idempotency_key(&self) -> Option<Uuid> {
+        self.idempotency_key
+    }
+}
+
+macro_rules! document_commands {
+    ($($variant:ident($ty:ty)),+ $(,)?) => {
+        /// Every document command, for exhaustive dispatch
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub enum DocumentCommand {
+            $($variant($ty),)+
+        }
+
+        $(
+            impl From<$ty> for DocumentCommand {
+                fn from(command: $ty) -> Self {
+                    Self::$variant(command)
+                }
+            }
+        )+
+
+        impl DocumentCommand {
+            /// Name of the wrapped command
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => stringify!($variant),)+
+                }
+            }
+
+            /// Key identifying redeliveries of the wrapped command
+            pub fn idempotency_key(&self) -> Option<Uuid> {
+                match self {
+                    $(Self::$variant(command) => Command::idempotency_key(command),)+
+                }
+            }
+
+            /// Recover the command from a boxed trait object
+            pub fn from_boxed(command: Box<dyn Command>) -> Option<Self> {
+                let command = command.into_any();
+                $(
+                    let command = match command.downcast::<$ty>() {
+                        Ok(command) => return Some(Self::$variant(*command)),
+                        Err(other) => other,
+                    };
+                )+
+                drop(command);
+                None
+            }
+        }
+    };
+}
+
+document_commands! {
+    UploadDocument(UploadDocument),
+    ClassifyDocument(ClassifyDocument),
+    CreateDocument(CreateDocument),
+    UpdateContent(UpdateContent),
+    ShareDocument(ShareDocument),
+    RevokeShare(RevokeShare),
+    ChangeState(ChangeState),
+    UpdateDocumentMetadata(UpdateDocumentMetadata),
+    PatchDocumentMetadata(PatchDocumentMetadata),
+    ArchiveDocument(ArchiveDocument),
+    ForkDocument(ForkDocument),
+    TagVersion(TagVersion),
+    AddComment(AddComment),
+    LinkDocuments(LinkDocuments),
+    MergeDocuments(MergeDocuments),
+    RollbackVersion(RollbackVersion),
+    ExtractEntities(ExtractEntities),
+    GenerateSummary(GenerateSummary),
+    ApplyTemplate(ApplyTemplate),
+    CreateCollection(CreateCollection),
+    AddToCollection(AddToCollection),
+    RemoveDocumentFromCollection(RemoveDocumentFromCollection),
+    ImportDocument(ImportDocument),
+    ExportDocument(ExportDocument),
+    SaveSearch(SaveSearch),
+    DeleteSavedSearch(DeleteSavedSearch),
+    CreateDocumentSuccessor(CreateDocumentSuccessor),
+    EditDocumentDirect(EditDocumentDirect),
+    EditDocumentPatch(EditDocumentPatch),
+    EditDocumentStructured(EditDocumentStructured),
+    RequestEditAccess(RequestEditAccess),
+    CancelEditSession(CancelEditSession),
+    TransformDocument(TransformDocument),
+    MergeDocumentEdits(MergeDocumentEdits),
+    RollbackDocument(RollbackDocument),
+    IngestDocumentContent(IngestDocumentContent),
+    CreateDocumentFromCid(CreateDocumentFromCid),
+    PromoteDocumentContent(PromoteDocumentContent),
+    UpdateIngestedMetadata(ingestion_commands::UpdateDocumentMetadata),
+    QuarantineDocumentContent(QuarantineDocumentContent),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -529,14 +828,15 @@ mod tests {
     
     // Helper to create test document info component
     fn create_test_info() -> DocumentInfoComponent {
-        DocumentInfoComponent {
-            title: "Test Document".to_string(),
-            description: Some("Test description".to_string()),
-            mime_type: "text/plain".to_string(),
-            filename: Some("test.txt".to_string()),
-            size_bytes: 1024,
-            language: Some("en".to_string()),
-        }
+        DocumentInfoComponent::builder()
+            .with_title("Test Document")
+            .with_description("Test description")
+            .with_mime_type("text/plain")
+            .with_filename("test.txt")
+            .with_size_bytes(1024)
+            .with_language("en")
+            .build()
+            .unwrap()
     }
     
     // Helper to create test CID
@@ -574,6 +874,7 @@ mod tests {
             is_chunked: false,
             chunk_cids: vec![],
             uploaded_by: user_id,
+            idempotency_key: None,
         };
         
         // Test command properties
@@ -608,6 +909,7 @@ mod tests {
             is_chunked: true,
             chunk_cids: chunk_cids.clone(),
             uploaded_by: Uuid::new_v4(),
+            idempotency_key: None,
         };
         
         assert!(command.is_chunked);
@@ -690,6 +992,8 @@ mod tests {
             content_blocks: content_blocks.clone(),
             change_summary: "Added introduction section".to_string(),
             updated_by: user_id,
+            version_bump: VersionBump::Minor,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, doc_id);
@@ -711,6 +1015,7 @@ mod tests {
             share_with,
             access_level: AccessLevel::Write,
             shared_by,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, doc_id);
@@ -730,6 +1035,7 @@ mod tests {
             new_state: DocumentState::InReview,
             reason: "Ready for review".to_string(),
             changed_by: user_id,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, doc_id);
@@ -748,6 +1054,7 @@ mod tests {
             document_id: doc_id,
             metadata: metadata.clone(),
             updated_by: "user123".to_string(),
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, doc_id);
@@ -770,6 +1077,7 @@ mod tests {
             reason: "Project completed".to_string(),
             retention_days: Some(2555), // 7 years
             archived_by: user_id,
+            idempotency_key: None,
         };
         
         assert_eq!(command.document_id, doc_id);
@@ -786,6 +1094,7 @@ mod tests {
             reason: "Immediate archive".to_string(),
             retention_days: None,
             archived_by: Uuid::new_v4(),
+            idempotency_key: None,
         };
         
         assert!(command.retention_days.is_none());
@@ -897,6 +1206,7 @@ mod tests {
             link_type: LinkType::References,
             description: Some("References for background information".to_string()),
             linked_by: user_id,
+            idempotency_key: None,
         };
         
         assert_eq!(command.source_id, source_id);
@@ -1086,6 +1396,26 @@ mod tests {
         assert_eq!(command.added_by, user_id);
     }
 
+    #[test]
+    fn test_remove_document_from_collection_command() {
+        // US-005: Test command validation
+        let doc_id = DocumentId::new();
+        let collection_id = Uuid::new_v4();
+        let key = Uuid::new_v4();
+
+        let command = RemoveDocumentFromCollection {
+            document_id: doc_id,
+            collection_id,
+            removed_by: Uuid::new_v4(),
+            idempotency_key: Some(key),
+        };
+
+        assert_eq!(command.collection_id, collection_id);
+        let aggregate_id = command.aggregate_id().unwrap();
+        assert_eq!(*aggregate_id.as_uuid(), *doc_id.as_uuid());
+        assert_eq!(Command::idempotency_key(&command), Some(key));
+    }
+
     #[test]
     fn test_import_document_command() {
         // US-005: Test command validation
@@ -1139,6 +1469,7 @@ mod tests {
             is_chunked: false,
             chunk_cids: vec![],
             uploaded_by: Uuid::new_v4(),
+            idempotency_key: None,
         };
         
         // Should not panic - Debug is implemented
@@ -1205,12 +1536,15 @@ mod tests {
                 content_blocks: vec![],
                 change_summary: "Test".to_string(),
                 updated_by: Uuid::new_v4(),
+                version_bump: VersionBump::default(),
+                idempotency_key: None,
             }),
             Box::new(ShareDocument {
                 document_id: doc_id.clone(),
                 share_with: Uuid::new_v4(),
                 access_level: AccessLevel::Read,
                 shared_by: Uuid::new_v4(),
+                idempotency_key: None,
             }),
         ];
         
@@ -34,6 +34,9 @@ pub struct ContentUpdated {
     pub change_summary: String,
     pub updated_by: Uuid,
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Document version after the update
+    #[serde(default)]
+    pub version: DocumentVersion,
 }
 
 /// Document state changed
@@ -78,6 +81,15 @@ pub struct DocumentShared {
     pub shared_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A user's shared access was revoked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShareRevoked {
+    pub document_id: DocumentId,
+    pub revoked_from: Uuid,
+    pub revoked_by: String,
+    pub revoked_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Document was deleted
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentDeleted {
@@ -243,6 +255,9 @@ pub struct DocumentVersionRestored {
 pub struct TemplateApplied {
     pub document_id: DocumentId,
     pub template_id: TemplateId,
+    /// Version of the template that was rendered
+    #[serde(default)]
+    pub template_version: DocumentVersion,
     pub variables: HashMap<String, String>,
     pub applied_by: Uuid,
     pub applied_at: chrono::DateTime<chrono::Utc>,
@@ -269,6 +284,29 @@ pub struct DocumentAddedToCollection {
     pub added_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Document removed from collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentRemovedFromCollection {
+    pub document_id: DocumentId,
+    pub collection_id: Uuid,
+    pub removed_by: Uuid,
+    pub removed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Search query was saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchSaved {
+    pub search: SavedSearch,
+}
+
+/// Saved search was deleted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearchDeleted {
+    pub search_id: Uuid,
+    pub deleted_by: Uuid,
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Document was imported
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentImported {
@@ -459,6 +497,7 @@ mod tests {
             change_summary: "Updated introduction".to_string(),
             updated_by: user_id,
             updated_at: now,
+            version: DocumentVersion::new(1, 1, 0),
         };
 
         assert_eq!(event.document_id, doc_id);
@@ -684,6 +723,8 @@ mod tests {
                 target_content: "Target content".to_string(),
                 source_content: "Source content".to_string(),
                 base_content: Some("Base content".to_string()),
+                target_block: None,
+                source_block: None,
                 conflict_type: ConflictType::ContentModified,
             }
         ];
@@ -812,6 +853,7 @@ mod tests {
         let event = TemplateApplied {
             document_id: doc_id.clone(),
             template_id,
+            template_version: DocumentVersion::new(1, 2, 0),
             variables: variables.clone(),
             applied_by: user_id,
             applied_at: now,
@@ -819,6 +861,7 @@ mod tests {
 
         assert_eq!(event.document_id, doc_id);
         assert_eq!(event.template_id, template_id);
+        assert_eq!(event.template_version, DocumentVersion::new(1, 2, 0));
         assert_eq!(event.variables, variables);
         assert_eq!(event.applied_by, user_id);
         assert_eq!(event.applied_at, now);
@@ -1052,6 +1095,7 @@ mod tests {
                 change_summary: "Update".to_string(),
                 updated_by: user_id,
                 updated_at: now,
+                version: DocumentVersion::default(),
             }.document_id,
             
             StateChanged {
@@ -1158,10 +1202,45 @@ mod tests {
         assert!(delete_event.hard_delete);
         assert!(delete_event.reason.is_none());
     }
+
+    #[test]
+    fn test_domain_events_sort_by_occurred_at() {
+        let doc_id = DocumentId::new();
+        let user = Uuid::new_v4();
+        let start = chrono::Utc::now();
+        let at = |minutes| start + chrono::Duration::minutes(minutes);
+
+        let archived = DocumentDomainEvent::DocumentArchived(DocumentArchived {
+            document_id: doc_id,
+            reason: "done".to_string(),
+            archived_by: user,
+            archived_at: at(2),
+            metadata: HashMap::new(),
+        });
+        let deleted = DocumentDomainEvent::DocumentDeleted(DocumentDeleted {
+            document_id: doc_id,
+            hard_delete: false,
+            reason: None,
+            deleted_by: user,
+            deleted_at: at(3),
+        });
+        let restored = DocumentDomainEvent::DocumentRestored(DocumentRestored {
+            document_id: doc_id,
+            restored_from: RestorationSource::Archive,
+            restored_by: user,
+            restored_at: at(1),
+            reason: None,
+        });
+        assert_eq!(archived.occurred_at(), at(2));
+
+        let mut log = vec![deleted.clone(), restored.clone(), archived.clone()];
+        log.sort_by_key(|event| event.occurred_at());
+        assert_eq!(log, vec![restored, archived, deleted]);
+    }
 }
 
 /// Domain event enum for all document events
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DocumentDomainEvent {
     /// Document was uploaded
     DocumentUploaded(DocumentUploaded),
@@ -1169,6 +1248,8 @@ pub enum DocumentDomainEvent {
     DocumentMetadataUpdated(DocumentMetadataUpdated),
     /// Document was shared
     DocumentShared(DocumentShared),
+    /// Shared access was revoked
+    ShareRevoked(ShareRevoked),
     /// Document was deleted
     DocumentDeleted(DocumentDeleted),
     /// Document was archived
@@ -1211,6 +1292,8 @@ pub enum DocumentDomainEvent {
     CollectionCreated(CollectionCreated),
     /// Document added to collection
     DocumentAddedToCollection(DocumentAddedToCollection),
+    /// Document removed from collection
+    DocumentRemovedFromCollection(DocumentRemovedFromCollection),
     /// Document was imported
     DocumentImported(DocumentImported),
     /// Document was exported
@@ -1245,4 +1328,211 @@ pub enum DocumentDomainEvent {
     CidChainVerified(CidChainVerified),
     /// Document edit failed
     DocumentEditFailed(DocumentEditFailed),
+    /// Search query was saved
+    SearchSaved(SearchSaved),
+    /// Saved search was deleted
+    SavedSearchDeleted(SavedSearchDeleted),
+}
+
+impl DocumentDomainEvent {
+    /// When the event happened, taken from the variant's own timestamp field
+    pub fn occurred_at(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            Self::DocumentUploaded(e) => e.uploaded_at,
+            Self::DocumentMetadataUpdated(e) => e.updated_at,
+            Self::DocumentShared(e) => e.shared_at,
+            Self::ShareRevoked(e) => e.revoked_at,
+            Self::DocumentDeleted(e) => e.deleted_at,
+            Self::DocumentArchived(e) => e.archived_at,
+            Self::DocumentCreated(e) => e.created_at,
+            Self::ContentUpdated(e) => e.updated_at,
+            Self::StateChanged(e) => e.changed_at,
+            Self::DocumentForked(e) => e.forked_at,
+            Self::VersionTagged(e) => e.tag.tagged_at,
+            Self::CommentAdded(e) => e.comment.created_at,
+            Self::DocumentsLinked(e) => e.linked_at,
+            Self::DocumentsMerged(e) => e.merged_at,
+            Self::VersionRolledBack(e) => e.rolled_back_at,
+            Self::EntitiesExtracted(e) => e.extracted_at,
+            Self::SummaryGenerated(e) => e.generated_at,
+            Self::DocumentClassified(e) => e.classified_at,
+            Self::DocumentContentUpdated(e) => e.updated_at,
+            Self::DocumentTagged(e) => e.tagged_at,
+            Self::DocumentVersionCreated(e) => e.created_at,
+            Self::DocumentVersionRestored(e) => e.restored_at,
+            Self::TemplateApplied(e) => e.applied_at,
+            Self::CollectionCreated(e) => e.created_at,
+            Self::DocumentAddedToCollection(e) => e.added_at,
+            Self::DocumentRemovedFromCollection(e) => e.removed_at,
+            Self::DocumentImported(e) => e.imported_at,
+            Self::DocumentExported(e) => e.exported_at,
+            Self::DocumentRestored(e) => e.restored_at,
+            Self::VersionsCompared(e) => e.compared_at,
+            Self::DocumentSuccessorCreated(e) => e.edited_at,
+            Self::DocumentEditedDirect(e) => e.edited_at,
+            Self::DocumentEditedPatch(e) => e.edited_at,
+            Self::DocumentEditedStructured(e) => e.edited_at,
+            Self::EditAccessRequested(e) => e.requested_at,
+            Self::EditAccessGranted(e) => e.granted_at,
+            Self::EditSessionCancelled(e) => e.cancelled_at,
+            Self::DocumentTransformed(e) => e.transformed_at,
+            Self::DocumentEditsMerged(e) => e.merged_at,
+            Self::DocumentRolledBack(e) => e.rolled_back_at,
+            Self::CidChainVerified(e) => e.verified_at,
+            Self::DocumentEditFailed(e) => e.failed_at,
+            Self::SearchSaved(e) => e.search.created_at,
+            Self::SavedSearchDeleted(e) => e.deleted_at,
+        }
+    }
+
+    /// Documents the event is about; empty for collection and saved-search events
+    pub fn document_ids(&self) -> Vec<DocumentId> {
+        match self {
+            Self::DocumentUploaded(e) => vec![e.document_id],
+            Self::DocumentMetadataUpdated(e) => vec![e.document_id],
+            Self::DocumentShared(e) => vec![e.document_id],
+            Self::ShareRevoked(e) => vec![e.document_id],
+            Self::DocumentDeleted(e) => vec![e.document_id],
+            Self::DocumentArchived(e) => vec![e.document_id],
+            Self::DocumentCreated(e) => vec![e.document_id],
+            Self::ContentUpdated(e) => vec![e.document_id],
+            Self::StateChanged(e) => vec![e.document_id],
+            Self::DocumentForked(e) => vec![e.original_id, e.fork_id],
+            Self::VersionTagged(e) => vec![e.document_id],
+            Self::CommentAdded(e) => vec![e.document_id],
+            Self::DocumentsLinked(e) => vec![e.source_id, e.target_id],
+            Self::DocumentsMerged(e) => vec![e.target_id, e.source_id],
+            Self::VersionRolledBack(e) => vec![e.document_id],
+            Self::EntitiesExtracted(e) => vec![e.document_id],
+            Self::SummaryGenerated(e) => vec![e.document_id],
+            Self::DocumentClassified(e) => vec![e.document_id],
+            Self::DocumentContentUpdated(e) => vec![e.document_id],
+            Self::DocumentTagged(e) => vec![e.document_id],
+            Self::DocumentVersionCreated(e) => vec![e.document_id],
+            Self::DocumentVersionRestored(e) => vec![e.document_id],
+            Self::TemplateApplied(e) => vec![e.document_id],
+            Self::DocumentAddedToCollection(e) => vec![e.document_id],
+            Self::DocumentRemovedFromCollection(e) => vec![e.document_id],
+            Self::DocumentImported(e) => vec![e.document_id],
+            Self::DocumentExported(e) => vec![e.document_id],
+            Self::DocumentRestored(e) => vec![e.document_id],
+            Self::VersionsCompared(e) => vec![e.document_id],
+            Self::DocumentSuccessorCreated(e) => vec![e.document_id],
+            Self::DocumentEditedDirect(e) => vec![e.document_id],
+            Self::DocumentEditedPatch(e) => vec![e.document_id],
+            Self::DocumentEditedStructured(e) => vec![e.document_id],
+            Self::EditAccessRequested(e) => vec![e.document_id],
+            Self::EditAccessGranted(e) => vec![e.document_id],
+            Self::EditSessionCancelled(e) => vec![e.document_id],
+            Self::DocumentTransformed(e) => vec![e.document_id],
+            Self::DocumentEditsMerged(e) => vec![e.document_id],
+            Self::DocumentRolledBack(e) => vec![e.document_id],
+            Self::CidChainVerified(e) => vec![e.document_id],
+            Self::DocumentEditFailed(e) => vec![e.document_id],
+            Self::CollectionCreated(_) | Self::SearchSaved(_) | Self::SavedSearchDeleted(_) => Vec::new(),
+        }
+    }
+
+    /// Broad category of the event, for filtering history
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::DocumentUploaded(_)
+            | Self::DocumentCreated(_)
+            | Self::DocumentDeleted(_)
+            | Self::DocumentArchived(_)
+            | Self::DocumentRestored(_)
+            | Self::StateChanged(_)
+            | Self::DocumentForked(_)
+            | Self::DocumentSuccessorCreated(_) => EventKind::Lifecycle,
+            Self::ContentUpdated(_)
+            | Self::DocumentContentUpdated(_)
+            | Self::DocumentTransformed(_)
+            | Self::TemplateApplied(_) => EventKind::Content,
+            Self::DocumentEditedDirect(_)
+            | Self::DocumentEditedPatch(_)
+            | Self::DocumentEditedStructured(_)
+            | Self::EditAccessRequested(_)
+            | Self::EditAccessGranted(_)
+            | Self::EditSessionCancelled(_)
+            | Self::DocumentEditsMerged(_)
+            | Self::DocumentEditFailed(_)
+            | Self::DocumentsMerged(_) => EventKind::Edit,
+            Self::DocumentMetadataUpdated(_)
+            | Self::DocumentTagged(_)
+            | Self::DocumentClassified(_) => EventKind::Metadata,
+            Self::DocumentShared(_) | Self::ShareRevoked(_) => EventKind::Sharing,
+            Self::VersionTagged(_)
+            | Self::VersionRolledBack(_)
+            | Self::DocumentVersionCreated(_)
+            | Self::DocumentVersionRestored(_)
+            | Self::DocumentRolledBack(_)
+            | Self::VersionsCompared(_) => EventKind::Version,
+            Self::CommentAdded(_) => EventKind::Comment,
+            Self::DocumentsLinked(_) => EventKind::Link,
+            Self::CollectionCreated(_)
+            | Self::DocumentAddedToCollection(_)
+            | Self::DocumentRemovedFromCollection(_) => EventKind::Collection,
+            Self::EntitiesExtracted(_) | Self::SummaryGenerated(_) => EventKind::Analysis,
+            Self::DocumentImported(_) | Self::DocumentExported(_) => EventKind::Transfer,
+            Self::CidChainVerified(_) => EventKind::Verification,
+            Self::SearchSaved(_) | Self::SavedSearchDeleted(_) => EventKind::Search,
+        }
+    }
+}
+
+/// Category of a [`DocumentDomainEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    /// Creation, deletion, archival and state changes
+    Lifecycle,
+    /// Content replaced or transformed
+    Content,
+    /// Edits, edit sessions and merges
+    Edit,
+    /// Metadata, tags and classification
+    Metadata,
+    /// Sharing granted or revoked
+    Sharing,
+    /// Version tags, creation, restores and rollbacks
+    Version,
+    Comment,
+    Link,
+    /// Collection membership
+    Collection,
+    /// Extracted entities and summaries
+    Analysis,
+    /// Imports and exports
+    Transfer,
+    /// CID chain verification
+    Verification,
+    /// Saved searches
+    Search,
+}
+
+impl EventKind {
+    /// Whether events of this kind change document content
+    pub fn is_content_change(self) -> bool {
+        matches!(self, Self::Content | Self::Edit)
+    }
+}
+
+/// A [`DocumentDomainEvent`] with the position it was appended at
+///
+/// Timestamps can tie, so replay orders by `sequence` instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    /// Position in the document's own stream, starting at 1
+    pub sequence: u64,
+    /// Position across all streams in the store, starting at 1
+    pub stream_position: u64,
+    pub event: DocumentDomainEvent,
+}
+
+impl EventEnvelope {
+    /// Events from `envelopes` in sequence order
+    pub fn in_sequence(envelopes: &[EventEnvelope]) -> Vec<&DocumentDomainEvent> {
+        let mut ordered: Vec<&EventEnvelope> = envelopes.iter().collect();
+        ordered.sort_by_key(|envelope| envelope.sequence);
+        ordered.into_iter().map(|envelope| &envelope.event).collect()
+    }
 }
@@ -1,9 +1,12 @@
 //! Document command handler
 
-use cim_domain::{AggregateRepository, DomainResult, DomainError};
+use cim_domain::{AggregateRepository, AggregateRoot, DomainResult, DomainError};
 use crate::{Document, commands::*, value_objects::{DocumentType, DocumentMetadata}, events::*};
 use async_trait::async_trait;
-use crate::aggregate::DocumentAggregate;
+use std::sync::Arc;
+use crate::services::{AccessAction, AccessAuditEntry, AuditSink, IdempotencyStore, Reservation};
+use crate::aggregate::{DocumentAggregate, RelationshipsComponent, RelationType};
+use crate::handlers::SavedSearchHandler;
 
 /// Trait for handling document commands
 #[async_trait]
@@ -14,12 +17,31 @@ pub trait DocumentCommandHandler: Send + Sync {
     /// Handle update metadata command
     async fn handle_update_metadata(&self, cmd: UpdateDocumentMetadata) -> DomainResult<Vec<DocumentDomainEvent>>;
     
+    /// Handle patch metadata command
+    async fn handle_patch_metadata(&self, cmd: PatchDocumentMetadata) -> DomainResult<Vec<DocumentDomainEvent>>;
+    
     /// Handle share document command
     async fn handle_share_document(&self, cmd: ShareDocument) -> DomainResult<Vec<DocumentDomainEvent>>;
     
+    /// Handle revoke share command
+    async fn handle_revoke_share(&self, cmd: RevokeShare) -> DomainResult<Vec<DocumentDomainEvent>>;
+    
     /// Handle archive document command
     async fn handle_archive_document(&self, cmd: ArchiveDocument) -> DomainResult<Vec<DocumentDomainEvent>>;
     
+    /// Handle change state command
+    async fn handle_change_state(&self, cmd: ChangeState) -> DomainResult<Vec<DocumentDomainEvent>>;
+    
+    /// Handle update content command
+    async fn handle_update_content(&self, _cmd: UpdateContent) -> DomainResult<Vec<DocumentDomainEvent>> {
+        Err(DomainError::generic("Updating content is not supported by this handler"))
+    }
+    
+    /// Handle link documents command
+    async fn handle_link_documents(&self, _cmd: LinkDocuments) -> DomainResult<Vec<DocumentDomainEvent>> {
+        Err(DomainError::generic("Linking documents is not supported by this handler"))
+    }
+    
     /// Handle edit document direct command
     async fn handle_edit_document_direct(&self, cmd: EditDocumentDirect) -> DomainResult<Vec<DocumentDomainEvent>>;
     
@@ -37,21 +59,205 @@ pub trait DocumentCommandHandler: Send + Sync {
     
     /// Handle rollback document command
     async fn handle_rollback_document(&self, cmd: RollbackDocument) -> DomainResult<Vec<DocumentDomainEvent>>;
+    
+    /// Handle remove document from collection command
+    async fn handle_remove_from_collection(&self, _cmd: RemoveDocumentFromCollection) -> DomainResult<Vec<DocumentDomainEvent>> {
+        Err(DomainError::generic("Collections are not supported by this handler"))
+    }
+    
+    /// Handle save search command
+    async fn handle_save_search(&self, _cmd: SaveSearch) -> DomainResult<Vec<DocumentDomainEvent>> {
+        Err(DomainError::generic("Saved searches are not supported by this handler"))
+    }
+    
+    /// Handle delete saved search command
+    async fn handle_delete_saved_search(&self, _cmd: DeleteSavedSearch) -> DomainResult<Vec<DocumentDomainEvent>> {
+        Err(DomainError::generic("Saved searches are not supported by this handler"))
+    }
+    
+    /// Store of recently-seen idempotency keys, if deduplication is enabled
+    fn idempotency_store(&self) -> Option<&dyn IdempotencyStore> {
+        None
+    }
+    
+    /// Handle a boxed command
+    async fn handle_command(&self, command: Box<dyn Command>) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let command = DocumentCommand::from_boxed(command)
+            .ok_or_else(|| DomainError::ValidationError("Unsupported command type".to_string()))?;
+        self.handle(command).await
+    }
+    
+    /// Handle a command, replaying cached events for a seen idempotency key
+    ///
+    /// The key is reserved before the command runs, so a concurrent redelivery
+    /// is refused rather than executed a second time.
+    async fn handle(&self, command: DocumentCommand) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let (Some(key), Some(store)) = (command.idempotency_key(), self.idempotency_store()) else {
+            return self.dispatch(command).await;
+        };
+        match store.reserve(key) {
+            Reservation::Reserved => {}
+            Reservation::Completed(events) => return Ok(events),
+            Reservation::InFlight => {
+                return Err(DomainError::ValidationError(format!(
+                    "Command with idempotency key {key} is already being handled"
+                )))
+            }
+        }
+        
+        match self.dispatch(command).await {
+            Ok(events) => {
+                store.complete(key, events.clone());
+                Ok(events)
+            }
+            Err(error) => {
+                store.release(&key);
+                Err(error)
+            }
+        }
+    }
+    
+    /// Dispatch a command to its handler
+    async fn dispatch(&self, command: DocumentCommand) -> DomainResult<Vec<DocumentDomainEvent>> {
+        match command {
+            DocumentCommand::UploadDocument(cmd) => self.handle_upload_document(cmd).await,
+            DocumentCommand::UpdateDocumentMetadata(cmd) => self.handle_update_metadata(cmd).await,
+            DocumentCommand::PatchDocumentMetadata(cmd) => self.handle_patch_metadata(cmd).await,
+            DocumentCommand::ShareDocument(cmd) => self.handle_share_document(cmd).await,
+            DocumentCommand::RevokeShare(cmd) => self.handle_revoke_share(cmd).await,
+            DocumentCommand::ArchiveDocument(cmd) => self.handle_archive_document(cmd).await,
+            DocumentCommand::ChangeState(cmd) => self.handle_change_state(cmd).await,
+            DocumentCommand::UpdateContent(cmd) => self.handle_update_content(cmd).await,
+            DocumentCommand::LinkDocuments(cmd) => self.handle_link_documents(cmd).await,
+            DocumentCommand::EditDocumentDirect(cmd) => self.handle_edit_document_direct(cmd).await,
+            DocumentCommand::EditDocumentPatch(cmd) => self.handle_edit_document_patch(cmd).await,
+            DocumentCommand::EditDocumentStructured(cmd) => self.handle_edit_document_structured(cmd).await,
+            DocumentCommand::TransformDocument(cmd) => self.handle_transform_document(cmd).await,
+            DocumentCommand::MergeDocumentEdits(cmd) => self.handle_merge_document_edits(cmd).await,
+            DocumentCommand::RollbackDocument(cmd) => self.handle_rollback_document(cmd).await,
+            DocumentCommand::RemoveDocumentFromCollection(cmd) => self.handle_remove_from_collection(cmd).await,
+            DocumentCommand::SaveSearch(cmd) => self.handle_save_search(cmd).await,
+            DocumentCommand::DeleteSavedSearch(cmd) => self.handle_delete_saved_search(cmd).await,
+            unsupported @ (DocumentCommand::ClassifyDocument(_)
+            | DocumentCommand::CreateDocument(_)
+            | DocumentCommand::ForkDocument(_)
+            | DocumentCommand::TagVersion(_)
+            | DocumentCommand::AddComment(_)
+            | DocumentCommand::MergeDocuments(_)
+            | DocumentCommand::RollbackVersion(_)
+            | DocumentCommand::ExtractEntities(_)
+            | DocumentCommand::GenerateSummary(_)
+            | DocumentCommand::ApplyTemplate(_)
+            | DocumentCommand::CreateCollection(_)
+            | DocumentCommand::AddToCollection(_)
+            | DocumentCommand::ImportDocument(_)
+            | DocumentCommand::ExportDocument(_)
+            | DocumentCommand::CreateDocumentSuccessor(_)
+            | DocumentCommand::RequestEditAccess(_)
+            | DocumentCommand::CancelEditSession(_)
+            | DocumentCommand::IngestDocumentContent(_)
+            | DocumentCommand::CreateDocumentFromCid(_)
+            | DocumentCommand::PromoteDocumentContent(_)
+            | DocumentCommand::UpdateIngestedMetadata(_)
+            | DocumentCommand::QuarantineDocumentContent(_)) => Err(DomainError::ValidationError(
+                format!("No handler for command {}", unsupported.name()),
+            )),
+        }
+    }
+    
+    /// Apply commands in order, stopping at the first failure
+    ///
+    /// The error carries the failing index and the events produced before it.
+    async fn handle_batch(&self, commands: Vec<Box<dyn Command>>) -> Result<Vec<DocumentDomainEvent>, BatchError> {
+        let mut events = Vec::new();
+        for (index, command) in commands.into_iter().enumerate() {
+            match self.handle_command(command).await {
+                Ok(produced) => events.extend(produced),
+                Err(error) => return Err(BatchError { index, error, events }),
+            }
+        }
+        Ok(events)
+    }
+    
+    /// Apply every command in order, collecting each one's result
+    async fn handle_batch_all(&self, commands: Vec<Box<dyn Command>>) -> Vec<DomainResult<Vec<DocumentDomainEvent>>> {
+        let mut results = Vec::with_capacity(commands.len());
+        for command in commands {
+            results.push(self.handle_command(command).await);
+        }
+        results
+    }
+}
+
+/// A batch stopped at a failing command
+#[derive(Debug, thiserror::Error)]
+#[error("batch command {index} failed: {error}")]
+pub struct BatchError {
+    /// Position of the failing command
+    pub index: usize,
+    pub error: DomainError,
+    /// Events produced by the commands before `index`
+    pub events: Vec<DocumentDomainEvent>,
 }
 
 /// Implementation of document command handler
 pub struct DocumentCommandHandlerImpl<R: AggregateRepository<Document>> {
     repository: R,
+    audit_sink: Option<Arc<dyn AuditSink>>,
+    idempotency_store: Option<Arc<dyn IdempotencyStore>>,
+    saved_searches: Option<Arc<SavedSearchHandler>>,
 }
 
 impl<R: AggregateRepository<Document>> DocumentCommandHandlerImpl<R> {
     pub fn new(repository: R) -> Self {
-        Self { repository }
+        Self { repository, audit_sink: None, idempotency_store: None, saved_searches: None }
+    }
+
+    /// Record sharing changes on audited documents to `sink`
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(sink);
+        self
+    }
+
+    /// Deduplicate keyed commands through `store`
+    pub fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency_store = Some(store);
+        self
+    }
+
+    /// Route saved search commands to `handler`
+    pub fn with_saved_searches(mut self, handler: Arc<SavedSearchHandler>) -> Self {
+        self.saved_searches = Some(handler);
+        self
+    }
+
+    /// Saved search handler, if saved searches are enabled
+    fn saved_searches(&self) -> DomainResult<&SavedSearchHandler> {
+        self.saved_searches.as_deref()
+            .ok_or_else(|| DomainError::generic("Saved searches are not enabled"))
+    }
+
+    /// Record an access change on an audited document
+    fn audit<T>(
+        &self,
+        audited: bool,
+        document_id: crate::value_objects::DocumentId,
+        user: uuid::Uuid,
+        action: AccessAction,
+        result: &DomainResult<T>,
+    ) {
+        if let Some(sink) = self.audit_sink.as_ref().filter(|_| audited) {
+            sink.record(document_id, AccessAuditEntry::new(user, action, result.is_ok()));
+        }
     }
 }
 
 #[async_trait]
 impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for DocumentCommandHandlerImpl<R> {
+    fn idempotency_store(&self) -> Option<&dyn IdempotencyStore> {
+        self.idempotency_store.as_deref()
+    }
+    
     async fn handle_upload_document(&self, cmd: UploadDocument) -> DomainResult<Vec<DocumentDomainEvent>> {
         // Create new document aggregate
         let document_id = cmd.document_id;
@@ -76,7 +282,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
             std::path::PathBuf::from(cmd.info.filename.clone().unwrap_or_default()),
             cmd.content_cid,
             metadata,
-            DocumentType::Other("Unknown".to_string()), // Default type
+            DocumentType::from_mime(&cmd.info.mime_type),
             cmd.uploaded_by.to_string(),
         )?;
         
@@ -118,6 +324,26 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         Ok(domain_events)
     }
     
+    async fn handle_patch_metadata(&self, cmd: PatchDocumentMetadata) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(cmd.document_id);
+        let document = self.repository.load(entity_id)
+            .map_err(DomainError::InternalError)?
+            .ok_or_else(|| cim_domain::DomainError::EntityNotFound { 
+                entity_type: "Document".to_string(),
+                id: cmd.document_id.to_string()
+            })?;
+        let mut aggregate = DocumentAggregate::from(document);
+        
+        let events = aggregate.patch_metadata(cmd.patch, cmd.updated_by)?;
+        
+        self.repository.save(&aggregate.into())
+            .map_err(DomainError::InternalError)?;
+        
+        Ok(events.into_iter()
+            .map(DocumentDomainEvent::DocumentMetadataUpdated)
+            .collect())
+    }
+    
     async fn handle_share_document(&self, cmd: ShareDocument) -> DomainResult<Vec<DocumentDomainEvent>> {
         // Load existing aggregate
         let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
@@ -127,6 +353,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
                 entity_type: "Document".to_string(),
                 id: cmd.document_id.to_string()
             })?;
+        let audited = document.is_audited();
         let mut aggregate = DocumentAggregate::from(document);
         
         // Convert access level to permissions
@@ -141,11 +368,15 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         shared_with.insert(cmd.share_with.to_string());
         
         // Process the share command
-        let events = aggregate.share(shared_with, permissions, cmd.shared_by.to_string())?;
-        
-        // Save updated aggregate
-        self.repository.save(&aggregate.into())
-            .map_err(DomainError::InternalError)?;
+        let result = aggregate.share(shared_with, permissions, cmd.shared_by.to_string())
+            .and_then(|events| {
+                // Save updated aggregate
+                self.repository.save(&aggregate.into())
+                    .map_err(DomainError::InternalError)?;
+                Ok(events)
+            });
+        self.audit(audited, cmd.document_id, cmd.shared_by, AccessAction::Share { with: cmd.share_with, level: cmd.access_level }, &result);
+        let events = result?;
         
         // Convert to domain events
         let domain_events = events.into_iter()
@@ -155,6 +386,37 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         Ok(domain_events)
     }
     
+    async fn handle_revoke_share(&self, cmd: RevokeShare) -> DomainResult<Vec<DocumentDomainEvent>> {
+        // Load existing aggregate
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
+        let document = self.repository.load(entity_id)
+            .map_err(DomainError::InternalError)?
+            .ok_or_else(|| cim_domain::DomainError::EntityNotFound { 
+                entity_type: "Document".to_string(),
+                id: cmd.document_id.to_string()
+            })?;
+        let audited = document.is_audited();
+        let mut aggregate = DocumentAggregate::from(document);
+        
+        // Process the revoke command
+        let result = aggregate.revoke_share(cmd.revoke_from, cmd.revoked_by.to_string())
+            .and_then(|events| {
+                // Save updated aggregate
+                self.repository.save(&aggregate.into())
+                    .map_err(DomainError::InternalError)?;
+                Ok(events)
+            });
+        self.audit(audited, cmd.document_id, cmd.revoked_by, AccessAction::RevokeShare { from: cmd.revoke_from }, &result);
+        let events = result?;
+        
+        // Convert to domain events
+        let domain_events = events.into_iter()
+            .map(DocumentDomainEvent::ShareRevoked)
+            .collect();
+        
+        Ok(domain_events)
+    }
+    
     async fn handle_archive_document(&self, cmd: ArchiveDocument) -> DomainResult<Vec<DocumentDomainEvent>> {
         // Load existing aggregate
         let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(cmd.document_id);
@@ -181,6 +443,103 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         Ok(domain_events)
     }
     
+    async fn handle_change_state(&self, cmd: ChangeState) -> DomainResult<Vec<DocumentDomainEvent>> {
+        // Load existing aggregate
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
+        let document = self.repository.load(entity_id)
+            .map_err(DomainError::InternalError)?
+            .ok_or_else(|| cim_domain::DomainError::EntityNotFound { 
+                entity_type: "Document".to_string(),
+                id: cmd.document_id.to_string()
+            })?;
+        let mut aggregate = DocumentAggregate::from(document);
+        
+        // Process the state change, rejecting illegal transitions
+        let events = aggregate.change_state(cmd.new_state, cmd.reason, cmd.changed_by)?;
+        
+        // Save updated aggregate
+        self.repository.save(&aggregate.into())
+            .map_err(DomainError::InternalError)?;
+        
+        // Convert to domain events
+        let domain_events = events.into_iter()
+            .map(DocumentDomainEvent::StateChanged)
+            .collect();
+        
+        Ok(domain_events)
+    }
+    
+    async fn handle_link_documents(&self, cmd: LinkDocuments) -> DomainResult<Vec<DocumentDomainEvent>> {
+        // Load both ends of the link
+        let find = |document_id: crate::value_objects::DocumentId| -> DomainResult<Option<Document>> {
+            let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*document_id.as_uuid());
+            self.repository.load(entity_id).map_err(DomainError::InternalError)
+        };
+        let load = |document_id: crate::value_objects::DocumentId| -> DomainResult<Document> {
+            find(document_id)?.ok_or_else(|| cim_domain::DomainError::EntityNotFound {
+                entity_type: "Document".to_string(),
+                id: document_id.to_string()
+            })
+        };
+        let source_document = load(cmd.source_id)?;
+        let target_document = load(cmd.target_id)?;
+        
+        // Reject supersedes links that would close a version-chain cycle
+        if cmd.link_type == crate::value_objects::LinkType::Supersedes {
+            let mut edges = vec![(cmd.source_id, cmd.target_id, RelationType::Supersedes)];
+            let mut pending = vec![target_document.clone()];
+            let mut seen = std::collections::HashSet::from([cmd.target_id]);
+            while let Some(document) = pending.pop() {
+                let from = crate::value_objects::DocumentId::from(document.id());
+                let Some(relationships) = document.get_component::<RelationshipsComponent>() else {
+                    continue;
+                };
+                for relation in &relationships.related_documents {
+                    if relation.relation_type != RelationType::Supersedes {
+                        continue;
+                    }
+                    let to = crate::value_objects::DocumentId::from(relation.document_id);
+                    edges.push((from, to, RelationType::Supersedes));
+                    // A missing document ends the chain; a storage error aborts the link
+                    if seen.insert(to) {
+                        if let Some(next) = find(to)? {
+                            pending.push(next);
+                        }
+                    }
+                }
+            }
+            if let Some(cycle) = crate::aggregate::detect_relationship_cycle(cmd.source_id, &edges) {
+                let path: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+                return Err(DomainError::ValidationError(format!(
+                    "Link would create a supersedes cycle: {}",
+                    path.join(" -> ")
+                )));
+            }
+        }
+        
+        // Record the relation on both documents; nothing is saved unless both accept it
+        let original_source = source_document.clone();
+        let mut source = DocumentAggregate::from(source_document);
+        let mut target = DocumentAggregate::from(target_document);
+        let events = source.link(&mut target, cmd.link_type, cmd.description, cmd.linked_by)?;
+        
+        // Save both aggregates, restoring the source if the target cannot be saved
+        self.repository.save(&source.into())
+            .map_err(DomainError::InternalError)?;
+        if let Err(error) = self.repository.save(&target.into()) {
+            self.repository.save(&original_source)
+                .map_err(DomainError::InternalError)?;
+            return Err(DomainError::InternalError(error));
+        }
+        
+        // Convert to domain events
+        let domain_events = events.into_iter()
+            .map(DocumentDomainEvent::DocumentsLinked)
+            .collect();
+        
+        Ok(domain_events)
+    }
+    
     async fn handle_edit_document_direct(&self, cmd: EditDocumentDirect) -> DomainResult<Vec<DocumentDomainEvent>> {
         // Load existing aggregate
         let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
@@ -240,8 +599,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         // Apply patch to aggregate (simplified - would apply actual patch logic)
         if let Some(mut content_address) = aggregate.document.get_component::<crate::aggregate::ContentAddressComponent>() {
             content_address.metadata_cid = Some(cmd.base_cid.clone()); // Store patch reference
-            aggregate.document.remove_component::<crate::aggregate::ContentAddressComponent>().ok();
-            aggregate.document.add_component(content_address, &cmd.edited_by.to_string(), Some("Patch applied".to_string()))?;
+            aggregate.document.replace_component(content_address, &cmd.edited_by.to_string(), Some("Patch applied".to_string()))?;
         }
         
         // Create the patch edit event
@@ -288,8 +646,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
                         info.size_bytes += new_content.len() as u64;
                     },
                 }
-                aggregate.document.remove_component::<crate::aggregate::DocumentInfoComponent>().ok();
-                aggregate.document.add_component(info, &cmd.edited_by.to_string(), Some("Structured edit applied".to_string()))?;
+                aggregate.document.replace_component(info, &cmd.edited_by.to_string(), Some("Structured edit applied".to_string()))?;
             }
         }
         
@@ -323,8 +680,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         // Apply format transformation to aggregate
         if let Some(mut info) = aggregate.document.get_component::<crate::aggregate::DocumentInfoComponent>() {
             info.mime_type = cmd.target_format.clone();
-            aggregate.document.remove_component::<crate::aggregate::DocumentInfoComponent>().ok();
-            aggregate.document.add_component(info, &cmd.transformed_by.to_string(), Some("Format transformation".to_string()))?;
+            aggregate.document.replace_component(info, &cmd.transformed_by.to_string(), Some("Format transformation".to_string()))?;
         }
         
         // Create transformation metrics
@@ -367,8 +723,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         // Apply merge result to aggregate
         if let Some(mut content_address) = aggregate.document.get_component::<crate::aggregate::ContentAddressComponent>() {
             content_address.content_cid = cmd.base_cid.clone(); // Result CID in real implementation
-            aggregate.document.remove_component::<crate::aggregate::ContentAddressComponent>().ok();
-            aggregate.document.add_component(content_address, &cmd.merged_by.to_string(), Some("Merge applied".to_string()))?;
+            aggregate.document.replace_component(content_address, &cmd.merged_by.to_string(), Some("Merge applied".to_string()))?;
         }
         
         // Create the document edits merged event
@@ -407,8 +762,7 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         // Apply rollback to aggregate
         if let Some(mut content_address) = aggregate.document.get_component::<crate::aggregate::ContentAddressComponent>() {
             content_address.content_cid = cmd.target_cid.clone();
-            aggregate.document.remove_component::<crate::aggregate::ContentAddressComponent>().ok();
-            aggregate.document.add_component(content_address, &cmd.rolled_back_by.to_string(), Some("Rollback applied".to_string()))?;
+            aggregate.document.replace_component(content_address, &cmd.rolled_back_by.to_string(), Some("Rollback applied".to_string()))?;
         }
         
         // Create the document rolled back event
@@ -426,6 +780,56 @@ impl<R: AggregateRepository<Document> + Send + Sync> DocumentCommandHandler for
         
         Ok(vec![DocumentDomainEvent::DocumentRolledBack(event)])
     }
+    
+    async fn handle_update_content(&self, cmd: UpdateContent) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
+        let mut document = self.repository.load(entity_id)
+            .map_err(DomainError::InternalError)?
+            .ok_or_else(|| cim_domain::DomainError::EntityNotFound {
+                entity_type: "Document".to_string(),
+                id: cmd.document_id.to_string()
+            })?;
+        
+        let version = document.bump_version(cmd.version_bump, &cmd.updated_by.to_string())?;
+        self.repository.save(&document)
+            .map_err(DomainError::InternalError)?;
+        
+        Ok(vec![DocumentDomainEvent::ContentUpdated(ContentUpdated {
+            document_id: cmd.document_id,
+            content_blocks: cmd.content_blocks,
+            change_summary: cmd.change_summary,
+            updated_by: cmd.updated_by,
+            updated_at: chrono::Utc::now(),
+            version,
+        })])
+    }
+    
+    async fn handle_remove_from_collection(&self, cmd: RemoveDocumentFromCollection) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*cmd.document_id.as_uuid());
+        self.repository.load(entity_id)
+            .map_err(DomainError::InternalError)?
+            .ok_or_else(|| DomainError::EntityNotFound {
+                entity_type: "Document".to_string(),
+                id: cmd.document_id.to_string(),
+            })?;
+        
+        Ok(vec![DocumentDomainEvent::DocumentRemovedFromCollection(DocumentRemovedFromCollection {
+            document_id: cmd.document_id,
+            collection_id: cmd.collection_id,
+            removed_by: cmd.removed_by,
+            removed_at: chrono::Utc::now(),
+        })])
+    }
+    
+    async fn handle_save_search(&self, cmd: SaveSearch) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let event = self.saved_searches()?.save_search(cmd).await?;
+        Ok(vec![DocumentDomainEvent::SearchSaved(event)])
+    }
+    
+    async fn handle_delete_saved_search(&self, cmd: DeleteSavedSearch) -> DomainResult<Vec<DocumentDomainEvent>> {
+        let event = self.saved_searches()?.delete_saved_search(cmd).await?;
+        Ok(vec![DocumentDomainEvent::SavedSearchDeleted(event)])
+    }
 }
 
 #[cfg(test)]
@@ -500,4 +904,498 @@ mod tests {
         // US-024: Test rollback document handler method signature
         assert!(true);
     }
+    
+    /// Handler that counts uploads and archives unless the reason is "fail"; other commands produce no events
+    #[derive(Default)]
+    struct StubHandler {
+        uploads: std::sync::atomic::AtomicUsize,
+        idempotency: Option<crate::services::InMemoryIdempotencyStore>,
+    }
+    
+    #[async_trait]
+    impl DocumentCommandHandler for StubHandler {
+        fn idempotency_store(&self) -> Option<&dyn IdempotencyStore> {
+            self.idempotency.as_ref().map(|store| store as &dyn IdempotencyStore)
+        }
+        
+        async fn handle_upload_document(&self, cmd: UploadDocument) -> DomainResult<Vec<DocumentDomainEvent>> {
+            self.uploads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![DocumentDomainEvent::DocumentUploaded(DocumentUploaded {
+                document_id: cmd.document_id.into(),
+                path: std::path::PathBuf::from("report.pdf"),
+                content_cid: cmd.content_cid,
+                document_type: DocumentType::Pdf,
+                metadata: DocumentMetadata {
+                    title: cmd.info.title,
+                    description: None,
+                    tags: vec![],
+                    custom_attributes: std::collections::HashMap::new(),
+                    filename: cmd.info.filename,
+                    mime_type: Some(cmd.info.mime_type),
+                    size_bytes: Some(cmd.info.size_bytes),
+                    language: None,
+                    category: None,
+                    subcategories: None,
+                },
+                uploaded_by: cmd.uploaded_by.to_string(),
+                uploaded_at: chrono::Utc::now(),
+            })])
+        }
+        async fn handle_update_metadata(&self, _: UpdateDocumentMetadata) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_patch_metadata(&self, _: PatchDocumentMetadata) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_share_document(&self, _: ShareDocument) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_revoke_share(&self, _: RevokeShare) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_change_state(&self, _: ChangeState) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_link_documents(&self, _: LinkDocuments) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_edit_document_direct(&self, _: EditDocumentDirect) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_edit_document_patch(&self, _: EditDocumentPatch) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_edit_document_structured(&self, _: EditDocumentStructured) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_transform_document(&self, _: TransformDocument) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_merge_document_edits(&self, _: MergeDocumentEdits) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        async fn handle_rollback_document(&self, _: RollbackDocument) -> DomainResult<Vec<DocumentDomainEvent>> { Ok(vec![]) }
+        
+        async fn handle_archive_document(&self, cmd: ArchiveDocument) -> DomainResult<Vec<DocumentDomainEvent>> {
+            if cmd.reason == "fail" {
+                return Err(DomainError::ValidationError("archive rejected".to_string()));
+            }
+            Ok(vec![DocumentDomainEvent::DocumentArchived(DocumentArchived {
+                document_id: cmd.document_id.into(),
+                reason: cmd.reason,
+                archived_by: cmd.archived_by,
+                archived_at: chrono::Utc::now(),
+                metadata: std::collections::HashMap::new(),
+            })])
+        }
+    }
+    
+    fn archive(reason: &str) -> Box<dyn Command> {
+        Box::new(ArchiveDocument {
+            document_id: uuid::Uuid::new_v4(),
+            reason: reason.to_string(),
+            retention_days: None,
+            archived_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
+        })
+    }
+    
+    fn reasons(events: &[DocumentDomainEvent]) -> Vec<&str> {
+        events.iter().map(|event| match event {
+            DocumentDomainEvent::DocumentArchived(e) => e.reason.as_str(),
+            other => panic!("unexpected event {other:?}"),
+        }).collect()
+    }
+    
+    #[tokio::test]
+    async fn test_handle_batch_all_succeed() {
+        // US-011: Test a batch applies every command in order
+        let events = StubHandler::default()
+            .handle_batch(vec![archive("a"), archive("b"), archive("c")])
+            .await
+            .unwrap();
+        
+        assert_eq!(reasons(&events), vec!["a", "b", "c"]);
+    }
+    
+    #[tokio::test]
+    async fn test_handle_batch_stops_at_failure() {
+        // US-011: Test a failing command reports its index and the partial events
+        let batch = vec![archive("a"), archive("b"), archive("fail"), archive("d")];
+        
+        let error = StubHandler::default().handle_batch(batch).await.unwrap_err();
+        
+        assert_eq!(error.index, 2);
+        assert!(matches!(error.error, DomainError::ValidationError(_)));
+        assert_eq!(reasons(&error.events), vec!["a", "b"]);
+    }
+    
+    #[tokio::test]
+    async fn test_handle_batch_all_continues_past_failure() {
+        // US-011: Test collecting per-command results keeps going after a failure
+        let batch = vec![archive("a"), archive("fail"), archive("c")];
+        
+        let results = StubHandler::default().handle_batch_all(batch).await;
+        
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_err());
+        assert_eq!(reasons(results[2].as_ref().unwrap()), vec!["c"]);
+    }
+    
+    fn keyed_upload(key: uuid::Uuid) -> Box<dyn Command> {
+        Box::new(upload(Some(key)))
+    }
+    
+    fn upload(idempotency_key: Option<uuid::Uuid>) -> UploadDocument {
+        UploadDocument {
+            document_id: uuid::Uuid::new_v4(),
+            info: crate::DocumentInfoComponent::builder()
+                .with_title("Report")
+                .with_mime_type("application/pdf")
+                .with_filename("report.pdf")
+                .with_size_bytes(1024)
+                .build()
+                .unwrap(),
+            content_cid: cid::Cid::default(),
+            is_chunked: false,
+            chunk_cids: vec![],
+            uploaded_by: uuid::Uuid::new_v4(),
+            idempotency_key,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_redelivered_command_returns_cached_events() {
+        // US-001: Test a redelivered upload is not executed twice
+        let handler = StubHandler {
+            idempotency: Some(crate::services::InMemoryIdempotencyStore::default()),
+            ..Default::default()
+        };
+        let key = uuid::Uuid::new_v4();
+        
+        let first = handler.handle_command(keyed_upload(key)).await.unwrap();
+        let second = handler.handle_command(keyed_upload(key)).await.unwrap();
+        
+        assert_eq!(handler.uploads.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(first.len(), 1);
+        assert_eq!(second, first);
+        
+        handler.handle_command(keyed_upload(uuid::Uuid::new_v4())).await.unwrap();
+        assert_eq!(handler.uploads.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+    
+    #[tokio::test]
+    async fn test_redelivered_non_upload_command_returns_cached_events() {
+        // US-001: Test every mutating command carries its own idempotency key
+        let handler = StubHandler {
+            idempotency: Some(crate::services::InMemoryIdempotencyStore::default()),
+            ..Default::default()
+        };
+        let mut command = *archive("retired").into_any().downcast::<ArchiveDocument>().unwrap();
+        command.idempotency_key = Some(uuid::Uuid::new_v4());
+        
+        let first = handler.handle(command.clone().into()).await.unwrap();
+        let second = handler.handle(command.into()).await.unwrap();
+        
+        // A re-execution would stamp a new archived_at
+        assert_eq!(second, first);
+    }
+    
+    #[tokio::test]
+    async fn test_handle_deduplicates_and_refuses_in_flight_keys() {
+        // US-001: Test deduplication also guards commands handled directly
+        let handler = StubHandler {
+            idempotency: Some(crate::services::InMemoryIdempotencyStore::default()),
+            ..Default::default()
+        };
+        let key = uuid::Uuid::new_v4();
+        
+        let first = handler.handle(upload(Some(key)).into()).await.unwrap();
+        let second = handler.handle(upload(Some(key)).into()).await.unwrap();
+        assert_eq!(second, first);
+        assert_eq!(handler.uploads.load(std::sync::atomic::Ordering::SeqCst), 1);
+        
+        // A delivery still executing holds its key
+        let in_flight = uuid::Uuid::new_v4();
+        let store = handler.idempotency_store().unwrap();
+        assert_eq!(store.reserve(in_flight), Reservation::Reserved);
+        let error = handler.handle(upload(Some(in_flight)).into()).await.unwrap_err();
+        assert!(error.to_string().contains("already being handled"));
+        assert_eq!(handler.uploads.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_handle_dispatches_by_variant() {
+        // US-011: Test each command variant reaches its own handler
+        let handler = StubHandler::default();
+        let upload = *keyed_upload(uuid::Uuid::new_v4()).into_any().downcast::<UploadDocument>().unwrap();
+        let archive = *archive("a").into_any().downcast::<ArchiveDocument>().unwrap();
+        let fork = ForkDocument {
+            document_id: crate::value_objects::DocumentId::new(),
+            fork_id: crate::value_objects::DocumentId::new(),
+            description: "experiment".to_string(),
+            forked_by: uuid::Uuid::new_v4(),
+        };
+        
+        let uploaded = handler.handle(upload.into()).await.unwrap();
+        assert!(matches!(uploaded.as_slice(), [DocumentDomainEvent::DocumentUploaded(_)]));
+        
+        let archived = handler.handle(DocumentCommand::from(archive)).await.unwrap();
+        assert!(matches!(archived.as_slice(), [DocumentDomainEvent::DocumentArchived(_)]));
+        
+        let error = handler.handle(fork.into()).await.unwrap_err();
+        assert!(error.to_string().contains("ForkDocument"));
+        assert_eq!(handler.uploads.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+    
+    /// Repository keeping documents in memory
+    #[derive(Default)]
+    struct MemoryRepository(std::sync::Mutex<std::collections::HashMap<uuid::Uuid, Document>>);
+    
+    impl AggregateRepository<Document> for MemoryRepository {
+        fn load(&self, id: cim_domain::EntityId<crate::aggregate::DocumentMarker>) -> Result<Option<Document>, String> {
+            Ok(self.0.lock().unwrap().get(id.as_uuid()).cloned())
+        }
+        
+        fn save(&self, document: &Document) -> Result<(), String> {
+            self.0.lock().unwrap().insert(*document.id().as_uuid(), document.clone());
+            Ok(())
+        }
+    }
+    
+    async fn uploaded(handler: &DocumentCommandHandlerImpl<MemoryRepository>) -> crate::value_objects::DocumentId {
+        let command = upload(None);
+        let document_id = command.document_id;
+        handler.handle_upload_document(command).await.unwrap();
+        document_id.into()
+    }
+    
+    fn supersedes(source_id: crate::value_objects::DocumentId, target_id: crate::value_objects::DocumentId) -> LinkDocuments {
+        LinkDocuments {
+            source_id,
+            target_id,
+            link_type: crate::value_objects::LinkType::Supersedes,
+            description: None,
+            linked_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_link_rejects_supersedes_cycles() {
+        // US-005: Test supersedes links cannot close a version-chain cycle
+        let handler = DocumentCommandHandlerImpl::new(MemoryRepository::default());
+        let (a, b, c) = (uploaded(&handler).await, uploaded(&handler).await, uploaded(&handler).await);
+        
+        handler.handle_link_documents(supersedes(a, b)).await.unwrap();
+        let error = handler.handle_link_documents(supersedes(b, a)).await.unwrap_err();
+        assert!(error.to_string().contains("cycle"), "{error}");
+        
+        handler.handle_link_documents(supersedes(b, c)).await.unwrap();
+        let error = handler.handle_link_documents(supersedes(c, a)).await.unwrap_err();
+        assert!(error.to_string().contains("cycle"), "{error}");
+    }
+    
+    #[tokio::test]
+    async fn test_update_content_bumps_version() {
+        // US-003: Test content updates advance the version by the requested bump
+        let handler = DocumentCommandHandlerImpl::new(MemoryRepository::default());
+        let document_id = uploaded(&handler).await;
+        let update = |version_bump| UpdateContent {
+            document_id,
+            content_blocks: vec![],
+            change_summary: "Revised figures".to_string(),
+            updated_by: uuid::Uuid::new_v4(),
+            version_bump,
+            idempotency_key: None,
+        };
+        let version = |events: Vec<DocumentDomainEvent>| match events.as_slice() {
+            [DocumentDomainEvent::ContentUpdated(e)] => e.version.clone(),
+            other => panic!("unexpected events {other:?}"),
+        };
+        
+        let minor = handler.handle(update(crate::value_objects::VersionBump::Minor).into()).await.unwrap();
+        assert_eq!(version(minor), crate::value_objects::DocumentVersion::new(1, 1, 0));
+        let patch = handler.handle(update(crate::value_objects::VersionBump::Patch).into()).await.unwrap();
+        assert_eq!(version(patch), crate::value_objects::DocumentVersion::new(1, 1, 1));
+        
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*document_id.as_uuid());
+        let stored = handler.repository.load(entity_id).unwrap().unwrap();
+        assert_eq!(stored.get_component::<crate::aggregate::LifecycleComponent>().unwrap().version_number, "1.1.1");
+    }
+    
+    #[tokio::test]
+    async fn test_saved_search_commands_reach_saved_search_handler() {
+        // US-009: Test saved search commands are routed and produce events
+        let searches = Arc::new(SavedSearchHandler::new());
+        let handler = DocumentCommandHandlerImpl::new(MemoryRepository::default())
+            .with_saved_searches(searches.clone());
+        let owner = uuid::Uuid::new_v4();
+        let search_id = uuid::Uuid::new_v4();
+        let save = SaveSearch {
+            search_id,
+            name: "Budgets".to_string(),
+            query: crate::value_objects::SearchQuery {
+                query: "budget".to_string(),
+                fields: vec![crate::value_objects::SearchField::All],
+                filters: vec![],
+                sort: crate::value_objects::SearchSort {
+                    field: "score".to_string(),
+                    direction: crate::value_objects::SortDirection::Descending,
+                },
+                pagination: crate::value_objects::SearchPagination::default(),
+                fuzzy: false,
+            },
+            owner,
+            idempotency_key: None,
+        };
+        
+        let saved = handler.handle(save.into()).await.unwrap();
+        assert!(matches!(saved.as_slice(), [DocumentDomainEvent::SearchSaved(e)] if e.search.id == search_id));
+        assert_eq!(searches.list_saved_searches(owner).await.len(), 1);
+        
+        let delete = DeleteSavedSearch { search_id, deleted_by: owner, idempotency_key: None };
+        let deleted = handler.handle(delete.clone().into()).await.unwrap();
+        assert!(matches!(deleted.as_slice(), [DocumentDomainEvent::SavedSearchDeleted(_)]));
+        assert!(matches!(
+            handler.handle(delete.into()).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+        
+        // Without a saved search handler the commands are refused
+        let plain = DocumentCommandHandlerImpl::new(MemoryRepository::default());
+        let delete = DeleteSavedSearch { search_id, deleted_by: owner, idempotency_key: None };
+        assert!(plain.handle(delete.into()).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_remove_from_collection_updates_projection() {
+        // US-015: Test removal flows from command to event to the collection read model
+        use crate::handlers::{DocumentEventHandler, DocumentEventHandlerImpl};
+        use crate::queries::{CollectionContentsView, DocumentQueryHandler, GetDocumentsInCollection, InMemoryDocumentReadStore};
+        
+        let handler = DocumentCommandHandlerImpl::new(MemoryRepository::default());
+        let (kept, removed) = (uploaded(&handler).await, uploaded(&handler).await);
+        let store = Arc::new(InMemoryDocumentReadStore::new());
+        let collection = crate::value_objects::Collection {
+            id: uuid::Uuid::new_v4(),
+            name: "Reports".to_string(),
+            description: None,
+            parent_id: None,
+            metadata: std::collections::HashMap::new(),
+        };
+        store.insert_collection(collection.clone()).await;
+        store.add_to_collection(collection.id, kept).await;
+        store.add_to_collection(collection.id, removed).await;
+        
+        let events = handler.handle(RemoveDocumentFromCollection {
+            document_id: removed,
+            collection_id: collection.id,
+            removed_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
+        }.into()).await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [DocumentDomainEvent::DocumentRemovedFromCollection(e)] if e.document_id == removed
+        ));
+        
+        let projector = DocumentEventHandlerImpl::new().with_read_store(store.clone());
+        for event in &events {
+            projector.handle(event).await.unwrap();
+        }
+        
+        let contents = DocumentQueryHandler::new(store)
+            .handle(&GetDocumentsInCollection { collection_id: collection.id, recursive: false })
+            .await.unwrap()
+            .downcast::<CollectionContentsView>().unwrap();
+        let ids: Vec<_> = contents.documents.iter().map(|entry| entry.document_id).collect();
+        assert_eq!(ids, vec![kept]);
+        
+        // Unknown documents are refused
+        assert!(matches!(
+            handler.handle(RemoveDocumentFromCollection {
+                document_id: crate::value_objects::DocumentId::new(),
+                collection_id: collection.id,
+                removed_by: uuid::Uuid::new_v4(),
+                idempotency_key: None,
+            }.into()).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+    }
+    
+    /// Repository whose saves can be made to fail
+    #[derive(Default)]
+    struct FlakyRepository {
+        documents: MemoryRepository,
+        fail_saves: std::sync::atomic::AtomicBool,
+    }
+    
+    impl AggregateRepository<Document> for FlakyRepository {
+        fn load(&self, id: cim_domain::EntityId<crate::aggregate::DocumentMarker>) -> Result<Option<Document>, String> {
+            self.documents.load(id)
+        }
+        
+        fn save(&self, document: &Document) -> Result<(), String> {
+            if self.fail_saves.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err("storage unavailable".to_string());
+            }
+            self.documents.save(document)
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_share_is_audited_after_save() {
+        // US-010: Test audit entries reflect whether the share was persisted
+        use crate::services::InMemoryAuditSink;
+        
+        let sink = Arc::new(InMemoryAuditSink::new());
+        let handler = DocumentCommandHandlerImpl::new(FlakyRepository::default())
+            .with_audit_sink(sink.clone());
+        let command = upload(None);
+        let document_id: crate::value_objects::DocumentId = command.document_id.into();
+        handler.handle_upload_document(command).await.unwrap();
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*document_id.as_uuid());
+        let mut document = handler.repository.load(entity_id).unwrap().unwrap();
+        document.add_component(crate::aggregate::AccessControlComponent {
+            read_access: vec![],
+            write_access: vec![],
+            share_access: vec![],
+            audit_access: true,
+            encryption_key_id: None,
+            block_access: std::collections::HashMap::new(),
+        }, "test", None).unwrap();
+        handler.repository.save(&document).unwrap();
+        let share = || ShareDocument {
+            document_id,
+            share_with: uuid::Uuid::new_v4(),
+            access_level: crate::value_objects::AccessLevel::Read,
+            shared_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
+        };
+        
+        handler.repository.fail_saves.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(handler.handle(share().into()).await.is_err());
+        handler.repository.fail_saves.store(false, std::sync::atomic::Ordering::SeqCst);
+        handler.handle(share().into()).await.unwrap();
+        
+        let granted: Vec<bool> = sink.entries(&document_id).iter().map(|entry| entry.granted).collect();
+        assert_eq!(granted, vec![false, true]);
+    }
+    
+    #[tokio::test]
+    async fn test_share_grants_requested_level() {
+        // US-004: Test sharing through the handler grants the requested access level
+        let handler = DocumentCommandHandlerImpl::new(MemoryRepository::default());
+        let document_id = uploaded(&handler).await;
+        let editor = uuid::Uuid::new_v4();
+        
+        let events = handler.handle(ShareDocument {
+            document_id,
+            share_with: editor,
+            access_level: crate::value_objects::AccessLevel::Write,
+            shared_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
+        }.into()).await.unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [DocumentDomainEvent::DocumentShared(e)] if e.shared_with.contains(&editor.to_string())
+        ));
+        
+        let entity_id = cim_domain::EntityId::<crate::aggregate::DocumentMarker>::from_uuid(*document_id.as_uuid());
+        let stored = handler.repository.load(entity_id).unwrap().unwrap();
+        let access = stored.get_component::<crate::aggregate::AccessControlComponent>().unwrap();
+        assert!(access.can(editor, crate::value_objects::AccessLevel::Write));
+        assert!(!access.can(editor, crate::value_objects::AccessLevel::Admin));
+    }
+    
+    #[tokio::test]
+    async fn test_handle_command_rejects_unsupported_type() {
+        // US-011: Test commands without a handler are rejected
+        let fork: Box<dyn Command> = Box::new(ForkDocument {
+            document_id: crate::value_objects::DocumentId::new(),
+            fork_id: crate::value_objects::DocumentId::new(),
+            description: "experiment".to_string(),
+            forked_by: uuid::Uuid::new_v4(),
+        });
+        
+        assert!(StubHandler::default().handle_command(fork).await.is_err());
+    }
 }
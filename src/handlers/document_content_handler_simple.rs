@@ -102,8 +102,7 @@ impl DocumentContentHandler {
         let document = documents.get(&document_id)
             .ok_or_else(|| cim_domain::DomainError::generic("Document not found"))?;
         
-        let content_address = document.get_component::<crate::aggregate::ContentAddressComponent>()
-            .ok_or_else(|| cim_domain::DomainError::generic("Document has no content"))?;
+        let content_address = document.require_component::<crate::aggregate::ContentAddressComponent>()?;
         
         self.content_store.retrieve(&content_address.content_cid).await
     }
@@ -6,7 +6,7 @@ use crate::{
     aggregate::{DocumentAggregate, ClassificationComponent, DocumentInfoComponent},
     Document,
     events::*,
-    value_objects::{DocumentId, DocumentMetadata, DocumentType},
+    value_objects::{normalize_tags, DocumentId, DocumentMetadata, DocumentType},
 };
 use cim_domain::{DomainResult, DomainError};
 use std::collections::{HashMap, HashSet};
@@ -122,6 +122,11 @@ impl DocumentMetadataHandler {
         tags: Vec<String>,
         added_by: String,
     ) -> DomainResult<DocumentTagged> {
+        if tags.iter().any(|tag| tag.trim().is_empty()) {
+            return Err(DomainError::ValidationError("Tags cannot be empty".to_string()));
+        }
+        let tags = normalize_tags(&tags);
+
         let documents = self.documents.read().await;
         let document = documents.get(&document_id)
             .ok_or_else(|| DomainError::generic("Document not found"))?;
@@ -138,12 +143,9 @@ impl DocumentMetadataHandler {
                 confidentiality: crate::aggregate::ConfidentialityLevel::Internal,
             });
         
-        // Add new tags (avoid duplicates)
-        for tag in &tags {
-            if !classification.tags.contains(tag) {
-                classification.tags.push(tag.clone());
-            }
-        }
+        // Add new tags, collapsing case and whitespace variants
+        classification.tags.extend(tags.iter().cloned());
+        classification.tags = normalize_tags(&classification.tags);
         
         // Note: In a real implementation, we would update the classification through
         // proper aggregate methods.
@@ -356,10 +358,13 @@ impl DocumentMetadataHandler {
         Ok(results)
     }
     
-    /// Get documents by tag
+    /// Get documents by tag, matching it the way stored tags are normalized
     pub async fn get_documents_by_tag(&self, tag: &str) -> DomainResult<Vec<uuid::Uuid>> {
+        let Some(tag) = tag_key(tag) else {
+            return Ok(vec![]);
+        };
         let tag_index = self.tag_index.read().await;
-        Ok(tag_index.get(tag).cloned().unwrap_or_default().into_iter().collect())
+        Ok(tag_index.get(&tag).cloned().unwrap_or_default().into_iter().collect())
     }
     
     /// Get documents by category
@@ -453,8 +458,11 @@ impl DocumentMetadataHandler {
     
     /// Update tag index
     async fn update_tag_index(&self, document_id: uuid::Uuid, tag: &str) -> DomainResult<()> {
+        let Some(tag) = tag_key(tag) else {
+            return Ok(());
+        };
         let mut tag_index = self.tag_index.write().await;
-        tag_index.entry(tag.to_string())
+        tag_index.entry(tag)
             .or_insert_with(HashSet::new)
             .insert(document_id);
         Ok(())
@@ -462,11 +470,14 @@ impl DocumentMetadataHandler {
     
     /// Remove from tag index
     async fn remove_from_tag_index(&self, document_id: uuid::Uuid, tag: &str) -> DomainResult<()> {
+        let Some(tag) = tag_key(tag) else {
+            return Ok(());
+        };
         let mut tag_index = self.tag_index.write().await;
-        if let Some(doc_ids) = tag_index.get_mut(tag) {
+        if let Some(doc_ids) = tag_index.get_mut(&tag) {
             doc_ids.remove(&document_id);
             if doc_ids.is_empty() {
-                tag_index.remove(tag);
+                tag_index.remove(&tag);
             }
         }
         Ok(())
@@ -482,8 +493,71 @@ impl DocumentMetadataHandler {
     }
 }
 
+/// Tag index key for `tag`, or `None` for a blank tag
+fn tag_key(tag: &str) -> Option<String> {
+    normalize_tags(&[tag.to_string()]).pop()
+}
+
 impl Default for DocumentMetadataHandler {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{ConfidentialityLevel, ContentAddressComponent, DocumentMarker};
+    use cim_domain::EntityId;
+
+    async fn handler_with_tags(tags: &[&str]) -> (DocumentMetadataHandler, uuid::Uuid) {
+        let document_id = uuid::Uuid::new_v4();
+        let mut document = Document::new(
+            EntityId::<DocumentMarker>::from_uuid(document_id),
+            DocumentInfoComponent::builder()
+                .with_title("Tagged")
+                .with_mime_type("text/plain")
+                .build()
+                .unwrap(),
+            ContentAddressComponent::from_content(b"tagged", "sha2-256").unwrap().content_cid,
+        ).unwrap();
+        document.add_component(ClassificationComponent {
+            document_type: "General".to_string(),
+            category: "Uncategorized".to_string(),
+            subcategories: vec![],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            confidentiality: ConfidentialityLevel::Internal,
+        }, "test", None).unwrap();
+
+        let handler = DocumentMetadataHandler::new();
+        handler.documents.write().await.insert(document_id, document);
+        (handler, document_id)
+    }
+
+    #[tokio::test]
+    async fn test_add_tags_normalizes_all_tags() {
+        // US-019: Test mixed-case duplicate tags collapse to one canonical entry
+        let (handler, document_id) = handler_with_tags(&["Rust"]).await;
+
+        let event = handler.add_tags(
+            document_id,
+            vec!["rust ".to_string(), "Async".to_string(), "ASYNC".to_string()],
+            "alice".to_string(),
+        ).await.unwrap();
+
+        assert_eq!(event.tags, vec!["rust", "async"]);
+        assert_eq!(event.all_tags, vec!["rust", "async"]);
+        assert_eq!(handler.get_documents_by_tag("async").await.unwrap(), vec![document_id]);
+        assert_eq!(handler.get_documents_by_tag(" ASYNC ").await.unwrap(), vec![document_id]);
+        assert!(handler.get_documents_by_tag("  ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_add_tags_rejects_empty_tags() {
+        // US-019: Test blank tags are rejected
+        let (handler, document_id) = handler_with_tags(&[]).await;
+
+        let result = handler.add_tags(document_id, vec!["ok".to_string(), "  ".to_string()], "alice".to_string()).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+    }
+}
@@ -3,10 +3,12 @@
 //! This handler manages document version history with a simplified approach.
 
 use crate::{
+    aggregate::{ContentAddressComponent, Document},
     events::*,
+    services::CidResolver,
     value_objects::*,
 };
-use cim_domain::DomainResult;
+use cim_domain::{AggregateRoot, DomainError, DomainResult};
 use cid::Cid;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -107,20 +109,63 @@ impl DocumentVersionHandler {
         Ok(history.get(&document_id).cloned().unwrap_or_default())
     }
     
-    /// Calculate next version number
-    pub fn calculate_next_version(&self, current_version: &str, version_type: &VersionType) -> String {
-        let parts: Vec<&str> = current_version.split('.').collect();
-        let major = parts.get(0).unwrap_or(&"1").parse::<u32>().unwrap_or(1);
-        let minor = parts.get(1).unwrap_or(&"0").parse::<u32>().unwrap_or(0);
-        
-        match version_type {
-            VersionType::Major => format!("{}.0", major + 1),
-            VersionType::Minor => format!("{}.{}", major, minor + 1),
-            VersionType::Patch => {
-                let patch = parts.get(2).unwrap_or(&"0").parse::<u32>().unwrap_or(0);
-                format!("{}.{}.{}", major, minor, patch + 1)
-            }
+    /// Roll a document back to a version recorded in its history
+    ///
+    /// The target must exist in the history and its content must be
+    /// resolvable; the document's content CID is then restored to it.
+    pub async fn rollback_to_version(
+        &self,
+        document: &mut Document,
+        target_version: &DocumentVersion,
+        resolver: &dyn CidResolver,
+        reason: String,
+        rolled_back_by: uuid::Uuid,
+    ) -> Result<VersionRolledBack, RollbackError> {
+        let document_id = DocumentId::from(document.id());
+        let history = self.version_history.read().await;
+        let entries = history.get(document_id.as_uuid()).map(Vec::as_slice).unwrap_or_default();
+        let parsed = |entry: &VersionHistoryEntry| entry.version_number.parse::<DocumentVersion>().ok();
+
+        let target = entries
+            .iter()
+            .rev()
+            .find(|&entry| parsed(entry).as_ref() == Some(target_version))
+            .ok_or_else(|| RollbackError::VersionNotFound {
+                document_id,
+                version: target_version.clone(),
+            })?;
+        if resolver.resolve(&target.content_cid).is_none() {
+            return Err(RollbackError::ContentUnavailable(target.content_cid));
         }
+        let from_version = entries.last().and_then(parsed).unwrap_or_default();
+
+        let mut content_address = document.require_component::<ContentAddressComponent>()?.clone();
+        content_address.content_cid = target.content_cid;
+        document.replace_component(
+            content_address,
+            &rolled_back_by.to_string(),
+            Some(format!("Rollback to {target_version}")),
+        )?;
+
+        Ok(VersionRolledBack {
+            document_id,
+            from_version,
+            to_version: target_version.clone(),
+            reason,
+            rolled_back_by,
+            rolled_back_at: Utc::now(),
+        })
+    }
+    
+    /// Calculate next version number
+    ///
+    /// An unparsable `current_version` is treated as the initial version.
+    pub fn calculate_next_version(&self, current_version: &str, bump: VersionBump) -> String {
+        current_version
+            .parse::<DocumentVersion>()
+            .unwrap_or_default()
+            .bump(bump)
+            .to_string()
     }
 }
 
@@ -130,10 +175,94 @@ impl Default for DocumentVersionHandler {
     }
 }
 
-/// Version type for version number calculation
-#[derive(Debug, Clone, PartialEq)]
-pub enum VersionType {
-    Major,
-    Minor,
-    Patch,
-}
\ No newline at end of file
+/// Why a rollback was refused
+#[derive(Debug, thiserror::Error)]
+pub enum RollbackError {
+    #[error("Version {version} not found in history of document {document_id}")]
+    VersionNotFound {
+        document_id: DocumentId,
+        version: DocumentVersion,
+    },
+
+    #[error("Content {0} for rollback target cannot be resolved")]
+    ContentUnavailable(Cid),
+
+    #[error(transparent)]
+    Domain(#[from] DomainError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::DocumentInfoComponent;
+
+    struct Store(HashMap<Cid, Vec<u8>>);
+
+    impl CidResolver for Store {
+        fn resolve(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.0.get(cid).cloned()
+        }
+    }
+
+    async fn versioned_document() -> (DocumentVersionHandler, Document, Store, Vec<Cid>) {
+        let contents: [&[u8]; 2] = [b"first draft", b"second draft"];
+        let cids: Vec<Cid> = contents
+            .iter()
+            .map(|content| ContentAddressComponent::from_content(content, "sha2-256").unwrap().content_cid)
+            .collect();
+        let store = Store(cids.iter().copied().zip(contents.iter().map(|c| c.to_vec())).collect());
+
+        let info = DocumentInfoComponent::builder()
+            .with_title("Versioned")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let document = Document::new(cim_domain::EntityId::new(), info, cids[1]).unwrap();
+        let document_id = *DocumentId::from(document.id()).as_uuid();
+
+        let handler = DocumentVersionHandler::new();
+        for (version, cid) in ["1.0.0", "1.1.0"].into_iter().zip(&cids) {
+            handler
+                .record_version(document_id, version.to_string(), *cid, "edit".to_string(), "author".to_string())
+                .await
+                .unwrap();
+        }
+        (handler, document, store, cids)
+    }
+
+    #[tokio::test]
+    async fn test_rollback_to_recorded_version() {
+        let (handler, mut document, store, cids) = versioned_document().await;
+
+        let event = handler
+            .rollback_to_version(&mut document, &DocumentVersion::new(1, 0, 0), &store, "regression".to_string(), uuid::Uuid::new_v4())
+            .await
+            .unwrap();
+
+        assert_eq!(event.from_version, DocumentVersion::new(1, 1, 0));
+        assert_eq!(event.to_version, DocumentVersion::new(1, 0, 0));
+        assert_eq!(document.get_component::<ContentAddressComponent>().unwrap().content_cid, cids[0]);
+    }
+
+    #[test]
+    fn test_calculate_next_version_follows_version_bump() {
+        let handler = DocumentVersionHandler::new();
+
+        assert_eq!(handler.calculate_next_version("1.2.3", VersionBump::Major), "2.0.0");
+        assert_eq!(handler.calculate_next_version("1.2.3", VersionBump::Minor), "1.3.0");
+        assert_eq!(handler.calculate_next_version("1.2", VersionBump::Patch), "1.2.1");
+        assert_eq!(handler.calculate_next_version("draft", VersionBump::Patch), "1.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_rollback_to_unknown_version_is_rejected() {
+        let (handler, mut document, store, cids) = versioned_document().await;
+
+        let result = handler
+            .rollback_to_version(&mut document, &DocumentVersion::new(0, 9, 0), &store, "regression".to_string(), uuid::Uuid::new_v4())
+            .await;
+
+        assert!(matches!(result, Err(RollbackError::VersionNotFound { version, .. }) if version == DocumentVersion::new(0, 9, 0)));
+        assert_eq!(document.get_component::<ContentAddressComponent>().unwrap().content_cid, cids[1]);
+    }
+}
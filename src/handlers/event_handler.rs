@@ -1,16 +1,18 @@
 //! Document event handlers
 
 use crate::events::{
-    DocumentDomainEvent, DocumentUploaded, DocumentMetadataUpdated, DocumentShared,
+    DocumentDomainEvent, DocumentUploaded, DocumentMetadataUpdated, DocumentShared, ShareRevoked,
     DocumentDeleted, DocumentArchived, DocumentCreated, ContentUpdated, StateChanged,
     DocumentForked, VersionTagged, CommentAdded, DocumentsLinked, DocumentsMerged,
     VersionRolledBack, EntitiesExtracted, SummaryGenerated, DocumentClassified,
-    TemplateApplied, CollectionCreated, DocumentAddedToCollection, DocumentImported,
+    TemplateApplied, CollectionCreated, DocumentAddedToCollection, DocumentRemovedFromCollection, DocumentImported,
     DocumentExported, DocumentRestored, VersionsCompared, DocumentContentUpdated,
     DocumentTagged, DocumentVersionCreated, DocumentVersionRestored
 };
+use crate::queries::InMemoryDocumentReadStore;
 use cim_domain::DomainResult;
 use async_trait::async_trait;
+use std::sync::Arc;
 
 /// Trait for handling document events
 #[async_trait]
@@ -21,7 +23,8 @@ pub trait DocumentEventHandler: Send + Sync {
 
 /// Implementation of document event handler
 pub struct DocumentEventHandlerImpl {
-    // Could have projections or other services here
+    /// Read model kept in step with collection membership events
+    read_store: Option<Arc<InMemoryDocumentReadStore>>,
 }
 
 impl Default for DocumentEventHandlerImpl {
@@ -32,7 +35,13 @@ impl Default for DocumentEventHandlerImpl {
 
 impl DocumentEventHandlerImpl {
     pub fn new() -> Self {
-        Self {}
+        Self { read_store: None }
+    }
+
+    /// Project collection membership changes into `store`
+    pub fn with_read_store(mut self, store: Arc<InMemoryDocumentReadStore>) -> Self {
+        self.read_store = Some(store);
+        self
     }
 }
 
@@ -43,6 +52,7 @@ impl DocumentEventHandler for DocumentEventHandlerImpl {
             DocumentDomainEvent::DocumentUploaded(e) => self.handle_document_uploaded(e).await,
             DocumentDomainEvent::DocumentMetadataUpdated(e) => self.handle_metadata_updated(e).await,
             DocumentDomainEvent::DocumentShared(e) => self.handle_document_shared(e).await,
+            DocumentDomainEvent::ShareRevoked(e) => self.handle_share_revoked(e).await,
             DocumentDomainEvent::DocumentDeleted(e) => self.handle_document_deleted(e).await,
             DocumentDomainEvent::DocumentArchived(e) => self.handle_document_archived(e).await,
             DocumentDomainEvent::DocumentCreated(e) => self.handle_document_created(e).await,
@@ -60,6 +70,7 @@ impl DocumentEventHandler for DocumentEventHandlerImpl {
             DocumentDomainEvent::TemplateApplied(e) => self.handle_template_applied(e).await,
             DocumentDomainEvent::CollectionCreated(e) => self.handle_collection_created(e).await,
             DocumentDomainEvent::DocumentAddedToCollection(e) => self.handle_document_added_to_collection(e).await,
+            DocumentDomainEvent::DocumentRemovedFromCollection(e) => self.handle_document_removed_from_collection(e).await,
             DocumentDomainEvent::DocumentImported(e) => self.handle_document_imported(e).await,
             DocumentDomainEvent::DocumentExported(e) => self.handle_document_exported(e).await,
             DocumentDomainEvent::DocumentRestored(e) => self.handle_document_restored(e).await,
@@ -82,6 +93,10 @@ impl DocumentEventHandler for DocumentEventHandlerImpl {
             DocumentDomainEvent::CidChainVerified(_) => Ok(()),
             DocumentDomainEvent::EditSessionCancelled(_) => Ok(()),
             DocumentDomainEvent::DocumentEditFailed(_) => Ok(()),
+            
+            // Saved searches are kept by the saved search handler
+            DocumentDomainEvent::SearchSaved(_) => Ok(()),
+            DocumentDomainEvent::SavedSearchDeleted(_) => Ok(()),
         }
     }
 }
@@ -119,6 +134,15 @@ impl DocumentEventHandlerImpl {
         Ok(())
     }
     
+    async fn handle_share_revoked(&self, event: &ShareRevoked) -> DomainResult<()> {
+        println!("Document share revoked: {} from {}", event.document_id, event.revoked_from);
+        
+        // Update access control projections
+        // Log audit trail
+        
+        Ok(())
+    }
+    
     async fn handle_document_deleted(&self, event: &DocumentDeleted) -> DomainResult<()> {
         println!("Document deleted: {}", event.document_id);
         
@@ -205,8 +229,17 @@ impl DocumentEventHandlerImpl {
         Ok(())
     }
 
-    async fn handle_document_added_to_collection(&self, _event: &DocumentAddedToCollection) -> DomainResult<()> {
-        // Implementation needed
+    async fn handle_document_added_to_collection(&self, event: &DocumentAddedToCollection) -> DomainResult<()> {
+        if let Some(store) = &self.read_store {
+            store.add_to_collection(event.collection_id, event.document_id).await;
+        }
+        Ok(())
+    }
+
+    async fn handle_document_removed_from_collection(&self, event: &DocumentRemovedFromCollection) -> DomainResult<()> {
+        if let Some(store) = &self.read_store {
+            store.remove_from_collection(event.collection_id, event.document_id).await;
+        }
         Ok(())
     }
 
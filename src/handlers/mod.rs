@@ -5,12 +5,14 @@ mod event_handler;
 mod document_content_handler_simple;
 mod document_version_handler_simple;
 mod document_metadata_handler;
+mod saved_search_handler;
 
-pub use command_handler::{DocumentCommandHandler as DocumentCommandHandlerTrait, DocumentCommandHandlerImpl};
+pub use command_handler::{DocumentCommandHandler as DocumentCommandHandlerTrait, DocumentCommandHandlerImpl, BatchError};
 pub use event_handler::{DocumentEventHandler, DocumentEventHandlerImpl};
 pub use document_content_handler_simple::*;
 pub use document_version_handler_simple::*;
 pub use document_metadata_handler::*;
+pub use saved_search_handler::SavedSearchHandler;
 
 use crate::events::*;
 
@@ -66,18 +68,20 @@ mod tests {
         // Create a test command
         let command = UploadDocument {
             document_id: uuid::Uuid::new_v4(),
-            info: crate::DocumentInfoComponent {
-                title: "Test Document".to_string(),
-                description: Some("A test document".to_string()),
-                filename: Some("test.txt".to_string()),
-                mime_type: "text/plain".to_string(),
-                size_bytes: 1024,
-                language: Some("en".to_string()),
-            },
+            info: crate::DocumentInfoComponent::builder()
+                .with_title("Test Document")
+                .with_description("A test document")
+                .with_mime_type("text/plain")
+                .with_filename("test.txt")
+                .with_size_bytes(1024)
+                .with_language("en")
+                .build()
+                .unwrap(),
             content_cid: cid::Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap(),
             is_chunked: false,
             chunk_cids: vec![],
             uploaded_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
         };
 
         let result = handler.handle(command).await;
@@ -109,6 +113,7 @@ mod tests {
                 filename: Some("updated.txt".to_string()),
             },
             updated_by: "user123".to_string(),
+            idempotency_key: None,
         };
 
         let result = handler.handle(update_command).await;
@@ -129,6 +134,7 @@ mod tests {
             share_with: uuid::Uuid::new_v4(),
             access_level: AccessLevel::Read,
             shared_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
         };
 
         let result = handler.handle(share_command).await;
@@ -149,6 +155,7 @@ mod tests {
             reason: "Test archival".to_string(),
             retention_days: None,
             archived_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
         };
 
         let result = handler.handle(archive_command).await;
@@ -194,6 +201,7 @@ mod tests {
             is_chunked: false,
             chunk_cids: vec![],
             uploaded_by: uuid::Uuid::new_v4(),
+            idempotency_key: None,
         };
 
         let result = handler.handle(minimal_command).await;
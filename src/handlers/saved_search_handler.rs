@@ -0,0 +1,264 @@
+//! Saved search handler
+//!
+//! Stores named [`SearchQuery`](crate::value_objects::SearchQuery)s per owner
+//! and re-runs them against a [`DocumentSearchService`].
+
+use crate::{
+    commands::{DeleteSavedSearch, SaveSearch},
+    events::{SavedSearchDeleted, SearchSaved},
+    projections::DocumentSearchView,
+    queries::QueryLimits,
+    services::DocumentSearchService,
+    value_objects::SavedSearch,
+};
+use cim_domain::{DomainError, DomainResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Handler for saved search commands and execution
+#[derive(Default)]
+pub struct SavedSearchHandler {
+    searches: Arc<RwLock<HashMap<Uuid, SavedSearch>>>,
+    limits: QueryLimits,
+}
+
+impl SavedSearchHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject queries exceeding `limits` instead of the defaults
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Save a query; saving under an existing ID is rejected
+    pub async fn save_search(&self, cmd: SaveSearch) -> DomainResult<SearchSaved> {
+        if cmd.name.trim().is_empty() {
+            return Err(DomainError::ValidationError("Saved search name is empty".to_string()));
+        }
+        self.limits.check_search_query(&cmd.query)?;
+        let mut searches = self.searches.write().await;
+        if searches.contains_key(&cmd.search_id) {
+            return Err(DomainError::ValidationError(format!(
+                "Saved search {} already exists",
+                cmd.search_id
+            )));
+        }
+
+        let search = SavedSearch {
+            id: cmd.search_id,
+            name: cmd.name,
+            query: cmd.query,
+            owner: cmd.owner,
+            created_at: chrono::Utc::now(),
+        };
+        searches.insert(search.id, search.clone());
+
+        Ok(SearchSaved { search })
+    }
+
+    /// Delete a saved search; only its owner may do so
+    pub async fn delete_saved_search(&self, cmd: DeleteSavedSearch) -> DomainResult<SavedSearchDeleted> {
+        let mut searches = self.searches.write().await;
+        let search = searches.get(&cmd.search_id)
+            .ok_or_else(|| not_found(cmd.search_id))?;
+        if search.owner != cmd.deleted_by {
+            return Err(DomainError::PermissionDenied(
+                "Only the owner can delete a saved search".to_string(),
+            ));
+        }
+        searches.remove(&cmd.search_id);
+
+        Ok(SavedSearchDeleted {
+            search_id: cmd.search_id,
+            deleted_by: cmd.deleted_by,
+            deleted_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Saved searches belonging to `owner`, oldest first
+    pub async fn list_saved_searches(&self, owner: Uuid) -> Vec<SavedSearch> {
+        let mut searches: Vec<SavedSearch> = self.searches.read().await
+            .values()
+            .filter(|search| search.owner == owner)
+            .cloned()
+            .collect();
+        searches.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.name.cmp(&b.name)));
+        searches
+    }
+
+    /// Run the saved search `search_id` against `service`
+    pub async fn execute_saved_search(
+        &self,
+        search_id: Uuid,
+        service: &DocumentSearchService,
+    ) -> DomainResult<Vec<DocumentSearchView>> {
+        let searches = self.searches.read().await;
+        let search = searches.get(&search_id)
+            .ok_or_else(|| not_found(search_id))?;
+        self.limits.check_search_query(&search.query)?;
+
+        service.search(&search.query)
+            .map_err(|e| DomainError::generic(e.to_string()))
+    }
+}
+
+fn not_found(search_id: Uuid) -> DomainError {
+    DomainError::EntityNotFound {
+        entity_type: "SavedSearch".to_string(),
+        id: search_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projections::DocumentFullView;
+    use crate::value_objects::{
+        DocumentId, DocumentType, DocumentVersion, SearchField, SearchPagination, SearchQuery,
+        SearchSort, SortDirection,
+    };
+
+    fn budget_query() -> SearchQuery {
+        SearchQuery {
+            query: "budget".to_string(),
+            fields: vec![SearchField::All],
+            filters: vec![],
+            sort: SearchSort {
+                field: "score".to_string(),
+                direction: SortDirection::Descending,
+            },
+            pagination: SearchPagination::default(),
+            fuzzy: false,
+        }
+    }
+
+    fn seeded_service() -> DocumentSearchService {
+        let mut service = DocumentSearchService::new();
+        for (title, content) in [
+            ("Budget review", "Quarterly budget numbers"),
+            ("Offsite agenda", "Team offsite schedule"),
+        ] {
+            service.index_document(&DocumentFullView {
+                id: DocumentId::new(),
+                title: title.to_string(),
+                content: content.to_string(),
+                version: DocumentVersion::new(1, 0, 0),
+                doc_type: DocumentType::Report,
+                tags: vec![],
+                author: Uuid::new_v4(),
+                metadata: HashMap::new(),
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            }).unwrap();
+        }
+        service
+    }
+
+    #[tokio::test]
+    async fn test_save_list_execute_and_delete() {
+        let handler = SavedSearchHandler::new();
+        let owner = Uuid::new_v4();
+        let search_id = Uuid::new_v4();
+
+        let saved = handler.save_search(SaveSearch {
+            search_id,
+            name: "Budgets".to_string(),
+            query: budget_query(),
+            owner,
+            idempotency_key: None,
+        }).await.unwrap();
+        assert_eq!(saved.search.id, search_id);
+
+        let listed = handler.list_saved_searches(owner).await;
+        assert_eq!(listed, vec![saved.search]);
+        assert!(handler.list_saved_searches(Uuid::new_v4()).await.is_empty());
+
+        let results = handler.execute_saved_search(search_id, &seeded_service()).await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Budget review"]);
+
+        let deleted = handler.delete_saved_search(DeleteSavedSearch {
+            search_id,
+            deleted_by: owner,
+            idempotency_key: None,
+        }).await.unwrap();
+        assert_eq!(deleted.search_id, search_id);
+        assert!(handler.list_saved_searches(owner).await.is_empty());
+        assert!(matches!(
+            handler.execute_saved_search(search_id, &seeded_service()).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+        assert!(matches!(
+            handler.delete_saved_search(DeleteSavedSearch { search_id, deleted_by: owner, idempotency_key: None }).await,
+            Err(DomainError::EntityNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_only_owner_can_delete() {
+        let handler = SavedSearchHandler::new();
+        let owner = Uuid::new_v4();
+        let search_id = Uuid::new_v4();
+        handler.save_search(SaveSearch {
+            search_id,
+            name: "Budgets".to_string(),
+            query: budget_query(),
+            owner,
+            idempotency_key: None,
+        }).await.unwrap();
+
+        let result = handler.delete_saved_search(DeleteSavedSearch {
+            search_id,
+            deleted_by: Uuid::new_v4(),
+            idempotency_key: None,
+        }).await;
+
+        assert!(matches!(result, Err(DomainError::PermissionDenied(_))));
+        assert_eq!(handler.list_saved_searches(owner).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_query_is_refused() {
+        let limits = QueryLimits { max_query_len: 8, ..QueryLimits::default() };
+        let handler = SavedSearchHandler::new().with_limits(limits);
+        let search_id = Uuid::new_v4();
+
+        let result = handler.save_search(SaveSearch {
+            search_id,
+            name: "Long".to_string(),
+            query: SearchQuery { query: "budget numbers".to_string(), ..budget_query() },
+            owner: Uuid::new_v4(),
+            idempotency_key: None,
+        }).await;
+        assert!(matches!(result, Err(DomainError::ValidationError(_))));
+        assert!(handler.list_saved_searches(Uuid::new_v4()).await.is_empty());
+
+        // A search saved under looser limits is still refused when executed
+        let owner = Uuid::new_v4();
+        let loose = SavedSearchHandler {
+            searches: handler.searches.clone(),
+            limits: QueryLimits::default(),
+        };
+        loose.save_search(SaveSearch {
+            search_id,
+            name: "Long".to_string(),
+            query: SearchQuery { query: "budget numbers".to_string(), ..budget_query() },
+            owner,
+            idempotency_key: None,
+        }).await.unwrap();
+        assert!(matches!(
+            handler.execute_saved_search(search_id, &seeded_service()).await,
+            Err(DomainError::ValidationError(_))
+        ));
+
+        let service = seeded_service().with_limits(limits);
+        assert!(service.search(&SearchQuery { query: "budget numbers".to_string(), ..budget_query() }).is_err());
+        let zero = SearchQuery { pagination: SearchPagination { page: 0, size: 0 }, ..budget_query() };
+        assert!(seeded_service().search(&zero).is_err());
+    }
+}
@@ -22,10 +22,14 @@ pub use aggregate::{
     Document, DocumentMarker,
     DocumentInfoComponent, ContentAddressComponent, ClassificationComponent,
     OwnershipComponent, LifecycleComponent, AccessControlComponent,
-    RelationshipsComponent, ProcessingComponent,
+    RelationshipsComponent, ProcessingComponent, CustomAttributesComponent,
     ConfidentialityLevel, DocumentStatus, RelationType,
     DocumentRelation, ExternalReference, ThumbnailInfo,
-    PublicDocumentView, SearchIndexProjection,
+    DocumentInfoError, PublicDocumentView, PublicViewPolicy, SearchIndexProjection, CidMismatch, ChunkManifest, ReassemblyError,
+    NoWorkflowState, ReplaySummary, visible_blocks, detect_relationship_cycle,
+    compute_cid, CidCodec, CidHash,
+    ComponentBuildError, DocumentInfoComponentBuilder, ClassificationComponentBuilder,
+    OwnershipComponentBuilder, LifecycleComponentBuilder, AccessControlComponentBuilder,
 };
 
 pub use commands::*;
@@ -34,4 +38,4 @@ pub use value_objects::*;
 pub use services::*;
 pub use handlers::{DocumentCommandHandler, DocumentEventHandler};
 pub use projections::DocumentView;
-pub use queries::{SearchDocuments, GetDocument, GetDocumentHistory, DocumentQueryHandler, DocumentView as DocumentQueryView, DocumentHistoryView};
+pub use queries::{SearchDocuments, GetDocument, GetDocumentHistory, DocumentQueryHandler, QueryLimits, DocumentReadStore, InMemoryDocumentReadStore, DocumentView as DocumentQueryView, DocumentHistoryView};
@@ -9,6 +9,13 @@ use std::time::SystemTime;
 use uuid::Uuid;
 use cid::Cid;
 
+/// Header holding the message ID
+pub const MESSAGE_ID_HEADER: &str = "Cim-Message-Id";
+/// Header holding the correlation ID
+pub const CORRELATION_ID_HEADER: &str = "Cim-Correlation-Id";
+/// Header holding the causation ID
+pub const CAUSATION_ID_HEADER: &str = "Cim-Causation-Id";
+
 /// Message identifiers required by CIM principles
 /// Every message in the system MUST have correlation and causation IDs
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -67,6 +74,28 @@ impl MessageIdentity {
         self.message_id.0 == self.correlation_id.0 && self.correlation_id.0 == self.causation_id.0
     }
 
+    /// Wrap an outgoing payload with this identity
+    pub fn attach<T>(&self, payload: T) -> CimMessage<T> {
+        CimMessage {
+            metadata: EventMetadata {
+                identity: self.clone(),
+                timestamp: SystemTime::now(),
+                actor: None,
+                schema_version: "1.0".to_string(),
+            },
+            payload,
+        }
+    }
+
+    /// Header name/value pairs carrying this identity on a published message
+    pub fn headers(&self) -> [(&'static str, String); 3] {
+        [
+            (MESSAGE_ID_HEADER, self.message_id.to_string()),
+            (CORRELATION_ID_HEADER, self.correlation_id.to_string()),
+            (CAUSATION_ID_HEADER, self.causation_id.to_string()),
+        ]
+    }
+
     /// Get the correlation chain depth (0 for root, 1+ for caused messages)
     pub fn chain_depth(&self) -> u32 {
         if self.is_root() {
@@ -583,6 +612,39 @@ mod tests {
         assert!(!child2.is_root());
     }
 
+    #[test]
+    fn test_caused_by_chain_keeps_correlation_across_generations() {
+        let root = MessageIdentity::new_root();
+        let child = MessageIdentity::new_caused_by(&root);
+        let grandchild = MessageIdentity::new_caused_by(&child);
+
+        for identity in [&root, &child, &grandchild] {
+            assert_eq!(identity.correlation_id.0, root.message_id.0);
+            assert!(identity.validate().is_ok());
+        }
+        assert_eq!(grandchild.causation_id.0, child.message_id.0);
+        assert_ne!(grandchild.message_id, child.message_id);
+
+        let json = serde_json::to_string(&grandchild).unwrap();
+        let restored: MessageIdentity = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, grandchild);
+    }
+
+    #[test]
+    fn test_attach_identity_to_outgoing_event() {
+        let parent = MessageIdentity::new_root();
+        let identity = MessageIdentity::new_caused_by(&parent);
+
+        let message = identity.attach(serde_json::json!({"event": "DocumentShared"}));
+        assert_eq!(message.identity(), &identity);
+        assert_eq!(message.payload["event"], "DocumentShared");
+
+        let headers = identity.headers();
+        assert_eq!(headers[0], (MESSAGE_ID_HEADER, identity.message_id.to_string()));
+        assert_eq!(headers[1], (CORRELATION_ID_HEADER, parent.message_id.to_string()));
+        assert_eq!(headers[2], (CAUSATION_ID_HEADER, parent.message_id.to_string()));
+    }
+
     #[test]
     fn test_cim_message_to_domain_event() {
         #[derive(Serialize)]
@@ -5,6 +5,12 @@
 
 pub mod subjects;
 pub mod message_identity;
+pub mod publisher;
+pub mod subscriber;
+pub mod responder;
 
 pub use subjects::*;
-pub use message_identity::*;
\ No newline at end of file
+pub use message_identity::*;
+pub use publisher::*;
+pub use subscriber::*;
+pub use responder::*;
\ No newline at end of file
@@ -0,0 +1,139 @@
+//! Publishing document events to NATS
+//!
+//! [`DocumentEventPublisher`] serializes a [`DocumentDomainEvent`], publishes
+//! it on the subject from [`DocumentDomainEvent::subject`] and carries the
+//! [`MessageIdentity`] as headers. The connection is abstracted by
+//! [`MessageTransport`]; the `nats-client` feature implements it for
+//! `async_nats::Client`.
+
+use super::{DocumentSubject, IdentityError, MessageIdentity};
+use crate::events::DocumentDomainEvent;
+use crate::value_objects::DocumentId;
+use async_trait::async_trait;
+
+/// Connection that can publish a message with headers
+#[async_trait]
+pub trait MessageTransport: Send + Sync {
+    async fn publish(
+        &self,
+        subject: String,
+        headers: Vec<(&'static str, String)>,
+        payload: Vec<u8>,
+    ) -> Result<(), PublishError>;
+}
+
+/// Publishing failures
+#[derive(Debug, thiserror::Error)]
+pub enum PublishError {
+    #[error("Invalid message identity: {0}")]
+    Identity(#[from] IdentityError),
+
+    #[error("Failed to serialize event: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+/// Publishes document domain events on their subjects
+pub struct DocumentEventPublisher<T> {
+    transport: T,
+}
+
+impl<T: MessageTransport> DocumentEventPublisher<T> {
+    pub fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Publish `event` for `document_id`, returning the subject it was sent on
+    pub async fn publish(
+        &self,
+        event: &DocumentDomainEvent,
+        document_id: &DocumentId,
+        identity: &MessageIdentity,
+    ) -> Result<DocumentSubject, PublishError> {
+        identity.validate()?;
+        let subject = event.subject(document_id);
+        let payload = serde_json::to_vec(event)?;
+
+        self.transport
+            .publish(subject.to_subject(), identity.headers().to_vec(), payload)
+            .await?;
+        Ok(subject)
+    }
+}
+
+#[cfg(feature = "nats-client")]
+#[async_trait]
+impl MessageTransport for async_nats::Client {
+    async fn publish(
+        &self,
+        subject: String,
+        headers: Vec<(&'static str, String)>,
+        payload: Vec<u8>,
+    ) -> Result<(), PublishError> {
+        let mut header_map = async_nats::HeaderMap::new();
+        for (name, value) in &headers {
+            header_map.insert(*name, value.as_str());
+        }
+        self.publish_with_headers(subject, header_map, payload.into())
+            .await
+            .map_err(|e| PublishError::Transport(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::DocumentArchived;
+    use crate::nats::{CORRELATION_ID_HEADER, MESSAGE_ID_HEADER};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    type Sent = (String, Vec<(&'static str, String)>, Vec<u8>);
+
+    #[derive(Default)]
+    struct RecordingTransport {
+        sent: Mutex<Vec<Sent>>,
+    }
+
+    #[async_trait]
+    impl MessageTransport for &RecordingTransport {
+        async fn publish(
+            &self,
+            subject: String,
+            headers: Vec<(&'static str, String)>,
+            payload: Vec<u8>,
+        ) -> Result<(), PublishError> {
+            self.sent.lock().unwrap().push((subject, headers, payload));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_sends_event_on_its_subject() {
+        let transport = RecordingTransport::default();
+        let publisher = DocumentEventPublisher::new(&transport);
+        let document_id = DocumentId::new();
+        let event = DocumentDomainEvent::DocumentArchived(DocumentArchived {
+            document_id,
+            reason: "expired".to_string(),
+            archived_by: uuid::Uuid::new_v4(),
+            archived_at: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        });
+        let identity = MessageIdentity::new_root();
+
+        let subject = publisher.publish(&event, &document_id, &identity).await.unwrap();
+
+        let sent = transport.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let (sent_subject, headers, payload) = &sent[0];
+        assert_eq!(sent_subject, &format!("events.document.document.archived.{document_id}"));
+        assert_eq!(sent_subject, &subject.to_subject());
+        assert!(headers.contains(&(MESSAGE_ID_HEADER, identity.message_id.to_string())));
+        assert!(headers.contains(&(CORRELATION_ID_HEADER, identity.correlation_id.to_string())));
+        let decoded: DocumentDomainEvent = serde_json::from_slice(payload).unwrap();
+        assert_eq!(decoded, event);
+    }
+}
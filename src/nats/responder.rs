@@ -0,0 +1,222 @@
+//! Serving document queries over NATS request/reply
+//!
+//! [`QueryResponder`] reads requests from `queries.document.>`, picks the
+//! query type from the subject's [`QueryType`], runs it through a
+//! [`DocumentQueryHandler`] and replies with a [`QueryReply`].
+
+use super::{
+    DocumentSubject, IncomingMessage, MessageSource, MessageTransport, PublishError, QueryType,
+    SubjectOperation,
+};
+use crate::queries::*;
+use cim_domain::Query;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Reply envelope for a query request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryReply {
+    /// The serialized view
+    Ok(serde_json::Value),
+    /// Why the query could not be answered
+    Error(String),
+}
+
+/// Answers query requests with a [`DocumentQueryHandler`]
+pub struct QueryResponder<T> {
+    handler: DocumentQueryHandler,
+    transport: T,
+}
+
+impl<T: MessageTransport> QueryResponder<T> {
+    /// Responder replying through `transport`
+    pub fn new(handler: DocumentQueryHandler, transport: T) -> Self {
+        Self { handler, transport }
+    }
+
+    /// Reply to every request from `requests` until it ends
+    ///
+    /// Requests without a reply subject are skipped.
+    pub async fn run(&self, mut requests: impl MessageSource) -> Result<(), PublishError> {
+        while let Some(request) = requests.next_message().await {
+            let Some(reply_to) = request.reply.clone() else {
+                continue;
+            };
+            let reply = self.respond(&request).await;
+            self.transport
+                .publish(reply_to, Vec::new(), serde_json::to_vec(&reply)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Run the query a request names and build its reply
+    pub async fn respond(&self, request: &IncomingMessage) -> QueryReply {
+        let query_type = match DocumentSubject::from_subject(&request.subject) {
+            Ok(DocumentSubject { operation: SubjectOperation::Query(query_type), .. }) => query_type,
+            Ok(_) => return QueryReply::Error(format!("{} is not a query subject", request.subject)),
+            Err(e) => return QueryReply::Error(e.to_string()),
+        };
+
+        let payload = &request.payload;
+        let view = match query_type {
+            QueryType::Get => self.answer::<GetDocument, DocumentView>(payload).await,
+            QueryType::GetHistory => self.answer::<GetDocumentHistory, DocumentHistoryView>(payload).await,
+            QueryType::Search => self.answer::<SearchDocuments, SearchResultsView>(payload).await,
+            QueryType::GetVersions => self.answer::<GetDocumentVersions, VersionsView>(payload).await,
+            QueryType::GetLinked => self.answer::<GetLinkedDocuments, LinkedDocumentsView>(payload).await,
+            QueryType::GetDocumentsInCollection => {
+                self.answer::<GetDocumentsInCollection, CollectionContentsView>(payload).await
+            }
+            QueryType::GetComments => self.answer::<GetDocumentComments, CommentsView>(payload).await,
+            QueryType::GetWorkflowStatus => self.answer::<GetWorkflowStatus, WorkflowStatusView>(payload).await,
+            QueryType::GetActiveWorkflows => self.answer::<GetActiveWorkflows, ActiveWorkflowsView>(payload).await,
+            QueryType::GetStats => self.answer::<GetDocumentStats, DocumentStatsView>(payload).await,
+            QueryType::GetActivity => self.answer::<GetActivity, ActivityView>(payload).await,
+            unsupported => Err(format!("Unsupported query type {}", unsupported.as_str())),
+        };
+
+        match view {
+            Ok(view) => QueryReply::Ok(view),
+            Err(error) => QueryReply::Error(error),
+        }
+    }
+
+    async fn answer<Q, V>(&self, payload: &[u8]) -> Result<serde_json::Value, String>
+    where
+        Q: Query + DeserializeOwned + 'static,
+        V: Serialize + 'static,
+    {
+        let query: Q = serde_json::from_slice(payload).map_err(|e| format!("Invalid query payload: {e}"))?;
+        let view = self.handler.handle(&query).await.map_err(|e| e.to_string())?;
+        let view = view
+            .downcast::<V>()
+            .map_err(|_| "Query handler returned an unexpected view".to_string())?;
+        serde_json::to_value(*view).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "nats-client")]
+impl QueryResponder<async_nats::Client> {
+    /// Serve `queries.document.>` on `client` until the subscription ends
+    pub async fn serve(&self) -> Result<(), PublishError> {
+        let requests = self
+            .transport
+            .subscribe(super::SubjectPatterns::all_document_queries())
+            .await
+            .map_err(|e| PublishError::Transport(e.to_string()))?;
+        self.run(requests).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nats::DocumentAggregate;
+    use crate::value_objects::{ContentBlock, DocumentId, DocumentState, DocumentType};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tokio::sync::mpsc;
+
+    #[derive(Default)]
+    struct Replies(Mutex<Vec<(String, Vec<u8>)>>);
+
+    #[async_trait]
+    impl MessageTransport for &Replies {
+        async fn publish(
+            &self,
+            subject: String,
+            _headers: Vec<(&'static str, String)>,
+            payload: Vec<u8>,
+        ) -> Result<(), PublishError> {
+            self.0.lock().unwrap().push((subject, payload));
+            Ok(())
+        }
+    }
+
+    struct Requests(mpsc::UnboundedReceiver<IncomingMessage>);
+
+    #[async_trait]
+    impl MessageSource for Requests {
+        async fn next_message(&mut self) -> Option<IncomingMessage> {
+            self.0.recv().await
+        }
+    }
+
+    fn view(title: &str) -> DocumentView {
+        DocumentView {
+            document_id: DocumentId::new(),
+            title: title.to_string(),
+            document_type: DocumentType::Report,
+            state: DocumentState::Draft,
+            author_id: uuid::Uuid::new_v4(),
+            content_blocks: vec![ContentBlock {
+                id: "body".to_string(),
+                block_type: "paragraph".to_string(),
+                title: None,
+                content: "Figures".to_string(),
+                metadata: HashMap::new(),
+            }],
+            tags: vec![],
+            mime_type: None,
+            metadata: HashMap::new(),
+            access_list: HashMap::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    fn request(query_type: QueryType, entity: &DocumentId, reply: &str, payload: Vec<u8>) -> IncomingMessage {
+        let subject = DocumentSubject::query(DocumentAggregate::Document, query_type, Some(entity.to_string()));
+        IncomingMessage {
+            subject: subject.to_subject(),
+            reply: Some(reply.to_string()),
+            payload,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_document_request_reply() {
+        let store = Arc::new(InMemoryDocumentReadStore::new());
+        let budget = view("Quarterly Budget");
+        store.insert_document(budget.clone()).await;
+        let replies = Replies::default();
+        let responder = QueryResponder::new(DocumentQueryHandler::new(store), &replies);
+
+        let get = GetDocument {
+            document_id: budget.document_id,
+            include_content: true,
+            include_metadata: true,
+        };
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let id = budget.document_id;
+        sender.send(request(QueryType::Get, &id, "_INBOX.1", serde_json::to_vec(&get).unwrap())).unwrap();
+        sender.send(request(QueryType::GetUsage, &id, "_INBOX.2", b"{}".to_vec())).unwrap();
+        sender.send(request(QueryType::Get, &id, "_INBOX.3", b"not json".to_vec())).unwrap();
+        drop(sender);
+
+        responder.run(Requests(receiver)).await.unwrap();
+
+        let replies = replies.0.lock().unwrap();
+        let subjects: Vec<&str> = replies.iter().map(|(subject, _)| subject.as_str()).collect();
+        assert_eq!(subjects, vec!["_INBOX.1", "_INBOX.2", "_INBOX.3"]);
+
+        let QueryReply::Ok(found) = serde_json::from_slice(&replies[0].1).unwrap() else {
+            panic!("GetDocument failed");
+        };
+        let found: DocumentView = serde_json::from_value(found).unwrap();
+        assert_eq!(found.document_id, budget.document_id);
+        assert_eq!(found.title, "Quarterly Budget");
+        assert_eq!(found.content_blocks.len(), 1);
+
+        assert_eq!(
+            serde_json::from_slice::<QueryReply>(&replies[1].1).unwrap(),
+            QueryReply::Error("Unsupported query type get_usage".to_string())
+        );
+        assert!(matches!(
+            serde_json::from_slice::<QueryReply>(&replies[2].1).unwrap(),
+            QueryReply::Error(error) if error.starts_with("Invalid query payload")
+        ));
+    }
+}
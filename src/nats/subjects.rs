@@ -14,6 +14,9 @@
 //! - `events.document.user.{event_type}.{user_id}` - User-scoped events
 //! - `events.document.user.{user_id}.{aggregate}.{event_type}.{entity_id}` - User + entity events
 //! - `events.document.cid.{content_cid}.{aggregate}.{event_type}` - CID + aggregate events
+//! - `events.document.document_type.{type}.{event_type}` - Events addressed by document type
+//! - `events.document.entity_type.{type}.{event_type}` - Events addressed by extracted entity type
+//! - `integration.document.{target_domain}.{event_type}.{entity_id}` - Events announced to another domain
 //!
 //! This algebra ensures:
 //! - Perfect domain isolation through event boundaries
@@ -26,7 +29,8 @@
 
 use serde::{Serialize, Deserialize};
 use std::fmt;
-use crate::value_objects::{DocumentId, TemplateId};
+use crate::events::DocumentDomainEvent;
+use crate::value_objects::{DocumentId, DocumentType, EntityType, TemplateId};
 use uuid::Uuid;
 use cid::Cid;
 
@@ -70,6 +74,18 @@ pub enum SubjectScope {
         content_cid: String,
         user_id: String,
     },
+    /// Another domain consuming an integration event
+    Integration {
+        target_domain: String,
+    },
+    /// Document type scope, holding a [`DocumentType::subject_token`]
+    DocumentType {
+        document_type: String,
+    },
+    /// Extracted entity type scope, holding an [`EntityType::subject_token`]
+    EntityType {
+        entity_type: String,
+    },
 }
 
 impl DocumentSubject {
@@ -212,6 +228,31 @@ impl DocumentSubject {
         )
     }
 
+    /// Create an integration event subject announced to `target_domain`
+    ///
+    /// The target must be a single subject token: non-empty, without dots,
+    /// whitespace or wildcards.
+    pub fn integration_event(
+        target_domain: &str,
+        event_type: EventType,
+        entity_id: String,
+    ) -> Result<Self, SubjectError> {
+        let is_token = !target_domain.is_empty()
+            && !target_domain.chars().any(|c| c == '.' || c == '*' || c == '>' || c.is_whitespace());
+        if !is_token {
+            return Err(SubjectError::InvalidTargetDomain(target_domain.to_string()));
+        }
+
+        Ok(Self::new(
+            SubjectNamespace::Integration,
+            SubjectScope::Integration {
+                target_domain: target_domain.to_string(),
+            },
+            SubjectOperation::Event(event_type),
+            Some(entity_id),
+        ))
+    }
+
     /// Get subject for wildcard subscription
     pub fn wildcard_pattern(&self) -> String {
         let base_pattern = self.build_base_subject();
@@ -258,7 +299,94 @@ impl DocumentSubject {
             SubjectScope::CidUser { content_cid, user_id } => {
                 format!("{}.{}.cid.{}.user.{}.{}", namespace, domain, content_cid, user_id, operation)
             }
+            SubjectScope::Integration { target_domain } => {
+                format!("{}.{}.{}.{}", namespace, domain, target_domain, operation)
+            }
+            SubjectScope::DocumentType { document_type } => {
+                format!("{}.{}.document_type.{}.{}", namespace, domain, document_type, operation)
+            }
+            SubjectScope::EntityType { entity_type } => {
+                format!("{}.{}.entity_type.{}.{}", namespace, domain, entity_type, operation)
+            }
+        }
+    }
+}
+
+impl DocumentSubject {
+    /// Parse a subject string produced by [`to_subject`](Self::to_subject)
+    pub fn from_subject(s: &str) -> Result<Self, SubjectError> {
+        let invalid = || SubjectError::InvalidFormat(s.to_string());
+        let tokens: Vec<&str> = s.split('.').collect();
+        if tokens.len() < 4 || tokens.iter().any(|token| token.is_empty()) {
+            return Err(invalid());
         }
+
+        let namespace = SubjectNamespace::parse(tokens[0]).ok_or_else(invalid)?;
+        let domain = DocumentDomain::parse(tokens[1]).ok_or_else(invalid)?;
+        let is_operation = |token: &str| SubjectOperation::parse(&namespace, token).is_some();
+        // An optional aggregate is only present when an operation follows it
+        let optional_aggregate = |tail: &[&str]| match tail {
+            [aggregate, next, ..] if is_operation(*next) => DocumentAggregate::parse(aggregate),
+            _ => None,
+        };
+
+        let (scope, tail) = match &tokens[2..] {
+            [target_domain, tail @ ..] if namespace == SubjectNamespace::Integration => {
+                (SubjectScope::Integration { target_domain: target_domain.to_string() }, tail)
+            }
+            ["user", user_id, "document", document_id, tail @ ..]
+                if !tail.is_empty() && !is_operation(*document_id) =>
+            {
+                let scope = SubjectScope::UserDocument {
+                    user_id: user_id.to_string(),
+                    document_id: document_id.to_string(),
+                };
+                (scope, tail)
+            }
+            ["user", user_id, tail @ ..] => {
+                let aggregate = optional_aggregate(tail);
+                let tail = if aggregate.is_some() { &tail[1..] } else { tail };
+                (SubjectScope::User { user_id: user_id.to_string(), aggregate }, tail)
+            }
+            ["cid", content_cid, "user", user_id, tail @ ..] => {
+                let scope = SubjectScope::CidUser {
+                    content_cid: content_cid.to_string(),
+                    user_id: user_id.to_string(),
+                };
+                (scope, tail)
+            }
+            ["document_type", document_type, tail @ ..] => {
+                (SubjectScope::DocumentType { document_type: document_type.to_string() }, tail)
+            }
+            ["entity_type", entity_type, tail @ ..] => {
+                (SubjectScope::EntityType { entity_type: entity_type.to_string() }, tail)
+            }
+            ["cid", content_cid, tail @ ..] => {
+                let aggregate = optional_aggregate(tail);
+                let tail = if aggregate.is_some() { &tail[1..] } else { tail };
+                (SubjectScope::Cid { content_cid: content_cid.to_string(), aggregate }, tail)
+            }
+            [aggregate, tail @ ..] => {
+                let aggregate = DocumentAggregate::parse(aggregate).ok_or_else(invalid)?;
+                (SubjectScope::Aggregate(aggregate), tail)
+            }
+            [] => return Err(invalid()),
+        };
+
+        let (operation, entity_id) = match tail {
+            [operation] => (operation, None),
+            [operation, entity_id] => (operation, Some(entity_id.to_string())),
+            _ => return Err(invalid()),
+        };
+        let operation = SubjectOperation::parse(&namespace, operation).ok_or_else(invalid)?;
+
+        Ok(Self {
+            namespace,
+            domain,
+            scope,
+            operation,
+            entity_id,
+        })
     }
 }
 
@@ -293,6 +421,18 @@ impl SubjectNamespace {
             Self::Integration => "integration",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "domain" => Some(Self::Domain),
+            "events" => Some(Self::Events),
+            "commands" => Some(Self::Commands),
+            "queries" => Some(Self::Queries),
+            "integration" => Some(Self::Integration),
+            _ => None,
+        }
+    }
 }
 
 /// Document domain identifier
@@ -307,6 +447,14 @@ impl DocumentDomain {
             Self::Document => "document",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "document" => Some(Self::Document),
+            _ => None,
+        }
+    }
 }
 
 /// Aggregates within the Document domain
@@ -352,6 +500,24 @@ impl DocumentAggregate {
             Self::Comment => "comment",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "document" => Some(Self::Document),
+            "version" => Some(Self::Version),
+            "metadata" => Some(Self::Metadata),
+            "content" => Some(Self::Content),
+            "template" => Some(Self::Template),
+            "collection" => Some(Self::Collection),
+            "workflow" => Some(Self::Workflow),
+            "search" => Some(Self::Search),
+            "classification" => Some(Self::Classification),
+            "relationship" => Some(Self::Relationship),
+            "comment" => Some(Self::Comment),
+            _ => None,
+        }
+    }
 }
 
 /// Operations within subject algebra
@@ -376,6 +542,20 @@ impl SubjectOperation {
             Self::Workflow(workflow_op) => workflow_op.as_str().to_string(),
         }
     }
+
+    /// Parse an operation token; its kind follows the namespace
+    ///
+    /// Events and integration subjects carry an [`EventType`], commands a
+    /// [`CommandType`], queries a [`QueryType`] and domain subjects a
+    /// [`WorkflowOperation`].
+    pub fn parse(namespace: &SubjectNamespace, s: &str) -> Option<Self> {
+        match namespace {
+            SubjectNamespace::Events | SubjectNamespace::Integration => EventType::parse(s).map(Self::Event),
+            SubjectNamespace::Commands => CommandType::parse(s).map(Self::Command),
+            SubjectNamespace::Queries => QueryType::parse(s).map(Self::Query),
+            SubjectNamespace::Domain => WorkflowOperation::parse(s).map(Self::Workflow),
+        }
+    }
 }
 
 /// Event types in the Document domain (past tense - things that happened)
@@ -407,6 +587,7 @@ pub enum EventType {
     
     // Relationship events
     Shared,
+    ShareRevoked,
     Linked,
     Merged,
     Forked,
@@ -455,9 +636,31 @@ pub enum EventType {
     
     // State events
     StateChanged,
+    
+    // Search events
+    SearchSaved,
+    SavedSearchDeleted,
 }
 
 impl EventType {
+    /// Every variant, in declaration order
+    pub const ALL: &'static [Self] = &[
+        Self::Created, Self::Uploaded, Self::Updated, Self::Deleted, Self::Archived, Self::Restored,
+        Self::ContentUpdated, Self::ContentReplaced, Self::VersionCreated, Self::VersionTagged,
+        Self::VersionRestored, Self::VersionRolledBack, Self::VersionsCompared,
+        Self::MetadataUpdated, Self::Classified, Self::Tagged, Self::Shared, Self::ShareRevoked,
+        Self::Linked, Self::Merged, Self::Forked, Self::AddedToCollection,
+        Self::RemovedFromCollection, Self::CollectionCreated, Self::EntitiesExtracted,
+        Self::SummaryGenerated, Self::TemplateApplied, Self::Imported, Self::Exported,
+        Self::Transformed, Self::CommentAdded, Self::CommentUpdated, Self::CommentDeleted,
+        Self::CommentResolved, Self::EditAccessRequested, Self::EditAccessGranted,
+        Self::EditSessionStarted, Self::EditSessionCancelled, Self::EditedDirect, Self::EditedPatch,
+        Self::EditedStructured, Self::EditsMerged, Self::EditFailed, Self::SuccessorCreated,
+        Self::ChainVerified, Self::ChainBroken, Self::WorkflowStarted, Self::WorkflowTransitioned,
+        Self::WorkflowCompleted, Self::WorkflowFailed, Self::WorkflowCancelled, Self::StateChanged,
+        Self::SearchSaved, Self::SavedSearchDeleted,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Created => "created",
@@ -477,6 +680,7 @@ impl EventType {
             Self::Classified => "classified",
             Self::Tagged => "tagged",
             Self::Shared => "shared",
+            Self::ShareRevoked => "share_revoked",
             Self::Linked => "linked",
             Self::Merged => "merged",
             Self::Forked => "forked",
@@ -511,6 +715,69 @@ impl EventType {
             Self::WorkflowFailed => "workflow_failed",
             Self::WorkflowCancelled => "workflow_cancelled",
             Self::StateChanged => "state_changed",
+            Self::SearchSaved => "search_saved",
+            Self::SavedSearchDeleted => "saved_search_deleted",
+        }
+    }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "created" => Some(Self::Created),
+            "uploaded" => Some(Self::Uploaded),
+            "updated" => Some(Self::Updated),
+            "deleted" => Some(Self::Deleted),
+            "archived" => Some(Self::Archived),
+            "restored" => Some(Self::Restored),
+            "content_updated" => Some(Self::ContentUpdated),
+            "content_replaced" => Some(Self::ContentReplaced),
+            "version_created" => Some(Self::VersionCreated),
+            "version_tagged" => Some(Self::VersionTagged),
+            "version_restored" => Some(Self::VersionRestored),
+            "version_rolled_back" => Some(Self::VersionRolledBack),
+            "versions_compared" => Some(Self::VersionsCompared),
+            "metadata_updated" => Some(Self::MetadataUpdated),
+            "classified" => Some(Self::Classified),
+            "tagged" => Some(Self::Tagged),
+            "shared" => Some(Self::Shared),
+            "share_revoked" => Some(Self::ShareRevoked),
+            "linked" => Some(Self::Linked),
+            "merged" => Some(Self::Merged),
+            "forked" => Some(Self::Forked),
+            "added_to_collection" => Some(Self::AddedToCollection),
+            "removed_from_collection" => Some(Self::RemovedFromCollection),
+            "collection_created" => Some(Self::CollectionCreated),
+            "entities_extracted" => Some(Self::EntitiesExtracted),
+            "summary_generated" => Some(Self::SummaryGenerated),
+            "template_applied" => Some(Self::TemplateApplied),
+            "imported" => Some(Self::Imported),
+            "exported" => Some(Self::Exported),
+            "transformed" => Some(Self::Transformed),
+            "comment_added" => Some(Self::CommentAdded),
+            "comment_updated" => Some(Self::CommentUpdated),
+            "comment_deleted" => Some(Self::CommentDeleted),
+            "comment_resolved" => Some(Self::CommentResolved),
+            "edit_access_requested" => Some(Self::EditAccessRequested),
+            "edit_access_granted" => Some(Self::EditAccessGranted),
+            "edit_session_started" => Some(Self::EditSessionStarted),
+            "edit_session_cancelled" => Some(Self::EditSessionCancelled),
+            "edited_direct" => Some(Self::EditedDirect),
+            "edited_patch" => Some(Self::EditedPatch),
+            "edited_structured" => Some(Self::EditedStructured),
+            "edits_merged" => Some(Self::EditsMerged),
+            "edit_failed" => Some(Self::EditFailed),
+            "successor_created" => Some(Self::SuccessorCreated),
+            "chain_verified" => Some(Self::ChainVerified),
+            "chain_broken" => Some(Self::ChainBroken),
+            "workflow_started" => Some(Self::WorkflowStarted),
+            "workflow_transitioned" => Some(Self::WorkflowTransitioned),
+            "workflow_completed" => Some(Self::WorkflowCompleted),
+            "workflow_failed" => Some(Self::WorkflowFailed),
+            "workflow_cancelled" => Some(Self::WorkflowCancelled),
+            "state_changed" => Some(Self::StateChanged),
+            "search_saved" => Some(Self::SearchSaved),
+            "saved_search_deleted" => Some(Self::SavedSearchDeleted),
+            _ => None,
         }
     }
 }
@@ -544,6 +811,7 @@ pub enum CommandType {
     
     // Relationship commands
     Share,
+    RevokeShare,
     Link,
     Merge,
     Fork,
@@ -592,6 +860,22 @@ pub enum CommandType {
 }
 
 impl CommandType {
+    /// Every variant, in declaration order
+    pub const ALL: &'static [Self] = &[
+        Self::Create, Self::Upload, Self::Update, Self::Delete, Self::Archive, Self::Restore,
+        Self::UpdateContent, Self::ReplaceContent, Self::CreateVersion, Self::TagVersion,
+        Self::RestoreVersion, Self::RollbackVersion, Self::CompareVersions, Self::UpdateMetadata,
+        Self::Classify, Self::Tag, Self::Share, Self::RevokeShare, Self::Link, Self::Merge,
+        Self::Fork, Self::AddToCollection, Self::RemoveFromCollection, Self::CreateCollection,
+        Self::ExtractEntities, Self::GenerateSummary, Self::ApplyTemplate, Self::Import,
+        Self::Export, Self::Transform, Self::AddComment, Self::UpdateComment, Self::DeleteComment,
+        Self::ResolveComment, Self::RequestEditAccess, Self::GrantEditAccess,
+        Self::StartEditSession, Self::CancelEditSession, Self::EditDirect, Self::EditPatch,
+        Self::EditStructured, Self::MergeEdits, Self::CreateSuccessor, Self::VerifyChain,
+        Self::StartWorkflow, Self::TransitionWorkflow, Self::CompleteWorkflow, Self::CancelWorkflow,
+        Self::ChangeState,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Create => "create",
@@ -611,6 +895,7 @@ impl CommandType {
             Self::Classify => "classify",
             Self::Tag => "tag",
             Self::Share => "share",
+            Self::RevokeShare => "revoke_share",
             Self::Link => "link",
             Self::Merge => "merge",
             Self::Fork => "fork",
@@ -644,6 +929,62 @@ impl CommandType {
             Self::ChangeState => "change_state",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "create" => Some(Self::Create),
+            "upload" => Some(Self::Upload),
+            "update" => Some(Self::Update),
+            "delete" => Some(Self::Delete),
+            "archive" => Some(Self::Archive),
+            "restore" => Some(Self::Restore),
+            "update_content" => Some(Self::UpdateContent),
+            "replace_content" => Some(Self::ReplaceContent),
+            "create_version" => Some(Self::CreateVersion),
+            "tag_version" => Some(Self::TagVersion),
+            "restore_version" => Some(Self::RestoreVersion),
+            "rollback_version" => Some(Self::RollbackVersion),
+            "compare_versions" => Some(Self::CompareVersions),
+            "update_metadata" => Some(Self::UpdateMetadata),
+            "classify" => Some(Self::Classify),
+            "tag" => Some(Self::Tag),
+            "share" => Some(Self::Share),
+            "revoke_share" => Some(Self::RevokeShare),
+            "link" => Some(Self::Link),
+            "merge" => Some(Self::Merge),
+            "fork" => Some(Self::Fork),
+            "add_to_collection" => Some(Self::AddToCollection),
+            "remove_from_collection" => Some(Self::RemoveFromCollection),
+            "create_collection" => Some(Self::CreateCollection),
+            "extract_entities" => Some(Self::ExtractEntities),
+            "generate_summary" => Some(Self::GenerateSummary),
+            "apply_template" => Some(Self::ApplyTemplate),
+            "import" => Some(Self::Import),
+            "export" => Some(Self::Export),
+            "transform" => Some(Self::Transform),
+            "add_comment" => Some(Self::AddComment),
+            "update_comment" => Some(Self::UpdateComment),
+            "delete_comment" => Some(Self::DeleteComment),
+            "resolve_comment" => Some(Self::ResolveComment),
+            "request_edit_access" => Some(Self::RequestEditAccess),
+            "grant_edit_access" => Some(Self::GrantEditAccess),
+            "start_edit_session" => Some(Self::StartEditSession),
+            "cancel_edit_session" => Some(Self::CancelEditSession),
+            "edit_direct" => Some(Self::EditDirect),
+            "edit_patch" => Some(Self::EditPatch),
+            "edit_structured" => Some(Self::EditStructured),
+            "merge_edits" => Some(Self::MergeEdits),
+            "create_successor" => Some(Self::CreateSuccessor),
+            "verify_chain" => Some(Self::VerifyChain),
+            "start_workflow" => Some(Self::StartWorkflow),
+            "transition_workflow" => Some(Self::TransitionWorkflow),
+            "complete_workflow" => Some(Self::CompleteWorkflow),
+            "cancel_workflow" => Some(Self::CancelWorkflow),
+            "change_state" => Some(Self::ChangeState),
+            _ => None,
+        }
+    }
 }
 
 /// Query types in the Document domain (interrogative - things to ask)
@@ -696,6 +1037,17 @@ pub enum QueryType {
 }
 
 impl QueryType {
+    /// Every variant, in declaration order
+    pub const ALL: &'static [Self] = &[
+        Self::Get, Self::GetHistory, Self::Search, Self::List, Self::GetVersion, Self::GetVersions,
+        Self::CompareVersions, Self::GetMetadata, Self::GetClassification, Self::GetTags,
+        Self::GetRelationships, Self::GetSharedWith, Self::GetLinked, Self::GetCollection,
+        Self::GetCollections, Self::GetDocumentsInCollection, Self::GetContent, Self::GetDiff,
+        Self::ExtractText, Self::GetComments, Self::GetComment, Self::GetWorkflowStatus,
+        Self::GetWorkflowHistory, Self::GetActiveWorkflows, Self::GetStats, Self::GetActivity,
+        Self::GetUsage,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Get => "get",
@@ -727,6 +1079,40 @@ impl QueryType {
             Self::GetUsage => "get_usage",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "get" => Some(Self::Get),
+            "get_history" => Some(Self::GetHistory),
+            "search" => Some(Self::Search),
+            "list" => Some(Self::List),
+            "get_version" => Some(Self::GetVersion),
+            "get_versions" => Some(Self::GetVersions),
+            "compare_versions" => Some(Self::CompareVersions),
+            "get_metadata" => Some(Self::GetMetadata),
+            "get_classification" => Some(Self::GetClassification),
+            "get_tags" => Some(Self::GetTags),
+            "get_relationships" => Some(Self::GetRelationships),
+            "get_shared_with" => Some(Self::GetSharedWith),
+            "get_linked" => Some(Self::GetLinked),
+            "get_collection" => Some(Self::GetCollection),
+            "get_collections" => Some(Self::GetCollections),
+            "get_documents_in_collection" => Some(Self::GetDocumentsInCollection),
+            "get_content" => Some(Self::GetContent),
+            "get_diff" => Some(Self::GetDiff),
+            "extract_text" => Some(Self::ExtractText),
+            "get_comments" => Some(Self::GetComments),
+            "get_comment" => Some(Self::GetComment),
+            "get_workflow_status" => Some(Self::GetWorkflowStatus),
+            "get_workflow_history" => Some(Self::GetWorkflowHistory),
+            "get_active_workflows" => Some(Self::GetActiveWorkflows),
+            "get_stats" => Some(Self::GetStats),
+            "get_activity" => Some(Self::GetActivity),
+            "get_usage" => Some(Self::GetUsage),
+            _ => None,
+        }
+    }
 }
 
 /// Workflow operations
@@ -778,6 +1164,201 @@ impl WorkflowOperation {
             Self::EvaluateCondition => "evaluate_condition",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "start" => Some(Self::Start),
+            "transition" => Some(Self::Transition),
+            "complete" => Some(Self::Complete),
+            "cancel" => Some(Self::Cancel),
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "register" => Some(Self::Register),
+            "update" => Some(Self::Update),
+            "delete" => Some(Self::Delete),
+            "get_status" => Some(Self::GetStatus),
+            "get_history" => Some(Self::GetHistory),
+            "list_active" => Some(Self::ListActive),
+            "enter_node" => Some(Self::EnterNode),
+            "exit_node" => Some(Self::ExitNode),
+            "execute_action" => Some(Self::ExecuteAction),
+            "evaluate_condition" => Some(Self::EvaluateCondition),
+            _ => None,
+        }
+    }
+}
+
+/// Subject each domain event is published on
+impl DocumentDomainEvent {
+    /// Event subject for this event on `document_id`
+    pub fn subject(&self, document_id: &DocumentId) -> DocumentSubject {
+        let (aggregate, event_type) = self.subject_parts();
+        DocumentSubject::event(aggregate, event_type, document_id.to_string())
+    }
+
+    /// Aggregate and event type this event is published under
+    ///
+    /// The match is exhaustive so new variants must be given a subject.
+    pub fn subject_parts(&self) -> (DocumentAggregate, EventType) {
+        use DocumentAggregate as Aggregate;
+        use DocumentDomainEvent as Event;
+
+        match self {
+            Event::DocumentUploaded(_) => (Aggregate::Document, EventType::Uploaded),
+            Event::DocumentMetadataUpdated(_) => (Aggregate::Metadata, EventType::MetadataUpdated),
+            Event::DocumentShared(_) => (Aggregate::Relationship, EventType::Shared),
+            Event::ShareRevoked(_) => (Aggregate::Relationship, EventType::ShareRevoked),
+            Event::DocumentDeleted(_) => (Aggregate::Document, EventType::Deleted),
+            Event::DocumentArchived(_) => (Aggregate::Document, EventType::Archived),
+            Event::DocumentCreated(_) => (Aggregate::Document, EventType::Created),
+            Event::ContentUpdated(_) => (Aggregate::Content, EventType::ContentUpdated),
+            Event::StateChanged(_) => (Aggregate::Document, EventType::StateChanged),
+            Event::DocumentForked(_) => (Aggregate::Relationship, EventType::Forked),
+            Event::VersionTagged(_) => (Aggregate::Version, EventType::VersionTagged),
+            Event::CommentAdded(_) => (Aggregate::Comment, EventType::CommentAdded),
+            Event::DocumentsLinked(_) => (Aggregate::Relationship, EventType::Linked),
+            Event::DocumentsMerged(_) => (Aggregate::Relationship, EventType::Merged),
+            Event::VersionRolledBack(_) => (Aggregate::Version, EventType::VersionRolledBack),
+            Event::EntitiesExtracted(_) => (Aggregate::Content, EventType::EntitiesExtracted),
+            Event::SummaryGenerated(_) => (Aggregate::Content, EventType::SummaryGenerated),
+            Event::DocumentClassified(_) => (Aggregate::Classification, EventType::Classified),
+            Event::DocumentContentUpdated(_) => (Aggregate::Content, EventType::ContentUpdated),
+            Event::DocumentTagged(_) => (Aggregate::Metadata, EventType::Tagged),
+            Event::DocumentVersionCreated(_) => (Aggregate::Version, EventType::VersionCreated),
+            Event::DocumentVersionRestored(_) => (Aggregate::Version, EventType::VersionRestored),
+            Event::TemplateApplied(_) => (Aggregate::Template, EventType::TemplateApplied),
+            Event::CollectionCreated(_) => (Aggregate::Collection, EventType::CollectionCreated),
+            Event::DocumentAddedToCollection(_) => (Aggregate::Collection, EventType::AddedToCollection),
+            Event::DocumentRemovedFromCollection(_) => (Aggregate::Collection, EventType::RemovedFromCollection),
+            Event::DocumentImported(_) => (Aggregate::Document, EventType::Imported),
+            Event::DocumentExported(_) => (Aggregate::Document, EventType::Exported),
+            Event::DocumentRestored(_) => (Aggregate::Document, EventType::Restored),
+            Event::VersionsCompared(_) => (Aggregate::Version, EventType::VersionsCompared),
+            Event::DocumentSuccessorCreated(_) => (Aggregate::Version, EventType::SuccessorCreated),
+            Event::DocumentEditedDirect(_) => (Aggregate::Content, EventType::EditedDirect),
+            Event::DocumentEditedPatch(_) => (Aggregate::Content, EventType::EditedPatch),
+            Event::DocumentEditedStructured(_) => (Aggregate::Content, EventType::EditedStructured),
+            Event::EditAccessRequested(_) => (Aggregate::Document, EventType::EditAccessRequested),
+            Event::EditAccessGranted(_) => (Aggregate::Document, EventType::EditAccessGranted),
+            Event::EditSessionCancelled(_) => (Aggregate::Document, EventType::EditSessionCancelled),
+            Event::DocumentTransformed(_) => (Aggregate::Content, EventType::Transformed),
+            Event::DocumentEditsMerged(_) => (Aggregate::Content, EventType::EditsMerged),
+            Event::DocumentRolledBack(_) => (Aggregate::Version, EventType::VersionRolledBack),
+            Event::CidChainVerified(e) if e.verification_result.is_valid => {
+                (Aggregate::Version, EventType::ChainVerified)
+            }
+            Event::CidChainVerified(_) => (Aggregate::Version, EventType::ChainBroken),
+            Event::DocumentEditFailed(_) => (Aggregate::Document, EventType::EditFailed),
+            Event::SearchSaved(_) => (Aggregate::Search, EventType::SearchSaved),
+            Event::SavedSearchDeleted(_) => (Aggregate::Search, EventType::SavedSearchDeleted),
+        }
+    }
+}
+
+/// Make free text usable as a single subject token
+///
+/// Lowercases ASCII letters, keeps digits, `-` and `_`, and turns every other
+/// run of characters (dots, spaces, wildcards, non-ASCII) into one `_`.
+/// Leading and trailing separators are dropped, so the result may be empty.
+pub fn sanitize_for_subject(s: &str) -> String {
+    let mut token = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            token.push(c.to_ascii_lowercase());
+        } else if !token.ends_with('_') {
+            token.push('_');
+        }
+    }
+    token.trim_matches(|c| c == '_' || c == '-').to_string()
+}
+
+/// [`sanitize_for_subject`], rejecting text with nothing usable left
+fn custom_token(prefix: &str, s: &str) -> Result<String, SubjectError> {
+    let token = sanitize_for_subject(s);
+    if token.is_empty() {
+        return Err(SubjectError::InvalidToken(s.to_string()));
+    }
+    Ok(format!("{prefix}-{token}"))
+}
+
+impl DocumentType {
+    /// Subject token for this type; custom types are sanitized and prefixed `other-`
+    pub fn subject_token(&self) -> Result<String, SubjectError> {
+        match self {
+            DocumentType::Other(name) => custom_token("other", name),
+            known => Ok(known.as_str().to_string()),
+        }
+    }
+
+    /// Inverse of [`subject_token`](Self::subject_token); custom types come back sanitized
+    pub fn from_subject_token(token: &str) -> Option<Self> {
+        match token.strip_prefix("other-") {
+            Some(name) if !name.is_empty() => Some(DocumentType::Other(name.to_string())),
+            Some(_) => None,
+            None => match DocumentType::from_name(token) {
+                DocumentType::Other(_) => None,
+                known => Some(known),
+            },
+        }
+    }
+}
+
+impl EntityType {
+    /// Subject token for this type; custom types are sanitized and prefixed `custom-`
+    pub fn subject_token(&self) -> Result<String, SubjectError> {
+        Ok(match self {
+            EntityType::Person => "person".to_string(),
+            EntityType::Organization => "organization".to_string(),
+            EntityType::Location => "location".to_string(),
+            EntityType::DateTime => "date_time".to_string(),
+            EntityType::Concept => "concept".to_string(),
+            EntityType::Keyword => "keyword".to_string(),
+            EntityType::Email => "email".to_string(),
+            EntityType::PhoneNumber => "phone_number".to_string(),
+            EntityType::Custom(name) => return custom_token("custom", name),
+        })
+    }
+
+    /// Inverse of [`subject_token`](Self::subject_token); custom types come back sanitized
+    pub fn from_subject_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "person" => EntityType::Person,
+            "organization" => EntityType::Organization,
+            "location" => EntityType::Location,
+            "date_time" => EntityType::DateTime,
+            "concept" => EntityType::Concept,
+            "keyword" => EntityType::Keyword,
+            "email" => EntityType::Email,
+            "phone_number" => EntityType::PhoneNumber,
+            _ => match token.strip_prefix("custom-") {
+                Some(name) if !name.is_empty() => EntityType::Custom(name.to_string()),
+                _ => return None,
+            },
+        })
+    }
+}
+
+impl DocumentSubject {
+    /// Classification event addressed by document type, e.g. `events.document.document_type.report.classified`
+    pub fn document_type_event(document_type: &DocumentType, event_type: EventType) -> Result<Self, SubjectError> {
+        Ok(Self::new(
+            SubjectNamespace::Events,
+            SubjectScope::DocumentType { document_type: document_type.subject_token()? },
+            SubjectOperation::Event(event_type),
+            None,
+        ))
+    }
+
+    /// Entity extraction event addressed by entity type, e.g. `events.document.entity_type.person.entities_extracted`
+    pub fn entity_type_event(entity_type: &EntityType) -> Result<Self, SubjectError> {
+        Ok(Self::new(
+            SubjectNamespace::Events,
+            SubjectScope::EntityType { entity_type: entity_type.subject_token()? },
+            SubjectOperation::Event(EventType::EntitiesExtracted),
+            None,
+        ))
+    }
 }
 
 /// Predefined subject patterns for common operations
@@ -819,6 +1400,11 @@ impl SubjectPatterns {
         "events.document.version.>".to_string()
     }
     
+    /// All document queries
+    pub fn all_document_queries() -> String {
+        "queries.document.>".to_string()
+    }
+    
     /// Search queries
     pub fn search_queries() -> String {
         "queries.document.search.*".to_string()
@@ -909,6 +1495,36 @@ impl SubjectPatterns {
     }
 }
 
+/// Whether `subject` would be delivered to a subscription on `pattern`
+///
+/// `*` matches exactly one token and a trailing `>` matches one or more.
+/// Empty tokens, or a `>` anywhere but last, never match.
+pub fn subject_matches(pattern: &str, subject: &str) -> bool {
+    if subject.split('.').any(str::is_empty) {
+        return false;
+    }
+    let mut subject_tokens = subject.split('.');
+    let mut pattern_tokens = pattern.split('.').peekable();
+
+    while let Some(token) = pattern_tokens.next() {
+        match token {
+            ">" => return pattern_tokens.peek().is_none() && subject_tokens.next().is_some(),
+            "" => return false,
+            "*" => {
+                if subject_tokens.next().is_none() {
+                    return false;
+                }
+            }
+            literal => {
+                if subject_tokens.next() != Some(literal) {
+                    return false;
+                }
+            }
+        }
+    }
+    subject_tokens.next().is_none()
+}
+
 /// Subject builder for programmatic subject construction
 pub struct SubjectBuilder {
     namespace: Option<SubjectNamespace>,
@@ -1023,6 +1639,12 @@ pub enum SubjectError {
     
     #[error("Invalid subject format: {0}")]
     InvalidFormat(String),
+
+    #[error("Invalid target domain: {0:?}")]
+    InvalidTargetDomain(String),
+
+    #[error("No subject-safe characters in {0:?}")]
+    InvalidToken(String),
 }
 
 #[cfg(test)]
@@ -1036,13 +1658,13 @@ mod tests {
         let subject = DocumentSubject::event(
             DocumentAggregate::Document,
             EventType::Created,
-            doc_id.as_str().to_string(),
+            doc_id.to_string(),
         );
         
         assert_eq!(subject.namespace, SubjectNamespace::Events);
         assert!(matches!(subject.scope, SubjectScope::Aggregate(DocumentAggregate::Document)));
         assert!(matches!(subject.operation, SubjectOperation::Event(EventType::Created)));
-        assert_eq!(subject.entity_id, Some(doc_id.as_str().to_string()));
+        assert_eq!(subject.entity_id, Some(doc_id.to_string()));
     }
     
     #[test]
@@ -1051,7 +1673,7 @@ mod tests {
         let subject = DocumentSubject::event(
             DocumentAggregate::Document,
             EventType::Created,
-            doc_id.as_str().to_string(),
+            doc_id.to_string(),
         );
         
         let subject_str = subject.to_subject();
@@ -1063,7 +1685,7 @@ mod tests {
         let doc_id = DocumentId::new();
         let subject = DocumentSubject::workflow(
             WorkflowOperation::Start,
-            doc_id.as_str().to_string(),
+            doc_id.to_string(),
         );
         
         let subject_str = subject.to_subject();
@@ -1113,6 +1735,229 @@ mod tests {
                                         doc_id.to_string()));
     }
     
+    #[test]
+    fn test_from_subject_round_trips_constructors() {
+        use std::str::FromStr;
+        let test_cid = Cid::from_str("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
+        let user_id = Uuid::new_v4();
+        let doc_id = DocumentId::new();
+
+        let subjects = vec![
+            DocumentSubject::event(DocumentAggregate::Document, EventType::Created, doc_id.to_string()),
+            DocumentSubject::command(DocumentAggregate::Content, CommandType::UpdateContent, doc_id.to_string()),
+            DocumentSubject::query(DocumentAggregate::Search, QueryType::Search, None),
+            DocumentSubject::query(DocumentAggregate::Version, QueryType::GetVersions, Some(doc_id.to_string())),
+            DocumentSubject::workflow(WorkflowOperation::Start, doc_id.to_string()),
+            DocumentSubject::cid_event(&test_cid, EventType::MetadataUpdated, Some(DocumentAggregate::Metadata)),
+            DocumentSubject::cid_event(&test_cid, EventType::ContentUpdated, None),
+            DocumentSubject::user_event(&user_id, EventType::Created, Some(DocumentAggregate::Document)),
+            DocumentSubject::user_event(&user_id, EventType::Shared, None),
+            DocumentSubject::user_document_event(&user_id, &doc_id, EventType::Shared),
+            DocumentSubject::cid_user_event(&test_cid, &user_id, EventType::Tagged),
+            DocumentSubject::new(
+                SubjectNamespace::Queries,
+                SubjectScope::User { user_id: user_id.to_string(), aggregate: None },
+                SubjectOperation::Query(QueryType::Search),
+                Some(doc_id.to_string()),
+            ),
+        ];
+
+        for subject in subjects {
+            let parsed = DocumentSubject::from_subject(&subject.to_subject()).unwrap();
+            assert_eq!(parsed, subject, "{}", subject);
+        }
+    }
+
+    #[test]
+    fn test_integration_event_subject() {
+        let doc_id = DocumentId::new();
+        let subject = DocumentSubject::integration_event("workflow", EventType::Shared, doc_id.to_string()).unwrap();
+
+        assert_eq!(subject.to_subject(), format!("integration.document.workflow.shared.{}", doc_id));
+        assert!(subject_matches(&SubjectPatterns::integration_events(), &subject.to_subject()));
+        let parsed = DocumentSubject::from_subject(&subject.to_subject()).unwrap();
+        assert_eq!(parsed, subject);
+        assert_eq!(parsed.scope, SubjectScope::Integration { target_domain: "workflow".to_string() });
+
+        for target in ["", "graph.nodes", "identity service", "*", "agents>"] {
+            assert!(
+                matches!(
+                    DocumentSubject::integration_event(target, EventType::Shared, doc_id.to_string()),
+                    Err(SubjectError::InvalidTargetDomain(_))
+                ),
+                "{:?}",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_custom_types_are_sanitized_for_subjects() {
+        assert_eq!(sanitize_for_subject("Quarterly Review"), "quarterly_review");
+        assert_eq!(sanitize_for_subject("  Legal.Memo  v2 "), "legal_memo_v2");
+        assert_eq!(sanitize_for_subject("a*b>c"), "a_b_c");
+
+        let custom = DocumentType::Other("Quarterly Review".to_string());
+        let token = custom.subject_token().unwrap();
+        assert_eq!(token, "other-quarterly_review");
+        assert_eq!(DocumentType::Other("quarterly.review".to_string()).subject_token().unwrap(), token);
+        assert_eq!(DocumentType::Report.subject_token().unwrap(), "report");
+        assert_ne!(DocumentType::Other("Report".to_string()).subject_token().unwrap(), "report");
+
+        let relabeled = DocumentType::from_subject_token(&token).unwrap();
+        assert_eq!(relabeled, DocumentType::Other("quarterly_review".to_string()));
+        assert_eq!(relabeled.subject_token().unwrap(), token);
+        assert_eq!(DocumentType::from_subject_token("report"), Some(DocumentType::Report));
+
+        let subject = DocumentSubject::document_type_event(&custom, EventType::Classified).unwrap();
+        assert_eq!(subject.to_subject(), "events.document.document_type.other-quarterly_review.classified");
+        assert_eq!(subject.entity_id, None);
+        assert_eq!(DocumentSubject::from_subject(&subject.to_subject()).unwrap(), subject);
+
+        let entity = DocumentSubject::entity_type_event(&EntityType::Custom("Part Number".to_string())).unwrap();
+        assert_eq!(entity.to_subject(), "events.document.entity_type.custom-part_number.entities_extracted");
+        assert_eq!(entity.entity_id, None);
+        assert_eq!(DocumentSubject::from_subject(&entity.to_subject()).unwrap(), entity);
+        assert_eq!(EntityType::PhoneNumber.subject_token().unwrap(), "phone_number");
+    }
+
+    #[test]
+    fn test_type_tokens_round_trip() {
+        let document_types = [
+            DocumentType::Text, DocumentType::Image, DocumentType::Video, DocumentType::Audio,
+            DocumentType::Pdf, DocumentType::Spreadsheet, DocumentType::Presentation,
+            DocumentType::Archive, DocumentType::Note, DocumentType::Article,
+            DocumentType::Proposal, DocumentType::Report, DocumentType::Contract,
+            DocumentType::Other("memo".to_string()),
+        ];
+        for document_type in document_types {
+            let token = document_type.subject_token().unwrap();
+            assert_eq!(DocumentType::from_subject_token(&token), Some(document_type));
+        }
+
+        let entity_types = [
+            EntityType::Person, EntityType::Organization, EntityType::Location,
+            EntityType::DateTime, EntityType::Concept, EntityType::Keyword,
+            EntityType::Email, EntityType::PhoneNumber, EntityType::Custom("part_number".to_string()),
+        ];
+        for entity_type in entity_types {
+            let token = entity_type.subject_token().unwrap();
+            assert_eq!(EntityType::from_subject_token(&token), Some(entity_type));
+        }
+        assert_eq!(EntityType::from_subject_token("custom-"), None);
+        assert_eq!(EntityType::from_subject_token("unknown"), None);
+    }
+
+    #[test]
+    fn test_unsanitizable_custom_types_are_rejected() {
+        for name in ["", "   ", "...", "日本"] {
+            assert!(
+                matches!(DocumentType::Other(name.to_string()).subject_token(), Err(SubjectError::InvalidToken(_))),
+                "{:?}",
+                name
+            );
+            assert!(DocumentSubject::entity_type_event(&EntityType::Custom(name.to_string())).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_subject_scopes() {
+        let user_id = Uuid::new_v4();
+        let doc_id = DocumentId::new();
+
+        let subject = DocumentSubject::from_subject(&format!(
+            "events.document.user.{}.document.{}.shared", user_id, doc_id
+        )).unwrap();
+        assert_eq!(subject.scope, SubjectScope::UserDocument {
+            user_id: user_id.to_string(),
+            document_id: doc_id.to_string(),
+        });
+        assert_eq!(subject.operation, SubjectOperation::Event(EventType::Shared));
+
+        let subject = DocumentSubject::from_subject(&format!(
+            "events.document.user.{}.document.created", user_id
+        )).unwrap();
+        assert_eq!(subject.scope, SubjectScope::User {
+            user_id: user_id.to_string(),
+            aggregate: Some(DocumentAggregate::Document),
+        });
+        assert_eq!(subject.entity_id, None);
+    }
+
+    #[test]
+    fn test_from_subject_rejects_malformed() {
+        for subject in [
+            "",
+            "events.document",
+            "events.document.document",
+            "telemetry.document.document.created.x",
+            "events.document.unknown.created.x",
+            "events.document.document.not_an_event.x",
+            "events.document.document.created.x.y",
+            "events..document.created",
+        ] {
+            assert!(
+                matches!(DocumentSubject::from_subject(subject), Err(SubjectError::InvalidFormat(_))),
+                "{}",
+                subject
+            );
+        }
+    }
+
+    #[test]
+    fn test_domain_event_subject() {
+        use crate::events::{DocumentShared, DocumentTagged};
+
+        let doc_id = DocumentId::new();
+        let shared = DocumentDomainEvent::DocumentShared(DocumentShared {
+            document_id: doc_id,
+            shared_with: std::collections::HashSet::new(),
+            permissions: vec!["read".to_string()],
+            shared_by: "alice".to_string(),
+            shared_at: chrono::Utc::now(),
+        });
+        let subject = shared.subject(&doc_id);
+        assert_eq!(subject.to_subject(), format!("events.document.relationship.shared.{}", doc_id));
+        assert_eq!(DocumentSubject::from_subject(&subject.to_subject()).unwrap(), subject);
+
+        let tagged = DocumentDomainEvent::DocumentTagged(DocumentTagged {
+            document_id: doc_id,
+            tags: vec!["q3".to_string()],
+            all_tags: vec!["q3".to_string()],
+            tagged_by: "alice".to_string(),
+            tagged_at: chrono::Utc::now(),
+        });
+        assert_eq!(tagged.subject(&doc_id).to_subject(), format!("events.document.metadata.tagged.{}", doc_id));
+    }
+
+    #[test]
+    fn test_chain_verification_subjects() {
+        use crate::events::{ChainVerificationResult, CidChainVerified};
+        use crate::value_objects::CidChain;
+        use std::str::FromStr;
+
+        let doc_id = DocumentId::new();
+        let root = Cid::from_str("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
+        let verified = |is_valid| DocumentDomainEvent::CidChainVerified(CidChainVerified {
+            document_id: doc_id,
+            verified_chain: CidChain::new(doc_id, root),
+            verification_result: ChainVerificationResult {
+                is_valid,
+                issues: vec![],
+                verification_time_ms: 0,
+                links_verified: 1,
+            },
+            verified_at: chrono::Utc::now(),
+        });
+
+        assert_eq!(verified(true).subject_parts(), (DocumentAggregate::Version, EventType::ChainVerified));
+        assert_eq!(verified(false).subject_parts(), (DocumentAggregate::Version, EventType::ChainBroken));
+        assert_eq!(
+            verified(false).subject(&doc_id).to_subject(),
+            format!("events.document.version.chain_broken.{}", doc_id)
+        );
+    }
+
     #[test]
     fn test_subject_builder() {
         let doc_id = DocumentId::new();
@@ -1128,7 +1973,7 @@ mod tests {
         assert_eq!(subject.namespace, SubjectNamespace::Commands);
         assert!(matches!(subject.scope, SubjectScope::Aggregate(DocumentAggregate::Content)));
         assert!(matches!(subject.operation, SubjectOperation::Command(CommandType::Update)));
-        assert_eq!(subject.entity_id, Some(doc_id.as_str().to_string()));
+        assert_eq!(subject.entity_id, Some(doc_id.to_string()));
     }
     
     #[test]
@@ -1162,6 +2007,44 @@ mod tests {
         assert_eq!(wildcard, "events.document.document.created.>");
     }
     
+    #[test]
+    fn test_subject_matches() {
+        let doc_id = DocumentId::new();
+        let created = DocumentSubject::event(DocumentAggregate::Document, EventType::Created, doc_id.to_string())
+            .to_subject();
+        let user_created = DocumentSubject::user_event(&Uuid::new_v4(), EventType::Created, Some(DocumentAggregate::Document))
+            .to_subject();
+
+        assert!(subject_matches(&SubjectPatterns::all_document_events(), &created));
+        assert!(subject_matches(&SubjectPatterns::all_document_events(), &user_created));
+        assert!(subject_matches("*.document.*.created.*", &created));
+        assert!(subject_matches(&SubjectPatterns::document_events(&doc_id), &created));
+        assert!(subject_matches("events.document.user.*.>", &user_created));
+        assert!(subject_matches(&created, &created));
+
+        // Non-matches: wrong literal, `*` is one token only, `>` needs at least one token
+        assert!(!subject_matches("*.document.*.deleted.*", &created));
+        assert!(!subject_matches("events.document.*", &created));
+        assert!(!subject_matches("events.document.document.created", &created));
+        assert!(!subject_matches("events.document.document.created.*.*", &created));
+        assert!(!subject_matches("events.document.>", "events.document"));
+        assert!(!subject_matches(&SubjectPatterns::document_events(&DocumentId::new()), &created));
+        assert!(!subject_matches("commands.document.>", &created));
+    }
+
+    #[test]
+    fn test_subject_matches_rejects_malformed_input() {
+        assert!(subject_matches("a.>", "a.b.c"));
+        assert!(subject_matches("*", "a"));
+        assert!(subject_matches(">", "a.b"));
+        assert!(!subject_matches("a.>.c", "a.b.c"));
+        assert!(!subject_matches("a..c", "a..c"));
+        assert!(!subject_matches("a.*", "a."));
+        assert!(!subject_matches("*", ""));
+        assert!(!subject_matches("", ""));
+        assert!(!subject_matches("a.*.c", "a.b.c.d"));
+    }
+
     #[test]
     fn test_predefined_patterns() {
         let doc_id = DocumentId::new();
@@ -1262,6 +2145,23 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_type_tokens_parse_back_to_every_variant() {
+        fn assert_round_trips<T: PartialEq + std::fmt::Debug>(all: &[T], as_str: fn(&T) -> &'static str, parse: fn(&str) -> Option<T>) {
+            let mut tokens = std::collections::HashSet::new();
+            for variant in all {
+                let token = as_str(variant);
+                assert!(tokens.insert(token), "duplicate token {token}");
+                assert_eq!(parse(token).as_ref(), Some(variant));
+            }
+            assert_eq!(parse("no_such_token"), None);
+        }
+
+        assert_round_trips(EventType::ALL, EventType::as_str, EventType::parse);
+        assert_round_trips(CommandType::ALL, CommandType::as_str, CommandType::parse);
+        assert_round_trips(QueryType::ALL, QueryType::as_str, QueryType::parse);
+    }
+
     #[test]
     fn test_subject_uniqueness() {
         let doc_id_1 = DocumentId::new();
@@ -1270,13 +2170,13 @@ mod tests {
         let subject_1 = DocumentSubject::event(
             DocumentAggregate::Document,
             EventType::Created,
-            doc_id_1.as_str().to_string(),
+            doc_id_1.to_string(),
         );
         
         let subject_2 = DocumentSubject::event(
             DocumentAggregate::Document,
             EventType::Created,
-            doc_id_2.as_str().to_string(),
+            doc_id_2.to_string(),
         );
         
         assert_ne!(subject_1.to_subject(), subject_2.to_subject());
@@ -0,0 +1,257 @@
+//! Consuming document events from NATS
+//!
+//! [`DocumentEventSubscriber`] reads messages from a [`MessageSource`],
+//! parses each subject and decodes the payload into a [`DocumentDomainEvent`].
+//! Messages that cannot be decoded come back as errors rather than being
+//! skipped. With the `nats-client` feature, an `async_nats::Subscriber` is a
+//! source and the subscriber can be turned into a `Stream`.
+
+use super::{DocumentSubject, SubjectError, SubjectOperation, SubjectScope};
+use crate::events::DocumentDomainEvent;
+use crate::value_objects::DocumentId;
+use async_trait::async_trait;
+
+/// A message received on a subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    pub subject: String,
+    /// Subject to send a reply to, for requests
+    pub reply: Option<String>,
+    pub payload: Vec<u8>,
+}
+
+/// Subscription delivering messages in order
+#[async_trait]
+pub trait MessageSource: Send {
+    /// Next message, or `None` once the subscription has ended
+    async fn next_message(&mut self) -> Option<IncomingMessage>;
+}
+
+/// A message that could not be turned into a document event
+#[derive(Debug, thiserror::Error)]
+pub enum SubscribeError {
+    #[error("Unparseable subject {subject}: {source}")]
+    Subject {
+        subject: String,
+        source: SubjectError,
+    },
+
+    #[error("Subject {0} is not a document event subject")]
+    NotAnEvent(String),
+
+    #[error("Undecodable payload on {subject}: {source}")]
+    Payload {
+        subject: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Payload on {subject} is a {found:?} event")]
+    EventTypeMismatch {
+        subject: String,
+        found: super::EventType,
+    },
+
+    #[error("Payload on {subject} is about documents {found:?}")]
+    DocumentMismatch {
+        subject: String,
+        found: Vec<DocumentId>,
+    },
+
+    #[error("Transport error: {0}")]
+    Transport(String),
+}
+
+/// Decodes document events from a subscription
+pub struct DocumentEventSubscriber<S> {
+    source: S,
+}
+
+impl<S: MessageSource> DocumentEventSubscriber<S> {
+    pub fn new(source: S) -> Self {
+        Self { source }
+    }
+
+    /// Next decoded event, or `None` once the subscription has ended
+    pub async fn next(&mut self) -> Option<Result<(DocumentSubject, DocumentDomainEvent), SubscribeError>> {
+        let message = self.source.next_message().await?;
+        Some(decode_event(&message))
+    }
+}
+
+/// Parse `message`'s subject and decode its payload as the event type the subject names
+///
+/// The subject's entity ID, when present, must be one of the documents the event is about.
+pub fn decode_event(message: &IncomingMessage) -> Result<(DocumentSubject, DocumentDomainEvent), SubscribeError> {
+    let subject = DocumentSubject::from_subject(&message.subject).map_err(|source| SubscribeError::Subject {
+        subject: message.subject.clone(),
+        source,
+    })?;
+    let (SubjectScope::Aggregate(aggregate), SubjectOperation::Event(event_type)) = (&subject.scope, &subject.operation)
+    else {
+        return Err(SubscribeError::NotAnEvent(message.subject.clone()));
+    };
+
+    let event: DocumentDomainEvent = serde_json::from_slice(&message.payload).map_err(|source| SubscribeError::Payload {
+        subject: message.subject.clone(),
+        source,
+    })?;
+    let (event_aggregate, event_event_type) = event.subject_parts();
+    if &event_aggregate != aggregate || &event_event_type != event_type {
+        return Err(SubscribeError::EventTypeMismatch {
+            subject: message.subject.clone(),
+            found: event_event_type,
+        });
+    }
+    let document_ids = event.document_ids();
+    if let Some(entity_id) = &subject.entity_id {
+        if !document_ids.is_empty() && !document_ids.iter().any(|id| id.to_string() == *entity_id) {
+            return Err(SubscribeError::DocumentMismatch {
+                subject: message.subject.clone(),
+                found: document_ids,
+            });
+        }
+    }
+
+    Ok((subject, event))
+}
+
+#[cfg(feature = "nats-client")]
+#[async_trait]
+impl MessageSource for async_nats::Subscriber {
+    async fn next_message(&mut self) -> Option<IncomingMessage> {
+        use futures::StreamExt;
+
+        let message = StreamExt::next(self).await?;
+        Some(IncomingMessage {
+            subject: message.subject.to_string(),
+            reply: message.reply.map(|reply| reply.to_string()),
+            payload: message.payload.to_vec(),
+        })
+    }
+}
+
+#[cfg(feature = "nats-client")]
+impl DocumentEventSubscriber<async_nats::Subscriber> {
+    /// Subscribe `client` to `pattern`, e.g. [`SubjectPatterns::all_document_events`](super::SubjectPatterns::all_document_events)
+    pub async fn subscribe(client: &async_nats::Client, pattern: impl Into<String>) -> Result<Self, SubscribeError> {
+        let subscriber = client
+            .subscribe(pattern.into())
+            .await
+            .map_err(|e| SubscribeError::Transport(e.to_string()))?;
+        Ok(Self::new(subscriber))
+    }
+}
+
+#[cfg(feature = "nats-client")]
+impl<S: MessageSource + 'static> DocumentEventSubscriber<S> {
+    /// Decoded events as a stream
+    pub fn into_stream(
+        self,
+    ) -> impl futures::Stream<Item = Result<(DocumentSubject, DocumentDomainEvent), SubscribeError>> {
+        futures::stream::unfold(self, |mut subscriber| async move {
+            let item = subscriber.next().await?;
+            Some((item, subscriber))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{DocumentArchived, DocumentDeleted};
+    use crate::nats::{DocumentEventPublisher, MessageIdentity, MessageTransport, PublishError};
+    use std::collections::HashMap;
+    use tokio::sync::mpsc;
+
+    /// In-memory stand-in for a NATS connection
+    struct Channel(mpsc::UnboundedSender<IncomingMessage>);
+
+    #[async_trait]
+    impl MessageTransport for Channel {
+        async fn publish(
+            &self,
+            subject: String,
+            _headers: Vec<(&'static str, String)>,
+            payload: Vec<u8>,
+        ) -> Result<(), PublishError> {
+            self.0
+                .send(IncomingMessage { subject, reply: None, payload })
+                .map_err(|e| PublishError::Transport(e.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl MessageSource for mpsc::UnboundedReceiver<IncomingMessage> {
+        async fn next_message(&mut self) -> Option<IncomingMessage> {
+            self.recv().await
+        }
+    }
+
+    fn archived(document_id: DocumentId) -> DocumentDomainEvent {
+        DocumentDomainEvent::DocumentArchived(DocumentArchived {
+            document_id,
+            reason: "expired".to_string(),
+            archived_by: uuid::Uuid::new_v4(),
+            archived_at: chrono::Utc::now(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_published_events_round_trip() {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let publisher = DocumentEventPublisher::new(Channel(sender));
+        let mut subscriber = DocumentEventSubscriber::new(receiver);
+        let document_id = DocumentId::new();
+        let archived = archived(document_id);
+        let deleted = DocumentDomainEvent::DocumentDeleted(DocumentDeleted {
+            document_id,
+            hard_delete: false,
+            reason: None,
+            deleted_by: uuid::Uuid::new_v4(),
+            deleted_at: chrono::Utc::now(),
+        });
+
+        for event in [&archived, &deleted] {
+            publisher.publish(event, &document_id, &MessageIdentity::new_root()).await.unwrap();
+        }
+        drop(publisher);
+
+        let (subject, event) = subscriber.next().await.unwrap().unwrap();
+        assert_eq!(subject, archived.subject(&document_id));
+        assert_eq!(event, archived);
+        let (subject, event) = subscriber.next().await.unwrap().unwrap();
+        assert_eq!(subject.entity_id, Some(document_id.to_string()));
+        assert_eq!(event, deleted);
+        assert!(subscriber.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_undecodable_messages_are_errors() {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut subscriber = DocumentEventSubscriber::new(receiver);
+        let document_id = DocumentId::new();
+        let subject = format!("events.document.document.archived.{document_id}");
+        let payload = serde_json::to_vec(&archived(document_id)).unwrap();
+        for (subject, payload) in [
+            (subject.clone(), b"not json".to_vec()),
+            ("events.document.nonsense".to_string(), payload.clone()),
+            (format!("events.document.document.deleted.{document_id}"), payload.clone()),
+            (format!("events.document.document.archived.{}", DocumentId::new()), payload.clone()),
+            (subject, payload),
+        ] {
+            sender.send(IncomingMessage { subject, reply: None, payload }).unwrap();
+        }
+        drop(sender);
+
+        assert!(matches!(subscriber.next().await, Some(Err(SubscribeError::Payload { .. }))));
+        assert!(matches!(subscriber.next().await, Some(Err(SubscribeError::Subject { .. }))));
+        assert!(matches!(subscriber.next().await, Some(Err(SubscribeError::EventTypeMismatch { .. }))));
+        assert!(matches!(
+            subscriber.next().await,
+            Some(Err(SubscribeError::DocumentMismatch { found, .. })) if found == vec![document_id]
+        ));
+        assert!(subscriber.next().await.unwrap().is_ok());
+        assert!(subscriber.next().await.is_none());
+    }
+}
@@ -84,6 +84,6 @@ pub struct DocumentSearchView {
     pub snippet: String,
     /// Relevance score
     pub score: f32,
-    /// Highlight positions
+    /// Byte spans of matched terms within `snippet`
     pub highlights: Vec<(usize, usize)>,
 }
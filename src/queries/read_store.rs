@@ -0,0 +1,187 @@
+//! Read-model storage backing the document query handler
+
+use super::{DocumentLink, DocumentView, VersionInfo};
+use crate::events::{DocumentDomainEvent, EventEnvelope};
+use crate::value_objects::{Collection, Comment, DocumentId};
+use crate::workflow::DocumentWorkflowBinding;
+use cim_domain::DomainResult;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Trait for read-model lookups used by queries
+#[async_trait::async_trait]
+pub trait DocumentReadStore: Send + Sync {
+    /// Get a document view by ID
+    async fn get_document(&self, document_id: &DocumentId) -> DomainResult<Option<DocumentView>>;
+
+    /// List all document views
+    async fn list_documents(&self) -> DomainResult<Vec<DocumentView>>;
+
+    /// Get the recorded events for a document, in sequence order
+    async fn get_history(&self, document_id: &DocumentId) -> DomainResult<Vec<EventEnvelope>>;
+
+    /// Get comments on a document
+    async fn get_comments(&self, document_id: &DocumentId) -> DomainResult<Vec<Comment>>;
+
+    /// Get known versions of a document
+    async fn get_versions(&self, document_id: &DocumentId) -> DomainResult<Vec<VersionInfo>>;
+
+    /// Get outgoing links from a document
+    async fn get_links(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentLink>>;
+
+    /// Get links pointing at a document, with `target_id` set to the linking document
+    async fn get_incoming_links(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentLink>>;
+
+    /// Get workflow instances bound to a document
+    async fn get_workflow_bindings(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentWorkflowBinding>>;
+
+    /// Get a collection by ID
+    async fn get_collection(&self, collection_id: &Uuid) -> DomainResult<Option<Collection>>;
+
+    /// List all collections
+    async fn list_collections(&self) -> DomainResult<Vec<Collection>>;
+
+    /// Get the documents directly in a collection, in insertion order
+    async fn get_collection_documents(&self, collection_id: &Uuid) -> DomainResult<Vec<DocumentId>>;
+}
+
+/// In-memory read store for testing
+#[derive(Default)]
+pub struct InMemoryDocumentReadStore {
+    documents: Arc<RwLock<HashMap<DocumentId, DocumentView>>>,
+    history: Arc<RwLock<HashMap<DocumentId, Vec<EventEnvelope>>>>,
+    stream_position: AtomicU64,
+    comments: Arc<RwLock<HashMap<DocumentId, Vec<Comment>>>>,
+    versions: Arc<RwLock<HashMap<DocumentId, Vec<VersionInfo>>>>,
+    links: Arc<RwLock<HashMap<DocumentId, Vec<DocumentLink>>>>,
+    workflows: Arc<RwLock<HashMap<DocumentId, Vec<DocumentWorkflowBinding>>>>,
+    collections: Arc<RwLock<HashMap<Uuid, Collection>>>,
+    collection_documents: Arc<RwLock<HashMap<Uuid, Vec<DocumentId>>>>,
+}
+
+impl InMemoryDocumentReadStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a document view
+    pub async fn insert_document(&self, view: DocumentView) {
+        self.documents.write().await.insert(view.document_id, view);
+    }
+
+    /// Append an event for a document, assigning its sequence and stream position
+    pub async fn add_event(&self, document_id: DocumentId, event: DocumentDomainEvent) -> EventEnvelope {
+        let mut history = self.history.write().await;
+        let stream = history.entry(document_id).or_default();
+        let envelope = EventEnvelope {
+            sequence: stream.len() as u64 + 1,
+            stream_position: self.stream_position.fetch_add(1, Ordering::SeqCst) + 1,
+            event,
+        };
+        stream.push(envelope.clone());
+        envelope
+    }
+
+    /// Add a comment to a document
+    pub async fn add_comment(&self, document_id: DocumentId, comment: Comment) {
+        self.comments.write().await.entry(document_id).or_default().push(comment);
+    }
+
+    /// Add a version entry to a document
+    pub async fn add_version(&self, document_id: DocumentId, version: VersionInfo) {
+        self.versions.write().await.entry(document_id).or_default().push(version);
+    }
+
+    /// Add an outgoing link from a document
+    pub async fn add_link(&self, document_id: DocumentId, link: DocumentLink) {
+        self.links.write().await.entry(document_id).or_default().push(link);
+    }
+
+    /// Bind a workflow instance to its document
+    pub async fn bind_workflow(&self, binding: DocumentWorkflowBinding) {
+        self.workflows.write().await.entry(binding.document_id).or_default().push(binding);
+    }
+
+    /// Store a collection
+    pub async fn insert_collection(&self, collection: Collection) {
+        self.collections.write().await.insert(collection.id, collection);
+    }
+
+    /// Add a document to a collection, ignoring duplicates
+    pub async fn add_to_collection(&self, collection_id: Uuid, document_id: DocumentId) {
+        let mut members = self.collection_documents.write().await;
+        let documents = members.entry(collection_id).or_default();
+        if !documents.contains(&document_id) {
+            documents.push(document_id);
+        }
+    }
+
+    /// Remove a document from a collection
+    pub async fn remove_from_collection(&self, collection_id: Uuid, document_id: DocumentId) {
+        if let Some(documents) = self.collection_documents.write().await.get_mut(&collection_id) {
+            documents.retain(|id| *id != document_id);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DocumentReadStore for InMemoryDocumentReadStore {
+    async fn get_document(&self, document_id: &DocumentId) -> DomainResult<Option<DocumentView>> {
+        Ok(self.documents.read().await.get(document_id).cloned())
+    }
+
+    async fn list_documents(&self) -> DomainResult<Vec<DocumentView>> {
+        Ok(self.documents.read().await.values().cloned().collect())
+    }
+
+    async fn get_history(&self, document_id: &DocumentId) -> DomainResult<Vec<EventEnvelope>> {
+        Ok(self.history.read().await.get(document_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_comments(&self, document_id: &DocumentId) -> DomainResult<Vec<Comment>> {
+        Ok(self.comments.read().await.get(document_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_versions(&self, document_id: &DocumentId) -> DomainResult<Vec<VersionInfo>> {
+        Ok(self.versions.read().await.get(document_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_links(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentLink>> {
+        Ok(self.links.read().await.get(document_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_incoming_links(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentLink>> {
+        let links = self.links.read().await;
+        Ok(links
+            .iter()
+            .flat_map(|(source_id, outgoing)| {
+                outgoing
+                    .iter()
+                    .filter(|link| &link.target_id == document_id)
+                    .map(move |link| DocumentLink {
+                        target_id: *source_id,
+                        ..link.clone()
+                    })
+            })
+            .collect())
+    }
+
+    async fn get_workflow_bindings(&self, document_id: &DocumentId) -> DomainResult<Vec<DocumentWorkflowBinding>> {
+        Ok(self.workflows.read().await.get(document_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_collection(&self, collection_id: &Uuid) -> DomainResult<Option<Collection>> {
+        Ok(self.collections.read().await.get(collection_id).cloned())
+    }
+
+    async fn list_collections(&self) -> DomainResult<Vec<Collection>> {
+        Ok(self.collections.read().await.values().cloned().collect())
+    }
+
+    async fn get_collection_documents(&self, collection_id: &Uuid) -> DomainResult<Vec<DocumentId>> {
+        Ok(self.collection_documents.read().await.get(collection_id).cloned().unwrap_or_default())
+    }
+}
@@ -0,0 +1,136 @@
+//! Audit trail for document access checks and sharing changes
+//!
+//! Entries are only recorded for documents whose `AccessControlComponent`
+//! has `audit_access` set.
+
+use crate::value_objects::{AccessLevel, DocumentId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use uuid::Uuid;
+
+/// What an audited user attempted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccessAction {
+    /// An access check for the given level
+    Check(AccessLevel),
+    /// Sharing the document with another user
+    Share { with: Uuid, level: AccessLevel },
+    /// Revoking another user's shared access
+    RevokeShare { from: Uuid },
+}
+
+/// One recorded access attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessAuditEntry {
+    /// User making the attempt
+    pub user: Uuid,
+    pub action: AccessAction,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Whether the attempt was allowed and succeeded
+    pub granted: bool,
+}
+
+impl AccessAuditEntry {
+    /// Entry stamped with the current time
+    pub fn new(user: Uuid, action: AccessAction, granted: bool) -> Self {
+        Self {
+            user,
+            action,
+            timestamp: chrono::Utc::now(),
+            granted,
+        }
+    }
+}
+
+/// Pluggable storage for per-document audit trails
+pub trait AuditSink: Send + Sync {
+    /// Append an entry to a document's trail
+    fn record(&self, document_id: DocumentId, entry: AccessAuditEntry);
+
+    /// A document's trail in recording order
+    fn entries(&self, document_id: &DocumentId) -> Vec<AccessAuditEntry>;
+}
+
+/// In-memory audit sink for testing
+#[derive(Debug, Default)]
+pub struct InMemoryAuditSink {
+    entries: RwLock<HashMap<DocumentId, Vec<AccessAuditEntry>>>,
+}
+
+impl InMemoryAuditSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AuditSink for InMemoryAuditSink {
+    fn record(&self, document_id: DocumentId, entry: AccessAuditEntry) {
+        self.entries.write().unwrap().entry(document_id).or_default().push(entry);
+    }
+
+    fn entries(&self, document_id: &DocumentId) -> Vec<AccessAuditEntry> {
+        self.entries.read().unwrap().get(document_id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{AccessControlComponent, Document, DocumentInfoComponent};
+    use cid::Cid;
+    use cim_domain::EntityId;
+
+    fn document(audit_access: bool, readers: Vec<Uuid>) -> Document {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Audited")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+        document
+            .add_component(
+                AccessControlComponent {
+                    read_access: readers,
+                    write_access: vec![],
+                    share_access: vec![],
+                    audit_access,
+                    encryption_key_id: None,
+                    block_access: HashMap::new(),
+                },
+                "test",
+                None,
+            )
+            .unwrap();
+        document
+    }
+
+    #[test]
+    fn test_denied_check_is_recorded_when_auditing() {
+        let reader = Uuid::new_v4();
+        let document = document(true, vec![reader]);
+        let document_id = DocumentId::from(cim_domain::AggregateRoot::id(&document));
+        let sink = InMemoryAuditSink::new();
+
+        assert!(document.check_access(reader, AccessLevel::Read, &sink).is_ok());
+        assert!(document.check_access(reader, AccessLevel::Write, &sink).is_err());
+
+        let entries = sink.entries(&document_id);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].granted);
+        assert_eq!(entries[1].user, reader);
+        assert_eq!(entries[1].action, AccessAction::Check(AccessLevel::Write));
+        assert!(!entries[1].granted);
+    }
+
+    #[test]
+    fn test_nothing_recorded_when_auditing_off() {
+        let reader = Uuid::new_v4();
+        let document = document(false, vec![reader]);
+        let document_id = DocumentId::from(cim_domain::AggregateRoot::id(&document));
+        let sink = InMemoryAuditSink::new();
+
+        assert!(document.check_access(reader, AccessLevel::Write, &sink).is_err());
+        assert!(sink.entries(&document_id).is_empty());
+    }
+}
@@ -8,6 +8,8 @@ use cid::Cid;
 use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 
+use crate::aggregate::{cid_matches, Document, LifecycleComponent};
+
 use crate::value_objects::{
     DocumentId, CidChain, ChainError
 };
@@ -295,6 +297,82 @@ impl CidChainVerificationService for DefaultCidChainVerificationService {
     }
 }
 
+/// Resolves CIDs to stored blocks
+pub trait CidResolver {
+    /// Bytes stored under `cid`, if present
+    fn resolve(&self, cid: &Cid) -> Option<Vec<u8>>;
+
+    /// CID of the version preceding the one stored under `cid`
+    fn previous_version(&self, _cid: &Cid) -> Option<Cid> {
+        None
+    }
+}
+
+/// Walk a document's version chain from its current content back through
+/// `previous_version_cid` links
+///
+/// Position 0 is the current content, position 1 its predecessor, and so on.
+/// Each block must resolve and hash to its CID; the walk stops at the first
+/// broken link, which is reported as the only issue.
+pub fn verify_chain(document: &Document, resolver: &dyn CidResolver) -> ChainVerificationResult {
+    let start_time = std::time::Instant::now();
+    let mut links_verified = 0u32;
+    let mut seen = HashSet::new();
+
+    let mut current = document.content_cid();
+    let mut previous = document
+        .get_component::<LifecycleComponent>()
+        .and_then(|lifecycle| lifecycle.previous_version_cid);
+    let mut position = 0u64;
+
+    let issue = loop {
+        let Some(cid) = current else {
+            break None;
+        };
+
+        if !seen.insert(cid) {
+            break Some(ChainIssue {
+                issue_type: ChainIssueType::BrokenLink,
+                position,
+                description: format!("Version chain loops back to {cid}"),
+                severity: IssueSeverity::Critical,
+            });
+        }
+
+        let Some(data) = resolver.resolve(&cid) else {
+            break Some(ChainIssue {
+                issue_type: ChainIssueType::MissingContent,
+                position,
+                description: format!("CID {cid} not found in storage"),
+                severity: IssueSeverity::Critical,
+            });
+        };
+
+        if !cid_matches(&cid, &data) {
+            break Some(ChainIssue {
+                issue_type: ChainIssueType::HashMismatch,
+                position,
+                description: format!("Content stored under {cid} does not hash to its CID"),
+                severity: IssueSeverity::Critical,
+            });
+        }
+
+        if position > 0 {
+            links_verified += 1;
+        }
+        current = previous;
+        previous = current.and_then(|cid| resolver.previous_version(&cid));
+        position += 1;
+    };
+
+    ChainVerificationResult {
+        is_valid: issue.is_none(),
+        issues: issue.into_iter().collect(),
+        verification_time_ms: start_time.elapsed().as_millis() as u64,
+        links_verified,
+    }
+}
+
 /// Helper service for performing chain repairs
 pub struct CidChainRepairService {
     verification_service: Box<dyn CidChainVerificationService>,
@@ -601,4 +679,95 @@ mod tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    struct FakeStore {
+        blocks: HashMap<Cid, Vec<u8>>,
+        previous: HashMap<Cid, Cid>,
+    }
+
+    impl CidResolver for FakeStore {
+        fn resolve(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.blocks.get(cid).cloned()
+        }
+
+        fn previous_version(&self, cid: &Cid) -> Option<Cid> {
+            self.previous.get(cid).copied()
+        }
+    }
+
+    fn raw_cid(data: &[u8]) -> Cid {
+        crate::aggregate::ContentAddressComponent::compute_cid(data, "sha2-256", "raw").unwrap()
+    }
+
+    /// Store with versions v1 <- v2 <- v3 and a document whose content is v3
+    fn three_version_chain() -> (Document, FakeStore, [Cid; 3]) {
+        let versions: [&[u8]; 3] = [b"version one", b"version two", b"version three"];
+        let cids = versions.map(raw_cid);
+
+        let store = FakeStore {
+            blocks: cids.iter().copied().zip(versions.iter().map(|v| v.to_vec())).collect(),
+            previous: HashMap::from([(cids[1], cids[0])]),
+        };
+
+        let info = crate::aggregate::DocumentInfoComponent::builder()
+            .with_title("Chained")
+            .with_mime_type("text/plain")
+            .with_size_bytes(13)
+            .build()
+            .unwrap();
+        let mut document = Document::new(cim_domain::EntityId::new(), info, cids[2]).unwrap();
+        document.add_component(
+            LifecycleComponent {
+                status: crate::aggregate::DocumentStatus::Draft,
+                created_at: Utc::now(),
+                modified_at: Utc::now(),
+                version_number: "3".to_string(),
+                previous_version_cid: Some(cids[1]),
+                expires_at: None,
+                retention_policy: None,
+            },
+            "test",
+            None,
+        ).unwrap();
+
+        (document, store, cids)
+    }
+
+    #[test]
+    fn test_verify_intact_version_chain() {
+        let (document, store, _) = three_version_chain();
+
+        let result = verify_chain(&document, &store);
+
+        assert!(result.is_valid);
+        assert!(result.issues.is_empty());
+        assert_eq!(result.links_verified, 2);
+    }
+
+    #[test]
+    fn test_verify_chain_reports_missing_middle_version() {
+        let (document, mut store, cids) = three_version_chain();
+        store.blocks.remove(&cids[1]);
+
+        let result = verify_chain(&document, &store);
+
+        assert!(!result.is_valid);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].issue_type, ChainIssueType::MissingContent);
+        assert_eq!(result.issues[0].position, 1);
+        assert_eq!(result.links_verified, 0);
+    }
+
+    #[test]
+    fn test_verify_chain_reports_tampered_block() {
+        let (document, mut store, cids) = three_version_chain();
+        store.blocks.insert(cids[0], b"version 0ne".to_vec());
+
+        let result = verify_chain(&document, &store);
+
+        assert!(!result.is_valid);
+        assert_eq!(result.issues[0].issue_type, ChainIssueType::HashMismatch);
+        assert_eq!(result.issues[0].position, 2);
+        assert_eq!(result.links_verified, 1);
+    }
+}
@@ -0,0 +1,234 @@
+//! Content chunking for chunked documents
+//!
+//! [`chunk_content`] splits content into chunks, stores each chunk and a
+//! [`ChunkManifest`] in an [`ObjectStore`], and returns the CIDs that
+//! [`Document::new_chunked`](crate::aggregate::Document::new_chunked) expects.
+
+use crate::aggregate::{compute_cid, ChunkManifest, CidCodec, CidHash};
+use crate::services::{ObjectStore, ObjectStoreError};
+use cid::Cid;
+
+/// How content is split into chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkStrategy {
+    /// Chunks of exactly this many bytes; the last may be shorter
+    FixedSize(usize),
+    /// Boundaries chosen by a rolling (gear) hash, averaging this many bytes
+    ///
+    /// Must be a power of two of at least 64. Chunks are kept between a
+    /// quarter and four times the average. Because boundaries depend only on
+    /// nearby bytes, an edit changes just the chunks around it.
+    ContentDefined(usize),
+}
+
+/// Chunks stored for a piece of content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkedResult {
+    /// Chunk CIDs in content order
+    pub chunk_cids: Vec<Cid>,
+    /// CID of the stored [`ChunkManifest`]
+    pub metadata_cid: Cid,
+    /// CID of the complete content
+    pub root_cid: Cid,
+}
+
+/// Chunking failures
+#[derive(Debug, thiserror::Error)]
+pub enum ChunkingError {
+    #[error("Invalid chunk strategy: {0}")]
+    InvalidStrategy(String),
+
+    #[error(transparent)]
+    Store(#[from] ObjectStoreError),
+}
+
+/// Split `content`, store every chunk and the manifest, and return their CIDs
+pub fn chunk_content(
+    content: &[u8],
+    strategy: ChunkStrategy,
+    store: &dyn ObjectStore,
+) -> Result<ChunkedResult, ChunkingError> {
+    let boundaries = match strategy {
+        ChunkStrategy::FixedSize(0) => {
+            return Err(ChunkingError::InvalidStrategy("chunk size must be positive".to_string()));
+        }
+        ChunkStrategy::FixedSize(size) => (size..content.len()).step_by(size).collect(),
+        ChunkStrategy::ContentDefined(avg) if avg < 64 || !avg.is_power_of_two() => {
+            return Err(ChunkingError::InvalidStrategy(format!(
+                "average chunk size {avg} is not a power of two of at least 64"
+            )));
+        }
+        ChunkStrategy::ContentDefined(avg) => content_defined_boundaries(content, avg),
+    };
+
+    let mut chunk_cids = Vec::with_capacity(boundaries.len() + 1);
+    let mut start = 0;
+    for end in boundaries.into_iter().chain(std::iter::once(content.len())) {
+        if end > start {
+            chunk_cids.push(store.put(&content[start..end])?);
+        }
+        start = end;
+    }
+
+    let root_cid = compute_cid(content, CidCodec::Raw, CidHash::Sha2_256);
+    let manifest = ChunkManifest {
+        root: root_cid,
+        chunks: chunk_cids.clone(),
+    };
+    let metadata_cid = store.put(&manifest.to_bytes())?;
+
+    Ok(ChunkedResult {
+        chunk_cids,
+        metadata_cid,
+        root_cid,
+    })
+}
+
+/// Random per-byte values for the gear hash, fixed so boundaries are reproducible
+const GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0;
+    let mut i = 0;
+    while i < 256 {
+        // splitmix64
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+};
+
+/// End offsets of every chunk but the last
+///
+/// The gear hash shifts left once per byte, so its top bits depend only on
+/// the last 64 bytes; a boundary falls where the top `log2(avg)` bits are zero.
+fn content_defined_boundaries(content: &[u8], avg: usize) -> Vec<usize> {
+    let min = avg / 4;
+    let max = avg * 4;
+    let shift = 64 - avg.trailing_zeros();
+
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    let mut hash = 0u64;
+    for (i, byte) in content.iter().enumerate() {
+        hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+        let len = i + 1 - start;
+        if (len >= min && hash >> shift == 0) || len >= max {
+            boundaries.push(i + 1);
+            start = i + 1;
+            hash = 0;
+        }
+    }
+    boundaries.retain(|end| *end < content.len());
+    boundaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::{Document, DocumentInfoComponent};
+    use crate::services::{CidResolver, InMemoryObjectStore};
+
+    struct StoreResolver<'a>(&'a dyn ObjectStore);
+
+    impl CidResolver for StoreResolver<'_> {
+        fn resolve(&self, cid: &Cid) -> Option<Vec<u8>> {
+            self.0.get(cid).ok()
+        }
+    }
+
+    /// Deterministic pseudo-random bytes
+    fn sample(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn reassemble(result: &ChunkedResult, store: &dyn ObjectStore) -> Vec<u8> {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Chunked")
+            .with_mime_type("application/octet-stream")
+            .build()
+            .unwrap();
+        let document = Document::new_chunked(
+            cim_domain::EntityId::new(),
+            info,
+            result.chunk_cids.clone(),
+            result.metadata_cid,
+        );
+        document.reassemble(&StoreResolver(store)).unwrap()
+    }
+
+    #[test]
+    fn test_fixed_size_chunks_reassemble() {
+        let store = InMemoryObjectStore::new();
+        let content = sample(10_000);
+
+        let result = chunk_content(&content, ChunkStrategy::FixedSize(4096), &store).unwrap();
+
+        assert_eq!(result.chunk_cids.len(), 3);
+        assert_eq!(store.get(&result.chunk_cids[2]).unwrap().len(), 10_000 - 2 * 4096);
+        assert_eq!(reassemble(&result, &store), content);
+    }
+
+    #[test]
+    fn test_content_defined_chunks_reassemble() {
+        let store = InMemoryObjectStore::new();
+        let content = sample(64 * 1024);
+
+        let result = chunk_content(&content, ChunkStrategy::ContentDefined(1024), &store).unwrap();
+
+        assert!(result.chunk_cids.len() > 10);
+        for cid in &result.chunk_cids {
+            let len = store.get(cid).unwrap().len();
+            assert!(len <= 4096, "chunk of {len} bytes exceeds the maximum");
+        }
+        assert_eq!(reassemble(&result, &store), content);
+    }
+
+    #[test]
+    fn test_early_insert_only_changes_nearby_chunks_under_cdc() {
+        let store = InMemoryObjectStore::new();
+        let original = sample(64 * 1024);
+        let mut edited = original.clone();
+        edited.splice(100..100, b"inserted!!".iter().copied());
+
+        let before = chunk_content(&original, ChunkStrategy::ContentDefined(1024), &store).unwrap();
+        let after = chunk_content(&edited, ChunkStrategy::ContentDefined(1024), &store).unwrap();
+
+        assert_ne!(before.chunk_cids[0], after.chunk_cids[0]);
+        let changed = before.chunk_cids.iter().filter(|cid| !after.chunk_cids.contains(cid)).count();
+        assert!(changed <= 3, "{changed} of {} chunks changed", before.chunk_cids.len());
+        let tail = before.chunk_cids.len() - 3;
+        assert!(after.chunk_cids.ends_with(&before.chunk_cids[before.chunk_cids.len() - tail..]));
+        assert_eq!(reassemble(&after, &store), edited);
+
+        // Fixed-size chunking shifts every chunk after the insert
+        let before = chunk_content(&original, ChunkStrategy::FixedSize(1024), &store).unwrap();
+        let after = chunk_content(&edited, ChunkStrategy::FixedSize(1024), &store).unwrap();
+        assert!(before.chunk_cids.iter().all(|cid| !after.chunk_cids.contains(cid)));
+    }
+
+    #[test]
+    fn test_invalid_strategies_are_rejected() {
+        let store = InMemoryObjectStore::new();
+
+        assert!(matches!(
+            chunk_content(b"abc", ChunkStrategy::FixedSize(0), &store),
+            Err(ChunkingError::InvalidStrategy(_))
+        ));
+        assert!(matches!(
+            chunk_content(b"abc", ChunkStrategy::ContentDefined(1000), &store),
+            Err(ChunkingError::InvalidStrategy(_))
+        ));
+    }
+}
@@ -3,166 +3,236 @@
 use crate::value_objects::*;
 use crate::events::Classification;
 use cim_domain::DomainResult;
+use super::search::tokenize;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Service for extracting entities from document content
-pub struct EntityExtractionService {
-    // In a real implementation, this would use NLP models
+/// Pluggable entity extractor
+///
+/// Offsets on returned entities are byte positions into `text`.
+pub trait EntityExtractor: Send + Sync {
+    fn extract(&self, text: &str, opts: &ExtractionOptions) -> Vec<ExtractedEntity>;
 }
 
-impl Default for EntityExtractionService {
+const ORGANIZATION_SUFFIXES: &str = r"Inc\.|LLC|Corp\.|Ltd\.|Company|Foundation|Institute";
+const MONTHS: &str =
+    "January|February|March|April|May|June|July|August|September|October|November|December";
+/// Capitalized words that start sentences rather than names
+const LEADING_STOPWORDS: &[&str] = &[
+    "A", "An", "And", "At", "But", "By", "Dear", "For", "From", "In", "On", "The", "This", "That",
+];
+const CONCEPTS: &[&str] = &["workflow", "document", "process", "system", "integration"];
+
+/// Regex and heuristic based extractor used by default
+///
+/// Finds capitalized multi-word names (organizations when they end in a
+/// company suffix, persons otherwise), ISO and written dates, known concepts
+/// and frequently repeated keywords.
+pub struct HeuristicEntityExtractor {
+    organization: Regex,
+    capitalized: Regex,
+    word: Regex,
+    dates: Vec<(Regex, &'static str)>,
+    concepts: Vec<(&'static str, Regex)>,
+}
+
+impl Default for HeuristicEntityExtractor {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl EntityExtractionService {
+impl HeuristicEntityExtractor {
     pub fn new() -> Self {
-        Self {}
-    }
-
-    /// Extract entities from document content
-    pub fn extract_entities(
-        &self,
-        content: &str,
-        options: &ExtractionOptions,
-    ) -> DomainResult<Vec<ExtractedEntity>> {
-        let mut entities = Vec::new();
-
-        // Simulate entity extraction with simple pattern matching
-        if options.extract_entities {
-            // Extract person names (simple heuristic)
-            entities.extend(self.extract_person_names(content, options.confidence_threshold));
-            
-            // Extract organizations
-            entities.extend(self.extract_organizations(content, options.confidence_threshold));
-            
-            // Extract locations
-            entities.extend(self.extract_locations(content, options.confidence_threshold));
+        Self {
+            organization: Regex::new(&format!(
+                r"\b[A-Z][A-Za-z&]*(?:\s+[A-Z][A-Za-z&]*)*?\s+(?:{ORGANIZATION_SUFFIXES})"
+            ))
+            .unwrap(),
+            capitalized: Regex::new(r"\b[A-Z][a-z]+(?:[ \t]+[A-Z][a-z]+)+\b").unwrap(),
+            word: Regex::new(r"[A-Za-z]+").unwrap(),
+            dates: vec![
+                (Regex::new(r"\b\d{4}-\d{2}-\d{2}\b").unwrap(), "%Y-%m-%d"),
+                (
+                    Regex::new(&format!(r"\b(?:{MONTHS})\s+\d{{1,2}},?\s+\d{{4}}\b")).unwrap(),
+                    "%B %d %Y",
+                ),
+                (
+                    Regex::new(&format!(r"\b\d{{1,2}}\s+(?:{MONTHS})\s+\d{{4}}\b")).unwrap(),
+                    "%d %B %Y",
+                ),
+            ],
+            concepts: CONCEPTS
+                .iter()
+                .map(|c| (*c, Regex::new(&format!(r"(?i)\b{c}\b")).unwrap()))
+                .collect(),
         }
+    }
 
-        if options.extract_concepts {
-            entities.extend(self.extract_concepts(content, options.confidence_threshold));
-        }
+    fn organizations(&self, text: &str) -> Vec<ExtractedEntity> {
+        self.organization
+            .find_iter(text)
+            .map(|m| entity(m.as_str(), EntityType::Organization, 0.85, m.start(), m.end()))
+            .collect()
+    }
 
-        if options.extract_keywords {
-            entities.extend(self.extract_keywords(content, options.confidence_threshold));
+    fn dates(&self, text: &str) -> Vec<ExtractedEntity> {
+        let mut entities: Vec<ExtractedEntity> = Vec::new();
+        for (re, format) in &self.dates {
+            for m in re.find_iter(text) {
+                let normalized = m.as_str().replace(',', "");
+                let normalized = normalized.split_whitespace().collect::<Vec<_>>().join(" ");
+                let Ok(date) = chrono::NaiveDate::parse_from_str(&normalized, format) else {
+                    continue;
+                };
+                let mut e = entity(m.as_str(), EntityType::DateTime, 0.9, m.start(), m.end());
+                e.metadata
+                    .insert("date".to_string(), date.format("%Y-%m-%d").to_string());
+                entities.push(e);
+            }
         }
+        entities
+    }
 
-        // Apply max entities limit
-        if let Some(max) = options.max_entities {
-            entities.truncate(max);
+    /// Capitalized multi-word runs not already claimed by another entity
+    fn persons(&self, text: &str, taken: &[ExtractedEntity]) -> Vec<ExtractedEntity> {
+        let mut entities = Vec::new();
+        for m in self.capitalized.find_iter(text) {
+            let words: Vec<(usize, usize)> = self
+                .word
+                .find_iter(m.as_str())
+                .map(|w| (m.start() + w.start(), m.start() + w.end()))
+                .skip_while(|(s, e)| LEADING_STOPWORDS.contains(&&text[*s..*e]))
+                .collect();
+            if words.len() < 2 {
+                continue;
+            }
+            let (start, end) = (words[0].0, words[words.len() - 1].1);
+            if taken.iter().any(|t| start < t.end_offset && t.start_offset < end) {
+                continue;
+            }
+            entities.push(entity(&text[start..end], EntityType::Person, 0.8, start, end));
         }
+        entities
+    }
 
-        Ok(entities)
+    fn concepts(&self, text: &str) -> Vec<ExtractedEntity> {
+        self.concepts
+            .iter()
+            .filter_map(|(concept, re)| {
+                re.find(text)
+                    .map(|m| entity(concept, EntityType::Concept, 0.7, m.start(), m.end()))
+            })
+            .collect()
     }
 
-    fn extract_person_names(&self, content: &str, threshold: f32) -> Vec<ExtractedEntity> {
-        // Simplified: look for capitalized words that might be names
-        let mut entities = Vec::new();
-        let words: Vec<&str> = content.split_whitespace().collect();
-        
-        for (i, word) in words.iter().enumerate() {
-            if word.chars().next().is_some_and(|c| c.is_uppercase()) && word.len() > 2 {
-                // Check if next word is also capitalized (likely a full name)
-                if i + 1 < words.len() && words[i + 1].chars().next().is_some_and(|c| c.is_uppercase()) {
-                    let full_name = format!("{} {}", word, words[i + 1]);
-                    entities.push(ExtractedEntity {
-                        text: full_name,
-                        entity_type: EntityType::Person,
-                        confidence: 0.8,
-                        start_offset: 0, // Would calculate actual offset
-                        end_offset: 0,
-                        metadata: HashMap::new(),
-                    });
-                }
-            }
+    /// Words longer than four letters that occur more than once, at their first offset
+    fn keywords(&self, text: &str) -> Vec<ExtractedEntity> {
+        let mut counts: HashMap<String, (usize, usize, usize)> = HashMap::new();
+        for m in self.word.find_iter(text).filter(|m| m.len() > 4) {
+            counts
+                .entry(m.as_str().to_lowercase())
+                .or_insert((m.start(), m.end(), 0))
+                .2 += 1;
         }
 
-        entities.into_iter().filter(|e| e.confidence >= threshold).collect()
+        counts
+            .into_iter()
+            .filter(|(_, (_, _, count))| *count > 1)
+            .map(|(word, (start, end, count))| {
+                let mut e = entity(
+                    &word,
+                    EntityType::Keyword,
+                    (count as f32 / 10.0).min(0.9),
+                    start,
+                    end,
+                );
+                e.metadata.insert("frequency".to_string(), count.to_string());
+                e
+            })
+            .collect()
     }
+}
 
-    fn extract_organizations(&self, content: &str, threshold: f32) -> Vec<ExtractedEntity> {
-        // Look for common organization patterns
-        let org_keywords = ["Inc.", "LLC", "Corp.", "Company", "Foundation", "Institute"];
+impl EntityExtractor for HeuristicEntityExtractor {
+    fn extract(&self, text: &str, opts: &ExtractionOptions) -> Vec<ExtractedEntity> {
         let mut entities = Vec::new();
 
-        for keyword in &org_keywords {
-            if let Some(pos) = content.find(keyword) {
-                // Extract surrounding context
-                let start = content[..pos].rfind(' ').unwrap_or(0);
-                let end = pos + keyword.len();
-                let org_name = content[start..end].trim();
-                
-                entities.push(ExtractedEntity {
-                    text: org_name.to_string(),
-                    entity_type: EntityType::Organization,
-                    confidence: 0.85,
-                    start_offset: start,
-                    end_offset: end,
-                    metadata: HashMap::new(),
-                });
-            }
+        if opts.extract_entities {
+            entities.extend(self.organizations(text));
+            entities.extend(self.dates(text));
+            let persons = self.persons(text, &entities);
+            entities.extend(persons);
+        }
+        if opts.extract_concepts {
+            entities.extend(self.concepts(text));
+        }
+        if opts.extract_keywords {
+            entities.extend(self.keywords(text));
         }
 
-        entities.into_iter().filter(|e| e.confidence >= threshold).collect()
+        entities.retain(|e| e.confidence >= opts.confidence_threshold);
+        // Most confident first so the limit keeps the strongest candidates
+        entities.sort_by(|a, b| {
+            b.confidence
+                .total_cmp(&a.confidence)
+                .then(a.start_offset.cmp(&b.start_offset))
+        });
+        if let Some(max) = opts.max_entities {
+            entities.truncate(max);
+        }
+        entities
     }
+}
 
-    fn extract_locations(&self, _content: &str, _threshold: f32) -> Vec<ExtractedEntity> {
-        // Would use NER model or gazetteer
-        Vec::new()
+fn entity(
+    text: &str,
+    entity_type: EntityType,
+    confidence: f32,
+    start_offset: usize,
+    end_offset: usize,
+) -> ExtractedEntity {
+    ExtractedEntity {
+        text: text.to_string(),
+        entity_type,
+        confidence,
+        start_offset,
+        end_offset,
+        metadata: HashMap::new(),
     }
+}
 
-    fn extract_concepts(&self, content: &str, threshold: f32) -> Vec<ExtractedEntity> {
-        // Extract domain-specific concepts
-        let concepts = ["workflow", "document", "process", "system", "integration"];
-        let mut entities = Vec::new();
+/// Service for extracting entities from document content
+pub struct EntityExtractionService {
+    extractor: Box<dyn EntityExtractor>,
+}
 
-        for concept in &concepts {
-            if content.to_lowercase().contains(concept) {
-                entities.push(ExtractedEntity {
-                    text: concept.to_string(),
-                    entity_type: EntityType::Concept,
-                    confidence: 0.7,
-                    start_offset: 0,
-                    end_offset: 0,
-                    metadata: HashMap::new(),
-                });
-            }
-        }
+impl Default for EntityExtractionService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-        entities.into_iter().filter(|e| e.confidence >= threshold).collect()
+impl EntityExtractionService {
+    /// Service backed by the [`HeuristicEntityExtractor`]
+    pub fn new() -> Self {
+        Self::with_extractor(HeuristicEntityExtractor::new())
     }
 
-    fn extract_keywords(&self, content: &str, threshold: f32) -> Vec<ExtractedEntity> {
-        // Simple keyword extraction based on word frequency
-        let words: Vec<&str> = content.split_whitespace()
-            .filter(|w| w.len() > 4)
-            .collect();
-        
-        let mut word_count: HashMap<String, usize> = HashMap::new();
-        for word in words {
-            *word_count.entry(word.to_lowercase()).or_insert(0) += 1;
+    pub fn with_extractor(extractor: impl EntityExtractor + 'static) -> Self {
+        Self {
+            extractor: Box::new(extractor),
         }
+    }
 
-        let mut keywords: Vec<_> = word_count.into_iter()
-            .filter(|(_, count)| *count > 1)
-            .map(|(word, count)| {
-                ExtractedEntity {
-                    text: word,
-                    entity_type: EntityType::Keyword,
-                    confidence: (count as f32 / 10.0).min(0.9),
-                    start_offset: 0,
-                    end_offset: 0,
-                    metadata: HashMap::new(),
-                }
-            })
-            .filter(|e| e.confidence >= threshold)
-            .collect();
-
-        keywords.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap());
-        keywords.truncate(10);
-        keywords
+    /// Extract entities from document content
+    pub fn extract_entities(
+        &self,
+        content: &str,
+        options: &ExtractionOptions,
+    ) -> DomainResult<Vec<ExtractedEntity>> {
+        Ok(self.extractor.extract(content, options))
     }
 }
 
@@ -189,67 +259,299 @@ impl SummarizationService {
         length: &SummaryLength,
         language: &str,
     ) -> DomainResult<DocumentSummary> {
-        let sentences: Vec<&str> = content.split(". ")
-            .filter(|s| !s.is_empty())
-            .collect();
+        Ok(summarize(content, length.clone(), language))
+    }
+}
 
-        let (summary_text, _num_sentences) = match length {
-            SummaryLength::Brief => {
-                // Take first 1-2 sentences
-                let text = sentences.iter()
-                    .take(2)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(". ");
-                (text, 2)
-            }
-            SummaryLength::Standard => {
-                // Take first paragraph worth
-                let text = sentences.iter()
-                    .take(5)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(". ");
-                (text, 5)
-            }
-            SummaryLength::Detailed => {
-                // Take multiple paragraphs
-                let text = sentences.iter()
-                    .take(10)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(". ");
-                (text, 10)
+/// Number of top-ranked sentences reported as key points
+const KEY_POINTS: usize = 3;
+const SUMMARY_STOPWORDS: &[&str] = &[
+    "the", "and", "for", "are", "was", "were", "this", "that", "with", "from", "has", "have",
+    "had", "but", "not", "its", "our", "their", "they", "will", "been", "into", "than",
+];
+
+/// Extractive summary of `text`
+///
+/// Sentences are ranked by the mean document frequency of their terms and the
+/// best ones are kept, in source order, within the budget of `length`:
+/// `Brief` keeps up to 2 sentences, `Standard` 5, `Detailed` 10 and
+/// `Custom(words)` as many as fit in the word count. `quality_score` is the
+/// share of term occurrences covered by the summary.
+pub fn summarize(text: &str, length: SummaryLength, language: &str) -> DocumentSummary {
+    let sentences = split_sentences(text);
+    let terms = |sentence: &str| -> Vec<String> {
+        tokenize(sentence)
+            .into_iter()
+            .map(|t| t.term)
+            .filter(|t| t.chars().count() > 2 && !SUMMARY_STOPWORDS.contains(&t.as_str()))
+            .collect()
+    };
+
+    let mut frequencies: HashMap<String, usize> = HashMap::new();
+    for sentence in &sentences {
+        for term in terms(sentence) {
+            *frequencies.entry(term).or_insert(0) += 1;
+        }
+    }
+
+    let scores: Vec<f32> = sentences
+        .iter()
+        .map(|sentence| {
+            let terms = terms(sentence);
+            if terms.is_empty() {
+                return 0.0;
             }
-            SummaryLength::Custom(words) => {
-                // Approximate sentences based on word count
-                let sentences_needed = words / 15; // Assume ~15 words per sentence
-                let text = sentences.iter()
-                    .take(sentences_needed)
-                    .cloned()
-                    .collect::<Vec<_>>()
-                    .join(". ");
-                (text, sentences_needed)
+            terms.iter().map(|t| frequencies[t] as f32).sum::<f32>() / terms.len() as f32
+        })
+        .collect();
+
+    // Best first; ties go to the earlier sentence
+    let mut ranked: Vec<usize> = (0..sentences.len()).collect();
+    ranked.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b)));
+
+    let word_count = |i: &usize| sentences[*i].split_whitespace().count();
+    let mut selected: Vec<usize> = match length {
+        SummaryLength::Brief => ranked.iter().take(2).copied().collect(),
+        SummaryLength::Standard => ranked.iter().take(5).copied().collect(),
+        SummaryLength::Detailed => ranked.iter().take(10).copied().collect(),
+        SummaryLength::Custom(budget) => {
+            let mut used = 0;
+            ranked
+                .iter()
+                .filter(|i| {
+                    let words = word_count(*i);
+                    let fits = used + words <= budget;
+                    if fits {
+                        used += words;
+                    }
+                    fits
+                })
+                .copied()
+                .collect()
+        }
+    };
+    selected.sort_unstable();
+
+    let mut summary_text = selected
+        .iter()
+        .map(|i| sentences[*i])
+        .collect::<Vec<_>>()
+        .join(" ");
+    // No whole sentence fits a custom budget: cut the best one to size
+    if let (SummaryLength::Custom(budget), Some(best)) = (&length, ranked.first()) {
+        if selected.is_empty() {
+            summary_text = sentences[*best]
+                .split_whitespace()
+                .take(*budget)
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+
+    let mut key_point_indices: Vec<usize> = ranked.iter().take(KEY_POINTS).copied().collect();
+    key_point_indices.sort_unstable();
+    let key_points = key_point_indices
+        .iter()
+        .map(|i| sentences[*i].to_string())
+        .collect();
+
+    let total: usize = frequencies.values().sum();
+    let quality_score = if summary_text.is_empty() {
+        0.0
+    } else if total == 0 {
+        1.0
+    } else {
+        let covered: usize = terms(&summary_text)
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>()
+            .iter()
+            .map(|t| frequencies.get(t).copied().unwrap_or(0))
+            .sum();
+        covered as f32 / total as f32
+    };
+
+    DocumentSummary {
+        text: summary_text,
+        key_points,
+        length,
+        language: language.to_string(),
+        generated_at: chrono::Utc::now(),
+        quality_score: Some(quality_score),
+    }
+}
+
+/// Split text after `.`, `!` or `?` followed by whitespace, keeping the punctuation
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let at_boundary = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if matches!(c, '.' | '!' | '?') && at_boundary {
+            let end = i + c.len_utf8();
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+/// Category a [`Classifier`] assigns when no keyword matches
+pub const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Keyword/weight classification model
+///
+/// Each category scores the sum of `weight * occurrences` of its keywords;
+/// confidence is the category's share of the total score.
+#[derive(Debug, Clone, Default)]
+pub struct Classifier {
+    categories: Vec<(String, Vec<(String, f32)>)>,
+}
+
+impl Classifier {
+    pub fn builder() -> ClassifierBuilder {
+        ClassifierBuilder::default()
+    }
+
+    /// Every matching category, most confident first
+    pub fn rank(&self, text: &str) -> Vec<Classification> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for token in tokenize(text) {
+            *counts.entry(token.term).or_insert(0) += 1;
+        }
+
+        let mut scored: Vec<(f32, Classification)> = Vec::new();
+        for (category, keywords) in &self.categories {
+            let mut matches: Vec<(f32, &String)> = keywords
+                .iter()
+                .filter_map(|(keyword, weight)| {
+                    counts.get(keyword).map(|n| (weight * *n as f32, keyword))
+                })
+                .filter(|(score, _)| *score > 0.0)
+                .collect();
+            if matches.is_empty() {
+                continue;
             }
-        };
+            matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+            scored.push((
+                matches.iter().map(|(score, _)| score).sum(),
+                Classification {
+                    category: category.clone(),
+                    confidence: 0.0,
+                    labels: matches.into_iter().map(|(_, k)| k.clone()).collect(),
+                },
+            ));
+        }
 
-        // Extract key points (simplified)
-        let key_points = sentences.iter()
-            .take(3)
-            .map(|s| s.to_string())
-            .collect();
+        let total: f32 = scored.iter().map(|(score, _)| score).sum();
+        // Stable sort keeps registration order on ties
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+        scored
+            .into_iter()
+            .map(|(score, mut classification)| {
+                classification.confidence = score / total;
+                classification
+            })
+            .collect()
+    }
+}
+
+/// Builder registering category keyword sets for a [`Classifier`]
+#[derive(Debug, Clone, Default)]
+pub struct ClassifierBuilder {
+    categories: Vec<(String, Vec<(String, f32)>)>,
+}
 
-        Ok(DocumentSummary {
-            text: summary_text,
-            key_points,
-            length: length.clone(),
-            language: language.to_string(),
-            generated_at: chrono::Utc::now(),
-            quality_score: Some(0.75), // Would be calculated based on coverage, coherence, etc.
+impl ClassifierBuilder {
+    /// Register a category with weighted keywords; keywords match whole words case-insensitively
+    pub fn category<'a>(
+        mut self,
+        name: impl Into<String>,
+        keywords: impl IntoIterator<Item = (&'a str, f32)>,
+    ) -> Self {
+        self.categories.push((
+            name.into(),
+            keywords
+                .into_iter()
+                .map(|(keyword, weight)| (keyword.to_lowercase(), weight))
+                .collect(),
+        ));
+        self
+    }
+
+    pub fn build(self) -> Classifier {
+        Classifier {
+            categories: self.categories,
+        }
+    }
+}
+
+/// Top category of `text` under `taxonomy`, or [`UNCATEGORIZED`] with zero confidence
+pub fn classify(text: &str, taxonomy: &Classifier) -> Classification {
+    taxonomy
+        .rank(text)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| Classification {
+            category: UNCATEGORIZED.to_string(),
+            confidence: 0.0,
+            labels: Vec::new(),
         })
+}
+
+/// Reading speed used for reading-time estimates
+pub const DEFAULT_WORDS_PER_MINUTE: u32 = 200;
+
+/// Size and reading-time figures for a piece of content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentStatistics {
+    pub word_count: usize,
+    pub character_count: usize,
+    pub sentence_count: usize,
+    /// Runs of non-blank lines separated by blank lines
+    pub paragraph_count: usize,
+    /// Minutes at the reading speed the statistics were computed for, rounded up
+    pub reading_time_minutes: u32,
+}
+
+impl DocumentStatistics {
+    /// Whole minutes needed at `words_per_minute`, rounded up; zero words read in zero minutes
+    pub fn reading_time(&self, words_per_minute: u32) -> u32 {
+        let wpm = words_per_minute.max(1) as usize;
+        self.word_count.div_ceil(wpm) as u32
     }
 }
 
+/// Word, character, sentence and paragraph counts of `content`, read at `words_per_minute`
+///
+/// Leading and trailing whitespace does not count towards `character_count`.
+pub fn document_statistics(content: &str, words_per_minute: u32) -> DocumentStatistics {
+    let mut paragraph_count = 0;
+    let mut in_paragraph = false;
+    for line in content.lines() {
+        let blank = line.trim().is_empty();
+        if !blank && !in_paragraph {
+            paragraph_count += 1;
+        }
+        in_paragraph = !blank;
+    }
+
+    let mut statistics = DocumentStatistics {
+        word_count: content.split_whitespace().count(),
+        character_count: content.trim().chars().count(),
+        sentence_count: split_sentences(content).len(),
+        paragraph_count,
+        reading_time_minutes: 0,
+    };
+    statistics.reading_time_minutes = statistics.reading_time(words_per_minute);
+    statistics
+}
+
 /// Service for document classification
 pub struct ClassificationService {
     // In a real implementation, this would use ML models
@@ -326,6 +628,78 @@ mod tests {
         assert!(entities.iter().any(|e| matches!(e.entity_type, EntityType::Organization)));
     }
 
+    #[test]
+    fn test_extractor_typed_entities_with_offsets() {
+        let extractor = HeuristicEntityExtractor::new();
+        let text = "On March 5, 2024 Ada Lovelace met the board of Acme Corp. again on 2024-03-12.";
+        let options = ExtractionOptions::default();
+
+        let entities = extractor.extract(text, &options);
+        for e in &entities {
+            assert_eq!(&text[e.start_offset..e.end_offset].to_lowercase(), &e.text.to_lowercase());
+        }
+
+        let person = entities
+            .iter()
+            .find(|e| e.entity_type == EntityType::Person)
+            .unwrap();
+        assert_eq!(person.text, "Ada Lovelace");
+        assert_eq!(person.start_offset, text.find("Ada").unwrap());
+
+        let dates: Vec<_> = entities
+            .iter()
+            .filter(|e| e.entity_type == EntityType::DateTime)
+            .collect();
+        assert_eq!(dates.len(), 2);
+        assert!(dates.iter().any(|d| d.text == "March 5, 2024" && d.start_offset == 3));
+        assert!(dates.iter().any(|d| d.metadata["date"] == "2024-03-12"));
+
+        assert!(entities
+            .iter()
+            .any(|e| e.entity_type == EntityType::Organization && e.text == "Acme Corp."));
+        // "On March" must not be mistaken for a name
+        assert!(!entities.iter().any(|e| e.text.contains("On March")));
+    }
+
+    #[test]
+    fn test_extractor_threshold_and_limit() {
+        let extractor = HeuristicEntityExtractor::new();
+        let text = "Grace Hopper joined Remington Company on 1949-06-01. The process and the system changed.";
+
+        let strict = ExtractionOptions {
+            confidence_threshold: 0.85,
+            ..Default::default()
+        };
+        let entities = extractor.extract(text, &strict);
+        assert!(entities.iter().all(|e| e.confidence >= 0.85));
+        assert!(!entities.iter().any(|e| e.entity_type == EntityType::Person));
+
+        let limited = ExtractionOptions {
+            max_entities: Some(1),
+            ..Default::default()
+        };
+        let entities = extractor.extract(text, &limited);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].entity_type, EntityType::DateTime);
+    }
+
+    #[test]
+    fn test_service_uses_custom_extractor() {
+        struct Fixed;
+        impl EntityExtractor for Fixed {
+            fn extract(&self, text: &str, _opts: &ExtractionOptions) -> Vec<ExtractedEntity> {
+                vec![entity(text, EntityType::Custom("fixed".to_string()), 1.0, 0, text.len())]
+            }
+        }
+
+        let service = EntityExtractionService::with_extractor(Fixed);
+        let entities = service
+            .extract_entities("anything", &ExtractionOptions::default())
+            .unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].end_offset, 8);
+    }
+
     #[test]
     fn test_summarization() {
         let service = SummarizationService::new();
@@ -338,6 +712,146 @@ mod tests {
         assert_eq!(summary.key_points.len(), 3);
     }
 
+    #[test]
+    fn test_summarization_empty_content() {
+        let service = SummarizationService::new();
+
+        for content in ["", "   \n\t  "] {
+            let summary = service.generate_summary(content, &SummaryLength::Standard, "en").unwrap();
+            assert!(summary.text.is_empty());
+            assert!(summary.key_points.is_empty());
+            assert_eq!(summary.quality_score, Some(0.0));
+        }
+
+        let summary = service.generate_summary("a", &SummaryLength::Brief, "en").unwrap();
+        assert_eq!(summary.text, "a");
+        assert_eq!(summary.key_points, vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn test_summarize_custom_word_budget() {
+        let text = "Invoices are issued monthly to every customer. \
+                    Each invoice lists the payment terms and due date. \
+                    Late payment of an invoice incurs a fee. \
+                    The office closes early on Fridays. \
+                    Customers can query any invoice through the portal.";
+
+        let summary = summarize(text, SummaryLength::Custom(20), "en");
+        let words = summary.text.split_whitespace().count();
+        assert!(words <= 20 && words >= 10, "{} words", words);
+        assert!(!summary.text.contains("Fridays"));
+
+        let sources = split_sentences(text);
+        assert_eq!(summary.key_points.len(), 3);
+        assert!(summary.key_points.iter().all(|p| sources.contains(&p.as_str())));
+        assert!(summary.quality_score.unwrap() > 0.0 && summary.quality_score.unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_summarize_budgets_grow() {
+        let text = (1..=12)
+            .map(|i| format!("Sentence number {} talks about budgets.", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let count = |length| split_sentences(&summarize(&text, length, "en").text).len();
+        assert_eq!(count(SummaryLength::Brief), 2);
+        assert_eq!(count(SummaryLength::Standard), 5);
+        assert_eq!(count(SummaryLength::Detailed), 10);
+
+        let detailed = summarize(&text, SummaryLength::Detailed, "en");
+        assert!(detailed.text.starts_with("Sentence number 1 "));
+    }
+
+    fn finance_taxonomy() -> Classifier {
+        Classifier::builder()
+            .category("Finance", [("invoice", 1.0), ("payment", 1.0), ("remittance", 0.8), ("tax", 0.5)])
+            .category("Procurement", [("purchase", 1.0), ("supplier", 1.0), ("order", 0.8), ("invoice", 0.3)])
+            .category("Legal", [("contract", 1.0), ("clause", 1.0)])
+            .build()
+    }
+
+    #[test]
+    fn test_classify_invoice_heavy_text_as_finance() {
+        let taxonomy = finance_taxonomy();
+        let text = "Invoice 1042 is attached. Payment is due within 30 days of the invoice date. \
+                    Please send remittance details with the payment. The supplier order number is on the invoice.";
+
+        let ranked = taxonomy.rank(text);
+        assert_eq!(ranked[0].category, "Finance");
+        assert_eq!(ranked[1].category, "Procurement");
+        assert!(ranked[0].confidence > ranked[1].confidence);
+        assert!(ranked.iter().all(|c| c.category != "Legal"));
+
+        let result = classify(text, &taxonomy);
+        assert_eq!(result, ranked[0]);
+        assert_eq!(result.labels[0], "invoice");
+        assert!(result.labels.contains(&"remittance".to_string()));
+        assert!((ranked.iter().map(|c| c.confidence).sum::<f32>() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_classify_without_matches() {
+        let result = classify("Nothing relevant here.", &finance_taxonomy());
+        assert_eq!(result.category, UNCATEGORIZED);
+        assert_eq!(result.confidence, 0.0);
+        assert!(result.labels.is_empty());
+    }
+
+    #[test]
+    fn test_classification_updates_component() {
+        use crate::aggregate::{ClassificationComponent, ConfidentialityLevel};
+
+        let mut component = ClassificationComponent {
+            document_type: "Invoice".to_string(),
+            category: "Inbox".to_string(),
+            subcategories: vec![],
+            tags: vec!["payment".to_string()],
+            confidentiality: ConfidentialityLevel::Internal,
+        };
+        let result = classify("Invoice payment overdue", &finance_taxonomy());
+        component.apply(&result);
+
+        assert_eq!(component.category, "Finance");
+        assert_eq!(component.tags, vec!["payment".to_string(), "invoice".to_string()]);
+    }
+
+    #[test]
+    fn test_document_statistics_counts() {
+        let content = "The quick brown fox jumps over the lazy dog. It barks! Does it bite?\n\nSecond paragraph here.";
+
+        let stats = document_statistics(content, DEFAULT_WORDS_PER_MINUTE);
+        assert_eq!(stats.word_count, 17);
+        assert_eq!(stats.character_count, 92);
+        assert_eq!(stats.sentence_count, 4);
+        assert_eq!(stats.paragraph_count, 2);
+        assert_eq!(stats.reading_time_minutes, 1);
+        assert_eq!(stats.reading_time(10), 2);
+    }
+
+    #[test]
+    fn test_document_statistics_reading_time_rounding() {
+        let words = |n: usize| vec!["word"; n].join(" ");
+
+        assert_eq!(document_statistics(&words(1000), DEFAULT_WORDS_PER_MINUTE).reading_time_minutes, 5);
+        assert_eq!(document_statistics(&words(1001), DEFAULT_WORDS_PER_MINUTE).reading_time_minutes, 6);
+        assert_eq!(document_statistics(&words(1001), 250).reading_time_minutes, 5);
+        assert_eq!(document_statistics(&words(1001), 250).reading_time(DEFAULT_WORDS_PER_MINUTE), 6);
+    }
+
+    #[test]
+    fn test_document_statistics_empty() {
+        for content in ["", "  \n\n\t "] {
+            let stats = document_statistics(content, DEFAULT_WORDS_PER_MINUTE);
+            assert_eq!(stats.word_count, 0);
+            assert_eq!(stats.character_count, 0);
+            assert_eq!(stats.sentence_count, 0);
+            assert_eq!(stats.paragraph_count, 0);
+            assert_eq!(stats.reading_time_minutes, 0);
+        }
+        assert_eq!(document_statistics("", DEFAULT_WORDS_PER_MINUTE), DocumentStatistics::default());
+    }
+
     #[test]
     fn test_classification() {
         let service = ClassificationService::new();
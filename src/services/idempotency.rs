@@ -0,0 +1,164 @@
+//! Deduplication of redelivered commands
+//!
+//! Commands carrying an idempotency key are executed once; a redelivery
+//! within the store's TTL gets the originally-produced events back.
+
+use crate::events::DocumentDomainEvent;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Outcome of claiming an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reservation {
+    /// The key was unseen and is now held by the caller
+    Reserved,
+    /// Another delivery of the command is still executing
+    InFlight,
+    /// The command already ran and produced these events
+    Completed(Vec<DocumentDomainEvent>),
+}
+
+/// Pluggable storage for recently-seen idempotency keys
+pub trait IdempotencyStore: Send + Sync {
+    /// Claim `key` for execution, or report what an earlier claim produced
+    ///
+    /// Checking and claiming happen atomically, so two concurrent deliveries
+    /// cannot both be `Reserved`.
+    fn reserve(&self, key: Uuid) -> Reservation;
+
+    /// Remember the events produced under a reserved `key`
+    fn complete(&self, key: Uuid, events: Vec<DocumentDomainEvent>);
+
+    /// Give up a reservation so a later delivery can execute the command
+    fn release(&self, key: &Uuid);
+}
+
+#[derive(Debug)]
+enum Entry {
+    InFlight,
+    Completed(Vec<DocumentDomainEvent>),
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    by_key: HashMap<Uuid, (Instant, Entry)>,
+    /// Keys in the order they were stamped; with a single TTL this is also expiry order
+    by_age: VecDeque<(Instant, Uuid)>,
+}
+
+impl Entries {
+    fn stamp(&mut self, key: Uuid, entry: Entry) {
+        let now = Instant::now();
+        self.by_key.insert(key, (now, entry));
+        self.by_age.push_back((now, key));
+    }
+
+    /// Drop expired keys from the oldest end until a live one is reached
+    fn evict_expired(&mut self, ttl: Duration) {
+        while let Some(&(stamped_at, key)) = self.by_age.front() {
+            if stamped_at.elapsed() < ttl {
+                break;
+            }
+            self.by_age.pop_front();
+            // The key may have been re-stamped or released since
+            if self.by_key.get(&key).is_some_and(|(current, _)| *current == stamped_at) {
+                self.by_key.remove(&key);
+            }
+        }
+    }
+}
+
+/// In-memory idempotency store that forgets keys after a TTL
+#[derive(Debug)]
+pub struct InMemoryIdempotencyStore {
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+impl InMemoryIdempotencyStore {
+    /// Default time a key is remembered
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    /// Drop every expired key
+    pub fn purge_expired(&self) {
+        self.entries.lock().unwrap().evict_expired(self.ttl);
+    }
+}
+
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TTL)
+    }
+}
+
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    fn reserve(&self, key: Uuid) -> Reservation {
+        let mut entries = self.entries.lock().unwrap();
+        entries.evict_expired(self.ttl);
+        match entries.by_key.get(&key) {
+            Some((_, Entry::InFlight)) => Reservation::InFlight,
+            Some((_, Entry::Completed(events))) => Reservation::Completed(events.clone()),
+            None => {
+                entries.stamp(key, Entry::InFlight);
+                Reservation::Reserved
+            }
+        }
+    }
+
+    fn complete(&self, key: Uuid, events: Vec<DocumentDomainEvent>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.evict_expired(self.ttl);
+        entries.stamp(key, Entry::Completed(events));
+    }
+
+    fn release(&self, key: &Uuid) {
+        self.entries.lock().unwrap().by_key.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_are_forgotten_after_ttl() {
+        let key = Uuid::new_v4();
+        let remembering = InMemoryIdempotencyStore::default();
+        let forgetting = InMemoryIdempotencyStore::new(Duration::ZERO);
+
+        assert_eq!(remembering.reserve(key), Reservation::Reserved);
+        assert_eq!(forgetting.reserve(key), Reservation::Reserved);
+        remembering.complete(key, vec![]);
+        forgetting.complete(key, vec![]);
+
+        assert_eq!(remembering.reserve(key), Reservation::Completed(vec![]));
+        assert_eq!(forgetting.reserve(key), Reservation::Reserved);
+
+        forgetting.purge_expired();
+        assert!(forgetting.entries.lock().unwrap().by_age.is_empty());
+    }
+
+    #[test]
+    fn test_reserved_key_is_held_until_completed_or_released() {
+        let key = Uuid::new_v4();
+        let store = InMemoryIdempotencyStore::default();
+
+        assert_eq!(store.reserve(key), Reservation::Reserved);
+        assert_eq!(store.reserve(key), Reservation::InFlight);
+
+        store.release(&key);
+        assert_eq!(store.reserve(key), Reservation::Reserved);
+
+        store.complete(key, vec![]);
+        assert_eq!(store.reserve(key), Reservation::Completed(vec![]));
+    }
+}
@@ -1,9 +1,80 @@
 //! Document import/export service
 
-use crate::value_objects::{DocumentId, DocumentType, ImportOptions, ExportOptions, ImportFormat, ExportFormat}; // DocumentId used in tests
-use crate::projections::DocumentFullView;
-use anyhow::{Result, anyhow};
-use std::collections::HashMap;
+use crate::value_objects::{DocumentId, DocumentType, DocumentVersion, ImportOptions, ExportOptions, ImportFormat, ExportFormat, WatermarkPlacement};
+use crate::projections::{DocumentFullView, DocumentHistoryView};
+use crate::queries::CommentsView;
+use super::render_cache::{RenderCache, RenderKey};
+use cid::Cid;
+use encoding_rs::{Encoding, UTF_8};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Why an import or export failed
+///
+/// Converts into `anyhow::Error` with `?` for callers that do not match on it.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportExportError {
+    #[error("Invalid UTF-8 in {kind} content")]
+    InvalidUtf8 { kind: String },
+
+    #[error("Invalid {encoding} in {kind} content")]
+    InvalidEncoding { encoding: String, kind: String },
+
+    #[error("Unknown encoding '{0}'")]
+    UnknownEncoding(String),
+
+    #[error("Unsupported format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("Cannot parse {format} content: {detail}")]
+    ParseError { format: String, detail: String },
+
+    #[error("Cannot serialize export: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Cannot access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Metadata key set on imported documents whose text had characters stripped
+pub const SANITIZED_METADATA_KEY: &str = "sanitized";
+
+/// Strip control characters that break rendering and JSON export
+///
+/// Tabs, newlines and carriage returns are kept, as is U+FFFD, which may be
+/// legitimate content.
+pub fn sanitize_content(text: &str) -> String {
+    text.chars()
+        .filter(|c| matches!(c, '\t' | '\n' | '\r') || !c.is_control())
+        .collect()
+}
+
+/// Decode imported bytes using the character encoding named in `options.encoding`
+///
+/// Encoding labels follow the WHATWG registry, so names such as `latin1`,
+/// `ISO-8859-1` and `Windows-1252` are accepted case-insensitively.
+fn decode_text(content: &[u8], options: &ImportOptions, kind: &str) -> Result<String, ImportExportError> {
+    let encoding = Encoding::for_label(options.encoding.trim().as_bytes())
+        .ok_or_else(|| ImportExportError::UnknownEncoding(options.encoding.clone()))?;
+
+    if encoding == UTF_8 {
+        return String::from_utf8(content.to_vec())
+            .map_err(|_| ImportExportError::InvalidUtf8 { kind: kind.to_string() });
+    }
+
+    let (text, had_errors) = encoding.decode_without_bom_handling(content);
+    if had_errors {
+        return Err(ImportExportError::InvalidEncoding {
+            encoding: encoding.name().to_string(),
+            kind: kind.to_string(),
+        });
+    }
+    Ok(text.into_owned())
+}
 
 /// Import/Export service for documents
 pub struct ImportExportService;
@@ -14,16 +85,91 @@ impl ImportExportService {
         content: &[u8],
         format: &ImportFormat,
         options: &ImportOptions,
-    ) -> Result<ImportedDocument> {
-        match format {
+    ) -> Result<ImportedDocument, ImportExportError> {
+        let mut imported = match format {
             ImportFormat::Markdown => Self::import_markdown(content, options),
             ImportFormat::PlainText => Self::import_plain_text(content, options),
             ImportFormat::Html => Self::import_html(content, options),
             ImportFormat::Json => Self::import_json(content, options),
-            ImportFormat::Pdf => Err(anyhow!("PDF import not yet implemented")),
-            ImportFormat::Word => Err(anyhow!("Word import not yet implemented")),
-            ImportFormat::Custom(fmt) => Err(anyhow!("Custom format '{}' not supported", fmt)),
+            ImportFormat::Pdf => Err(ImportExportError::UnsupportedFormat("PDF".to_string())),
+            ImportFormat::Word => Err(ImportExportError::UnsupportedFormat("Word".to_string())),
+            ImportFormat::Custom(fmt) => Err(ImportExportError::UnsupportedFormat(fmt.clone())),
+        }?;
+
+        let title = sanitize_content(&imported.title);
+        let content = sanitize_content(&imported.content);
+        let tags: Vec<String> = imported.tags
+            .iter()
+            .map(|tag| sanitize_content(tag))
+            .filter(|tag| !tag.trim().is_empty())
+            .collect();
+        if title != imported.title || content != imported.content || tags != imported.tags {
+            imported.title = title;
+            imported.content = content;
+            imported.tags = tags;
+            imported.metadata.insert(SANITIZED_METADATA_KEY.to_string(), "true".to_string());
+        }
+
+        Ok(imported)
+    }
+
+    /// Import every supported file in a directory (non-recursive)
+    ///
+    /// The format is chosen from the file extension; unsupported files are skipped.
+    /// Documents that end up sharing a title are reported as advisory warnings.
+    pub fn import_directory(dir: &Path, options: &ImportOptions) -> Result<DirectoryImport, ImportExportError> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|source| ImportExportError::Io { path: dir.to_path_buf(), source })?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        paths.sort();
+
+        let mut documents = Vec::new();
+        for path in paths {
+            let Some(format) = Self::format_for_path(&path) else {
+                continue;
+            };
+            let content = std::fs::read(&path)
+                .map_err(|source| ImportExportError::Io { path: path.clone(), source })?;
+            let document = Self::import_document(&content, &format, options)?;
+            documents.push(DirectoryImportEntry {
+                document_id: DocumentId::new(),
+                path,
+                document,
+            });
         }
+
+        let warnings = Self::duplicate_title_warnings(&documents);
+        Ok(DirectoryImport { documents, warnings })
+    }
+
+    fn format_for_path(path: &Path) -> Option<ImportFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(ImportFormat::Markdown),
+            "txt" => Some(ImportFormat::PlainText),
+            "html" | "htm" => Some(ImportFormat::Html),
+            "json" => Some(ImportFormat::Json),
+            _ => None,
+        }
+    }
+
+    fn duplicate_title_warnings(documents: &[DirectoryImportEntry]) -> Vec<DuplicateTitleWarning> {
+        let mut by_title: Vec<(String, Vec<DocumentId>)> = Vec::new();
+        for entry in documents {
+            let title = entry.document.title.trim();
+            match by_title.iter_mut().find(|(t, _)| t.as_str() == title) {
+                Some((_, ids)) => ids.push(entry.document_id),
+                None => by_title.push((title.to_string(), vec![entry.document_id])),
+            }
+        }
+
+        by_title
+            .into_iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .map(|(title, ids)| DuplicateTitleWarning { title, ids })
+            .collect()
     }
 
     /// Export document to external format
@@ -31,26 +177,121 @@ impl ImportExportService {
         document: &DocumentFullView,
         format: &ExportFormat,
         options: &ExportOptions,
-    ) -> Result<Vec<u8>> {
+    ) -> Result<Vec<u8>, ImportExportError> {
+        Self::export_document_with_views(document, format, options, ExportViews::default())
+    }
+
+    /// Export document, appending history and comments sections when
+    /// `options.include_history` / `options.include_comments` are set and the
+    /// corresponding view is supplied
+    pub fn export_document_with_views(
+        document: &DocumentFullView,
+        format: &ExportFormat,
+        options: &ExportOptions,
+        views: ExportViews<'_>,
+    ) -> Result<Vec<u8>, ImportExportError> {
+        let views = ExportViews {
+            history: views.history.filter(|_| options.include_history),
+            comments: views.comments.filter(|_| options.include_comments),
+        };
         match format {
-            ExportFormat::Markdown => Self::export_markdown(document, options),
-            ExportFormat::PlainText => Self::export_plain_text(document, options),
-            ExportFormat::Html => Self::export_html(document, options),
-            ExportFormat::Json => Self::export_json(document, options),
-            ExportFormat::Pdf => Err(anyhow!("PDF export not yet implemented")),
-            ExportFormat::Word => Err(anyhow!("Word export not yet implemented")),
-            ExportFormat::Custom(fmt) => Err(anyhow!("Custom format '{}' not supported", fmt)),
+            ExportFormat::Markdown => Self::export_markdown(document, options, views),
+            ExportFormat::PlainText => Self::export_plain_text(document, options, views),
+            ExportFormat::Html => Self::export_html(document, options, views),
+            ExportFormat::Json => Self::export_json(document, options, views),
+            ExportFormat::Pdf => Err(ImportExportError::UnsupportedFormat("PDF".to_string())),
+            ExportFormat::Word => Err(ImportExportError::UnsupportedFormat("Word".to_string())),
+            ExportFormat::Custom(fmt) => Err(ImportExportError::UnsupportedFormat(fmt.clone())),
         }
     }
 
+    /// Export several documents as one tar archive
+    ///
+    /// Each document is stored under a file name derived from its title
+    /// (`-2`, `-3`, … appended on collision) next to a `manifest.json`
+    /// listing ids, titles and file names.
+    pub fn export_collection(
+        docs: &[DocumentFullView],
+        format: &ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<Vec<u8>, ImportExportError> {
+        let extension = export_extension(format);
+        let mut used = HashSet::from([COLLECTION_MANIFEST_FILE.to_string()]);
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut manifest = CollectionManifest { documents: Vec::new() };
+
+        for doc in docs {
+            let exported = Self::export_document(doc, format, options)?;
+            let stem = filename_stem(&doc.title);
+            let mut filename = format!("{stem}.{extension}");
+            let mut suffix = 2;
+            while !used.insert(filename.clone()) {
+                filename = format!("{stem}-{suffix}.{extension}");
+                suffix += 1;
+            }
+
+            append_archive_entry(&mut builder, &filename, &exported)?;
+            manifest.documents.push(CollectionManifestEntry {
+                id: doc.id,
+                title: doc.title.clone(),
+                filename,
+            });
+        }
+
+        append_archive_entry(&mut builder, COLLECTION_MANIFEST_FILE, &serde_json::to_vec_pretty(&manifest)?)?;
+        builder
+            .into_inner()
+            .map_err(|source| ImportExportError::Io { path: PathBuf::from("collection archive"), source })
+    }
+
+    /// Export document, reusing a cached render when content, metadata, format and options are unchanged
+    pub fn export_document_cached(
+        cache: &mut RenderCache,
+        content_cid: Cid,
+        document: &DocumentFullView,
+        format: &ExportFormat,
+        options: &ExportOptions,
+    ) -> Result<Vec<u8>, ImportExportError> {
+        let key = RenderKey::new(document, content_cid, format, options);
+        cache.get_or_render(key, || Self::export_document(document, format, options))
+    }
+
+    /// Export an imported markdown document and import it again
+    ///
+    /// Title, type, tags and content survive unchanged, so repeated round
+    /// trips are idempotent.
+    pub fn roundtrip_markdown(document: &ImportedDocument) -> Result<ImportedDocument, ImportExportError> {
+        let now = chrono::Utc::now();
+        let view = DocumentFullView {
+            id: DocumentId::new(),
+            title: document.title.clone(),
+            content: document.content.clone(),
+            version: DocumentVersion::new(1, 0, 0),
+            doc_type: document.doc_type.clone(),
+            tags: document.tags.clone(),
+            author: uuid::Uuid::nil(),
+            metadata: document.metadata.clone(),
+            created_at: now,
+            updated_at: now,
+        };
+        let options = ExportOptions {
+            include_metadata: true,
+            ..ExportOptions::default()
+        };
+
+        let exported = Self::export_document(&view, &ExportFormat::Markdown, &options)?;
+        Self::import_document(&exported, &ImportFormat::Markdown, &ImportOptions::default())
+    }
+
     // Import implementations
 
-    fn import_markdown(content: &[u8], _options: &ImportOptions) -> Result<ImportedDocument> {
-        let text = String::from_utf8(content.to_vec())
-            .map_err(|_| anyhow!("Invalid UTF-8 in markdown content"))?;
+    fn import_markdown(content: &[u8], options: &ImportOptions) -> Result<ImportedDocument, ImportExportError> {
+        let text = decode_text(content, options, "markdown")?;
 
         let mut metadata = HashMap::new();
         let mut title = "Untitled".to_string();
+        let mut doc_type = ImportFormat::Markdown.document_type();
+        let mut tags = Vec::new();
 
         // Simple markdown parsing
         let lines: Vec<&str> = text.lines().collect();
@@ -58,19 +299,41 @@ impl ImportExportService {
 
         // Check for frontmatter
         if lines.first() == Some(&"---") {
+            let mut in_tags = false;
             for (i, line) in lines.iter().enumerate().skip(1) {
                 if *line == "---" {
                     content_start = i + 1;
                     break;
                 }
+                // Tag list items follow a bare `tags:` line
+                if in_tags {
+                    if let Some(tag) = line.trim().strip_prefix("- ") {
+                        tags.push(tag.trim().to_string());
+                        continue;
+                    }
+                    in_tags = false;
+                }
                 // Parse frontmatter
                 if let Some((key, value)) = line.split_once(':') {
                     let key = key.trim().to_string();
                     let value = value.trim().to_string();
-                    if key == "title" {
-                        title = value;
-                    } else {
-                        metadata.insert(key, value);
+                    match key.as_str() {
+                        "title" => title = value,
+                        "type" => doc_type = DocumentType::from_name(&value),
+                        "tags" if value.is_empty() => in_tags = true,
+                        "tags" => tags.extend(
+                            value
+                                .trim_start_matches('[')
+                                .trim_end_matches(']')
+                                .split(',')
+                                .map(|tag| tag.trim().to_string())
+                                .filter(|tag| !tag.is_empty()),
+                        ),
+                        key if EXPORTER_OWNED_FIELDS.contains(&key) => {}
+                        _ => {
+                            let key = key.strip_prefix(METADATA_KEY_PREFIX).unwrap_or(&key).to_string();
+                            metadata.insert(key, value);
+                        }
                     }
                 }
             }
@@ -86,21 +349,30 @@ impl ImportExportService {
             }
         }
 
-        // Build body
-        let body = lines[content_start..].join("\n");
+        // Build body, without the title heading export adds back
+        let mut body_lines = &lines[content_start..];
+        while body_lines.first().is_some_and(|line| line.trim().is_empty()) {
+            body_lines = &body_lines[1..];
+        }
+        if body_lines.first().and_then(|line| line.trim().strip_prefix("# ")).map(str::trim) == Some(title.as_str()) {
+            body_lines = &body_lines[1..];
+            while body_lines.first().is_some_and(|line| line.trim().is_empty()) {
+                body_lines = &body_lines[1..];
+            }
+        }
+        let body = body_lines.join("\n");
 
         Ok(ImportedDocument {
             title,
             content: body,
-            doc_type: DocumentType::Report,
+            doc_type,
             metadata,
-            tags: vec![],
+            tags,
         })
     }
 
-    fn import_plain_text(content: &[u8], _options: &ImportOptions) -> Result<ImportedDocument> {
-        let text = String::from_utf8(content.to_vec())
-            .map_err(|_| anyhow!("Invalid UTF-8 in text content"))?;
+    fn import_plain_text(content: &[u8], options: &ImportOptions) -> Result<ImportedDocument, ImportExportError> {
+        let text = decode_text(content, options, "text")?;
 
         // Extract title from first line
         let lines: Vec<&str> = text.lines().collect();
@@ -117,15 +389,14 @@ impl ImportExportService {
         Ok(ImportedDocument {
             title,
             content: body,
-            doc_type: DocumentType::Note,
+            doc_type: ImportFormat::PlainText.document_type(),
             metadata: HashMap::new(),
             tags: vec![],
         })
     }
 
-    fn import_html(content: &[u8], _options: &ImportOptions) -> Result<ImportedDocument> {
-        let html = String::from_utf8(content.to_vec())
-            .map_err(|_| anyhow!("Invalid UTF-8 in HTML content"))?;
+    fn import_html(content: &[u8], options: &ImportOptions) -> Result<ImportedDocument, ImportExportError> {
+        let html = decode_text(content, options, "HTML")?;
 
         // Very basic HTML extraction (production would use proper parser)
         let title = if let Some(start) = html.find("<title>") {
@@ -153,14 +424,17 @@ impl ImportExportService {
         Ok(ImportedDocument {
             title,
             content,
-            doc_type: DocumentType::Article,
+            doc_type: ImportFormat::Html.document_type(),
             metadata: HashMap::new(),
             tags: vec![],
         })
     }
 
-    fn import_json(content: &[u8], _options: &ImportOptions) -> Result<ImportedDocument> {
-        let json: serde_json::Value = serde_json::from_slice(content)?;
+    fn import_json(content: &[u8], _options: &ImportOptions) -> Result<ImportedDocument, ImportExportError> {
+        let json: serde_json::Value = serde_json::from_slice(content).map_err(|e| ImportExportError::ParseError {
+            format: "JSON".to_string(),
+            detail: e.to_string(),
+        })?;
 
         let title = json.get("title")
             .and_then(|v| v.as_str())
@@ -211,7 +485,7 @@ impl ImportExportService {
 
     // Export implementations
 
-    fn export_markdown(document: &DocumentFullView, options: &ExportOptions) -> Result<Vec<u8>> {
+    fn export_markdown(document: &DocumentFullView, options: &ExportOptions, views: ExportViews<'_>) -> Result<Vec<u8>, ImportExportError> {
         let mut output = String::new();
 
         // Add frontmatter if metadata included
@@ -219,7 +493,7 @@ impl ImportExportService {
             output.push_str("---\n");
             output.push_str(&format!("title: {}\n", document.title));
             output.push_str(&format!("version: {}\n", document.version));
-            output.push_str(&format!("type: {:?}\n", document.doc_type));
+            output.push_str(&format!("type: {}\n", document.doc_type.as_str()));
             output.push_str(&format!("created: {}\n", document.created_at.format("%Y-%m-%d")));
             output.push_str(&format!("updated: {}\n", document.updated_at.format("%Y-%m-%d")));
             
@@ -230,29 +504,90 @@ impl ImportExportService {
                 }
             }
 
-            for (key, value) in &document.metadata {
-                output.push_str(&format!("{key}: {value}\n"));
+            let mut keys: Vec<&String> = document.metadata.keys().collect();
+            keys.sort();
+            for key in keys {
+                // Escape keys the importer would otherwise read as frontmatter fields
+                let escape = FRONTMATTER_FIELDS.contains(&key.as_str()) || key.starts_with(METADATA_KEY_PREFIX);
+                let prefix = if escape { METADATA_KEY_PREFIX } else { "" };
+                output.push_str(&format!("{prefix}{key}: {}\n", document.metadata[key]));
             }
             
             output.push_str("---\n\n");
         }
 
-        // Add title
-        output.push_str(&format!("# {}\n\n", document.title));
+        let placement = options.watermark_placement();
+        match (&options.watermark, placement) {
+            (Some(watermark), WatermarkPlacement::Header) => output.push_str(&format!("> {watermark}\n\n")),
+            (Some(watermark), WatermarkPlacement::Diagonal) => {
+                output.push_str(&diagonal_watermark_html(watermark));
+                output.push_str("\n\n");
+            }
+            _ => {}
+        }
+
+        // Add title unless the content already opens with it
+        if leading_title(&document.content) != Some(document.title.as_str()) {
+            output.push_str(&format!("# {}\n\n", document.title));
+        }
 
         // Add content
-        output.push_str(&document.content);
+        match (&options.watermark, placement) {
+            (Some(watermark), WatermarkPlacement::EveryParagraph) => {
+                let paragraphs: Vec<String> = document
+                    .content
+                    .split("\n\n")
+                    .map(|paragraph| format!("{paragraph}\n\n*{watermark}*"))
+                    .collect();
+                output.push_str(&paragraphs.join("\n\n"));
+            }
+            _ => output.push_str(&document.content),
+        }
+
+        if let Some(history) = views.history {
+            output.push_str("\n\n## Version History\n");
+            for entry in &history.versions {
+                output.push_str(&format!(
+                    "\n- **{}** ({}): {}",
+                    entry.version,
+                    entry.timestamp.format("%Y-%m-%d"),
+                    entry.summary
+                ));
+            }
+        }
+
+        if let Some(comments) = views.comments {
+            output.push_str("\n\n## Comments\n");
+            for comment in &comments.comments {
+                output.push_str(&format!(
+                    "\n- **{}** ({}){}: {}",
+                    comment.author_id,
+                    comment.created_at.format("%Y-%m-%d"),
+                    if comment.resolved { " [resolved]" } else { "" },
+                    comment.content
+                ));
+            }
+        }
 
         // Add watermark if specified
-        if let Some(watermark) = &options.watermark {
+        if let (Some(watermark), WatermarkPlacement::Footer) = (&options.watermark, placement) {
             output.push_str(&format!("\n\n---\n*{watermark}*"));
         }
 
         Ok(output.into_bytes())
     }
 
-    fn export_plain_text(document: &DocumentFullView, options: &ExportOptions) -> Result<Vec<u8>> {
+    fn export_plain_text(document: &DocumentFullView, options: &ExportOptions, views: ExportViews<'_>) -> Result<Vec<u8>, ImportExportError> {
         let mut output = String::new();
+        // Plain text cannot be positioned, so a diagonal watermark becomes a footer
+        let placement = match options.watermark_placement() {
+            WatermarkPlacement::Diagonal => WatermarkPlacement::Footer,
+            placement => placement,
+        };
+
+        if let (Some(watermark), WatermarkPlacement::Header) = (&options.watermark, placement) {
+            output.push_str(&format!("{watermark}\n\n"));
+        }
 
         output.push_str(&document.title);
         output.push('\n');
@@ -265,16 +600,51 @@ impl ImportExportService {
             output.push_str(&format!("Updated: {}\n\n", document.updated_at.format("%Y-%m-%d")));
         }
 
-        output.push_str(&document.content);
+        match (&options.watermark, placement) {
+            (Some(watermark), WatermarkPlacement::EveryParagraph) => {
+                let paragraphs: Vec<String> = document
+                    .content
+                    .split("\n\n")
+                    .map(|paragraph| format!("{paragraph}\n[{watermark}]"))
+                    .collect();
+                output.push_str(&paragraphs.join("\n\n"));
+            }
+            _ => output.push_str(&document.content),
+        }
 
-        if let Some(watermark) = &options.watermark {
+        if let Some(history) = views.history {
+            output.push_str("\n\nVersion History\n---------------");
+            for entry in &history.versions {
+                output.push_str(&format!(
+                    "\n{}  {}  {}",
+                    entry.version,
+                    entry.timestamp.format("%Y-%m-%d"),
+                    entry.summary
+                ));
+            }
+        }
+
+        if let Some(comments) = views.comments {
+            output.push_str("\n\nComments\n--------");
+            for comment in &comments.comments {
+                output.push_str(&format!(
+                    "\n{}  {}{}  {}",
+                    comment.created_at.format("%Y-%m-%d"),
+                    comment.author_id,
+                    if comment.resolved { " (resolved)" } else { "" },
+                    comment.content
+                ));
+            }
+        }
+
+        if let (Some(watermark), WatermarkPlacement::Footer) = (&options.watermark, placement) {
             output.push_str(&format!("\n\n{watermark}"));
         }
 
         Ok(output.into_bytes())
     }
 
-    fn export_html(document: &DocumentFullView, options: &ExportOptions) -> Result<Vec<u8>> {
+    fn export_html(document: &DocumentFullView, options: &ExportOptions, views: ExportViews<'_>) -> Result<Vec<u8>, ImportExportError> {
         let mut output = String::new();
 
         output.push_str("<!DOCTYPE html>\n<html>\n<head>\n");
@@ -290,18 +660,75 @@ impl ImportExportService {
         }
         
         output.push_str("</head>\n<body>\n");
+
+        let placement = options.watermark_placement();
+        match (&options.watermark, placement) {
+            (Some(watermark), WatermarkPlacement::Header) => output.push_str(&format!(
+                "  <div class=\"watermark watermark-header\"><em>{}</em></div>\n",
+                html_escape(watermark)
+            )),
+            (Some(watermark), WatermarkPlacement::Diagonal) => {
+                output.push_str("  ");
+                output.push_str(&diagonal_watermark_html(watermark));
+                output.push('\n');
+            }
+            _ => {}
+        }
+
         output.push_str(&format!("  <h1>{}</h1>\n", html_escape(&document.title)));
         
-        // Convert markdown-style content to basic HTML
-        let html_content = document.content
-            .replace("\n\n", "</p>\n  <p>")
-            .replace("\n", "<br>\n");
-        
-        output.push_str("  <p>");
-        output.push_str(&html_content);
-        output.push_str("</p>\n");
+        match (&options.watermark, placement) {
+            (Some(watermark), WatermarkPlacement::EveryParagraph) => {
+                for paragraph in document.content.split("\n\n") {
+                    output.push_str(&format!(
+                        "  <p>{}</p>\n  <p class=\"watermark\"><em>{}</em></p>\n",
+                        paragraph.replace('\n', "<br>\n"),
+                        html_escape(watermark)
+                    ));
+                }
+            }
+            _ => {
+                // Convert markdown-style content to basic HTML
+                let html_content = document.content
+                    .replace("\n\n", "</p>\n  <p>")
+                    .replace("\n", "<br>\n");
+
+                output.push_str("  <p>");
+                output.push_str(&html_content);
+                output.push_str("</p>\n");
+            }
+        }
 
-        if let Some(watermark) = &options.watermark {
+        if let Some(history) = views.history {
+            output.push_str("  <section class=\"version-history\">\n    <h2>Version History</h2>\n    <ul>\n");
+            for entry in &history.versions {
+                output.push_str(&format!(
+                    "      <li><strong>{}</strong> <time datetime=\"{}\">{}</time>: {}</li>\n",
+                    entry.version,
+                    entry.timestamp.to_rfc3339(),
+                    entry.timestamp.format("%Y-%m-%d"),
+                    html_escape(&entry.summary)
+                ));
+            }
+            output.push_str("    </ul>\n  </section>\n");
+        }
+
+        if let Some(comments) = views.comments {
+            output.push_str("  <section class=\"comments\">\n    <h2>Comments</h2>\n    <ul>\n");
+            for comment in &comments.comments {
+                output.push_str(&format!(
+                    "      <li{}><strong>{}</strong> <time datetime=\"{}\">{}</time>: {}</li>\n",
+                    if comment.resolved { " class=\"resolved\"" } else { "" },
+                    comment.author_id,
+                    comment.created_at.to_rfc3339(),
+                    comment.created_at.format("%Y-%m-%d"),
+                    html_escape(&comment.content)
+                ));
+            }
+            output.push_str("    </ul>\n  </section>\n");
+        }
+
+        if let (Some(watermark), WatermarkPlacement::Footer) = (&options.watermark, placement) {
             output.push_str(&format!("  <hr>\n  <p><em>{}</em></p>\n", html_escape(watermark)));
         }
 
@@ -310,7 +737,7 @@ impl ImportExportService {
         Ok(output.into_bytes())
     }
 
-    fn export_json(document: &DocumentFullView, options: &ExportOptions) -> Result<Vec<u8>> {
+    fn export_json(document: &DocumentFullView, options: &ExportOptions, views: ExportViews<'_>) -> Result<Vec<u8>, ImportExportError> {
         let mut json = serde_json::json!({
             "title": document.title,
             "content": document.content,
@@ -326,14 +753,49 @@ impl ImportExportService {
             json["metadata"] = serde_json::json!(document.metadata);
         }
 
+        if let Some(history) = views.history {
+            json["history"] = serde_json::json!(history.versions);
+        }
+
+        if let Some(comments) = views.comments {
+            json["comments"] = serde_json::json!(comments.comments);
+        }
+
         if let Some(watermark) = &options.watermark {
             json["watermark"] = serde_json::json!(watermark);
+            json["watermark_placement"] = serde_json::json!(options.watermark_placement().as_str());
         }
 
         serde_json::to_vec_pretty(&json).map_err(Into::into)
     }
 }
 
+/// Optional views exported alongside a document
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportViews<'a> {
+    /// Rendered as a version-history section when `include_history` is set
+    pub history: Option<&'a DocumentHistoryView>,
+    /// Rendered as a comments section when `include_comments` is set
+    pub comments: Option<&'a CommentsView>,
+}
+
+/// Name of the manifest entry in a collection archive
+pub const COLLECTION_MANIFEST_FILE: &str = "manifest.json";
+
+/// Contents of a collection archive's `manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionManifest {
+    pub documents: Vec<CollectionManifestEntry>,
+}
+
+/// A document stored in a collection archive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionManifestEntry {
+    pub id: DocumentId,
+    pub title: String,
+    pub filename: String,
+}
+
 /// Imported document structure
 #[derive(Debug, Clone)]
 pub struct ImportedDocument {
@@ -344,6 +806,96 @@ pub struct ImportedDocument {
     pub tags: Vec<String>,
 }
 
+/// Result of importing a directory
+#[derive(Debug, Clone)]
+pub struct DirectoryImport {
+    pub documents: Vec<DirectoryImportEntry>,
+    pub warnings: Vec<DuplicateTitleWarning>,
+}
+
+/// A single file imported from a directory
+#[derive(Debug, Clone)]
+pub struct DirectoryImportEntry {
+    pub document_id: DocumentId,
+    pub path: PathBuf,
+    pub document: ImportedDocument,
+}
+
+/// Several imported documents share the same title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateTitleWarning {
+    pub title: String,
+    pub ids: Vec<DocumentId>,
+}
+
+/// Fixed-position watermark rotated across the page
+fn diagonal_watermark_html(watermark: &str) -> String {
+    format!(
+        "<div class=\"watermark watermark-diagonal\" style=\"position: fixed; top: 50%; left: 50%; \
+         transform: translate(-50%, -50%) rotate(-45deg); opacity: 0.15; font-size: 6em; \
+         pointer-events: none; z-index: 1000;\">{}</div>",
+        html_escape(watermark)
+    )
+}
+
+/// File extension for documents exported in `format`
+fn export_extension(format: &ExportFormat) -> &str {
+    match format {
+        ExportFormat::Markdown => "md",
+        ExportFormat::PlainText => "txt",
+        ExportFormat::Html => "html",
+        ExportFormat::Json => "json",
+        ExportFormat::Pdf => "pdf",
+        ExportFormat::Word => "docx",
+        ExportFormat::Custom(fmt) => fmt,
+    }
+}
+
+/// File-system safe name derived from a title
+fn filename_stem(title: &str) -> String {
+    let mut stem = String::new();
+    for c in title.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            stem.push(c);
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    let stem: String = stem.trim_end_matches('-').chars().take(80).collect();
+    if stem.is_empty() {
+        "untitled".to_string()
+    } else {
+        stem
+    }
+}
+
+fn append_archive_entry(builder: &mut tar::Builder<Vec<u8>>, path: &str, data: &[u8]) -> Result<(), ImportExportError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    builder
+        .append_data(&mut header, path, data)
+        .map_err(|source| ImportExportError::Io { path: PathBuf::from(path), source })
+}
+
+/// Frontmatter fields written by the markdown exporter itself
+const FRONTMATTER_FIELDS: &[&str] = &["title", "version", "type", "created", "updated", "tags"];
+
+/// Frontmatter fields the markdown exporter derives from the document; ignored on import
+const EXPORTER_OWNED_FIELDS: &[&str] = &["version", "created", "updated"];
+
+/// Prefix escaping metadata keys that collide with frontmatter fields
+const METADATA_KEY_PREFIX: &str = "meta.";
+
+/// Title of a leading `# ` heading, ignoring blank lines before it
+fn leading_title(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.trim().strip_prefix("# "))
+        .map(str::trim)
+}
+
 fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;")
         .replace('<', "&lt;")
@@ -355,7 +907,6 @@ fn html_escape(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value_objects::DocumentVersion;
     use uuid::Uuid;
 
     // Helper functions for test data
@@ -465,8 +1016,10 @@ More content here."#;
             &ImportOptions::default(),
         );
 
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Invalid UTF-8"));
+        assert!(matches!(
+            result,
+            Err(ImportExportError::InvalidUtf8 { kind }) if kind == "markdown"
+        ));
     }
 
     #[test]
@@ -489,6 +1042,101 @@ With multiple lines of text."#;
         assert!(imported.tags.is_empty());
     }
 
+    fn options_with_encoding(encoding: &str) -> ImportOptions {
+        ImportOptions {
+            encoding: encoding.to_string(),
+            ..ImportOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_import_latin1_plain_text() {
+        // "Café\nCrème brûlée" encoded as ISO-8859-1
+        let bytes = b"Caf\xe9\nCr\xe8me br\xfbl\xe9e";
+
+        let imported = ImportExportService::import_document(
+            bytes,
+            &ImportFormat::PlainText,
+            &options_with_encoding("ISO-8859-1"),
+        ).unwrap();
+
+        assert_eq!(imported.title, "Café");
+        assert_eq!(imported.content, "Crème brûlée");
+    }
+
+    #[test]
+    fn test_import_windows_1252_markdown() {
+        let bytes = b"# Na\xefve r\xe9sum\xe9\n\nSmart \x93quotes\x94";
+
+        let imported = ImportExportService::import_document(
+            bytes,
+            &ImportFormat::Markdown,
+            &options_with_encoding("windows-1252"),
+        ).unwrap();
+
+        assert_eq!(imported.title, "Naïve résumé");
+        assert!(imported.content.contains("Smart \u{201C}quotes\u{201D}"));
+    }
+
+    #[test]
+    fn test_import_unknown_encoding_errors() {
+        let result = ImportExportService::import_document(
+            b"Title\nBody",
+            &ImportFormat::PlainText,
+            &options_with_encoding("klingon-8"),
+        );
+
+        assert!(matches!(result, Err(ImportExportError::UnknownEncoding(label)) if label == "klingon-8"));
+    }
+
+    #[test]
+    fn test_sanitize_content_strips_control_characters() {
+        assert_eq!(sanitize_content("a\0b\x0Bc"), "abc");
+        assert_eq!(sanitize_content("col1\tcol2\nline\r\n"), "col1\tcol2\nline\r\n");
+        assert_eq!(sanitize_content("kept \u{FFFD} replacement"), "kept \u{FFFD} replacement");
+        assert_eq!(sanitize_content("clean text"), "clean text");
+    }
+
+    #[test]
+    fn test_import_sanitizes_and_records_metadata() {
+        let text = "Title\0\nBody\x0B with\ttab\nand newline";
+
+        let imported = ImportExportService::import_document(
+            text.as_bytes(),
+            &ImportFormat::PlainText,
+            &ImportOptions::default(),
+        ).unwrap();
+
+        assert_eq!(imported.title, "Title");
+        assert_eq!(imported.content, "Body with\ttab\nand newline");
+        assert_eq!(imported.metadata.get(SANITIZED_METADATA_KEY).map(String::as_str), Some("true"));
+    }
+
+    #[test]
+    fn test_import_clean_content_not_marked_sanitized() {
+        let imported = ImportExportService::import_document(
+            b"Title\nBody\twith tab",
+            &ImportFormat::PlainText,
+            &ImportOptions::default(),
+        ).unwrap();
+
+        assert!(!imported.metadata.contains_key(SANITIZED_METADATA_KEY));
+    }
+
+    #[test]
+    fn test_import_sanitizes_tags() {
+        let json = r#"{"content": "Body", "tags": ["fin\u0000ance", "\u0007", "caf\ufffd"]}"#;
+
+        let imported = ImportExportService::import_document(
+            json.as_bytes(),
+            &ImportFormat::Json,
+            &ImportOptions::default(),
+        ).unwrap();
+
+        assert_eq!(imported.tags, vec!["finance", "caf\u{FFFD}"]);
+        assert_eq!(imported.metadata.get(SANITIZED_METADATA_KEY).map(String::as_str), Some("true"));
+    }
+
     #[test]
     fn test_import_plain_text_single_line() {
         // US-020: Test plain text import with single line
@@ -634,7 +1282,10 @@ With multiple lines of text."#;
             &ImportOptions::default(),
         );
 
-        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(ImportExportError::ParseError { format, .. }) if format == "JSON"
+        ));
     }
 
     #[test]
@@ -675,7 +1326,7 @@ With multiple lines of text."#;
                 &format,
                 &ImportOptions::default(),
             );
-            assert!(result.is_err());
+            assert!(matches!(result, Err(ImportExportError::UnsupportedFormat(_))));
         }
     }
 
@@ -693,7 +1344,7 @@ With multiple lines of text."#;
         assert!(result.contains("---"));
         assert!(result.contains("title: Test Document"));
         assert!(result.contains("version: 1.2.3"));
-        assert!(result.contains("type: Article"));
+        assert!(result.contains("type: article"));
         assert!(result.contains("created: 2023-01-01"));
         assert!(result.contains("updated: 2023-01-02"));
         assert!(result.contains("tags:"));
@@ -908,7 +1559,7 @@ With multiple lines of text."#;
 
         for format in unsupported_formats {
             let result = ImportExportService::export_document(&doc, &format, &options);
-            assert!(result.is_err());
+            assert!(matches!(result, Err(ImportExportError::UnsupportedFormat(_))));
         }
     }
 
@@ -969,6 +1620,136 @@ With multiple lines of text."#;
         assert!(imported.content.contains(&doc.content));
     }
 
+    #[test]
+    fn test_markdown_round_trip_is_idempotent() {
+        let samples = [
+            "---\ntitle: Quarterly Report\ntype: Report\ntags:\n  - finance\n  - q3\nowner: ops\n---\n\n# Quarterly Report\n\nRevenue rose.\n\n## Costs\n\nFlat.",
+            "# Meeting Notes\n\nMonday standup.\n\n- item one\n- item two",
+            "---\ntitle: Frontmatter Only\ntype: Note\n---\n\nNo heading in the body.",
+            "Just a paragraph without any heading.",
+            "---\ntitle: Memo\ntype: Other(\"memo\")\ntags: [internal, draft]\n---\nIntro first.\n\n# Memo\n\nHeading is not leading.",
+        ];
+
+        for sample in samples {
+            let imported = ImportExportService::import_document(
+                sample.as_bytes(),
+                &ImportFormat::Markdown,
+                &ImportOptions::default(),
+            ).unwrap();
+
+            let once = ImportExportService::roundtrip_markdown(&imported).unwrap();
+            let twice = ImportExportService::roundtrip_markdown(&once).unwrap();
+
+            for round_tripped in [&once, &twice] {
+                assert_eq!(round_tripped.title, imported.title, "title drifted for {sample:?}");
+                assert_eq!(round_tripped.doc_type, imported.doc_type, "type drifted for {sample:?}");
+                assert_eq!(round_tripped.tags, imported.tags, "tags drifted for {sample:?}");
+                assert_eq!(round_tripped.content, imported.content, "content drifted for {sample:?}");
+                assert_eq!(round_tripped.metadata, imported.metadata, "metadata drifted for {sample:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_markdown_frontmatter_type_and_tags_are_imported() {
+        let markdown = "---\ntitle: Memo\ntype: Other(\"memo\")\ntags:\n  - internal\n  - draft\n---\n\n# Memo\n\nBody.";
+
+        let imported = ImportExportService::import_document(
+            markdown.as_bytes(),
+            &ImportFormat::Markdown,
+            &ImportOptions::default(),
+        ).unwrap();
+
+        assert_eq!(imported.doc_type, DocumentType::Other("memo".to_string()));
+        assert_eq!(imported.tags, vec!["internal".to_string(), "draft".to_string()]);
+        assert_eq!(imported.content, "Body.");
+        assert!(imported.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_markdown_metadata_colliding_with_frontmatter_round_trips() {
+        let mut doc = create_test_document();
+        doc.metadata.insert("version".to_string(), "draft-7".to_string());
+        doc.metadata.insert("title".to_string(), "Internal title".to_string());
+        doc.metadata.insert("meta.source".to_string(), "crm".to_string());
+
+        let exported = ImportExportService::export_document(
+            &doc,
+            &ExportFormat::Markdown,
+            &create_export_options(true, None),
+        ).unwrap();
+        let markdown = String::from_utf8(exported.clone()).unwrap();
+        assert!(markdown.contains("version: 1.2.3\n"));
+        assert!(markdown.contains("meta.version: draft-7\n"));
+        assert!(markdown.contains("meta.meta.source: crm\n"));
+
+        let imported = ImportExportService::import_document(
+            &exported,
+            &ImportFormat::Markdown,
+            &ImportOptions::default(),
+        ).unwrap();
+        assert_eq!(imported.title, doc.title);
+        assert_eq!(imported.doc_type, DocumentType::Article);
+        assert_eq!(imported.metadata, doc.metadata);
+    }
+
+    #[test]
+    fn test_markdown_export_does_not_duplicate_title() {
+        let mut doc = create_test_document();
+        doc.content = format!("# {}\n\n{}", doc.title, doc.content);
+
+        for include_metadata in [true, false] {
+            let exported = ImportExportService::export_document(
+                &doc,
+                &ExportFormat::Markdown,
+                &create_export_options(include_metadata, None),
+            ).unwrap();
+            let markdown = String::from_utf8(exported).unwrap();
+
+            assert_eq!(markdown.lines().filter(|line| *line == "# Test Document").count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_export_collection_bundles_documents_with_manifest() {
+        let first = create_test_document();
+        let mut second = create_test_document();
+        second.id = DocumentId::new();
+        second.content = "Another document with the same title.".to_string();
+        let mut third = create_test_document();
+        third.id = DocumentId::new();
+        third.title = "Q3 Plan: Draft / v2".to_string();
+        let docs = vec![first.clone(), second.clone(), third.clone()];
+
+        let archive = ImportExportService::export_collection(
+            &docs,
+            &ExportFormat::Markdown,
+            &create_export_options(false, None),
+        ).unwrap();
+
+        let mut entries = HashMap::new();
+        for entry in tar::Archive::new(archive.as_slice()).entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let path = entry.path().unwrap().to_string_lossy().into_owned();
+            let mut data = Vec::new();
+            std::io::Read::read_to_end(&mut entry, &mut data).unwrap();
+            entries.insert(path, data);
+        }
+        assert_eq!(entries.len(), 4);
+
+        let manifest: CollectionManifest = serde_json::from_slice(&entries[COLLECTION_MANIFEST_FILE]).unwrap();
+        let filenames: Vec<&str> = manifest.documents.iter().map(|d| d.filename.as_str()).collect();
+        assert_eq!(filenames, vec!["test-document.md", "test-document-2.md", "q3-plan-draft-v2.md"]);
+        for (entry, doc) in manifest.documents.iter().zip(&docs) {
+            assert_eq!(entry.id, doc.id);
+            assert_eq!(entry.title, doc.title);
+            assert!(entries.contains_key(&entry.filename));
+        }
+
+        let second_export = String::from_utf8(entries["test-document-2.md"].clone()).unwrap();
+        assert!(second_export.contains("Another document with the same title."));
+    }
+
     #[test]
     fn test_round_trip_json() {
         // US-019: Test round-trip JSON conversion
@@ -1052,4 +1833,312 @@ With multiple lines of text."#;
             assert!(result.is_ok());
         }
     }
-} 
\ No newline at end of file
+
+    // DIRECTORY IMPORT TESTS
+
+    fn create_import_dir(files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cim-import-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_import_directory_warns_on_duplicate_titles() {
+        let dir = create_import_dir(&[
+            ("a.md", "# Meeting Notes\n\nMonday"),
+            ("b.txt", "Meeting Notes\nTuesday"),
+            ("c.md", "# Roadmap\n\nQ4"),
+            ("ignored.bin", "binary"),
+        ]);
+
+        let result = ImportExportService::import_directory(&dir, &ImportOptions::default()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.documents.len(), 3);
+        assert_eq!(result.warnings.len(), 1);
+        let warning = &result.warnings[0];
+        assert_eq!(warning.title, "Meeting Notes");
+        assert_eq!(warning.ids, vec![result.documents[0].document_id, result.documents[1].document_id]);
+    }
+
+    #[test]
+    fn test_import_directory_unique_titles_no_warning() {
+        let dir = create_import_dir(&[
+            ("a.md", "# First"),
+            ("b.md", "# Second"),
+        ]);
+
+        let result = ImportExportService::import_directory(&dir, &ImportOptions::default()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.documents.len(), 2);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_import_directory_missing_dir_is_io_error() {
+        let dir = std::env::temp_dir().join(format!("cim-import-missing-{}", Uuid::new_v4()));
+
+        let error = ImportExportService::import_directory(&dir, &ImportOptions::default()).unwrap_err();
+        assert!(matches!(&error, ImportExportError::Io { path, .. } if path == &dir));
+
+        let error = anyhow::Error::from(error);
+        assert!(error.downcast_ref::<ImportExportError>().is_some());
+    }
+
+    // RENDER CACHE TESTS
+
+    fn create_test_cid() -> Cid {
+        Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap()
+    }
+
+    #[test]
+    fn test_export_cached_serves_repeat_from_cache() {
+        let mut cache = RenderCache::new();
+        let document = create_test_document();
+        let options = create_export_options(true, None);
+
+        let first = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown, &options,
+        ).unwrap();
+        let second = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown, &options,
+        ).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_export_cached_misses_on_changed_options() {
+        let mut cache = RenderCache::new();
+        let document = create_test_document();
+
+        ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown,
+            &create_export_options(true, None),
+        ).unwrap();
+        let watermarked = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown,
+            &create_export_options(true, Some("DRAFT".to_string())),
+        ).unwrap();
+
+        assert!(String::from_utf8(watermarked).unwrap().contains("DRAFT"));
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_export_cached_invalidates_on_new_content_cid() {
+        let mut cache = RenderCache::new();
+        let mut document = create_test_document();
+        let options = create_export_options(false, None);
+
+        ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::PlainText, &options,
+        ).unwrap();
+
+        document.content = "Revised content".to_string();
+        let revised = ImportExportService::export_document_cached(
+            &mut cache, Cid::default(), &document, &ExportFormat::PlainText, &options,
+        ).unwrap();
+
+        assert!(String::from_utf8(revised).unwrap().contains("Revised content"));
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_export_cached_keeps_documents_apart() {
+        let mut cache = RenderCache::new();
+        let options = create_export_options(true, None);
+        let report = create_test_document();
+        let mut copy = report.clone();
+        copy.id = DocumentId::new();
+        copy.title = "Copy of Report".to_string();
+
+        let rendered_report = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &report, &ExportFormat::Markdown, &options,
+        ).unwrap();
+        let rendered_copy = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &copy, &ExportFormat::Markdown, &options,
+        ).unwrap();
+
+        assert!(String::from_utf8(rendered_copy).unwrap().contains("Copy of Report"));
+        assert_eq!(cache.misses(), 2);
+
+        // A new revision of one document leaves the other's render cached
+        copy.content = "Revised content".to_string();
+        ImportExportService::export_document_cached(
+            &mut cache, Cid::default(), &copy, &ExportFormat::Markdown, &options,
+        ).unwrap();
+        let again = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &report, &ExportFormat::Markdown, &options,
+        ).unwrap();
+        assert_eq!(again, rendered_report);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_export_cached_misses_on_metadata_change() {
+        let mut cache = RenderCache::new();
+        let mut document = create_test_document();
+        let options = create_export_options(true, None);
+
+        ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown, &options,
+        ).unwrap();
+        document.title = "Renamed".to_string();
+        let renamed = ImportExportService::export_document_cached(
+            &mut cache, create_test_cid(), &document, &ExportFormat::Markdown, &options,
+        ).unwrap();
+
+        assert!(String::from_utf8(renamed).unwrap().contains("Renamed"));
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.len(), 1);
+    }
+
+    fn history_and_comments(document: &DocumentFullView) -> (DocumentHistoryView, CommentsView) {
+        use crate::projections::VersionEntry;
+        use crate::value_objects::Comment;
+
+        let at = |day: u32| chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2023, 1, day, 0, 0, 0).unwrap();
+        let history = DocumentHistoryView {
+            document_id: document.id,
+            versions: vec![
+                VersionEntry {
+                    version: DocumentVersion::new(1, 0, 0),
+                    summary: "Initial draft".to_string(),
+                    author: document.author,
+                    timestamp: at(1),
+                },
+                VersionEntry {
+                    version: DocumentVersion::new(1, 2, 3),
+                    summary: "Tightened <intro>".to_string(),
+                    author: document.author,
+                    timestamp: at(2),
+                },
+            ],
+        };
+        let comment = Comment {
+            id: Uuid::new_v4(),
+            content: "Please cite sources".to_string(),
+            author_id: Uuid::new_v4(),
+            block_id: None,
+            parent_id: None,
+            created_at: at(2),
+            resolved: false,
+        };
+        let comments = CommentsView {
+            document_id: document.id,
+            comments: vec![comment],
+            total_count: 1,
+            unresolved_count: 1,
+        };
+        (history, comments)
+    }
+
+    fn export_with_views(format: ExportFormat, include: bool) -> String {
+        let document = create_test_document();
+        let (history, comments) = history_and_comments(&document);
+        let options = ExportOptions {
+            include_history: include,
+            include_comments: include,
+            ..create_export_options(false, None)
+        };
+        let views = ExportViews {
+            history: Some(&history),
+            comments: Some(&comments),
+        };
+        let exported = ImportExportService::export_document_with_views(&document, &format, &options, views).unwrap();
+        String::from_utf8(exported).unwrap()
+    }
+
+    #[test]
+    fn test_export_includes_history_and_comments_when_enabled() {
+        let markdown = export_with_views(ExportFormat::Markdown, true);
+        assert!(markdown.contains("## Version History\n\n- **1.0.0** (2023-01-01): Initial draft"));
+        assert!(markdown.contains("## Comments"));
+        assert!(markdown.contains("Please cite sources"));
+
+        let html = export_with_views(ExportFormat::Html, true);
+        assert!(html.contains("<section class=\"version-history\">"));
+        assert!(html.contains("Tightened &lt;intro&gt;"));
+        assert!(html.contains("<section class=\"comments\">"));
+
+        let json: serde_json::Value = serde_json::from_str(&export_with_views(ExportFormat::Json, true)).unwrap();
+        assert_eq!(json["history"].as_array().unwrap().len(), 2);
+        assert_eq!(json["comments"][0]["content"], "Please cite sources");
+
+        let text = export_with_views(ExportFormat::PlainText, true);
+        assert!(text.contains("Version History"));
+        assert!(text.contains("Comments"));
+    }
+
+    #[test]
+    fn test_export_omits_history_and_comments_when_disabled() {
+        for format in [ExportFormat::Markdown, ExportFormat::Html, ExportFormat::PlainText] {
+            let exported = export_with_views(format, false);
+            assert!(!exported.contains("Version History"));
+            assert!(!exported.contains("Please cite sources"));
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&export_with_views(ExportFormat::Json, false)).unwrap();
+        assert!(json.get("history").is_none());
+        assert!(json.get("comments").is_none());
+    }
+
+    fn export_watermarked(format: ExportFormat, placement: &str) -> String {
+        let mut options = create_export_options(false, Some("CONFIDENTIAL".to_string()));
+        options.custom_options.insert(
+            crate::value_objects::WATERMARK_PLACEMENT_OPTION.to_string(),
+            placement.to_string(),
+        );
+        let exported = ImportExportService::export_document(&create_test_document(), &format, &options).unwrap();
+        String::from_utf8(exported).unwrap()
+    }
+
+    #[test]
+    fn test_markdown_watermark_placement() {
+        let header = export_watermarked(ExportFormat::Markdown, "header");
+        assert!(header.starts_with("> CONFIDENTIAL\n\n# Test Document"));
+        assert!(!header.ends_with("*CONFIDENTIAL*"));
+
+        let every = export_watermarked(ExportFormat::Markdown, "every_paragraph");
+        assert_eq!(every.matches("*CONFIDENTIAL*").count(), 2);
+        assert!(every.contains("of the document.\n\n*CONFIDENTIAL*\n\nIt has"));
+
+        let diagonal = export_watermarked(ExportFormat::Markdown, "diagonal");
+        assert!(diagonal.starts_with("<div class=\"watermark watermark-diagonal\""));
+
+        let unknown = export_watermarked(ExportFormat::Markdown, "sideways");
+        assert!(unknown.ends_with("\n\n---\n*CONFIDENTIAL*"));
+    }
+
+    #[test]
+    fn test_html_watermark_placement() {
+        let diagonal = export_watermarked(ExportFormat::Html, "diagonal");
+        let body = diagonal.find("<body>").unwrap();
+        let marker = diagonal.find("watermark-diagonal").unwrap();
+        let heading = diagonal.find("<h1>").unwrap();
+        assert!(body < marker && marker < heading);
+        assert!(diagonal.contains("position: fixed"));
+        assert!(!diagonal.contains("<hr>"));
+
+        let header = export_watermarked(ExportFormat::Html, "Header");
+        assert!(header.find("watermark-header").unwrap() < header.find("<h1>").unwrap());
+
+        let every = export_watermarked(ExportFormat::Html, "every_paragraph");
+        assert_eq!(every.matches("<p class=\"watermark\">").count(), 2);
+
+        let footer = export_watermarked(ExportFormat::Html, "footer");
+        assert!(footer.find("<hr>").unwrap() > footer.find("</p>").unwrap());
+        assert!(!footer.contains("class=\"watermark"));
+    }
+}
@@ -0,0 +1,334 @@
+//! Block-level document merge service
+
+use crate::value_objects::{ConflictResolution, ConflictType, ContentBlock, MergeConflict};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Outcome of merging two descendants of a common base
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult {
+    /// Merged blocks (conflicting blocks are left out)
+    pub blocks: Vec<ContentBlock>,
+    /// Conflicts requiring resolution
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Whether the merge completed without conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Three-way merge of content blocks, tracking block identity by `ContentBlock.id`
+///
+/// `ours` is the merge target and `theirs` the source. Changes made on only one
+/// side are applied automatically; overlapping changes become conflicts. Block
+/// order follows `ours`, with blocks added only in `theirs` appended in their order.
+pub fn three_way_merge(
+    base: &[ContentBlock],
+    ours: &[ContentBlock],
+    theirs: &[ContentBlock],
+) -> MergeResult {
+    let base_by_id = index_blocks(base);
+    let ours_by_id = index_blocks(ours);
+    let theirs_by_id = index_blocks(theirs);
+
+    let mut order: Vec<&str> = Vec::new();
+    let mut seen = HashSet::new();
+    for block in ours.iter().chain(theirs).chain(base) {
+        if seen.insert(block.id.as_str()) {
+            order.push(block.id.as_str());
+        }
+    }
+
+    let mut result = MergeResult {
+        blocks: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    for id in order {
+        let base_block = base_by_id.get(id).copied();
+        let our_block = ours_by_id.get(id).copied();
+        let their_block = theirs_by_id.get(id).copied();
+
+        match (base_block, our_block, their_block) {
+            (Some(b), Some(o), Some(t)) => {
+                if o == t || t == b {
+                    result.blocks.push(o.clone());
+                } else if o == b {
+                    result.blocks.push(t.clone());
+                } else {
+                    result.conflicts.push(conflict(id, Some(o), Some(t), Some(b), ConflictType::ContentModified));
+                }
+            }
+            // Deleted on one side: accept the deletion only if the other side left it untouched
+            (Some(b), Some(o), None) => {
+                if o != b {
+                    result.conflicts.push(conflict(id, Some(o), None, Some(b), ConflictType::BlockDeleted));
+                }
+            }
+            (Some(b), None, Some(t)) => {
+                if t != b {
+                    result.conflicts.push(conflict(id, None, Some(t), Some(b), ConflictType::BlockDeleted));
+                }
+            }
+            (Some(_), None, None) => {}
+            (None, Some(o), Some(t)) => {
+                if o == t {
+                    result.blocks.push(o.clone());
+                } else {
+                    result.conflicts.push(conflict(id, Some(o), Some(t), None, ConflictType::BlockAdded));
+                }
+            }
+            (None, Some(o), None) => result.blocks.push(o.clone()),
+            (None, None, Some(t)) => result.blocks.push(t.clone()),
+            (None, None, None) => {}
+        }
+    }
+
+    result
+}
+
+/// Resolve merge conflicts according to `strategy`
+///
+/// Returns the resolved blocks and the conflicts still needing manual handling.
+/// A resolved block is the chosen side's block, type, title and metadata
+/// included; preferring a side that deleted the block drops it. `Auto` keeps
+/// the surviving side of `BlockDeleted` conflicts and leaves everything else
+/// unresolved.
+pub fn resolve_conflicts(
+    conflicts: Vec<MergeConflict>,
+    strategy: ConflictResolution,
+) -> (Vec<ContentBlock>, Vec<MergeConflict>) {
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+
+    for conflict in conflicts {
+        let choice = match strategy {
+            ConflictResolution::PreferTarget => Some(conflict.target_block.clone()),
+            ConflictResolution::PreferSource => Some(conflict.source_block.clone()),
+            ConflictResolution::Auto if conflict.conflict_type == ConflictType::BlockDeleted => {
+                Some(conflict.target_block.clone().or_else(|| conflict.source_block.clone()))
+            }
+            ConflictResolution::Auto | ConflictResolution::Manual => None,
+        };
+
+        match choice {
+            Some(Some(block)) => resolved.push(block),
+            // The chosen side deleted the block
+            Some(None) => {}
+            None => unresolved.push(conflict),
+        }
+    }
+
+    (resolved, unresolved)
+}
+
+fn index_blocks(blocks: &[ContentBlock]) -> HashMap<&str, &ContentBlock> {
+    blocks.iter().map(|b| (b.id.as_str(), b)).collect()
+}
+
+fn conflict(
+    block_id: &str,
+    target: Option<&ContentBlock>,
+    source: Option<&ContentBlock>,
+    base: Option<&ContentBlock>,
+    conflict_type: ConflictType,
+) -> MergeConflict {
+    MergeConflict {
+        id: Uuid::new_v4(),
+        block_id: block_id.to_string(),
+        target_content: target.map(|b| b.content.clone()).unwrap_or_default(),
+        source_content: source.map(|b| b.content.clone()).unwrap_or_default(),
+        base_content: base.map(|b| b.content.clone()),
+        target_block: target.cloned(),
+        source_block: source.cloned(),
+        conflict_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(id: &str, content: &str) -> ContentBlock {
+        ContentBlock {
+            id: id.to_string(),
+            block_type: "paragraph".to_string(),
+            title: None,
+            content: content.to_string(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn ids(result: &MergeResult) -> Vec<&str> {
+        result.blocks.iter().map(|b| b.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_edit_on_one_side_merges_cleanly() {
+        let base = vec![block("intro", "Hello"), block("body", "Text")];
+        let ours = vec![block("intro", "Hello, world"), block("body", "Text")];
+        let theirs = vec![block("intro", "Hello"), block("body", "More text")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert!(result.is_clean());
+        assert_eq!(ids(&result), vec!["intro", "body"]);
+        assert_eq!(result.blocks[0].content, "Hello, world");
+        assert_eq!(result.blocks[1].content, "More text");
+    }
+
+    #[test]
+    fn test_same_block_edited_on_both_sides_conflicts() {
+        let base = vec![block("intro", "Hello")];
+        let ours = vec![block("intro", "Hi")];
+        let theirs = vec![block("intro", "Hey")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert!(result.blocks.is_empty());
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.conflict_type, ConflictType::ContentModified);
+        assert_eq!(conflict.target_content, "Hi");
+        assert_eq!(conflict.source_content, "Hey");
+        assert_eq!(conflict.base_content.as_deref(), Some("Hello"));
+    }
+
+    #[test]
+    fn test_identical_edits_do_not_conflict() {
+        let base = vec![block("intro", "Hello")];
+        let ours = vec![block("intro", "Hi")];
+        let theirs = vec![block("intro", "Hi")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert!(result.is_clean());
+        assert_eq!(result.blocks[0].content, "Hi");
+    }
+
+    #[test]
+    fn test_deleted_on_one_side_edited_on_other_conflicts() {
+        let base = vec![block("intro", "Hello"), block("legal", "Terms")];
+        let ours = vec![block("intro", "Hello")];
+        let theirs = vec![block("intro", "Hello"), block("legal", "Updated terms")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert_eq!(ids(&result), vec!["intro"]);
+        assert_eq!(result.conflicts.len(), 1);
+        let conflict = &result.conflicts[0];
+        assert_eq!(conflict.block_id, "legal");
+        assert_eq!(conflict.conflict_type, ConflictType::BlockDeleted);
+        assert!(conflict.target_content.is_empty());
+        assert_eq!(conflict.source_content, "Updated terms");
+    }
+
+    #[test]
+    fn test_untouched_block_deleted_on_one_side_is_removed() {
+        let base = vec![block("intro", "Hello"), block("legal", "Terms")];
+        let ours = vec![block("intro", "Hello"), block("legal", "Terms")];
+        let theirs = vec![block("intro", "Hello")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert!(result.is_clean());
+        assert_eq!(ids(&result), vec!["intro"]);
+    }
+
+    #[test]
+    fn test_blocks_added_on_both_sides() {
+        let base = vec![block("intro", "Hello")];
+        let ours = vec![block("intro", "Hello"), block("ours", "Ours"), block("shared", "A")];
+        let theirs = vec![block("intro", "Hello"), block("theirs", "Theirs"), block("shared", "B")];
+
+        let result = three_way_merge(&base, &ours, &theirs);
+
+        assert_eq!(ids(&result), vec!["intro", "ours", "theirs"]);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].block_id, "shared");
+        assert_eq!(result.conflicts[0].conflict_type, ConflictType::BlockAdded);
+        assert!(result.conflicts[0].base_content.is_none());
+    }
+
+    fn mixed_conflicts() -> Vec<MergeConflict> {
+        let base = vec![block("intro", "Hello"), block("legal", "Terms")];
+        let ours = vec![block("intro", "Hi"), block("new", "Ours")];
+        let theirs = vec![
+            block("intro", "Hey"),
+            block("legal", "Updated terms"),
+            block("new", "Theirs"),
+        ];
+        three_way_merge(&base, &ours, &theirs).conflicts
+    }
+
+    fn contents(blocks: &[ContentBlock]) -> Vec<(&str, &str)> {
+        blocks.iter().map(|b| (b.id.as_str(), b.content.as_str())).collect()
+    }
+
+    #[test]
+    fn test_resolve_prefer_target() {
+        let (blocks, unresolved) = resolve_conflicts(mixed_conflicts(), ConflictResolution::PreferTarget);
+
+        assert!(unresolved.is_empty());
+        assert_eq!(contents(&blocks), vec![("intro", "Hi"), ("new", "Ours")]);
+    }
+
+    #[test]
+    fn test_resolve_prefer_source() {
+        let (blocks, unresolved) = resolve_conflicts(mixed_conflicts(), ConflictResolution::PreferSource);
+
+        assert!(unresolved.is_empty());
+        assert_eq!(
+            contents(&blocks),
+            vec![("intro", "Hey"), ("new", "Theirs"), ("legal", "Updated terms")]
+        );
+    }
+
+    #[test]
+    fn test_resolve_auto_keeps_non_deleted_side() {
+        let (blocks, unresolved) = resolve_conflicts(mixed_conflicts(), ConflictResolution::Auto);
+
+        assert_eq!(contents(&blocks), vec![("legal", "Updated terms")]);
+        let types: Vec<ConflictType> = unresolved.into_iter().map(|c| c.conflict_type).collect();
+        assert_eq!(types, vec![ConflictType::ContentModified, ConflictType::BlockAdded]);
+    }
+
+    #[test]
+    fn test_resolve_keeps_block_attributes_and_empty_content() {
+        let mut heading = block("intro", "Hello");
+        heading.block_type = "heading".to_string();
+        heading.title = Some("Introduction".to_string());
+        heading.metadata.insert("level".to_string(), "2".to_string());
+        let mut emptied = heading.clone();
+        emptied.content = String::new();
+        let mut edited = heading.clone();
+        edited.content = "Hi".to_string();
+
+        let base = vec![heading.clone(), block("legal", "Terms")];
+        let ours = vec![emptied.clone()];
+        let theirs = vec![edited.clone(), block("legal", "Updated terms")];
+        let conflicts = three_way_merge(&base, &ours, &theirs).conflicts;
+
+        // An emptied block is an edit, not a deletion
+        let (blocks, unresolved) = resolve_conflicts(conflicts.clone(), ConflictResolution::PreferTarget);
+        assert!(unresolved.is_empty());
+        assert_eq!(blocks, vec![emptied]);
+
+        let (blocks, _) = resolve_conflicts(conflicts, ConflictResolution::PreferSource);
+        assert_eq!(blocks[0], edited);
+        assert_eq!(blocks[0].block_type, "heading");
+        assert_eq!(blocks[0].title.as_deref(), Some("Introduction"));
+        assert_eq!(blocks[0].metadata.get("level").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn test_resolve_manual_leaves_everything() {
+        let (blocks, unresolved) = resolve_conflicts(mixed_conflicts(), ConflictResolution::Manual);
+
+        assert!(blocks.is_empty());
+        assert_eq!(unresolved.len(), 3);
+    }
+}
@@ -7,6 +7,16 @@ pub mod import_export;
 pub mod version_comparison;
 pub mod chain_verification;
 pub mod object_store;
+pub mod merge;
+pub mod render_cache;
+pub mod retention;
+pub mod access_audit;
+pub mod thumbnails;
+pub mod ocr;
+pub mod idempotency;
+pub mod transform;
+pub mod redaction;
+pub mod chunking;
 
 pub use content_intelligence::*;
 pub use search::*;
@@ -14,4 +24,14 @@ pub use templates::*;
 pub use import_export::*;
 pub use version_comparison::*;
 pub use chain_verification::*;
-pub use object_store::*; 
\ No newline at end of file
+pub use object_store::*;
+pub use merge::*;
+pub use render_cache::*;
+pub use retention::*;
+pub use access_audit::*;
+pub use thumbnails::*;
+pub use ocr::*;
+pub use idempotency::*;
+pub use transform::*;
+pub use redaction::*;
+pub use chunking::*;
@@ -5,11 +5,16 @@
 //! subscriptions via the Subject Algebra.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
 use std::time::Duration;
 use chrono::{DateTime, Utc};
 use cid::Cid;
 use uuid::Uuid;
 
+use crate::aggregate::{compute_cid, AccessControlComponent, CidCodec, CidHash, Document};
 use crate::value_objects::DocumentId;
 use crate::nats::{MessageIdentity, ActorId};
 
@@ -174,6 +179,300 @@ pub enum ObjectStoreError {
     
     #[error("NATS Object Store error: {0}")]
     NatsError(String),
+
+    #[error("Object Store I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Cipher error for key '{key_id}': {reason}")]
+    Cipher { key_id: String, reason: String },
+}
+
+/// Synchronous CID-addressed content store
+pub trait ObjectStore: Send + Sync {
+    /// Store bytes, returning their CID
+    fn put(&self, bytes: &[u8]) -> Result<Cid, ObjectStoreError>;
+
+    /// Fetch the bytes stored under a CID
+    fn get(&self, cid: &Cid) -> Result<Vec<u8>, ObjectStoreError>;
+
+    /// Whether content is stored under a CID
+    fn has(&self, cid: &Cid) -> bool;
+
+    /// Counts of physical writes and deduplicated puts so far
+    ///
+    /// Stores that do not track puts report zero for both.
+    fn put_stats(&self) -> PutStats {
+        PutStats::default()
+    }
+}
+
+/// Outcome counts for [`ObjectStore::put`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PutStats {
+    /// Puts that stored new content
+    pub writes: u64,
+    /// Puts of content that was already present
+    pub deduplicated: u64,
+}
+
+/// Thread-safe [`PutStats`] counters
+#[derive(Debug, Default)]
+struct PutCounters {
+    writes: AtomicU64,
+    deduplicated: AtomicU64,
+}
+
+impl PutCounters {
+    fn record(&self, written: bool) {
+        let counter = if written { &self.writes } else { &self.deduplicated };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> PutStats {
+        PutStats {
+            writes: self.writes.load(Ordering::Relaxed),
+            deduplicated: self.deduplicated.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// CID under which `put` stores content (sha2-256, raw codec)
+fn content_cid(bytes: &[u8]) -> Cid {
+    compute_cid(bytes, CidCodec::Raw, CidHash::Sha2_256)
+}
+
+/// In-memory object store for testing
+#[derive(Debug, Default)]
+pub struct InMemoryObjectStore {
+    objects: RwLock<HashMap<Cid, Vec<u8>>>,
+    counters: PutCounters,
+}
+
+impl InMemoryObjectStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ObjectStore for InMemoryObjectStore {
+    fn put(&self, bytes: &[u8]) -> Result<Cid, ObjectStoreError> {
+        let cid = content_cid(bytes);
+        let mut objects = self.objects.write().unwrap();
+        let written = !objects.contains_key(&cid);
+        if written {
+            objects.insert(cid, bytes.to_vec());
+        }
+        self.counters.record(written);
+        Ok(cid)
+    }
+
+    fn get(&self, cid: &Cid) -> Result<Vec<u8>, ObjectStoreError> {
+        self.objects.read().unwrap()
+            .get(cid)
+            .cloned()
+            .ok_or(ObjectStoreError::ContentNotFound { content_cid: *cid })
+    }
+
+    fn has(&self, cid: &Cid) -> bool {
+        self.objects.read().unwrap().contains_key(cid)
+    }
+
+    fn put_stats(&self) -> PutStats {
+        self.counters.snapshot()
+    }
+}
+
+/// Object store keeping one file per CID under a root directory
+#[derive(Debug)]
+pub struct FileSystemObjectStore {
+    root: PathBuf,
+    counters: PutCounters,
+}
+
+impl FileSystemObjectStore {
+    /// Open a store rooted at `root`, creating the directory if needed
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, ObjectStoreError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        Ok(Self {
+            root,
+            counters: PutCounters::default(),
+        })
+    }
+
+    /// Path of the file holding `cid`
+    pub fn path_for(&self, cid: &Cid) -> PathBuf {
+        self.root.join(cid.to_string())
+    }
+}
+
+impl ObjectStore for FileSystemObjectStore {
+    fn put(&self, bytes: &[u8]) -> Result<Cid, ObjectStoreError> {
+        let cid = content_cid(bytes);
+        let path = self.path_for(&cid);
+        let written = !path.is_file();
+        if written {
+            std::fs::write(path, bytes)?;
+        }
+        self.counters.record(written);
+        Ok(cid)
+    }
+
+    fn get(&self, cid: &Cid) -> Result<Vec<u8>, ObjectStoreError> {
+        match std::fs::read(self.path_for(cid)) {
+            Ok(bytes) => Ok(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ObjectStoreError::ContentNotFound { content_cid: *cid })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn has(&self, cid: &Cid) -> bool {
+        self.path_for(cid).is_file()
+    }
+
+    fn put_stats(&self) -> PutStats {
+        self.counters.snapshot()
+    }
+}
+
+/// Encryption applied to document content at rest
+pub trait ContentCipher: Send + Sync {
+    /// Encrypt content under the key named `key_id`
+    fn encrypt(&self, plaintext: &[u8], key_id: &str) -> Result<Vec<u8>, ObjectStoreError>;
+
+    /// Decrypt content, failing if it was not encrypted under `key_id`
+    fn decrypt(&self, ciphertext: &[u8], key_id: &str) -> Result<Vec<u8>, ObjectStoreError>;
+}
+
+/// Length of the key check prefixed to [`XorCipher`] output
+#[cfg(any(test, feature = "insecure-test-cipher"))]
+const XOR_KEY_CHECK_LEN: usize = 8;
+
+/// XOR stream cipher keyed by a hash of the key id, for tests only
+///
+/// Output is prefixed with a key check so decrypting under the wrong key id
+/// fails instead of yielding garbage. It provides no real secrecy, so it is
+/// only built for tests or with the `insecure-test-cipher` feature.
+#[cfg(any(test, feature = "insecure-test-cipher"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XorCipher;
+
+#[cfg(any(test, feature = "insecure-test-cipher"))]
+impl XorCipher {
+    fn keystream(key_id: &str) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(key_id.as_bytes()).into()
+    }
+
+    fn apply(bytes: &[u8], key_id: &str) -> Vec<u8> {
+        let keystream = Self::keystream(key_id);
+        bytes.iter()
+            .zip(keystream.iter().cycle())
+            .map(|(byte, key)| byte ^ key)
+            .collect()
+    }
+
+    fn key_check(key_id: &str) -> Vec<u8> {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(Self::keystream(key_id))[..XOR_KEY_CHECK_LEN].to_vec()
+    }
+}
+
+#[cfg(any(test, feature = "insecure-test-cipher"))]
+impl ContentCipher for XorCipher {
+    fn encrypt(&self, plaintext: &[u8], key_id: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        let mut ciphertext = Self::key_check(key_id);
+        ciphertext.extend(Self::apply(plaintext, key_id));
+        Ok(ciphertext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8], key_id: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        if ciphertext.len() < XOR_KEY_CHECK_LEN || ciphertext[..XOR_KEY_CHECK_LEN] != Self::key_check(key_id)[..] {
+            return Err(ObjectStoreError::Cipher {
+                key_id: key_id.to_string(),
+                reason: "ciphertext was not produced with this key".to_string(),
+            });
+        }
+        Ok(Self::apply(&ciphertext[XOR_KEY_CHECK_LEN..], key_id))
+    }
+}
+
+/// Marks a stored blob as an encrypted envelope
+const ENVELOPE_MAGIC: &[u8] = b"cimenc\x01";
+
+/// Object store view that encrypts content of documents carrying an encryption key
+///
+/// Documents whose `AccessControlComponent` has an `encryption_key_id` are
+/// stored as ciphertext, so the returned CID addresses the encrypted bytes.
+/// The key id is stored alongside the ciphertext and used to decrypt it, so
+/// content written before a key rotation stays readable.
+/// Other documents pass through unchanged.
+pub struct DocumentContentStore<'a> {
+    store: &'a dyn ObjectStore,
+    cipher: &'a dyn ContentCipher,
+}
+
+impl<'a> DocumentContentStore<'a> {
+    pub fn new(store: &'a dyn ObjectStore, cipher: &'a dyn ContentCipher) -> Self {
+        Self { store, cipher }
+    }
+
+    /// Store a document's content, encrypting it when required
+    pub fn put(&self, document: &Document, plaintext: &[u8]) -> Result<Cid, ObjectStoreError> {
+        match encryption_key_id(document) {
+            Some(key_id) => self.store.put(&seal(key_id, &self.cipher.encrypt(plaintext, key_id)?)?),
+            None => self.store.put(plaintext),
+        }
+    }
+
+    /// Fetch a document's content, decrypting it with the key it was stored under
+    pub fn get(&self, document: &Document, cid: &Cid) -> Result<Vec<u8>, ObjectStoreError> {
+        let stored = self.store.get(cid)?;
+        match encryption_key_id(document) {
+            Some(current_key_id) => {
+                let (key_id, ciphertext) = open(&stored).ok_or_else(|| ObjectStoreError::Cipher {
+                    key_id: current_key_id.to_string(),
+                    reason: "stored content is not an encrypted envelope".to_string(),
+                })?;
+                self.cipher.decrypt(ciphertext, key_id)
+            }
+            None => Ok(stored),
+        }
+    }
+}
+
+/// Prefix `ciphertext` with the key id it was encrypted under
+fn seal(key_id: &str, ciphertext: &[u8]) -> Result<Vec<u8>, ObjectStoreError> {
+    let key_len = u16::try_from(key_id.len()).map_err(|_| ObjectStoreError::Cipher {
+        key_id: key_id.to_string(),
+        reason: "key id is too long".to_string(),
+    })?;
+    let mut envelope = Vec::with_capacity(ENVELOPE_MAGIC.len() + 2 + key_id.len() + ciphertext.len());
+    envelope.extend_from_slice(ENVELOPE_MAGIC);
+    envelope.extend_from_slice(&key_len.to_be_bytes());
+    envelope.extend_from_slice(key_id.as_bytes());
+    envelope.extend_from_slice(ciphertext);
+    Ok(envelope)
+}
+
+/// Split an envelope written by [`seal`] into its key id and ciphertext
+fn open(envelope: &[u8]) -> Option<(&str, &[u8])> {
+    let rest = envelope.strip_prefix(ENVELOPE_MAGIC)?;
+    let (key_len, rest) = rest.split_first_chunk::<2>()?;
+    let key_len = usize::from(u16::from_be_bytes(*key_len));
+    if rest.len() < key_len {
+        return None;
+    }
+    let (key_id, ciphertext) = rest.split_at(key_len);
+    Some((std::str::from_utf8(key_id).ok()?, ciphertext))
+}
+
+/// Key id a document's content is encrypted under, if any
+fn encryption_key_id(document: &Document) -> Option<&str> {
+    document.get_component::<AccessControlComponent>()
+        .and_then(|access| access.encryption_key_id.as_deref())
 }
 
 /// Object Store service for domain-partitioned content storage
@@ -320,4 +619,171 @@ mod tests {
         assert!(!metadata.is_encrypted);
         assert_eq!(metadata.size_bytes, 1024000);
     }
-}
\ No newline at end of file
+
+    fn temp_store() -> FileSystemObjectStore {
+        FileSystemObjectStore::new(std::env::temp_dir().join(format!("cim-objects-{}", Uuid::new_v4()))).unwrap()
+    }
+
+    fn assert_round_trip(store: &dyn ObjectStore) {
+        let bytes = b"content-addressed bytes";
+
+        let cid = store.put(bytes).unwrap();
+
+        assert!(store.has(&cid));
+        assert_eq!(store.get(&cid).unwrap(), bytes);
+        assert_eq!(cid, crate::aggregate::ContentAddressComponent::from_content(bytes, "sha2-256").unwrap().content_cid);
+    }
+
+    fn assert_get_miss(store: &dyn ObjectStore) {
+        let missing = content_cid(b"never stored");
+
+        assert!(!store.has(&missing));
+        assert!(matches!(
+            store.get(&missing),
+            Err(ObjectStoreError::ContentNotFound { content_cid }) if content_cid == missing
+        ));
+    }
+
+    fn document_with_key(encryption_key_id: Option<&str>) -> Document {
+        let info = crate::aggregate::DocumentInfoComponent::builder()
+            .with_title("Encrypted")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let mut document = Document::new(cim_domain::EntityId::new(), info, Cid::default()).unwrap();
+        document.add_component(AccessControlComponent {
+            read_access: vec![],
+            write_access: vec![],
+            share_access: vec![],
+            audit_access: false,
+            encryption_key_id: encryption_key_id.map(str::to_string),
+            block_access: HashMap::new(),
+        }, "test", None).unwrap();
+        document
+    }
+
+    #[test]
+    fn test_document_content_store_encrypts_at_rest() {
+        let store = InMemoryObjectStore::new();
+        let cipher = XorCipher;
+        let content = DocumentContentStore::new(&store, &cipher);
+        let plaintext = b"quarterly salaries";
+
+        let document = document_with_key(Some("hr-key"));
+        let cid = content.put(&document, plaintext).unwrap();
+
+        let stored = store.get(&cid).unwrap();
+        assert_ne!(stored, plaintext);
+        assert_eq!(cid, content_cid(&stored));
+        assert_eq!(content.get(&document, &cid).unwrap(), plaintext);
+
+        // Content stored in the clear is refused for an encrypted document
+        let clear = store.put(plaintext).unwrap();
+        assert!(matches!(
+            content.get(&document, &clear),
+            Err(ObjectStoreError::Cipher { key_id, .. }) if key_id == "hr-key"
+        ));
+    }
+
+    #[test]
+    fn test_document_content_store_decrypts_with_stored_key_after_rotation() {
+        let store = InMemoryObjectStore::new();
+        let cipher = XorCipher;
+        let content = DocumentContentStore::new(&store, &cipher);
+
+        let before = content.put(&document_with_key(Some("hr-key")), b"old salaries").unwrap();
+        let rotated = document_with_key(Some("hr-key-2"));
+        let after = content.put(&rotated, b"new salaries").unwrap();
+
+        assert_eq!(content.get(&rotated, &before).unwrap(), b"old salaries");
+        assert_eq!(content.get(&rotated, &after).unwrap(), b"new salaries");
+
+        // A tampered key id no longer matches the ciphertext's key check
+        let mut tampered = store.get(&before).unwrap();
+        let key_start = ENVELOPE_MAGIC.len() + 2;
+        tampered[key_start..key_start + 6].copy_from_slice(b"xx-key");
+        let tampered = store.put(&tampered).unwrap();
+        assert!(matches!(
+            content.get(&rotated, &tampered),
+            Err(ObjectStoreError::Cipher { key_id, .. }) if key_id == "xx-key"
+        ));
+    }
+
+    #[test]
+    fn test_document_content_store_passes_through_unencrypted() {
+        let store = InMemoryObjectStore::new();
+        let cipher = XorCipher;
+        let content = DocumentContentStore::new(&store, &cipher);
+
+        let document = document_with_key(None);
+        let cid = content.put(&document, b"public notes").unwrap();
+
+        assert_eq!(store.get(&cid).unwrap(), b"public notes");
+        assert_eq!(content.get(&document, &cid).unwrap(), b"public notes");
+    }
+
+    #[test]
+    fn test_in_memory_object_store() {
+        let store = InMemoryObjectStore::new();
+        assert_round_trip(&store);
+        assert_get_miss(&store);
+    }
+
+    #[test]
+    fn test_file_system_object_store() {
+        let store = temp_store();
+        assert_round_trip(&store);
+        assert_get_miss(&store);
+
+        let cid = store.put(b"on disk").unwrap();
+        assert_eq!(std::fs::read(store.path_for(&cid)).unwrap(), b"on disk");
+
+        std::fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    fn assert_deduplicated(store: &dyn ObjectStore) {
+        let first = store.put(b"same bytes").unwrap();
+        let second = store.put(b"same bytes").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(store.put_stats(), PutStats { writes: 1, deduplicated: 1 });
+    }
+
+    #[test]
+    fn test_in_memory_put_deduplicates() {
+        let store = InMemoryObjectStore::new();
+        assert_deduplicated(&store);
+        assert_eq!(store.objects.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_file_system_put_deduplicates() {
+        let store = temp_store();
+        assert_deduplicated(&store);
+        assert_eq!(std::fs::read_dir(&store.root).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[test]
+    fn test_put_stats_default_to_zero() {
+        struct Untracked;
+
+        impl ObjectStore for Untracked {
+            fn put(&self, _bytes: &[u8]) -> Result<Cid, ObjectStoreError> {
+                Ok(Cid::default())
+            }
+
+            fn get(&self, cid: &Cid) -> Result<Vec<u8>, ObjectStoreError> {
+                Err(ObjectStoreError::ContentNotFound { content_cid: *cid })
+            }
+
+            fn has(&self, _cid: &Cid) -> bool {
+                false
+            }
+        }
+
+        Untracked.put(b"bytes").unwrap();
+        assert_eq!(Untracked.put_stats(), PutStats::default());
+    }
+}
@@ -0,0 +1,148 @@
+//! OCR processing for image and scanned PDF documents
+//!
+//! Recognition is delegated to an [`OcrEngine`] so no OCR backend is a
+//! dependency of this crate.
+
+use crate::aggregate::{Document, DocumentInfoComponent, ProcessingComponent};
+use crate::services::ObjectStore;
+use cid::Cid;
+
+/// Text recognition backend
+pub trait OcrEngine: Send + Sync {
+    /// Recognize the text in image or scanned PDF content
+    fn recognize(&self, content: &[u8], mime_type: &str) -> Result<String, OcrError>;
+}
+
+/// Failure reported by an [`OcrEngine`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("OCR failed: {0}")]
+pub struct OcrError(pub String);
+
+/// Result of running OCR over a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrOutcome {
+    /// Text was extracted and stored under this CID
+    Extracted(Cid),
+    /// The document's MIME type is not OCR-able
+    Skipped,
+    /// OCR or storage failed; the message was added to `processing_errors`
+    Failed(String),
+}
+
+/// Whether OCR applies to content of this MIME type
+pub fn is_ocr_candidate(mime_type: &str) -> bool {
+    mime_type.starts_with("image/") || mime_type == "application/pdf"
+}
+
+/// Run OCR over a document's content and record the result
+///
+/// On success the extracted text is stored in `store` and the document's
+/// `ProcessingComponent` records its CID. Failures are appended to
+/// `processing_errors` rather than returned.
+pub fn run_ocr(
+    document: &mut Document,
+    content: &[u8],
+    engine: &dyn OcrEngine,
+    store: &dyn ObjectStore,
+) -> OcrOutcome {
+    let Some(mime_type) = document
+        .get_component::<DocumentInfoComponent>()
+        .map(|info| info.mime_type.clone())
+        .filter(|mime_type| is_ocr_candidate(mime_type))
+    else {
+        return OcrOutcome::Skipped;
+    };
+
+    let mut processing = document
+        .get_component::<ProcessingComponent>()
+        .cloned()
+        .unwrap_or_default();
+
+    let extracted = engine
+        .recognize(content, &mime_type)
+        .map_err(|e| e.to_string())
+        .and_then(|text| store.put(text.as_bytes()).map_err(|e| e.to_string()));
+    let outcome = match extracted {
+        Ok(cid) => {
+            processing.extracted_text_cid = Some(cid);
+            processing.text_extracted = true;
+            processing.ocr_performed = true;
+            OcrOutcome::Extracted(cid)
+        }
+        Err(message) => {
+            processing.processing_errors.push(message.clone());
+            OcrOutcome::Failed(message)
+        }
+    };
+
+    document
+        .replace_component(processing, "ocr", Some("OCR processing".to_string()))
+        .expect("replacing a component clears its slot first");
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::InMemoryObjectStore;
+    use cim_domain::EntityId;
+
+    struct StubEngine(Result<String, OcrError>);
+
+    impl OcrEngine for StubEngine {
+        fn recognize(&self, _content: &[u8], _mime_type: &str) -> Result<String, OcrError> {
+            self.0.clone()
+        }
+    }
+
+    fn document(mime_type: &str) -> Document {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Scan")
+            .with_mime_type(mime_type)
+            .build()
+            .unwrap();
+        Document::new(EntityId::new(), info, Cid::default()).unwrap()
+    }
+
+    #[test]
+    fn test_run_ocr_stores_extracted_text() {
+        let store = InMemoryObjectStore::new();
+        let engine = StubEngine(Ok("Invoice 42".to_string()));
+        let mut scan = document("image/png");
+
+        let outcome = run_ocr(&mut scan, b"png bytes", &engine, &store);
+
+        let processing = scan.get_component::<ProcessingComponent>().unwrap();
+        let cid = processing.extracted_text_cid.unwrap();
+        assert_eq!(outcome, OcrOutcome::Extracted(cid));
+        assert!(processing.ocr_performed);
+        assert!(processing.text_extracted);
+        assert!(processing.processing_errors.is_empty());
+        assert_eq!(store.get(&cid).unwrap(), b"Invoice 42");
+    }
+
+    #[test]
+    fn test_run_ocr_records_failure() {
+        let store = InMemoryObjectStore::new();
+        let engine = StubEngine(Err(OcrError("unreadable scan".to_string())));
+        let mut scan = document("application/pdf");
+
+        let outcome = run_ocr(&mut scan, b"%PDF", &engine, &store);
+
+        assert_eq!(outcome, OcrOutcome::Failed("OCR failed: unreadable scan".to_string()));
+        let processing = scan.get_component::<ProcessingComponent>().unwrap();
+        assert!(!processing.ocr_performed);
+        assert_eq!(processing.extracted_text_cid, None);
+        assert_eq!(processing.processing_errors, vec!["OCR failed: unreadable scan".to_string()]);
+    }
+
+    #[test]
+    fn test_run_ocr_skips_text_documents() {
+        let store = InMemoryObjectStore::new();
+        let engine = StubEngine(Ok("ignored".to_string()));
+        let mut notes = document("text/plain");
+
+        assert_eq!(run_ocr(&mut notes, b"notes", &engine, &store), OcrOutcome::Skipped);
+        assert!(notes.get_component::<ProcessingComponent>().is_none());
+    }
+}
@@ -0,0 +1,250 @@
+//! PII redaction as a transform pipeline step
+//!
+//! [`RedactTransformer`] finds emails, phone numbers and any configured
+//! patterns and replaces each match with `[REDACTED]` or a short keyed hash
+//! of it. Overlapping matches are merged and redacted as one span.
+//!
+//! The built-in phone detector only recognises North American (NANP) numbers
+//! such as `(555) 123-4567` or `+1 555 123 4567`; other numbering plans need
+//! a pattern added with [`RedactTransformer::with_pattern`].
+
+use crate::services::{TransformError, Transformer};
+use crate::value_objects::EntityType;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// How redacted text is replaced
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RedactionStyle {
+    /// Replace with `[REDACTED]`
+    #[default]
+    Mask,
+    /// Replace with `[REDACTED:<hmac>]` so equal values stay correlatable
+    ///
+    /// The HMAC-SHA256 is keyed so short values such as phone numbers cannot
+    /// be recovered by hashing every candidate; keep `key` secret.
+    Hash { key: Vec<u8> },
+}
+
+/// How many matches of each entity type were redacted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RedactionReport {
+    pub counts: HashMap<EntityType, usize>,
+}
+
+impl RedactionReport {
+    /// Matches redacted for `entity_type`
+    pub fn count(&self, entity_type: &EntityType) -> usize {
+        self.counts.get(entity_type).copied().unwrap_or(0)
+    }
+
+    /// Matches redacted across all types
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Redacts emails, phone numbers and custom patterns
+#[derive(Debug, Clone)]
+pub struct RedactTransformer {
+    /// Detectors in priority order; earlier ones win on overlap
+    patterns: Vec<(EntityType, Regex)>,
+    style: RedactionStyle,
+}
+
+impl RedactTransformer {
+    /// Redactor for emails and North American phone numbers
+    pub fn new() -> Self {
+        Self {
+            patterns: vec![
+                (
+                    EntityType::Email,
+                    Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap(),
+                ),
+                (
+                    EntityType::PhoneNumber,
+                    Regex::new(r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[\s.-]?)\d{3}[\s.-]?\d{4}\b").unwrap(),
+                ),
+            ],
+            style: RedactionStyle::default(),
+        }
+    }
+
+    /// Also redact matches of `pattern`, counted as `EntityType::Custom(name)`
+    pub fn with_pattern(mut self, name: impl Into<String>, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns
+            .push((EntityType::Custom(name.into()), Regex::new(pattern)?));
+        Ok(self)
+    }
+
+    pub fn with_style(mut self, style: RedactionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Redact `content`, reporting what was removed
+    ///
+    /// Overlapping matches are merged into one span, counted once under the
+    /// match that starts first (the earlier detector on a tie).
+    pub fn redact(&self, content: &str) -> (String, RedactionReport) {
+        let mut matches: Vec<(usize, usize, usize)> = self
+            .patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(priority, (_, regex))| {
+                regex
+                    .find_iter(content)
+                    .map(move |m| (m.start(), priority, m.end()))
+            })
+            .collect();
+        matches.sort();
+
+        let mut spans: Vec<(usize, usize, usize)> = Vec::with_capacity(matches.len());
+        for (start, priority, end) in matches {
+            match spans.last_mut() {
+                Some((_, _, span_end)) if start < *span_end => *span_end = (*span_end).max(end),
+                _ => spans.push((start, priority, end)),
+            }
+        }
+
+        let mut report = RedactionReport::default();
+        let mut redacted = String::with_capacity(content.len());
+        let mut cursor = 0;
+        for (start, priority, end) in spans {
+            redacted.push_str(&content[cursor..start]);
+            redacted.push_str(&self.replacement(&content[start..end]));
+            *report
+                .counts
+                .entry(self.patterns[priority].0.clone())
+                .or_default() += 1;
+            cursor = end;
+        }
+        redacted.push_str(&content[cursor..]);
+
+        (redacted, report)
+    }
+
+    fn replacement(&self, matched: &str) -> String {
+        match &self.style {
+            RedactionStyle::Mask => "[REDACTED]".to_string(),
+            RedactionStyle::Hash { key } => {
+                let digest = hex::encode(hmac_sha256(key, matched.as_bytes()));
+                format!("[REDACTED:{}]", &digest[..12])
+            }
+        }
+    }
+}
+
+/// HMAC-SHA256 of `message` under `key` (RFC 2104)
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_LEN: usize = 64;
+    let mut block = [0u8; BLOCK_LEN];
+    if key.len() > BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.map(|k| k ^ byte);
+
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(message).finalize();
+    Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize().into()
+}
+
+impl Default for RedactTransformer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformer for RedactTransformer {
+    fn name(&self) -> &str {
+        "redact"
+    }
+
+    fn transform(&self, content: String) -> Result<String, TransformError> {
+        Ok(self.redact(&content).0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{NormalizeWhitespace, TransformPipeline};
+
+    const MEMO: &str = "Contact alice@example.com or bob.smith@corp.io.\nEscalations: call (555) 123-4567.";
+
+    #[test]
+    fn test_redacts_emails_and_phone_numbers() {
+        let (redacted, report) = RedactTransformer::new().redact(MEMO);
+
+        assert_eq!(report.count(&EntityType::Email), 2);
+        assert_eq!(report.count(&EntityType::PhoneNumber), 1);
+        assert_eq!(report.total(), 3);
+        for original in ["alice@example.com", "bob.smith@corp.io", "123-4567"] {
+            assert!(!redacted.contains(original), "{original} survived: {redacted}");
+        }
+        assert_eq!(
+            redacted,
+            "Contact [REDACTED] or [REDACTED].\nEscalations: call [REDACTED]."
+        );
+    }
+
+    #[test]
+    fn test_hash_style_and_custom_patterns() {
+        let redactor = RedactTransformer::new()
+            .with_pattern("employee_id", r"\bEMP-\d{5}\b")
+            .unwrap()
+            .with_style(RedactionStyle::Hash { key: b"tenant-secret".to_vec() });
+
+        let (redacted, report) = redactor.redact("EMP-12345 and EMP-12345 emailed a@b.co");
+
+        assert_eq!(report.count(&EntityType::Custom("employee_id".to_string())), 2);
+        assert_eq!(report.count(&EntityType::Email), 1);
+        assert!(!redacted.contains("EMP-12345"));
+        let hashes: Vec<&str> = redacted.split_whitespace().filter(|w| w.starts_with("[REDACTED:")).collect();
+        assert_eq!(hashes.len(), 3);
+        assert_eq!(hashes[0], hashes[1]);
+
+        // The hash is keyed, not a bare digest of the value
+        let unkeyed = hex::encode(Sha256::digest(b"EMP-12345"));
+        assert_ne!(hashes[0], format!("[REDACTED:{}]", &unkeyed[..12]));
+        let other_key = redactor.with_style(RedactionStyle::Hash { key: b"other".to_vec() });
+        assert!(!other_key.redact("EMP-12345").0.contains(hashes[0]));
+    }
+
+    #[test]
+    fn test_hmac_matches_rfc_4231_vector() {
+        // RFC 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(mac),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_matches_are_merged() {
+        let redactor = RedactTransformer::new()
+            .with_pattern("ticket", r"ref 555 \d{3}")
+            .unwrap();
+
+        // The ticket pattern starts first and the phone number runs past its end
+        let (redacted, report) = redactor.redact("see ref 555 123 4567 today");
+
+        assert_eq!(redacted, "see [REDACTED] today");
+        assert_eq!(report.count(&EntityType::Custom("ticket".to_string())), 1);
+        assert_eq!(report.total(), 1);
+    }
+
+    #[test]
+    fn test_redactor_runs_in_pipeline() {
+        let pipeline = TransformPipeline::new()
+            .then(RedactTransformer::new())
+            .then(NormalizeWhitespace);
+
+        let output = pipeline.apply("mail   x@y.org  now".to_string()).unwrap();
+
+        assert_eq!(output, "mail [REDACTED] now");
+    }
+}
@@ -0,0 +1,151 @@
+//! Content-addressed cache for rendered exports
+
+use crate::projections::DocumentFullView;
+use crate::value_objects::{DocumentId, ExportFormat, ExportOptions};
+use cid::Cid;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// Cache key for a rendered export
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RenderKey {
+    /// Document being rendered
+    pub document_id: DocumentId,
+    /// CID of the rendered content
+    pub content_cid: Cid,
+    /// Hash of the document's title, type, version, tags, author and metadata
+    pub metadata_hash: u64,
+    /// Target format
+    pub format: ExportFormat,
+    /// Hash of the export options
+    pub options_hash: u64,
+}
+
+impl RenderKey {
+    pub fn new(
+        document: &DocumentFullView,
+        content_cid: Cid,
+        format: &ExportFormat,
+        options: &ExportOptions,
+    ) -> Self {
+        Self {
+            document_id: document.id,
+            content_cid,
+            metadata_hash: metadata_hash(document),
+            format: format.clone(),
+            options_hash: options_hash(options),
+        }
+    }
+
+    /// Whether both keys render the same revision of the same document
+    fn same_revision(&self, other: &RenderKey) -> bool {
+        self.document_id == other.document_id
+            && self.content_cid == other.content_cid
+            && self.metadata_hash == other.metadata_hash
+    }
+}
+
+/// Cache of rendered export buffers keyed by document revision, format and options
+#[derive(Debug, Default)]
+pub struct RenderCache {
+    entries: HashMap<RenderKey, Vec<u8>>,
+    /// Key last rendered for each document
+    current: HashMap<DocumentId, RenderKey>,
+    hits: u64,
+    misses: u64,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached buffer for the inputs, rendering and storing it on a miss
+    ///
+    /// When a document is seen with new content or metadata, renders of its
+    /// previous revision are evicted. Other documents' renders are kept.
+    pub fn get_or_render<F, E>(&mut self, key: RenderKey, render: F) -> Result<Vec<u8>, E>
+    where
+        F: FnOnce() -> Result<Vec<u8>, E>,
+    {
+        if let Some(previous) = self.current.insert(key.document_id, key.clone()) {
+            if !previous.same_revision(&key) {
+                self.entries.retain(|cached, _| !cached.same_revision(&previous));
+            }
+        }
+
+        if let Some(bytes) = self.entries.get(&key) {
+            self.hits += 1;
+            return Ok(bytes.clone());
+        }
+
+        self.misses += 1;
+        let bytes = render()?;
+        self.entries.insert(key, bytes.clone());
+        Ok(bytes)
+    }
+
+    /// Drop every cached render of the given content, across all documents
+    pub fn invalidate_cid(&mut self, content_cid: &Cid) {
+        self.entries.retain(|key, _| &key.content_cid != content_cid);
+    }
+
+    /// Drop all cached renders of a document
+    pub fn invalidate_document(&mut self, document_id: &DocumentId) {
+        self.current.remove(document_id);
+        self.entries.retain(|key, _| &key.document_id != document_id);
+    }
+
+    /// Number of cached buffers
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of lookups served from cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that required rendering
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+/// Hash of a document's rendered fields other than content
+///
+/// Uses SHA-256 over a serialized form, so the value is stable across builds.
+fn metadata_hash(document: &DocumentFullView) -> u64 {
+    let metadata: BTreeMap<_, _> = document.metadata.iter().collect();
+    stable_hash(&(
+        &document.title,
+        &document.doc_type,
+        &document.version,
+        &document.tags,
+        document.author,
+        metadata,
+    ))
+}
+
+/// Hash of export options; custom options are hashed in key order
+fn options_hash(options: &ExportOptions) -> u64 {
+    let custom: BTreeMap<_, _> = options.custom_options.iter().collect();
+    stable_hash(&(
+        options.include_metadata,
+        options.include_history,
+        options.include_comments,
+        &options.watermark,
+        custom,
+    ))
+}
+
+/// First eight bytes of the SHA-256 of `value`'s JSON form
+fn stable_hash(value: &impl serde::Serialize) -> u64 {
+    let json = serde_json::to_vec(value).expect("cache key fields serialize to JSON");
+    let digest = Sha256::digest(&json);
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digest is 32 bytes"))
+}
@@ -0,0 +1,201 @@
+//! Expiration and retention enforcement
+
+use crate::aggregate::{Document, DocumentStatus, LifecycleComponent};
+use crate::value_objects::DocumentId;
+use chrono::{DateTime, Duration, Months, Utc};
+use cim_domain::AggregateRoot;
+use std::fmt;
+use std::str::FromStr;
+
+/// Action due for an expired document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionAction {
+    /// Expired but still within its retention period
+    Archive,
+    /// Retention period after expiry has elapsed
+    Delete,
+}
+
+/// Unit of a retention period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionUnit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+/// How long an expired document is kept before deletion, e.g. `"7_years"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub amount: u32,
+    pub unit: RetentionUnit,
+}
+
+impl RetentionPolicy {
+    /// End of the retention period starting at `from`
+    pub fn retain_until(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        let end = match self.unit {
+            RetentionUnit::Days => from.checked_add_signed(Duration::days(self.amount.into())),
+            RetentionUnit::Weeks => from.checked_add_signed(Duration::weeks(self.amount.into())),
+            RetentionUnit::Months => from.checked_add_months(Months::new(self.amount)),
+            RetentionUnit::Years => self
+                .amount
+                .checked_mul(12)
+                .and_then(|months| from.checked_add_months(Months::new(months))),
+        };
+        end.unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+/// Malformed retention policy string
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid retention policy '{0}': expected '<count>_<days|weeks|months|years>'")]
+pub struct RetentionPolicyError(pub String);
+
+impl FromStr for RetentionPolicy {
+    type Err = RetentionPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RetentionPolicyError(s.to_string());
+        let (amount, unit) = s.trim().split_once('_').ok_or_else(invalid)?;
+
+        let amount: u32 = amount.parse().map_err(|_| invalid())?;
+        let unit = match unit.to_lowercase().as_str() {
+            "day" | "days" => RetentionUnit::Days,
+            "week" | "weeks" => RetentionUnit::Weeks,
+            "month" | "months" => RetentionUnit::Months,
+            "year" | "years" => RetentionUnit::Years,
+            _ => return Err(invalid()),
+        };
+        if amount == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self { amount, unit })
+    }
+}
+
+impl fmt::Display for RetentionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unit = match self.unit {
+            RetentionUnit::Days => "days",
+            RetentionUnit::Weeks => "weeks",
+            RetentionUnit::Months => "months",
+            RetentionUnit::Years => "years",
+        };
+        write!(f, "{}_{}", self.amount, unit)
+    }
+}
+
+/// Expired documents and the retention action due for each
+///
+/// Expired documents are archived until their retention period (counted from
+/// expiry) has elapsed, then deleted. Documents without a policy, or whose policy
+/// cannot be parsed, are only ever archived.
+pub fn documents_due_for_action(documents: &[Document], now: DateTime<Utc>) -> Vec<(DocumentId, RetentionAction)> {
+    documents
+        .iter()
+        .filter_map(|document| {
+            let document_id = DocumentId::from(document.id());
+            let lifecycle = document.get_component::<LifecycleComponent>()?;
+            let expires_at = lifecycle.expires_at.filter(|expires_at| *expires_at <= now)?;
+
+            let policy = lifecycle.retention_policy.as_deref().and_then(|policy| {
+                policy
+                    .parse::<RetentionPolicy>()
+                    .inspect_err(|e| tracing::warn!("Document {}: {}", document_id, e))
+                    .ok()
+            });
+
+            let action = match policy {
+                Some(policy) if policy.retain_until(expires_at) <= now => RetentionAction::Delete,
+                _ if lifecycle.status == DocumentStatus::Archived => return None,
+                _ => RetentionAction::Archive,
+            };
+            Some((document_id, action))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::DocumentInfoComponent;
+    use cid::Cid;
+    use cim_domain::EntityId;
+
+    fn lifecycle_document(
+        status: DocumentStatus,
+        expires_at: Option<DateTime<Utc>>,
+        retention_policy: Option<&str>,
+    ) -> Document {
+        let info = DocumentInfoComponent::builder()
+            .with_title("Contract")
+            .with_mime_type("application/pdf")
+            .build()
+            .unwrap();
+        let mut document = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+        document.add_component(
+            LifecycleComponent {
+                status,
+                created_at: Utc::now(),
+                modified_at: Utc::now(),
+                version_number: "1".to_string(),
+                previous_version_cid: None,
+                expires_at,
+                retention_policy: retention_policy.map(str::to_string),
+            },
+            "test",
+            None,
+        ).unwrap();
+        document
+    }
+
+    #[test]
+    fn test_parse_retention_policy() {
+        assert_eq!(
+            "7_years".parse::<RetentionPolicy>(),
+            Ok(RetentionPolicy { amount: 7, unit: RetentionUnit::Years })
+        );
+        assert_eq!(
+            "30_days".parse::<RetentionPolicy>().unwrap().to_string(),
+            "30_days"
+        );
+        for malformed in ["", "7years", "seven_years", "7_fortnights", "0_days", "-1_days"] {
+            assert!(malformed.parse::<RetentionPolicy>().is_err(), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now();
+
+        assert!(lifecycle_document(DocumentStatus::Published, Some(now - Duration::days(1)), None).is_expired(now));
+        assert!(!lifecycle_document(DocumentStatus::Published, Some(now + Duration::days(1)), None).is_expired(now));
+        assert!(!lifecycle_document(DocumentStatus::Published, None, None).is_expired(now));
+    }
+
+    #[test]
+    fn test_documents_due_for_action() {
+        let now = Utc::now();
+        let docs = vec![
+            lifecycle_document(DocumentStatus::Published, Some(now - Duration::days(10)), Some("30_days")),
+            lifecycle_document(DocumentStatus::Archived, Some(now - Duration::days(40)), Some("30_days")),
+            lifecycle_document(DocumentStatus::Published, Some(now + Duration::days(10)), Some("30_days")),
+            lifecycle_document(DocumentStatus::Published, Some(now - Duration::days(400)), Some("forever")),
+            lifecycle_document(DocumentStatus::Archived, Some(now - Duration::days(10)), Some("1_years")),
+        ];
+
+        let due = documents_due_for_action(&docs, now);
+
+        assert_eq!(
+            due,
+            vec![
+                (DocumentId::from(docs[0].id()), RetentionAction::Archive),
+                (DocumentId::from(docs[1].id()), RetentionAction::Delete),
+                (DocumentId::from(docs[3].id()), RetentionAction::Archive),
+            ]
+        );
+    }
+}
@@ -1,14 +1,86 @@
 //! Document search service
+//!
+//! Full-text search over an in-memory inverted index. Title, content, tags and
+//! author are tokenized separately so queries can be scoped with `SearchField`,
+//! and results are ranked with BM25.
 
-use crate::value_objects::{DocumentId, SearchQuery, SearchField, SearchFilter, FilterOperator};
-use crate::projections::DocumentFullView;
+use crate::value_objects::{DocumentId, DocumentType, SearchQuery, SearchField, SearchFilter, FilterOperator, SortDirection};
+use crate::projections::{DocumentFullView, DocumentSearchView};
+use crate::aggregate::{Document, ProcessingComponent, SearchIndexProjection};
+use crate::events::DocumentDomainEvent;
+use crate::queries::QueryLimits;
 use anyhow::Result;
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+/// BM25 term-frequency saturation
+const BM25_K1: f32 = 1.2;
+/// BM25 length normalization
+const BM25_B: f32 = 0.75;
+
+/// Score reduction per edit for fuzzy matches
+const FUZZY_PENALTY_PER_EDIT: f32 = 0.35;
+
+/// Number of separately indexed fields
+const FIELD_COUNT: usize = 4;
+
+/// Longest stretch of content, in bytes, shown in a result snippet
+const SNIPPET_LENGTH: usize = 200;
+
+/// Counts of matching documents per facet value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Facets {
+    pub tags: HashMap<String, usize>,
+    pub doc_types: HashMap<DocumentType, usize>,
+    /// Keyed by author ID
+    pub authors: HashMap<String, usize>,
+}
 
 /// Document search service
 pub struct DocumentSearchService {
-    /// Search index (simplified for now)
+    /// Indexed documents
     index: HashMap<DocumentId, DocumentIndex>,
+    /// Term -> document -> per-field term frequency
+    postings: HashMap<String, HashMap<DocumentId, [u32; FIELD_COUNT]>>,
+    /// Bounds checked before a query runs
+    limits: QueryLimits,
+}
+
+/// Indexed fields, in posting-array order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexedField {
+    Title = 0,
+    Content = 1,
+    Tags = 2,
+    Author = 3,
+}
+
+impl IndexedField {
+    const ALL: [IndexedField; FIELD_COUNT] = [
+        IndexedField::Title,
+        IndexedField::Content,
+        IndexedField::Tags,
+        IndexedField::Author,
+    ];
+
+    /// Relative weight of a match in this field
+    fn weight(self) -> f32 {
+        match self {
+            IndexedField::Title => 2.0,
+            IndexedField::Tags => 1.5,
+            IndexedField::Content | IndexedField::Author => 1.0,
+        }
+    }
+
+    fn from_search_field(field: &SearchField) -> &'static [IndexedField] {
+        match field {
+            SearchField::Title => &[IndexedField::Title],
+            SearchField::Content => &[IndexedField::Content],
+            SearchField::Tags => &[IndexedField::Tags],
+            SearchField::Author => &[IndexedField::Author],
+            SearchField::All => &IndexedField::ALL,
+        }
+    }
 }
 
 /// Document index entry
@@ -24,8 +96,122 @@ struct DocumentIndex {
     pub tags: Vec<String>,
     /// Author
     pub author: String,
+    /// Document type
+    pub doc_type: DocumentType,
     /// Metadata
     pub metadata: HashMap<String, String>,
+    /// Token count per indexed field
+    pub field_lengths: [usize; FIELD_COUNT],
+    /// Distinct terms with postings for this document
+    pub terms: HashSet<String>,
+    /// Creation timestamp
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Update timestamp
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl DocumentIndex {
+    fn field_text(&self, field: IndexedField) -> String {
+        match field {
+            IndexedField::Title => self.title.clone(),
+            IndexedField::Content => self.content.clone(),
+            IndexedField::Tags => self.tags.join(" "),
+            IndexedField::Author => self.author.clone(),
+        }
+    }
+}
+
+/// A token with its byte span in the source text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Lowercased term
+    pub term: String,
+    /// Byte offset of the token start
+    pub start: usize,
+    /// Byte offset just past the token end
+    pub end: usize,
+}
+
+/// Split text into lowercased alphanumeric tokens, dropping punctuation
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                tokens.push(Token { term: text[s..i].to_lowercase(), start: s, end: i });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(Token { term: text[s..].to_lowercase(), start: s, end: text.len() });
+    }
+
+    tokens
+}
+
+/// Jaccard similarity of two tag sets, compared case-insensitively
+pub fn tag_jaccard(a: &[String], b: &[String]) -> f32 {
+    let a: HashSet<String> = a.iter().map(|t| t.to_lowercase()).collect();
+    let b: HashSet<String> = b.iter().map(|t| t.to_lowercase()).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+/// Cosine similarity of the term-frequency vectors of two texts
+pub fn content_cosine(a: &str, b: &str) -> f32 {
+    let tf = |text: &str| {
+        let mut counts: HashMap<String, f32> = HashMap::new();
+        for token in tokenize(text) {
+            *counts.entry(token.term).or_insert(0.0) += 1.0;
+        }
+        counts
+    };
+    let (a, b) = (tf(a), tf(b));
+
+    let dot: f32 = a.iter().filter_map(|(term, x)| b.get(term).map(|y| x * y)).sum();
+    let norm = |v: &HashMap<String, f32>| v.values().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(&a) * norm(&b);
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    dot / denominator
+}
+
+/// Index a document for search and record the outcome on its `ProcessingComponent`
+///
+/// Sets `indexed` on success; otherwise it is cleared and the error is appended
+/// to `processing_errors`. Re-indexing replaces the document's previous entry.
+pub fn index_for_search(document: &mut Document, search: &mut DocumentSearchService) -> bool {
+    let mut processing = document
+        .get_component::<ProcessingComponent>()
+        .cloned()
+        .unwrap_or_default();
+    processing.indexed = false;
+
+    let indexed = match SearchIndexProjection::from_document(document) {
+        Ok(projection) => {
+            search.index_projection(&projection);
+            processing.indexed = true;
+            true
+        }
+        Err(e) => {
+            processing.processing_errors.push(format!("Indexing failed: {e}"));
+            false
+        }
+    };
+
+    document
+        .replace_component(processing, "indexer", Some("Search indexing".to_string()))
+        .expect("replacing a component clears its slot first");
+    indexed
 }
 
 impl Default for DocumentSearchService {
@@ -39,82 +225,307 @@ impl DocumentSearchService {
     pub fn new() -> Self {
         Self {
             index: HashMap::new(),
+            postings: HashMap::new(),
+            limits: QueryLimits::default(),
         }
     }
 
-    /// Index a document
+    /// Reject queries exceeding `limits` instead of the defaults
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Index a document, replacing any previous entry for the same ID
     pub fn index_document(&mut self, document: &DocumentFullView) -> Result<()> {
-        let index_entry = DocumentIndex {
-            document_id: document.id.clone(),
+        self.insert_entry(DocumentIndex {
+            document_id: document.id,
             title: document.title.clone(),
             content: document.content.clone(),
             tags: document.tags.clone(),
             author: document.author.to_string(),
+            doc_type: document.doc_type.clone(),
             metadata: document.metadata.clone(),
-        };
-
-        self.index.insert(document.id.clone(), index_entry);
+            field_lengths: [0; FIELD_COUNT],
+            terms: HashSet::new(),
+            created_at: document.created_at,
+            updated_at: document.updated_at,
+        });
         Ok(())
     }
 
-    /// Search documents
-    pub fn search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-        let mut results = Vec::new();
-
-        for (_, index) in &self.index {
-            if self.matches_query(index, query) {
-                let score = self.calculate_score(index, &query.query);
-                results.push(SearchResult {
-                    document_id: index.document_id.clone(),
-                    title: index.title.clone(),
-                    snippet: self.generate_snippet(&index.content, &query.query),
-                    score,
-                    highlights: self.find_highlights(&index.content, &query.query),
+    /// Index an aggregate's search projection, replacing any previous entry
+    ///
+    /// The description stands in for content, and authors are indexed together.
+    pub fn index_projection(&mut self, projection: &SearchIndexProjection) {
+        let metadata = HashMap::from([
+            ("mime_type".to_string(), projection.mime_type.clone()),
+            ("content_cid".to_string(), projection.content_cid.clone()),
+        ]);
+        let authors: Vec<String> = projection.authors.iter().map(|a| a.to_string()).collect();
+
+        self.insert_entry(DocumentIndex {
+            document_id: DocumentId::from(projection.document_id),
+            title: projection.title.clone(),
+            content: projection.description.clone().unwrap_or_default(),
+            tags: projection.tags.clone(),
+            author: authors.join(" "),
+            doc_type: DocumentType::from_mime(&projection.mime_type),
+            metadata,
+            field_lengths: [0; FIELD_COUNT],
+            terms: HashSet::new(),
+            created_at: projection.created_at,
+            updated_at: projection.modified_at,
+        });
+    }
+
+    /// Replace the entry for a document and rebuild its postings
+    fn insert_entry(&mut self, mut index_entry: DocumentIndex) {
+        let document_id = index_entry.document_id;
+        self.remove_document(&document_id);
+
+        index_entry.terms.clear();
+        for field in IndexedField::ALL {
+            let tokens = tokenize(&index_entry.field_text(field));
+            index_entry.field_lengths[field as usize] = tokens.len();
+            for token in tokens {
+                let frequencies = self.postings
+                    .entry(token.term.clone())
+                    .or_default()
+                    .entry(document_id)
+                    .or_insert([0; FIELD_COUNT]);
+                frequencies[field as usize] += 1;
+                index_entry.terms.insert(token.term);
+            }
+        }
+
+        self.index.insert(document_id, index_entry);
+    }
+
+    /// Update the index from one event, returning whether it changed
+    ///
+    /// Creation and upload events add an entry; metadata, content and tag
+    /// events update an existing one; a hard delete removes it. Other events,
+    /// and updates to documents that are not indexed, are ignored.
+    pub fn apply_event(&mut self, event: &DocumentDomainEvent) -> bool {
+        let document_id = match event {
+            DocumentDomainEvent::DocumentCreated(e) => {
+                self.insert_entry(DocumentIndex {
+                    document_id: e.document_id,
+                    title: e.title.clone(),
+                    content: String::new(),
+                    tags: Vec::new(),
+                    author: e.author_id.to_string(),
+                    doc_type: e.document_type.clone(),
+                    metadata: e.metadata.clone(),
+                    field_lengths: [0; FIELD_COUNT],
+                    terms: HashSet::new(),
+                    created_at: e.created_at,
+                    updated_at: e.created_at,
                 });
+                return true;
+            }
+            DocumentDomainEvent::DocumentUploaded(e) => {
+                self.insert_entry(DocumentIndex {
+                    document_id: e.document_id,
+                    title: e.metadata.title.clone(),
+                    content: e.metadata.description.clone().unwrap_or_default(),
+                    tags: e.metadata.tags.clone(),
+                    author: e.uploaded_by.clone(),
+                    doc_type: e.document_type.clone(),
+                    metadata: HashMap::new(),
+                    field_lengths: [0; FIELD_COUNT],
+                    terms: HashSet::new(),
+                    created_at: e.uploaded_at,
+                    updated_at: e.uploaded_at,
+                });
+                return true;
+            }
+            DocumentDomainEvent::DocumentDeleted(e) if e.hard_delete => {
+                return self.remove_document(&e.document_id);
             }
+            DocumentDomainEvent::DocumentMetadataUpdated(e) => e.document_id,
+            DocumentDomainEvent::ContentUpdated(e) => e.document_id,
+            DocumentDomainEvent::DocumentTagged(e) => e.document_id,
+            _ => return false,
+        };
+
+        let Some(mut entry) = self.index.get(&document_id).cloned() else {
+            return false;
+        };
+        match event {
+            DocumentDomainEvent::DocumentMetadataUpdated(e) => {
+                entry.title = e.metadata.title.clone();
+                entry.tags = e.metadata.tags.clone();
+            }
+            DocumentDomainEvent::ContentUpdated(e) => {
+                entry.content = e.content_blocks.iter()
+                    .map(|block| block.content.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+            }
+            DocumentDomainEvent::DocumentTagged(e) => entry.tags = e.all_tags.clone(),
+            _ => {}
         }
+        entry.updated_at = event.occurred_at();
+        self.insert_entry(entry);
+        true
+    }
 
-        // Sort by score
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    /// Remove a document from the index
+    ///
+    /// Only the postings of the document's own terms are touched.
+    pub fn remove_document(&mut self, document_id: &DocumentId) -> bool {
+        let Some(entry) = self.index.remove(document_id) else {
+            return false;
+        };
+        for term in &entry.terms {
+            if let Some(docs) = self.postings.get_mut(term) {
+                docs.remove(document_id);
+                if docs.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+        true
+    }
+
+    /// Number of indexed documents
+    pub fn document_count(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Search documents
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<DocumentSearchView>> {
+        self.limits.check_search_query(query)?;
+        let terms = self.expand_terms(&unique_terms(&query.query), query.fuzzy);
+        let mut results = self.matching(query, &terms);
+
+        results.sort_by(|(a, score_a), (b, score_b)| {
+            let ordering = match query.sort.field.as_str() {
+                "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+                "created_at" => a.created_at.cmp(&b.created_at),
+                "updated_at" => a.updated_at.cmp(&b.updated_at),
+                _ => score_a.partial_cmp(score_b).unwrap_or(Ordering::Equal),
+            };
+            let ordering = match query.sort.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            };
+            ordering.then_with(|| a.document_id.as_uuid().cmp(b.document_id.as_uuid()))
+        });
 
         // Apply pagination
-        let start = query.pagination.page * query.pagination.size;
-        let _end = start + query.pagination.size;
+        let start = query.pagination.page.saturating_mul(query.pagination.size);
         let paginated = results.into_iter()
             .skip(start)
             .take(query.pagination.size)
+            .map(|(index, score)| {
+                let matches = self.find_highlights(&index.content, &terms);
+                let (snippet, highlights) = self.generate_snippet(&index.content, &matches);
+                DocumentSearchView {
+                    document_id: index.document_id,
+                    title: index.title.clone(),
+                    snippet,
+                    score,
+                    highlights,
+                }
+            })
             .collect();
 
         Ok(paginated)
     }
 
-    /// Check if document matches query
-    fn matches_query(&self, index: &DocumentIndex, query: &SearchQuery) -> bool {
-        // Check text match in specified fields
-        let text_match = query.fields.iter().any(|field| {
-            let text = match field {
-                SearchField::Title => &index.title,
-                SearchField::Content => &index.content,
-                SearchField::Tags => &index.tags.join(" "),
-                SearchField::Author => &index.author,
-                SearchField::All => {
-                    return self.contains_text(&index.title, &query.query) ||
-                           self.contains_text(&index.content, &query.query) ||
-                           self.contains_text(&index.tags.join(" "), &query.query) ||
-                           self.contains_text(&index.author, &query.query);
+    /// Tag, type and author counts over every document matching `query`
+    ///
+    /// Counts cover the whole filtered result set; pagination is ignored.
+    pub fn facets(&self, query: &SearchQuery) -> Facets {
+        let terms = self.expand_terms(&unique_terms(&query.query), query.fuzzy);
+        let mut facets = Facets::default();
+        for (index, _) in self.matching(query, &terms) {
+            let tags: HashSet<&String> = index.tags.iter().collect();
+            for tag in tags {
+                *facets.tags.entry(tag.clone()).or_default() += 1;
+            }
+            *facets.doc_types.entry(index.doc_type.clone()).or_default() += 1;
+            // Projections index several authors as one space-separated field
+            for author in index.author.split_whitespace() {
+                *facets.authors.entry(author.to_string()).or_default() += 1;
+            }
+        }
+        facets
+    }
+
+    /// Documents matching the query terms and filters, with their scores, unordered
+    fn matching(&self, query: &SearchQuery, terms: &[(String, f32)]) -> Vec<(&DocumentIndex, f32)> {
+        let fields = Self::scoped_fields(&query.fields);
+
+        let candidates: Vec<&DocumentIndex> = if terms.is_empty() {
+            self.index.values().collect()
+        } else {
+            let mut ids = HashSet::new();
+            for (term, _) in terms {
+                if let Some(docs) = self.postings.get(term) {
+                    ids.extend(docs.iter()
+                        .filter(|(_, freqs)| fields.iter().any(|f| freqs[*f as usize] > 0))
+                        .map(|(id, _)| *id));
                 }
-            };
-            self.contains_text(text, &query.query)
-        });
+            }
+            ids.iter().filter_map(|id| self.index.get(id)).collect()
+        };
 
-        if !text_match {
-            return false;
+        candidates
+            .into_iter()
+            .filter(|index| query.filters.iter().all(|filter| self.matches_filter(index, filter)))
+            .map(|index| (index, self.calculate_score(index, terms, &fields)))
+            .collect()
+    }
+
+    /// Map query terms to indexed terms with a match weight
+    ///
+    /// Exact terms weigh 1.0. In fuzzy mode, indexed terms within the allowed
+    /// edit distance are added with a penalty proportional to that distance.
+    fn expand_terms(&self, query_terms: &[String], fuzzy: bool) -> Vec<(String, f32)> {
+        let mut expanded: HashMap<String, f32> = HashMap::new();
+
+        for term in query_terms {
+            expanded.insert(term.clone(), 1.0);
+            if !fuzzy {
+                continue;
+            }
+            let max_distance = max_edit_distance(term);
+            if max_distance == 0 {
+                continue;
+            }
+            for candidate in self.postings.keys() {
+                if candidate == term {
+                    continue;
+                }
+                if let Some(distance) = bounded_levenshtein(term, candidate, max_distance) {
+                    let weight = 1.0 - FUZZY_PENALTY_PER_EDIT * distance as f32;
+                    let entry = expanded.entry(candidate.clone()).or_insert(0.0);
+                    *entry = entry.max(weight);
+                }
+            }
         }
 
-        // Apply filters
-        query.filters.iter().all(|filter| {
-            self.matches_filter(index, filter)
-        })
+        expanded.into_iter().collect()
+    }
+
+    /// Indexed fields covered by the requested search fields
+    fn scoped_fields(fields: &[SearchField]) -> Vec<IndexedField> {
+        if fields.is_empty() {
+            return IndexedField::ALL.to_vec();
+        }
+        let mut scoped = Vec::new();
+        for field in fields {
+            for indexed in IndexedField::from_search_field(field) {
+                if !scoped.contains(indexed) {
+                    scoped.push(*indexed);
+                }
+            }
+        }
+        scoped
     }
 
     /// Check if document matches filter
@@ -140,80 +551,205 @@ impl DocumentSearchService {
         }
     }
 
-    /// Simple text search
-    fn contains_text(&self, text: &str, query: &str) -> bool {
-        text.to_lowercase().contains(&query.to_lowercase())
-    }
-
-    /// Calculate relevance score
-    fn calculate_score(&self, index: &DocumentIndex, query: &str) -> f32 {
+    /// BM25 relevance summed over weighted query terms and scoped fields
+    fn calculate_score(&self, index: &DocumentIndex, terms: &[(String, f32)], fields: &[IndexedField]) -> f32 {
+        let doc_count = self.index.len() as f32;
         let mut score = 0.0;
-        let query_lower = query.to_lowercase();
 
-        // Title match is weighted higher
-        if index.title.to_lowercase().contains(&query_lower) {
-            score += 10.0;
-        }
+        for (term, weight) in terms {
+            let Some(docs) = self.postings.get(term) else {
+                continue;
+            };
+            let Some(frequencies) = docs.get(&index.document_id) else {
+                continue;
+            };
 
-        // Count content matches
-        let content_matches = index.content.to_lowercase()
-            .matches(&query_lower)
-            .count() as f32;
-        score += content_matches;
+            let doc_freq = docs.len() as f32;
+            let idf = (1.0 + (doc_count - doc_freq + 0.5) / (doc_freq + 0.5)).ln();
 
-        // Tag matches
-        for tag in &index.tags {
-            if tag.to_lowercase().contains(&query_lower) {
-                score += 5.0;
+            for field in fields {
+                let tf = frequencies[*field as usize] as f32;
+                if tf == 0.0 {
+                    continue;
+                }
+                let length = index.field_lengths[*field as usize] as f32;
+                let avg_length = self.average_field_length(*field).max(1.0);
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * length / avg_length);
+                score += weight * field.weight() * idf * tf * (BM25_K1 + 1.0) / (tf + norm);
             }
         }
 
         score
     }
 
-    /// Generate snippet with context
-    fn generate_snippet(&self, content: &str, query: &str) -> String {
-        let query_lower = query.to_lowercase();
-        let content_lower = content.to_lowercase();
+    fn average_field_length(&self, field: IndexedField) -> f32 {
+        if self.index.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.index.values().map(|i| i.field_lengths[field as usize]).sum();
+        total as f32 / self.index.len() as f32
+    }
+
+    /// Snippet of up to `SNIPPET_LENGTH` bytes around the best cluster of matches
+    ///
+    /// The window covering the most distinct matched terms (then the most
+    /// matches) is widened evenly and trimmed back to word boundaries; cut
+    /// ends are marked with "...". Returns the snippet and the byte spans of
+    /// the matches it contains, relative to the snippet.
+    fn generate_snippet(&self, content: &str, matches: &[(usize, usize)]) -> (String, Vec<(usize, usize)>) {
+        let leading = content.len() - content.trim_start().len();
+        let trailing = content.trim_end().len();
+        if leading >= trailing {
+            return (String::new(), Vec::new());
+        }
+
+        let (keep_start, keep_end) = best_match_window(content, matches).unwrap_or((leading, leading));
+        let slack = SNIPPET_LENGTH.saturating_sub(keep_end - keep_start);
+        let start = keep_start.saturating_sub(slack / 2).max(leading);
+        let end = (start + SNIPPET_LENGTH).max(keep_end).min(trailing);
+        let mut start = floor_char_boundary(content, start.min(end.saturating_sub(SNIPPET_LENGTH)).max(leading));
+        let mut end = ceil_char_boundary(content, end);
 
-        if let Some(pos) = content_lower.find(&query_lower) {
-            let start = pos.saturating_sub(50);
-            let end = (pos + query.len() + 50).min(content.len());
-            
-            let mut snippet = String::new();
-            if start > 0 {
-                snippet.push_str("...");
+        // Don't cut words in half unless a single word fills the window
+        if start > leading && !is_word_break(content, start) {
+            if let Some(offset) = content[start..keep_start.max(start)].find(char::is_whitespace) {
+                start += offset;
             }
-            snippet.push_str(&content[start..end]);
-            if end < content.len() {
-                snippet.push_str("...");
+        }
+        if end < trailing && !is_word_break(content, end) {
+            let floor = keep_end.clamp(start, end);
+            if let Some(offset) = content[floor..end].rfind(char::is_whitespace) {
+                end = floor + offset;
             }
-            snippet
-        } else {
-            // Return first 100 chars if no match found
-            let end = 100.min(content.len());
-            format!("{}...", &content[..end])
         }
+        start += content[start..end].len() - content[start..end].trim_start().len();
+        end = start + content[start..end].trim_end().len();
+
+        let prefix = if start > leading { "..." } else { "" };
+        let mut snippet = format!("{prefix}{}", &content[start..end]);
+        if end < trailing {
+            snippet.push_str("...");
+        }
+        let highlights = matches
+            .iter()
+            .filter(|(match_start, match_end)| *match_start >= start && *match_end <= end)
+            .map(|(match_start, match_end)| {
+                (match_start - start + prefix.len(), match_end - start + prefix.len())
+            })
+            .collect();
+
+        (snippet, highlights)
+    }
+
+    /// Byte spans of query terms in the content
+    fn find_highlights(&self, content: &str, terms: &[(String, f32)]) -> Vec<(usize, usize)> {
+        tokenize(content)
+            .into_iter()
+            .filter(|token| terms.iter().any(|(term, _)| *term == token.term))
+            .map(|token| (token.start, token.end))
+            .collect()
+    }
+}
+
+/// First-to-last span of the matches in the best `SNIPPET_LENGTH` window
+///
+/// Windows start at each match and are ranked by distinct terms, then by match
+/// count; the earliest wins ties.
+fn best_match_window(content: &str, matches: &[(usize, usize)]) -> Option<(usize, usize)> {
+    let mut best: Option<((usize, usize), (usize, usize))> = None;
+    for (i, (first_start, _)) in matches.iter().enumerate() {
+        let window: Vec<&(usize, usize)> = matches[i..]
+            .iter()
+            .take_while(|(_, end)| end - first_start <= SNIPPET_LENGTH)
+            .collect();
+        let Some((_, last_end)) = window.last() else {
+            continue;
+        };
+        let terms: HashSet<String> = window
+            .iter()
+            .map(|(start, end)| content.get(*start..*end).unwrap_or_default().to_lowercase())
+            .collect();
+        let score = (terms.len(), window.len());
+        if best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, (*first_start, *last_end)));
+        }
+    }
+
+    best.map(|(_, (start, end))| {
+        (
+            floor_char_boundary(content, start.min(content.len())),
+            ceil_char_boundary(content, end.min(content.len())),
+        )
+    })
+}
+
+/// Whether `index` falls next to whitespace (or at either end of `s`)
+fn is_word_break(s: &str, index: usize) -> bool {
+    s[..index].chars().next_back().is_none_or(char::is_whitespace)
+        || s[index..].chars().next().is_none_or(char::is_whitespace)
+}
+
+/// Distinct query terms in order of first appearance
+fn unique_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for token in tokenize(query) {
+        if !terms.contains(&token.term) {
+            terms.push(token.term);
+        }
+    }
+    terms
+}
+
+/// Edits tolerated for a query term in fuzzy mode, scaled by its length
+fn max_edit_distance(term: &str) -> usize {
+    match term.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
     }
+}
 
-    /// Find highlight positions
-    fn find_highlights(&self, content: &str, query: &str) -> Vec<(usize, usize)> {
-        let mut highlights = Vec::new();
-        let query_lower = query.to_lowercase();
-        let content_lower = content.to_lowercase();
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds `max`
+fn bounded_levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
 
-        let mut start = 0;
-        while let Some(pos) = content_lower[start..].find(&query_lower) {
-            let absolute_pos = start + pos;
-            highlights.push((absolute_pos, absolute_pos + query.len()));
-            start = absolute_pos + 1;
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
         }
+        if current.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        previous = current;
+    }
 
-        highlights
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+fn floor_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
     }
+    index
+}
+
+fn ceil_char_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index += 1;
+    }
+    index
 }
 
 /// Search result
+#[deprecated(note = "search returns `DocumentSearchView`; use that instead")]
 #[derive(Debug, Clone, PartialEq)]
 pub struct SearchResult {
     /// Document ID
@@ -228,48 +764,464 @@ pub struct SearchResult {
     pub highlights: Vec<(usize, usize)>,
 }
 
+#[allow(deprecated)]
+impl From<DocumentSearchView> for SearchResult {
+    fn from(view: DocumentSearchView) -> Self {
+        Self {
+            document_id: view.document_id,
+            title: view.title,
+            snippet: view.snippet,
+            score: view.score,
+            highlights: view.highlights,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value_objects::{DocumentVersion, DocumentType, SearchPagination, SearchSort, SortDirection};
+    use crate::value_objects::{DocumentVersion, DocumentType, SearchPagination, SearchSort};
     use uuid::Uuid;
 
-    #[test]
-    fn test_document_search() {
-        let mut service = DocumentSearchService::new();
-
-        // Create test document
-        let doc = DocumentFullView {
+    fn create_doc(title: &str, content: &str, tags: &[&str]) -> DocumentFullView {
+        DocumentFullView {
             id: DocumentId::new(),
-            title: "Test Document".to_string(),
-            content: "This is a test document with some content about testing.".to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
             version: DocumentVersion::new(1, 0, 0),
             doc_type: DocumentType::Report,
-            tags: vec!["test".to_string(), "example".to_string()],
+            tags: tags.iter().map(|t| t.to_string()).collect(),
             author: Uuid::new_v4(),
             metadata: HashMap::new(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-        };
-
-        // Index document
-        service.index_document(&doc).unwrap();
+        }
+    }
 
-        // Search for document
-        let query = SearchQuery {
-            query: "test".to_string(),
+    fn create_query(text: &str, pagination: SearchPagination) -> SearchQuery {
+        SearchQuery {
+            query: text.to_string(),
             fields: vec![SearchField::All],
             filters: vec![],
             sort: SearchSort {
                 field: "score".to_string(),
                 direction: SortDirection::Descending,
             },
-            pagination: SearchPagination::default(),
-        };
+            pagination,
+            fuzzy: false,
+        }
+    }
 
-        let results = service.search(&query).unwrap();
+    #[test]
+    fn test_document_search() {
+        let mut service = DocumentSearchService::new();
+
+        // Create test document
+        let doc = create_doc(
+            "Test Document",
+            "This is a test document with some content about testing.",
+            &["test", "example"],
+        );
+
+        // Index document
+        service.index_document(&doc).unwrap();
+
+        let results = service.search(&create_query("test", SearchPagination::default())).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].document_id, doc.id);
         assert!(results[0].score > 0.0);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_index_for_search_sets_indexed_flag() {
+        use crate::aggregate::{ClassificationComponent, ConfidentialityLevel, DocumentInfoComponent};
+
+        let info = DocumentInfoComponent::builder()
+            .with_title("Vendor Contract")
+            .with_description("Signed agreement")
+            .with_mime_type("application/pdf")
+            .with_size_bytes(10)
+            .build()
+            .unwrap();
+        let mut document = Document::new(cim_domain::EntityId::new(), info, cid::Cid::default()).unwrap();
+        document.add_component(ClassificationComponent {
+            document_type: "Contract".to_string(),
+            category: "Legal".to_string(),
+            subcategories: vec![],
+            tags: vec!["procurement".to_string()],
+            confidentiality: ConfidentialityLevel::Internal,
+        }, "test", None).unwrap();
+        let mut service = DocumentSearchService::new();
+
+        assert!(index_for_search(&mut document, &mut service));
+        assert!(index_for_search(&mut document, &mut service));
+
+        assert_eq!(service.document_count(), 1);
+        let mut query = create_query("procurement", SearchPagination::default());
+        query.fields = vec![SearchField::Tags];
+        let results = service.search(&query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document_id, DocumentId::from(cim_domain::AggregateRoot::id(&document)));
+
+        let processing = document.get_component::<ProcessingComponent>().unwrap();
+        assert!(processing.indexed);
+        assert!(processing.processing_errors.is_empty());
+    }
+
+    #[test]
+    fn test_failed_reindex_clears_indexed_flag() {
+        use crate::aggregate::{ContentAddressComponent, DocumentInfoComponent};
+
+        let info = DocumentInfoComponent::builder()
+            .with_title("Draft")
+            .with_mime_type("text/plain")
+            .build()
+            .unwrap();
+        let mut document = Document::new(cim_domain::EntityId::new(), info, cid::Cid::default()).unwrap();
+        let mut service = DocumentSearchService::new();
+        assert!(index_for_search(&mut document, &mut service));
+
+        document.remove_component::<ContentAddressComponent>().unwrap();
+        assert!(!index_for_search(&mut document, &mut service));
+
+        let processing = document.get_component::<ProcessingComponent>().unwrap();
+        assert!(!processing.indexed);
+        assert_eq!(processing.processing_errors.len(), 1);
+    }
+
+    #[test]
+    fn test_tokenize_lowercases_and_strips_punctuation() {
+        let tokens = tokenize("Hello, World! It's v2.");
+        let terms: Vec<&str> = tokens.iter().map(|t| t.term.as_str()).collect();
+
+        assert_eq!(terms, vec!["hello", "world", "it", "s", "v2"]);
+        assert_eq!((tokens[1].start, tokens[1].end), (7, 12));
+    }
+
+    #[test]
+    fn test_ranking_orders_by_relevance() {
+        let mut service = DocumentSearchService::new();
+        let title_hit = create_doc("Budget Review", "Numbers for the quarter", &[]);
+        let dense = create_doc("Notes", "budget budget budget planning", &[]);
+        let sparse = create_doc("Minutes", "we briefly discussed the budget among many other long agenda items today", &[]);
+        let unrelated = create_doc("Roadmap", "Features and milestones", &[]);
+        for doc in [&title_hit, &dense, &sparse, &unrelated] {
+            service.index_document(doc).unwrap();
+        }
+
+        let results = service.search(&create_query("budget", SearchPagination::default())).unwrap();
+        let ids: Vec<DocumentId> = results.iter().map(|r| r.document_id).collect();
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[2], sparse.id);
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(!ids.contains(&unrelated.id));
+    }
+
+    #[test]
+    fn test_field_scoping() {
+        let mut service = DocumentSearchService::new();
+        let in_title = create_doc("Security Policy", "Rules", &[]);
+        let in_content = create_doc("Handbook", "Security rules for staff", &[]);
+        service.index_document(&in_title).unwrap();
+        service.index_document(&in_content).unwrap();
+
+        let mut query = create_query("security", SearchPagination::default());
+        query.fields = vec![SearchField::Title];
+        let results = service.search(&query).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document_id, in_title.id);
+    }
+
+    #[test]
+    fn test_pagination_returns_disjoint_pages() {
+        let mut service = DocumentSearchService::new();
+        for i in 0..7 {
+            service.index_document(&create_doc(&format!("Report {i}"), "quarterly report", &[])).unwrap();
+        }
+
+        let page = |n| service
+            .search(&create_query("report", SearchPagination { page: n, size: 3 }))
+            .unwrap()
+            .into_iter()
+            .map(|r| r.document_id)
+            .collect::<Vec<_>>();
+
+        let (first, second, third) = (page(0), page(1), page(2));
+        assert_eq!((first.len(), second.len(), third.len()), (3, 3, 1));
+        let all: HashSet<DocumentId> = first.iter().chain(&second).chain(&third).copied().collect();
+        assert_eq!(all.len(), 7);
+        assert!(page(usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_reindex_replaces_postings() {
+        let mut service = DocumentSearchService::new();
+        let mut doc = create_doc("Draft", "alpha", &[]);
+        service.index_document(&doc).unwrap();
+
+        doc.content = "beta".to_string();
+        service.index_document(&doc).unwrap();
+
+        assert!(service.search(&create_query("alpha", SearchPagination::default())).unwrap().is_empty());
+        assert_eq!(service.search(&create_query("beta", SearchPagination::default())).unwrap().len(), 1);
+        assert_eq!(service.document_count(), 1);
+    }
+
+    #[test]
+    fn test_remove_document_drops_only_its_postings() {
+        let mut service = DocumentSearchService::new();
+        let kept = create_doc("Shared", "common kept", &[]);
+        let removed = create_doc("Shared", "common gone", &[]);
+        service.index_document(&kept).unwrap();
+        service.index_document(&removed).unwrap();
+
+        assert!(service.remove_document(&removed.id));
+        assert!(!service.remove_document(&removed.id));
+
+        assert!(!service.postings.contains_key("gone"));
+        assert_eq!(service.postings["common"].keys().collect::<Vec<_>>(), vec![&kept.id]);
+        assert_eq!(service.search(&create_query("common", SearchPagination::default())).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_highlights_match_whole_tokens() {
+        let mut service = DocumentSearchService::new();
+        let doc = create_doc("Guide", "Search, research and searching: search!", &[]);
+        service.index_document(&doc).unwrap();
+
+        let results = service.search(&create_query("SEARCH", SearchPagination::default())).unwrap();
+        assert_eq!(results[0].highlights, vec![(0, 6), (32, 38)]);
+    }
+
+    #[test]
+    fn test_snippet_for_empty_and_short_content() {
+        let service = DocumentSearchService::new();
+
+        assert_eq!(service.generate_snippet("", &[]), (String::new(), vec![]));
+        assert_eq!(service.generate_snippet("   \n\t ", &[]), (String::new(), vec![]));
+        assert_eq!(service.generate_snippet("a", &[]), ("a".to_string(), vec![]));
+        assert_eq!(service.generate_snippet("a", &[(0, 1)]), ("a".to_string(), vec![(0, 1)]));
+        assert_eq!(service.generate_snippet("  a b", &[(4, 5)]), ("a b".to_string(), vec![(2, 3)]));
+    }
+
+    #[test]
+    fn test_snippet_respects_char_boundaries() {
+        let service = DocumentSearchService::new();
+        let content = "é".repeat(120);
+
+        let (snippet, _) = service.generate_snippet(&content, &[]);
+        assert!(snippet.ends_with("..."));
+        assert!(snippet.starts_with('é'));
+
+        let (snippet, _) = service.generate_snippet(&content, &[(101, 103)]);
+        assert!(snippet.starts_with("...é"));
+    }
+
+    #[test]
+    fn test_snippet_centres_on_mid_document_match() {
+        let mut service = DocumentSearchService::new();
+        let filler = "lorem ipsum dolor sit amet consectetur adipiscing elit ".repeat(10);
+        let content = format!("{filler}the kubernetes cluster migration finished early {filler}");
+        let doc = create_doc("Ops Log", &content, &[]);
+        service.index_document(&doc).unwrap();
+
+        let results = service.search(&create_query("kubernetes migration", SearchPagination::default())).unwrap();
+        let result = &results[0];
+
+        assert!(result.snippet.starts_with("...") && result.snippet.ends_with("..."));
+        assert!(result.snippet.len() <= SNIPPET_LENGTH + 6);
+        let spans: Vec<&str> = result.highlights.iter().map(|(start, end)| &result.snippet[*start..*end]).collect();
+        assert_eq!(spans, vec!["kubernetes", "migration"]);
+
+        // Only whole words of the content are shown
+        let body = &result.snippet[3..result.snippet.len() - 3];
+        let words: HashSet<&str> = content.split_whitespace().collect();
+        assert!(body.split_whitespace().all(|word| words.contains(word)), "{body}");
+    }
+
+    #[test]
+    fn test_snippet_prefers_window_with_most_terms() {
+        let service = DocumentSearchService::new();
+        let filler = "word ".repeat(60);
+        let content = format!("alpha {filler}alpha beta {filler}");
+        let alpha_first = (0, 5);
+        let alpha_second = (6 + filler.len(), 11 + filler.len());
+        let beta = (12 + filler.len(), 16 + filler.len());
+
+        let (snippet, highlights) = service.generate_snippet(&content, &[alpha_first, alpha_second, beta]);
+
+        assert_eq!(highlights.len(), 2);
+        assert_eq!(&snippet[highlights[0].0..highlights[0].1], "alpha");
+        assert_eq!(&snippet[highlights[1].0..highlights[1].1], "beta");
+    }
+
+    #[test]
+    fn test_fuzzy_matches_typos() {
+        let mut service = DocumentSearchService::new();
+        let doc = create_doc("Annual Report", "Financial summary", &[]);
+        service.index_document(&doc).unwrap();
+
+        let mut query = create_query("reprot", SearchPagination::default());
+        assert!(service.search(&query).unwrap().is_empty());
+
+        query.fuzzy = true;
+        let results = service.search(&query).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document_id, doc.id);
+    }
+
+    #[test]
+    fn test_fuzzy_rejects_three_edits() {
+        let mut service = DocumentSearchService::new();
+        service.index_document(&create_doc("Annual Report", "Financial summary", &[])).unwrap();
+
+        let mut query = create_query("rxxxrt", SearchPagination::default());
+        query.fuzzy = true;
+
+        assert!(service.search(&query).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_exact_match_outranks_fuzzy() {
+        let mut service = DocumentSearchService::new();
+        let fuzzy_hit = create_doc("Quarterly", "the report is ready", &[]);
+        let exact_hit = create_doc("Quarterly", "the reprot is ready", &[]);
+        service.index_document(&fuzzy_hit).unwrap();
+        service.index_document(&exact_hit).unwrap();
+
+        let mut query = create_query("reprot", SearchPagination::default());
+        query.fuzzy = true;
+        let results = service.search(&query).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].document_id, exact_hit.id);
+        assert!(results[0].score > results[1].score);
+    }
+
+    fn tag_query(tag: &str) -> SearchQuery {
+        SearchQuery {
+            fields: vec![SearchField::Tags],
+            ..create_query(tag, SearchPagination::default())
+        }
+    }
+
+    #[test]
+    fn test_apply_retag_event_updates_tag_matches() {
+        let mut service = DocumentSearchService::new();
+        let doc = create_doc("Roadmap", "Plans for next year", &["draft", "planning"]);
+        service.index_document(&doc).unwrap();
+        assert_eq!(service.search(&tag_query("draft")).unwrap().len(), 1);
+
+        let retagged = DocumentDomainEvent::DocumentTagged(crate::events::DocumentTagged {
+            document_id: doc.id,
+            tags: vec!["approved".to_string()],
+            all_tags: vec!["approved".to_string(), "planning".to_string()],
+            tagged_by: "alice".to_string(),
+            tagged_at: chrono::Utc::now(),
+        });
+        assert!(service.apply_event(&retagged));
+
+        assert!(service.search(&tag_query("draft")).unwrap().is_empty());
+        let results = service.search(&tag_query("approved")).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].document_id, doc.id);
+        assert_eq!(service.search(&tag_query("planning")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_apply_create_and_delete_events() {
+        let mut service = DocumentSearchService::new();
+        let document_id = DocumentId::new();
+        let created = DocumentDomainEvent::DocumentCreated(crate::events::DocumentCreated {
+            document_id,
+            document_type: DocumentType::Proposal,
+            title: "Vendor Proposal".to_string(),
+            author_id: Uuid::new_v4(),
+            metadata: HashMap::new(),
+            created_at: chrono::Utc::now(),
+        });
+        let deleted = |hard_delete| DocumentDomainEvent::DocumentDeleted(crate::events::DocumentDeleted {
+            document_id,
+            hard_delete,
+            reason: None,
+            deleted_by: Uuid::new_v4(),
+            deleted_at: chrono::Utc::now(),
+        });
+
+        assert!(service.apply_event(&created));
+        assert_eq!(service.search(&create_query("vendor", SearchPagination::default())).unwrap().len(), 1);
+
+        assert!(!service.apply_event(&deleted(false)));
+        assert_eq!(service.document_count(), 1);
+        assert!(service.apply_event(&deleted(true)));
+        assert_eq!(service.document_count(), 0);
+        assert!(service.search(&create_query("vendor", SearchPagination::default())).unwrap().is_empty());
+
+        let retag_missing = DocumentDomainEvent::DocumentTagged(crate::events::DocumentTagged {
+            document_id,
+            tags: vec![],
+            all_tags: vec!["orphan".to_string()],
+            tagged_by: "alice".to_string(),
+            tagged_at: chrono::Utc::now(),
+        });
+        assert!(!service.apply_event(&retag_missing));
+    }
+
+    #[test]
+    fn test_facets_count_the_matching_set() {
+        let mut service = DocumentSearchService::new();
+        let mut docs = vec![
+            create_doc("Budget review", "Quarterly budget numbers", &["finance", "q3"]),
+            create_doc("Budget forecast", "Next year's budget", &["finance"]),
+            create_doc("Budget memo", "Budget cuts for travel", &["finance", "travel"]),
+            create_doc("Offsite agenda", "Team offsite schedule", &["events"]),
+        ];
+        docs[1].doc_type = DocumentType::Proposal;
+        docs[2].author = docs[0].author;
+        for doc in &docs {
+            service.index_document(doc).unwrap();
+        }
+
+        let facets = service.facets(&create_query("budget", SearchPagination { page: 0, size: 1 }));
+
+        assert_eq!(facets.tags["finance"], 3);
+        assert_eq!(facets.tags["q3"], 1);
+        assert_eq!(facets.tags["travel"], 1);
+        assert!(!facets.tags.contains_key("events"));
+        assert_eq!(facets.doc_types[&DocumentType::Report], 2);
+        assert_eq!(facets.doc_types[&DocumentType::Proposal], 1);
+        assert_eq!(facets.authors[&docs[0].author.to_string()], 2);
+        assert_eq!(facets.authors[&docs[1].author.to_string()], 1);
+        assert!(!facets.authors.contains_key(&docs[3].author.to_string()));
+    }
+
+    #[test]
+    fn test_bounded_levenshtein() {
+        assert_eq!(bounded_levenshtein("report", "reprot", 2), Some(2));
+        assert_eq!(bounded_levenshtein("report", "report", 2), Some(0));
+        assert_eq!(bounded_levenshtein("report", "rxxxrt", 2), None);
+        assert_eq!(bounded_levenshtein("cat", "cats", 1), Some(1));
+    }
+
+    #[test]
+    fn test_tag_jaccard() {
+        let a = vec!["Rust".to_string(), "async".to_string()];
+        let b = vec!["rust".to_string(), "web".to_string()];
+
+        assert!((tag_jaccard(&a, &b) - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(tag_jaccard(&a, &a), 1.0);
+        assert_eq!(tag_jaccard(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_content_cosine() {
+        assert!((content_cosine("rust web server", "Rust web server") - 1.0).abs() < 1e-6);
+        assert_eq!(content_cosine("rust web", "bread recipes"), 0.0);
+        assert_eq!(content_cosine("", "anything"), 0.0);
+
+        let partial = content_cosine("async rust server", "async rust runtime");
+        assert!(partial > 0.0 && partial < 1.0);
+    }
+}
@@ -1,14 +1,27 @@
 //! Document template service
 
-use crate::value_objects::{DocumentTemplate, TemplateId, TemplateVariable, VariableType}; // TemplateVariable used in tests
+use crate::aggregate::{ClassificationComponent, Document, DocumentInfoComponent};
+use crate::events::TemplateApplied;
+use crate::value_objects::{DocumentId, DocumentTemplate, DocumentType, TemplateId, VariableType};
+use crate::value_objects::DocumentVersion;
+use super::{Change, ObjectStore, VersionComparisonService};
 use anyhow::{Result, anyhow};
-use std::collections::HashMap;
+use cim_domain::EntityId;
+use uuid::Uuid;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
 use regex::Regex;
+use serde_json::Value;
 
 /// Template service for document generation
+///
+/// Templates are kept in a [`TemplateRegistry`]; lookups without a version
+/// use the latest registered one.
 pub struct TemplateService {
     /// Template repository
-    templates: HashMap<TemplateId, DocumentTemplate>,
+    registry: TemplateRegistry,
+    /// Fail on placeholders that no variable resolves
+    strict_placeholders: bool,
 }
 
 impl Default for TemplateService {
@@ -21,65 +34,65 @@ impl TemplateService {
     /// Create new template service
     pub fn new() -> Self {
         Self {
-            templates: HashMap::new(),
+            registry: TemplateRegistry::new(),
+            strict_placeholders: false,
         }
     }
 
-    /// Register a template
+    /// Reject renders that would leave undeclared `{{placeholders}}` in the
+    /// output, instead of keeping them as literal text
+    pub fn with_strict_placeholders(mut self, strict: bool) -> Self {
+        self.strict_placeholders = strict;
+        self
+    }
+
+    /// Register a version of a template; a version can only be registered once
     pub fn register_template(&mut self, template: DocumentTemplate) -> Result<()> {
-        self.templates.insert(template.id, template);
-        Ok(())
+        self.registry.register(template)
     }
 
-    /// Get template by ID
+    /// Every registered version of each template
+    pub fn registry(&self) -> &TemplateRegistry {
+        &self.registry
+    }
+
+    /// Latest version of a template by ID
     pub fn get_template(&self, id: &TemplateId) -> Option<&DocumentTemplate> {
-        self.templates.get(id)
+        self.registry.latest(id)
     }
 
-    /// Apply template with variables
+    /// Apply the latest version of a template with variables
     pub fn apply_template(
         &self,
         template_id: &TemplateId,
         variables: &HashMap<String, String>,
     ) -> Result<String> {
-        let template = self.templates.get(template_id)
-            .ok_or_else(|| anyhow!("Template not found"))?;
-
-        // Validate required variables
-        for var in &template.required_variables {
-            if var.required && !variables.contains_key(&var.name)
-                && var.default_value.is_none() {
-                    return Err(anyhow!("Required variable '{}' not provided", var.name));
-                }
-        }
+        self.apply_template_version(template_id, None, variables)
+    }
 
-        // Apply variable substitution
-        let content = template.content.clone();
-        
-        // Use regex to find all variable placeholders
-        let re = Regex::new(r"\{\{([^}]+)\}\}").unwrap();
-        
-        let result = re.replace_all(&content, |caps: &regex::Captures| {
-            let var_name = caps[1].trim();
-            
-            // Check provided variables first
-            if let Some(value) = variables.get(var_name) {
-                return value.clone();
-            }
-            
-            // Check defaults
-            if let Some(var_def) = template.required_variables.iter()
-                .find(|v| v.name == var_name) {
-                if let Some(default) = &var_def.default_value {
-                    return default.clone();
-                }
-            }
-            
-            // Keep placeholder if not found
-            caps[0].to_string()
-        });
+    /// Apply a template at `version`, or at the latest version when `None`
+    pub fn apply_template_version(
+        &self,
+        template_id: &TemplateId,
+        version: Option<&DocumentVersion>,
+        variables: &HashMap<String, String>,
+    ) -> Result<String> {
+        render(self.registry.resolve(template_id, version)?, variables, self.strict_placeholders)
+    }
 
-        Ok(result.to_string())
+    /// [`TemplateRegistry::instantiate`] against this service's templates
+    pub fn instantiate(
+        &self,
+        template_id: &TemplateId,
+        version: Option<&DocumentVersion>,
+        document_type: &DocumentType,
+        values: &HashMap<String, String>,
+        store: &dyn ObjectStore,
+        by: Uuid,
+    ) -> Result<(Document, TemplateApplied)> {
+        let template = self.registry.resolve(template_id, version)?;
+        let content = render(template, values, self.strict_placeholders)?;
+        store_rendered(template, document_type, content, values, store, by)
     }
 
     /// Validate variables against template
@@ -88,8 +101,7 @@ impl TemplateService {
         template_id: &TemplateId,
         variables: &HashMap<String, String>,
     ) -> Result<Vec<ValidationError>> {
-        let template = self.templates.get(template_id)
-            .ok_or_else(|| anyhow!("Template not found"))?;
+        let template = self.registry.resolve(template_id, None)?;
 
         let mut errors = Vec::new();
 
@@ -125,10 +137,14 @@ impl TemplateService {
                         }
                     }
                     VariableType::List(options) => {
-                        if !options.contains(val) {
+                        let allowed = |item: &Value| {
+                            options.is_empty()
+                                || item.as_str().is_some_and(|item| options.iter().any(|o| o == item))
+                        };
+                        if !list_items(val).iter().all(allowed) {
                             errors.push(ValidationError {
                                 variable: var.name.clone(),
-                                error: format!("Value must be one of: {}", options.join(", ")),
+                                error: format!("Values must be among: {}", options.join(", ")),
                             });
                         }
                     }
@@ -147,19 +163,228 @@ impl TemplateService {
         Ok(errors)
     }
 
-    /// List available templates
+    /// List available templates, latest version of each
     pub fn list_templates(&self) -> Vec<&DocumentTemplate> {
-        self.templates.values().collect()
+        self.registry.latest_versions().collect()
     }
 
-    /// Search templates by category
+    /// Search templates by category, latest version of each
     pub fn find_by_category(&self, category: &str) -> Vec<&DocumentTemplate> {
-        self.templates.values()
+        self.registry.latest_versions()
             .filter(|t| t.category == category)
             .collect()
     }
 }
 
+/// Every registered version of each template
+#[derive(Debug, Default)]
+pub struct TemplateRegistry {
+    versions: HashMap<TemplateId, BTreeMap<DocumentVersion, DocumentTemplate>>,
+}
+
+impl TemplateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a version of a template; a version can only be registered once
+    pub fn register(&mut self, template: DocumentTemplate) -> Result<()> {
+        let versions = self.versions.entry(template.id).or_default();
+        if versions.contains_key(&template.version) {
+            return Err(anyhow!("Template {} version {} is already registered", template.id, template.version));
+        }
+        versions.insert(template.version.clone(), template);
+        Ok(())
+    }
+
+    /// A specific version of a template
+    pub fn get(&self, id: &TemplateId, version: &DocumentVersion) -> Option<&DocumentTemplate> {
+        self.versions.get(id)?.get(version)
+    }
+
+    /// The highest registered version of a template
+    pub fn latest(&self, id: &TemplateId) -> Option<&DocumentTemplate> {
+        self.versions.get(id)?.values().next_back()
+    }
+
+    /// The highest registered version of every template
+    pub fn latest_versions(&self) -> impl Iterator<Item = &DocumentTemplate> {
+        self.versions.values().filter_map(|versions| versions.values().next_back())
+    }
+
+    /// A template at `version`, or at the latest version when `None`
+    pub fn resolve(&self, id: &TemplateId, version: Option<&DocumentVersion>) -> Result<&DocumentTemplate> {
+        match version {
+            Some(version) => self.get(id, version)
+                .ok_or_else(|| anyhow!("Template {} has no version {}", id, version)),
+            None => self.latest(id).ok_or_else(|| anyhow!("Template not found")),
+        }
+    }
+
+    /// Registered versions of a template, oldest first
+    pub fn versions(&self, id: &TemplateId) -> Vec<&DocumentVersion> {
+        self.versions.get(id).map(|versions| versions.keys().collect()).unwrap_or_default()
+    }
+
+    /// [`instantiate_template`] at `version`, or at the latest version when `None`
+    pub fn instantiate(
+        &self,
+        id: &TemplateId,
+        version: Option<&DocumentVersion>,
+        document_type: &DocumentType,
+        values: &HashMap<String, String>,
+        store: &dyn ObjectStore,
+        by: Uuid,
+    ) -> Result<(Document, TemplateApplied)> {
+        instantiate_template(self.resolve(id, version)?, document_type, values, store, by)
+    }
+}
+
+/// Differences between two versions of a template
+#[derive(Debug, Clone)]
+pub struct TemplateVersionDiff {
+    pub from_version: DocumentVersion,
+    pub to_version: DocumentVersion,
+    pub content_changes: Vec<Change>,
+    /// Variables declared only by the newer version
+    pub variables_added: Vec<String>,
+    /// Variables declared only by the older version
+    pub variables_removed: Vec<String>,
+}
+
+/// Line diff of the content and the declared variables going from `from` to `to`
+pub fn diff_template_versions(from: &DocumentTemplate, to: &DocumentTemplate) -> TemplateVersionDiff {
+    let declared_only = |a: &DocumentTemplate, b: &DocumentTemplate| -> Vec<String> {
+        a.required_variables
+            .iter()
+            .filter(|v| !b.required_variables.iter().any(|other| other.name == v.name))
+            .map(|v| v.name.clone())
+            .collect()
+    };
+
+    TemplateVersionDiff {
+        from_version: from.version.clone(),
+        to_version: to.version.clone(),
+        content_changes: VersionComparisonService::diff_lines(&from.content, &to.content),
+        variables_added: declared_only(to, from),
+        variables_removed: declared_only(from, to),
+    }
+}
+
+/// Render `template` with `variables`, falling back to declared defaults
+///
+/// Unresolved placeholders are kept as literal text unless `strict` is set,
+/// in which case undeclared ones are an error.
+fn render(template: &DocumentTemplate, variables: &HashMap<String, String>, strict: bool) -> Result<String> {
+    // Validate required variables
+    for var in &template.required_variables {
+        if var.required && !variables.contains_key(&var.name)
+            && var.default_value.is_none() {
+                return Err(anyhow!("Required variable '{}' not provided", var.name));
+            }
+    }
+
+    // In strict mode, reject placeholders that would be left as literal braces
+    if strict {
+        let unresolved: Vec<String> = validate_template(template)
+            .into_iter()
+            .filter_map(|issue| match issue {
+                TemplateIssue::UndeclaredPlaceholder(name) if !variables.contains_key(&name) => Some(name),
+                _ => None,
+            })
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(anyhow!("Undeclared template placeholders: {}", unresolved.join(", ")));
+        }
+    }
+
+    // Expand {{#each}} blocks before flat substitution
+    let content = expand_loops(&template.content, None, &|name: &str| {
+        list_variable(template, variables, name)
+    })?;
+    
+    // Use regex to find all variable placeholders
+    let re = placeholder_regex();
+    
+    let result = re.replace_all(&content, |caps: &regex::Captures| {
+        let var_name = caps[1].trim();
+        
+        // Check provided variables first
+        if let Some(value) = variables.get(var_name) {
+            return value.clone();
+        }
+        
+        // Check defaults
+        if let Some(var_def) = template.required_variables.iter()
+            .find(|v| v.name == var_name) {
+            if let Some(default) = &var_def.default_value {
+                return default.clone();
+            }
+        }
+        
+        // Keep placeholder if not found
+        caps[0].to_string()
+    });
+
+    Ok(result.to_string())
+}
+
+/// Create a document from `template`, storing the rendered content
+///
+/// The document's title, description, category and MIME type come from the
+/// template; `document_type` classifies the new document. Rendering errors
+/// are returned before anything is stored.
+pub fn instantiate_template(
+    template: &DocumentTemplate,
+    document_type: &DocumentType,
+    values: &HashMap<String, String>,
+    store: &dyn ObjectStore,
+    by: Uuid,
+) -> Result<(Document, TemplateApplied)> {
+    let content = render(template, values, false)?;
+    store_rendered(template, document_type, content, values, store, by)
+}
+
+/// Store rendered `content` and build the document instantiated from `template`
+fn store_rendered(
+    template: &DocumentTemplate,
+    document_type: &DocumentType,
+    content: String,
+    values: &HashMap<String, String>,
+    store: &dyn ObjectStore,
+    by: Uuid,
+) -> Result<(Document, TemplateApplied)> {
+    let content_cid = store.put(content.as_bytes())?;
+
+    let mut info = DocumentInfoComponent::builder()
+        .with_title(template.name.clone())
+        .with_mime_type(template.mime_type.clone())
+        .with_size_bytes(content.len() as u64);
+    if let Some(description) = &template.description {
+        info = info.with_description(description.clone());
+    }
+    let classification = ClassificationComponent::builder()
+        .with_document_type(document_type.as_str())
+        .with_category(template.category.clone())
+        .build()?;
+
+    let id = EntityId::new();
+    let mut document = Document::new(id, info.build()?, content_cid)?;
+    document
+        .add_component(classification, &by.to_string(), Some(format!("Instantiated from template {}", template.id)))
+        .map_err(|e| anyhow!("{e}"))?;
+
+    let applied = TemplateApplied {
+        document_id: DocumentId::from(id),
+        template_id: template.id,
+        template_version: template.version.clone(),
+        variables: values.clone(),
+        applied_by: by,
+        applied_at: chrono::Utc::now(),
+    };
+    Ok((document, applied))
+}
+
 /// Validation error
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationError {
@@ -167,10 +392,186 @@ pub struct ValidationError {
     pub error: String,
 }
 
+/// Authoring problem found in a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateIssue {
+    /// `{{name}}` appears in the content without a matching variable
+    UndeclaredPlaceholder(String),
+    /// Variable is declared but never referenced in the content
+    UnusedVariable(String),
+}
+
+/// Check that placeholders and declared variables line up
+///
+/// Issues are reported in content order for placeholders, then declaration order
+/// for unused variables; each name is reported once.
+pub fn validate_template(template: &DocumentTemplate) -> Vec<TemplateIssue> {
+    let mut placeholders: Vec<String> = Vec::new();
+    for caps in placeholder_regex().captures_iter(&template.content) {
+        let Some(name) = placeholder_variable(&caps[1]) else {
+            continue;
+        };
+        if !placeholders.iter().any(|p| p == name) {
+            placeholders.push(name.to_string());
+        }
+    }
+
+    let declared = |name: &str| template.required_variables.iter().any(|v| v.name == name);
+    let mut issues: Vec<TemplateIssue> = placeholders
+        .iter()
+        .filter(|name| !declared(name))
+        .map(|name| TemplateIssue::UndeclaredPlaceholder(name.clone()))
+        .collect();
+
+    issues.extend(
+        template.required_variables
+            .iter()
+            .filter(|v| !placeholders.contains(&v.name))
+            .map(|v| TemplateIssue::UnusedVariable(v.name.clone())),
+    );
+
+    issues
+}
+
+fn placeholder_regex() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{\{([^}]+)\}\}").unwrap())
+}
+
+/// Variable referenced by a placeholder; loop closers and `this` references name none
+fn placeholder_variable(raw: &str) -> Option<&str> {
+    let name = raw.trim();
+    let name = name.strip_prefix("#each ").map(str::trim).unwrap_or(name);
+    if name.starts_with('/') || name == "this" || name.starts_with("this.") {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+const EACH_OPEN: &str = "{{#each ";
+const EACH_CLOSE: &str = "{{/each}}";
+
+/// Expand `{{#each name}}...{{/each}}` blocks
+///
+/// `name` is either a template variable (resolved through `lookup`) or a
+/// `this`/`this.field` reference into the enclosing loop item. Inside the body,
+/// `{{this}}` and `{{this.field}}` render the current item.
+fn expand_loops(
+    content: &str,
+    current: Option<&Value>,
+    lookup: &dyn Fn(&str) -> Result<Vec<Value>>,
+) -> Result<String> {
+    let mut output = String::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find(EACH_OPEN) {
+        output.push_str(&rest[..start]);
+        let tag = &rest[start..];
+        let tag_end = tag.find("}}")
+            .ok_or_else(|| anyhow!("Unterminated each tag"))?;
+        let name = tag[EACH_OPEN.len()..tag_end].trim();
+        let after_tag = &tag[tag_end + 2..];
+        let body_end = matching_close(after_tag)
+            .ok_or_else(|| anyhow!("Missing {{{{/each}}}} for '{}'", name))?;
+        let body = &after_tag[..body_end];
+
+        let items = match name.strip_prefix("this") {
+            Some(path) if path.is_empty() || path.starts_with('.') => current
+                .and_then(|item| field(item, path))
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            _ => lookup(name)?,
+        };
+        for item in &items {
+            let expanded = expand_loops(body, Some(item), lookup)?;
+            output.push_str(&substitute_this(&expanded, item));
+        }
+
+        rest = &after_tag[body_end + EACH_CLOSE.len()..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Offset of the `{{/each}}` closing the block that `text` starts inside
+fn matching_close(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut pos = 0;
+    loop {
+        let close = pos + text[pos..].find(EACH_CLOSE)?;
+        match text[pos..].find(EACH_OPEN).map(|i| pos + i) {
+            Some(open) if open < close => {
+                depth += 1;
+                pos = open + EACH_OPEN.len();
+            }
+            _ if depth == 0 => return Some(close),
+            _ => {
+                depth -= 1;
+                pos = close + EACH_CLOSE.len();
+            }
+        }
+    }
+}
+
+fn substitute_this(text: &str, item: &Value) -> String {
+    static THIS: OnceLock<Regex> = OnceLock::new();
+    let re = THIS.get_or_init(|| Regex::new(r"\{\{\s*this((?:\.\w+)*)\s*\}\}").unwrap());
+    re.replace_all(text, |caps: &regex::Captures| {
+        match field(item, &caps[1]) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        }
+    })
+    .to_string()
+}
+
+fn field<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(item, |value, key| value.get(key))
+}
+
+/// Items of a loop variable: a JSON array, or any [`list_items`] value for `List` variables
+fn list_variable(
+    template: &DocumentTemplate,
+    variables: &HashMap<String, String>,
+    name: &str,
+) -> Result<Vec<Value>> {
+    let declared = template.required_variables.iter().find(|v| v.name == name);
+    let Some(raw) = variables.get(name).or_else(|| declared.and_then(|v| v.default_value.as_ref())) else {
+        return Ok(Vec::new());
+    };
+
+    if matches!(declared.map(|v| &v.var_type), Some(VariableType::List(_))) {
+        return Ok(list_items(raw));
+    }
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Array(items)) => Ok(items),
+        _ => Err(anyhow!("Variable '{}' is not a list", name)),
+    }
+}
+
+/// Values of a [`VariableType::List`] variable: a JSON array, or comma-separated text
+fn list_items(raw: &str) -> Vec<Value> {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Array(items)) => items,
+        _ => raw
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_string()))
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::value_objects::DocumentVersion;
+    use crate::value_objects::TemplateVariable;
 
     #[test]
     fn test_apply_template() {
@@ -214,6 +615,7 @@ mod tests {
             ],
             category: "meetings".to_string(),
             version: DocumentVersion::new(1, 0, 0),
+            mime_type: "text/markdown".to_string(),
         };
 
         service.register_template(template.clone()).unwrap();
@@ -258,6 +660,7 @@ mod tests {
             ],
             category: "test".to_string(),
             version: DocumentVersion::new(1, 0, 0),
+            mime_type: "text/plain".to_string(),
         };
 
         service.register_template(template.clone()).unwrap();
@@ -270,4 +673,261 @@ mod tests {
         let errors = service.validate_variables(&template.id, &variables).unwrap();
         assert_eq!(errors.len(), 2);
     }
-} 
\ No newline at end of file
+
+    fn text_variable(name: &str) -> TemplateVariable {
+        TemplateVariable {
+            name: name.to_string(),
+            description: None,
+            var_type: VariableType::Text,
+            default_value: None,
+            required: false,
+        }
+    }
+
+    fn template_with(content: &str, variables: &[&str]) -> DocumentTemplate {
+        DocumentTemplate {
+            id: TemplateId::new(),
+            name: "Draft".to_string(),
+            description: None,
+            content: content.to_string(),
+            required_variables: variables.iter().map(|name| text_variable(name)).collect(),
+            category: "test".to_string(),
+            version: DocumentVersion::new(1, 0, 0),
+            mime_type: "text/plain".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_template_reports_orphans_and_unused() {
+        let template = template_with("Dear {{name}}, re: {{ subject }} and {{name}}", &["name", "signature"]);
+
+        let issues = validate_template(&template);
+
+        assert_eq!(
+            issues,
+            vec![
+                TemplateIssue::UndeclaredPlaceholder("subject".to_string()),
+                TemplateIssue::UnusedVariable("signature".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_template_clean() {
+        let template = template_with("Hello {{name}}", &["name"]);
+
+        assert!(validate_template(&template).is_empty());
+    }
+
+    #[test]
+    fn test_apply_template_rejects_unresolved_placeholder() {
+        let template = template_with("Dear {{name}}, re: {{subject}}", &["name"]);
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Ada".to_string());
+
+        // By default an unresolved placeholder is kept as literal text
+        let mut lenient = TemplateService::new();
+        lenient.register_template(template.clone()).unwrap();
+        assert_eq!(lenient.apply_template(&template.id, &variables).unwrap(), "Dear Ada, re: {{subject}}");
+
+        let mut service = TemplateService::new().with_strict_placeholders(true);
+        service.register_template(template.clone()).unwrap();
+        let err = service.apply_template(&template.id, &variables).unwrap_err();
+        assert!(err.to_string().contains("subject"));
+
+        // Supplying the value explicitly still renders
+        variables.insert("subject".to_string(), "Budget".to_string());
+        let rendered = service.apply_template(&template.id, &variables).unwrap();
+        assert_eq!(rendered, "Dear Ada, re: Budget");
+    }
+
+    fn report_template() -> DocumentTemplate {
+        template_with(
+            "# {{title}}\n{{#each items}}- {{this.name}}: {{this.qty}}\n{{/each}}Total rows above",
+            &["title", "items"],
+        )
+    }
+
+    #[test]
+    fn test_each_renders_list_items() {
+        let mut service = TemplateService::new();
+        let template = report_template();
+        service.register_template(template.clone()).unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert("title".to_string(), "Order".to_string());
+        variables.insert(
+            "items".to_string(),
+            r#"[{"name": "Bolts", "qty": 10}, {"name": "Nuts", "qty": 12}, {"name": "Washers", "qty": 4}]"#.to_string(),
+        );
+
+        let rendered = service.apply_template(&template.id, &variables).unwrap();
+
+        assert_eq!(rendered, "# Order\n- Bolts: 10\n- Nuts: 12\n- Washers: 4\nTotal rows above");
+    }
+
+    #[test]
+    fn test_each_over_empty_list_renders_no_rows() {
+        let mut service = TemplateService::new();
+        let template = report_template();
+        service.register_template(template.clone()).unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert("title".to_string(), "Order".to_string());
+        variables.insert("items".to_string(), "[]".to_string());
+
+        let rendered = service.apply_template(&template.id, &variables).unwrap();
+
+        assert_eq!(rendered, "# Order\nTotal rows above");
+    }
+
+    #[test]
+    fn test_nested_each_and_list_variable() {
+        let mut service = TemplateService::new();
+        let mut template = template_with(
+            "{{#each sections}}[{{this.title}}{{#each this.lines}} {{this}}{{/each}}]{{/each}} {{#each tags}}#{{this}}{{/each}}",
+            &["sections", "tags"],
+        );
+        template.required_variables[1].var_type = VariableType::List(vec![]);
+        service.register_template(template.clone()).unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert(
+            "sections".to_string(),
+            r#"[{"title": "A", "lines": ["a1", "a2"]}, {"title": "B", "lines": []}]"#.to_string(),
+        );
+        variables.insert("tags".to_string(), "x, y".to_string());
+
+        let rendered = service.apply_template(&template.id, &variables).unwrap();
+
+        assert_eq!(rendered, "[A a1 a2][B] #x#y");
+        assert!(validate_template(&template).is_empty());
+    }
+
+    #[test]
+    fn test_list_variable_validates_and_loops_over_the_same_values() {
+        let mut service = TemplateService::new();
+        let mut template = template_with("{{#each quarters}}[{{this}}]{{/each}}", &["quarters"]);
+        template.required_variables[0].var_type = VariableType::List(vec![
+            "Q1".to_string(),
+            "Q2".to_string(),
+            "Q3".to_string(),
+        ]);
+        service.register_template(template.clone()).unwrap();
+
+        let mut variables = HashMap::new();
+        for value in ["Q1, Q3", r#"["Q1", "Q3"]"#] {
+            variables.insert("quarters".to_string(), value.to_string());
+            assert!(service.validate_variables(&template.id, &variables).unwrap().is_empty());
+            assert_eq!(service.apply_template(&template.id, &variables).unwrap(), "[Q1][Q3]");
+        }
+
+        variables.insert("quarters".to_string(), "Q1, Q4".to_string());
+        let errors = service.validate_variables(&template.id, &variables).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].variable, "quarters");
+    }
+
+    #[test]
+    fn test_instantiate_template_stores_rendered_content() {
+        use cim_domain::AggregateRoot;
+
+        let store = crate::services::InMemoryObjectStore::new();
+        let mut template = template_with("Dear {{name}}, re: {{subject}}", &["name", "subject"]);
+        template.required_variables[0].required = true;
+        template.mime_type = "text/markdown".to_string();
+        template.description = Some("Cover letter".to_string());
+        let author = Uuid::new_v4();
+
+        let mut values = HashMap::new();
+        values.insert("name".to_string(), "Ada".to_string());
+        values.insert("subject".to_string(), "Budget".to_string());
+        let (document, applied) = instantiate_template(&template, &DocumentType::Report, &values, &store, author).unwrap();
+
+        let cid = document.require_component::<crate::aggregate::ContentAddressComponent>().unwrap().content_cid;
+        assert_eq!(store.get(&cid).unwrap(), b"Dear Ada, re: Budget".to_vec());
+        let info = document.get_component::<DocumentInfoComponent>().unwrap();
+        assert_eq!(info.title, "Draft");
+        assert_eq!(info.description.as_deref(), Some("Cover letter"));
+        assert_eq!(info.size_bytes, 20);
+        assert_eq!(info.mime_type, "text/markdown");
+        let classification = document.get_component::<ClassificationComponent>().unwrap();
+        assert_eq!(classification.category, "test");
+        assert_eq!(classification.document_type, "report");
+        assert_eq!(applied.document_id, DocumentId::from(document.id()));
+        assert_eq!(applied.template_id, template.id);
+        assert_eq!(applied.template_version, template.version);
+        assert_eq!(applied.variables, values);
+        assert_eq!(applied.applied_by, author);
+
+        values.remove("name");
+        let err = instantiate_template(&template, &DocumentType::Report, &values, &store, author).unwrap_err();
+        assert!(err.to_string().contains("Required variable 'name'"));
+        assert_eq!(store.put_stats().writes, 1);
+    }
+
+    #[test]
+    fn test_registry_pins_template_versions() {
+        let store = crate::services::InMemoryObjectStore::new();
+        let mut registry = TemplateRegistry::new();
+        let v1 = template_with("Dear {{name}}", &["name"]);
+        let mut v2 = v1.clone();
+        v2.version = DocumentVersion::new(1, 1, 0);
+        v2.content = "Dear {{name}}\nRegards, {{sender}}".to_string();
+        v2.required_variables.push(text_variable("sender"));
+
+        registry.register(v2.clone()).unwrap();
+        registry.register(v1.clone()).unwrap();
+        assert!(registry.register(v1.clone()).is_err());
+
+        assert_eq!(registry.latest(&v1.id), Some(&v2));
+        assert_eq!(registry.get(&v1.id, &DocumentVersion::new(1, 0, 0)), Some(&v1));
+        assert_eq!(registry.versions(&v1.id), vec![&v1.version, &v2.version]);
+
+        let mut values = HashMap::new();
+        values.insert("name".to_string(), "Ada".to_string());
+        values.insert("sender".to_string(), "Bob".to_string());
+        let (pinned, pinned_applied) = registry.instantiate(&v1.id, Some(&v1.version), &DocumentType::Note, &values, &store, Uuid::new_v4()).unwrap();
+        let (latest, latest_applied) = registry.instantiate(&v1.id, None, &DocumentType::Note, &values, &store, Uuid::new_v4()).unwrap();
+        assert_eq!(pinned_applied.template_version, v1.version);
+        assert_eq!(latest_applied.template_version, v2.version);
+        let content = |document: &Document| {
+            let cid = document.require_component::<crate::aggregate::ContentAddressComponent>().unwrap().content_cid;
+            String::from_utf8(store.get(&cid).unwrap()).unwrap()
+        };
+        assert_eq!(content(&pinned), "Dear Ada");
+        assert_eq!(content(&latest), "Dear Ada\nRegards, Bob");
+        assert!(registry.instantiate(&v1.id, Some(&DocumentVersion::new(2, 0, 0)), &DocumentType::Note, &values, &store, Uuid::new_v4()).is_err());
+
+        let diff = diff_template_versions(&v1, &v2);
+        assert_eq!(diff.variables_added, vec!["sender".to_string()]);
+        assert!(diff.variables_removed.is_empty());
+        assert!(matches!(
+            diff.content_changes.as_slice(),
+            [Change::Equal { .. }, Change::Added { line, .. }] if line == "Regards, {{sender}}"
+        ));
+    }
+
+    #[test]
+    fn test_service_serves_latest_and_pinned_versions() {
+        let mut service = TemplateService::new();
+        let v1 = template_with("Hello {{name}}", &["name"]);
+        let mut v2 = v1.clone();
+        v2.version = DocumentVersion::new(2, 0, 0);
+        v2.content = "Hi {{name}}".to_string();
+        service.register_template(v1.clone()).unwrap();
+        service.register_template(v2.clone()).unwrap();
+        assert!(service.register_template(v2.clone()).is_err());
+
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), "Ada".to_string());
+        assert_eq!(service.get_template(&v1.id), Some(&v2));
+        assert_eq!(service.list_templates().len(), 1);
+        assert_eq!(service.apply_template(&v1.id, &variables).unwrap(), "Hi Ada");
+        assert_eq!(
+            service.apply_template_version(&v1.id, Some(&v1.version), &variables).unwrap(),
+            "Hello Ada"
+        );
+        assert_eq!(service.registry().versions(&v1.id), vec![&v1.version, &v2.version]);
+    }
+}
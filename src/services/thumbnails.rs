@@ -0,0 +1,180 @@
+//! Thumbnail generation for image documents
+
+use crate::aggregate::{Document, ProcessingComponent, ThumbnailInfo};
+use crate::services::{ObjectStore, ObjectStoreError};
+use image::{ImageFormat, imageops::FilterType};
+use std::io::Cursor;
+
+/// Thumbnail sizes generated by default: label, max width, max height
+pub const DEFAULT_THUMBNAIL_SIZES: [(&str, u32, u32); 3] = [
+    ("small", 64, 64),
+    ("medium", 256, 256),
+    ("large", 1024, 1024),
+];
+
+/// Errors from thumbnail generation
+#[derive(Debug, thiserror::Error)]
+pub enum ThumbnailError {
+    #[error("Unsupported content for thumbnails: {0}")]
+    Unsupported(String),
+
+    #[error("Image decode failed: {0}")]
+    Decode(String),
+
+    #[error("Thumbnail encode failed: {0}")]
+    Encode(String),
+
+    #[error(transparent)]
+    Store(#[from] ObjectStoreError),
+}
+
+/// Decode an image and store a PNG thumbnail for each requested size
+///
+/// Each thumbnail fits within its width and height while keeping the source
+/// aspect ratio; images already within a size are stored as-is, never
+/// enlarged. Content that is not a recognised image format is rejected
+/// with [`ThumbnailError::Unsupported`].
+pub fn generate_thumbnails(
+    image: &[u8],
+    sizes: &[(&str, u32, u32)],
+    store: &dyn ObjectStore,
+) -> Result<Vec<ThumbnailInfo>, ThumbnailError> {
+    let format = image::guess_format(image)
+        .map_err(|_| ThumbnailError::Unsupported("content is not a recognised image".to_string()))?;
+    let source = image::load_from_memory_with_format(image, format)
+        .map_err(|e| ThumbnailError::Decode(e.to_string()))?;
+
+    sizes
+        .iter()
+        .map(|&(label, width, height)| {
+            let thumbnail = if source.width() <= width && source.height() <= height {
+                source.clone()
+            } else {
+                source.resize(width, height, FilterType::Triangle)
+            };
+            let mut encoded = Cursor::new(Vec::new());
+            thumbnail
+                .write_to(&mut encoded, ImageFormat::Png)
+                .map_err(|e| ThumbnailError::Encode(e.to_string()))?;
+
+            Ok(ThumbnailInfo {
+                size: label.to_string(),
+                width: thumbnail.width(),
+                height: thumbnail.height(),
+                cid: store.put(encoded.get_ref())?,
+            })
+        })
+        .collect()
+}
+
+/// Generate a document's thumbnails and record them on its `ProcessingComponent`
+///
+/// Sets `thumbnails_generated` and replaces `thumbnail_cids` on success;
+/// otherwise the error is appended to `processing_errors` and returned.
+pub fn generate_document_thumbnails(
+    document: &mut Document,
+    image: &[u8],
+    sizes: &[(&str, u32, u32)],
+    store: &dyn ObjectStore,
+) -> Result<Vec<ThumbnailInfo>, ThumbnailError> {
+    let mut processing = document
+        .get_component::<ProcessingComponent>()
+        .cloned()
+        .unwrap_or_default();
+
+    let result = generate_thumbnails(image, sizes, store);
+    match &result {
+        Ok(thumbnails) => {
+            processing.thumbnails_generated = true;
+            processing.thumbnail_cids = thumbnails.clone();
+        }
+        Err(e) => processing.processing_errors.push(e.to_string()),
+    }
+
+    document
+        .replace_component(processing, "thumbnailer", Some("Thumbnail generation".to_string()))
+        .expect("replacing a component clears its slot first");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregate::DocumentInfoComponent;
+    use crate::services::InMemoryObjectStore;
+    use cid::Cid;
+    use cim_domain::EntityId;
+
+    fn png_fixture(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 4) as u8, 128])
+        });
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_generate_small_and_medium_thumbnails() {
+        let store = InMemoryObjectStore::new();
+        let fixture = png_fixture(64, 32);
+
+        let thumbnails = generate_thumbnails(
+            &fixture,
+            &[("small", 16, 16), ("medium", 32, 32)],
+            &store,
+        ).unwrap();
+
+        let dimensions: Vec<(&str, u32, u32)> = thumbnails
+            .iter()
+            .map(|t| (t.size.as_str(), t.width, t.height))
+            .collect();
+        assert_eq!(dimensions, vec![("small", 16, 8), ("medium", 32, 16)]);
+
+        let stored = image::load_from_memory(&store.get(&thumbnails[1].cid).unwrap()).unwrap();
+        assert_eq!((stored.width(), stored.height()), (32, 16));
+    }
+
+    #[test]
+    fn test_non_image_content_is_unsupported() {
+        let store = InMemoryObjectStore::new();
+
+        let result = generate_thumbnails(b"%PDF-1.7 not an image", &DEFAULT_THUMBNAIL_SIZES, &store);
+
+        assert!(matches!(result, Err(ThumbnailError::Unsupported(_))));
+        assert_eq!(store.put_stats().writes, 0);
+    }
+
+    #[test]
+    fn test_small_images_are_not_enlarged() {
+        let store = InMemoryObjectStore::new();
+        let fixture = png_fixture(40, 20);
+
+        let thumbnails = generate_thumbnails(&fixture, &[("small", 16, 16), ("large", 1024, 1024)], &store).unwrap();
+
+        assert_eq!((thumbnails[0].width, thumbnails[0].height), (16, 8));
+        assert_eq!((thumbnails[1].width, thumbnails[1].height), (40, 20));
+    }
+
+    #[test]
+    fn test_document_thumbnails_are_recorded() {
+        let store = InMemoryObjectStore::new();
+        let info = DocumentInfoComponent::builder()
+            .with_title("Photo")
+            .with_mime_type("image/png")
+            .build()
+            .unwrap();
+        let mut photo = Document::new(EntityId::new(), info, Cid::default()).unwrap();
+
+        let thumbnails = generate_document_thumbnails(&mut photo, &png_fixture(64, 32), &[("small", 16, 16)], &store).unwrap();
+
+        let processing = photo.get_component::<ProcessingComponent>().unwrap();
+        assert!(processing.thumbnails_generated);
+        assert_eq!(processing.thumbnail_cids, thumbnails);
+
+        assert!(generate_document_thumbnails(&mut photo, b"not an image", &[("small", 16, 16)], &store).is_err());
+        let processing = photo.get_component::<ProcessingComponent>().unwrap();
+        assert_eq!(processing.thumbnail_cids, thumbnails);
+        assert_eq!(processing.processing_errors.len(), 1);
+    }
+}
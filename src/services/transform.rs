@@ -0,0 +1,298 @@
+//! Content transformation pipelines
+//!
+//! A [`TransformPipeline`] runs ordered [`Transformer`] steps over document
+//! text and describes the result as a `DocumentTransformed` event.
+
+use crate::aggregate::{compute_cid, CidCodec, CidHash};
+use crate::events::{DocumentTransformed, TransformationMetrics};
+use crate::commands::edit_commands::TransformationType;
+use crate::value_objects::DocumentId;
+use chrono::Utc;
+use cid::Cid;
+use regex::Regex;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// A single content transformation step
+pub trait Transformer: Send + Sync {
+    /// Name reported in events and errors
+    fn name(&self) -> &str;
+
+    fn transform(&self, content: String) -> Result<String, TransformError>;
+}
+
+/// Failure reported by a [`Transformer`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("transform failed: {0}")]
+pub struct TransformError(pub String);
+
+/// A pipeline step failed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("step {index} ({step}) failed: {source}")]
+pub struct TransformPipelineError {
+    /// Position of the failing step
+    pub index: usize,
+    /// Name of the failing step
+    pub step: String,
+    pub source: TransformError,
+}
+
+/// Output of running a pipeline over document content
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformOutput {
+    pub content: String,
+    pub result_cid: Cid,
+    pub event: DocumentTransformed,
+}
+
+/// Ordered sequence of transformation steps
+#[derive(Default)]
+pub struct TransformPipeline {
+    steps: Vec<Box<dyn Transformer>>,
+}
+
+impl TransformPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step
+    pub fn then(mut self, step: impl Transformer + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Names of the steps, in order
+    pub fn step_names(&self) -> Vec<&str> {
+        self.steps.iter().map(|step| step.name()).collect()
+    }
+
+    /// Run every step in order, stopping at the first failure
+    pub fn apply(&self, content: String) -> Result<String, TransformPipelineError> {
+        self.steps
+            .iter()
+            .enumerate()
+            .try_fold(content, |content, (index, step)| {
+                step.transform(content).map_err(|source| TransformPipelineError {
+                    index,
+                    step: step.name().to_string(),
+                    source,
+                })
+            })
+    }
+
+    /// Transform a document's content and describe the result as an event
+    pub fn run(
+        &self,
+        document_id: DocumentId,
+        content: &str,
+        processor: &str,
+    ) -> Result<TransformOutput, TransformPipelineError> {
+        let started = Instant::now();
+        let mut changes_count = 0;
+        let mut current = content.to_string();
+        for (index, step) in self.steps.iter().enumerate() {
+            let next = step.transform(current.clone()).map_err(|source| TransformPipelineError {
+                index,
+                step: step.name().to_string(),
+                source,
+            })?;
+            if next != current {
+                changes_count += 1;
+            }
+            current = next;
+        }
+
+        let source_cid = content_cid(content.as_bytes());
+        let result_cid = content_cid(current.as_bytes());
+        let size_change_percent = if content.is_empty() {
+            0.0
+        } else {
+            (current.len() as f64 - content.len() as f64) / content.len() as f64 * 100.0
+        };
+        let steps = self.step_names();
+
+        let event = DocumentTransformed {
+            document_id,
+            source_cid,
+            result_cid,
+            transformation_type: TransformationType::Custom {
+                transformation_name: steps.join(" -> "),
+            },
+            parameters: HashMap::from([("steps".to_string(), serde_json::json!(steps))]),
+            processor: processor.to_string(),
+            processing_time_ms: started.elapsed().as_millis() as u64,
+            metrics: TransformationMetrics {
+                success: true,
+                confidence_score: None,
+                quality_score: None,
+                changes_count,
+                size_change_percent,
+                warnings: vec![],
+            },
+            transformed_at: Utc::now(),
+        };
+
+        Ok(TransformOutput {
+            content: current,
+            result_cid,
+            event,
+        })
+    }
+}
+
+impl std::fmt::Debug for TransformPipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransformPipeline")
+            .field("steps", &self.step_names())
+            .finish()
+    }
+}
+
+/// Pipelines nest as a single step
+impl Transformer for TransformPipeline {
+    fn name(&self) -> &str {
+        "pipeline"
+    }
+
+    fn transform(&self, content: String) -> Result<String, TransformError> {
+        self.apply(content).map_err(|e| TransformError(e.to_string()))
+    }
+}
+
+/// Collapse runs of spaces and tabs, trim lines and drop repeated blank lines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NormalizeWhitespace;
+
+impl Transformer for NormalizeWhitespace {
+    fn name(&self) -> &str {
+        "normalize_whitespace"
+    }
+
+    fn transform(&self, content: String) -> Result<String, TransformError> {
+        let mut lines: Vec<String> = Vec::new();
+        for line in content.lines() {
+            let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+                continue;
+            }
+            lines.push(line);
+        }
+        while lines.last().is_some_and(|last| last.is_empty()) {
+            lines.pop();
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+/// Remove HTML tags, scripts and styles, and decode common entities
+#[derive(Debug, Clone)]
+pub struct StripHtml {
+    hidden: Regex,
+    breaks: Regex,
+    tags: Regex,
+}
+
+impl StripHtml {
+    pub fn new() -> Self {
+        Self {
+            hidden: Regex::new(r"(?is)<(script|style)\b[^>]*>.*?</(script|style)\s*>").unwrap(),
+            breaks: Regex::new(r"(?i)<(br|/p|/div|/li|/h[1-6])\s*/?>").unwrap(),
+            tags: Regex::new(r"<[^>]*>").unwrap(),
+        }
+    }
+}
+
+impl Default for StripHtml {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transformer for StripHtml {
+    fn name(&self) -> &str {
+        "strip_html"
+    }
+
+    fn transform(&self, content: String) -> Result<String, TransformError> {
+        let content = self.hidden.replace_all(&content, "");
+        let content = self.breaks.replace_all(&content, "\n");
+        let content = self.tags.replace_all(&content, "");
+        Ok(content
+            .replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&"))
+    }
+}
+
+/// CID of transformed content (sha2-256, raw codec)
+fn content_cid(bytes: &[u8]) -> Cid {
+    compute_cid(bytes, CidCodec::Raw, CidHash::Sha2_256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Failing;
+
+    impl Transformer for Failing {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn transform(&self, _content: String) -> Result<String, TransformError> {
+            Err(TransformError("unsupported encoding".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_two_step_pipeline() {
+        let pipeline = TransformPipeline::new()
+            .then(StripHtml::new())
+            .then(NormalizeWhitespace);
+        let html = "<h1>Quarterly   Report</h1>\n\n\n<p>Revenue &amp; costs\t rose.</p><script>track()</script>";
+        let document_id = DocumentId::new();
+
+        let output = pipeline.run(document_id, html, "transform-service").unwrap();
+
+        assert_eq!(output.content, "Quarterly Report\n\nRevenue & costs rose.");
+        assert_eq!(output.result_cid, content_cid(output.content.as_bytes()));
+        assert_eq!(output.event.document_id, document_id);
+        assert_eq!(output.event.source_cid, content_cid(html.as_bytes()));
+        assert_eq!(output.event.result_cid, output.result_cid);
+        assert_eq!(output.event.metrics.changes_count, 2);
+        assert_eq!(
+            output.event.transformation_type,
+            TransformationType::Custom {
+                transformation_name: "strip_html -> normalize_whitespace".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_failing_step_is_reported() {
+        let pipeline = TransformPipeline::new()
+            .then(NormalizeWhitespace)
+            .then(Failing)
+            .then(StripHtml::new());
+
+        let error = pipeline.apply("some  text".to_string()).unwrap_err();
+
+        assert_eq!(error.index, 1);
+        assert_eq!(error.step, "failing");
+        assert_eq!(error.source, TransformError("unsupported encoding".to_string()));
+        assert!(pipeline.run(DocumentId::new(), "some text", "svc").is_err());
+    }
+
+    #[test]
+    fn test_pipelines_compose() {
+        let inner = TransformPipeline::new().then(StripHtml::new());
+        let outer = TransformPipeline::new().then(inner).then(NormalizeWhitespace);
+
+        assert_eq!(outer.apply("<b>a</b>   b".to_string()).unwrap(), "a b");
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::value_objects::{DocumentId, DocumentVersion};
 use crate::projections::DocumentFullView;
+use super::search::{content_cosine, tag_jaccard};
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
 
@@ -41,6 +42,44 @@ impl VersionComparisonService {
         Ok(result)
     }
 
+    /// Compare two distinct documents, e.g. a contract against its template
+    ///
+    /// Tags are listed as added or removed going from `document_a` to
+    /// `document_b`, in the order they appear on each document.
+    pub fn compare_documents(document_a: &DocumentFullView, document_b: &DocumentFullView) -> DocumentComparison {
+        let content_changes = Self::diff_lines(&document_a.content, &document_b.content);
+        let statistics = Self::calculate_statistics(&content_changes);
+
+        let missing_from = |tags: &[String], other: &[String]| {
+            let mut missing: Vec<String> = Vec::new();
+            for tag in tags {
+                if !other.contains(tag) && !missing.contains(tag) {
+                    missing.push(tag.clone());
+                }
+            }
+            missing
+        };
+
+        DocumentComparison {
+            document_a: document_a.id,
+            document_b: document_b.id,
+            content_changes,
+            metadata_changes: Self::compare_metadata(&document_a.metadata, &document_b.metadata),
+            tags_added: missing_from(&document_b.tags, &document_a.tags),
+            tags_removed: missing_from(&document_a.tags, &document_b.tags),
+            statistics,
+            similarity: content_cosine(&document_a.content, &document_b.content),
+            tag_similarity: tag_jaccard(&document_a.tags, &document_b.tags),
+        }
+    }
+
+    /// Line diff of two texts
+    pub(crate) fn diff_lines(text_a: &str, text_b: &str) -> Vec<Change> {
+        let lines_a: Vec<&str> = text_a.lines().collect();
+        let lines_b: Vec<&str> = text_b.lines().collect();
+        Self::myers_diff(&lines_a, &lines_b)
+    }
+
     /// Compare content blocks
     fn compare_content(
         content_a: &str,
@@ -226,6 +265,24 @@ pub struct ComparisonResult {
     pub statistics: ComparisonStatistics,
 }
 
+/// Differences between two distinct documents
+#[derive(Debug, Clone)]
+pub struct DocumentComparison {
+    pub document_a: DocumentId,
+    pub document_b: DocumentId,
+    pub content_changes: Vec<Change>,
+    pub metadata_changes: HashMap<String, MetadataChange>,
+    /// Tags on `document_b` but not on `document_a`
+    pub tags_added: Vec<String>,
+    /// Tags on `document_a` but not on `document_b`
+    pub tags_removed: Vec<String>,
+    pub statistics: ComparisonStatistics,
+    /// Cosine similarity of the two contents, from 0.0 to 1.0
+    pub similarity: f32,
+    /// Jaccard similarity of the two tag sets
+    pub tag_similarity: f32,
+}
+
 /// Content diff
 #[derive(Debug, Clone)]
 struct ContentDiff {
@@ -327,4 +384,61 @@ mod tests {
         assert!(matches!(changes.get("key2"), Some(MetadataChange::Removed { .. })));
         assert!(matches!(changes.get("key3"), Some(MetadataChange::Added { .. })));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_compare_distinct_documents() {
+        let template = DocumentFullView {
+            id: DocumentId::new(),
+            title: "Service Agreement Template".to_string(),
+            content: "PARTIES\nThe provider delivers the services described below.\n[SCOPE]".to_string(),
+            version: DocumentVersion::new(1, 0, 0),
+            doc_type: crate::value_objects::DocumentType::Contract,
+            tags: vec!["contract".to_string(), "template".to_string()],
+            author: uuid::Uuid::new_v4(),
+            metadata: HashMap::from([("jurisdiction".to_string(), "NY".to_string())]),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let contract = DocumentFullView {
+            id: DocumentId::new(),
+            title: "Acme Service Agreement".to_string(),
+            content: "The provider delivers the services described below.\nScope: monthly audits.".to_string(),
+            tags: vec!["contract".to_string(), "acme".to_string(), "signed".to_string()],
+            metadata: HashMap::from([("jurisdiction".to_string(), "CA".to_string())]),
+            ..template.clone()
+        };
+
+        let comparison = VersionComparisonService::compare_documents(&template, &contract);
+
+        assert_eq!(comparison.document_a, template.id);
+        assert_eq!(comparison.document_b, contract.id);
+        let hunks: Vec<(char, &str)> = comparison
+            .content_changes
+            .iter()
+            .map(|change| match change {
+                Change::Added { line, .. } => ('+', line.as_str()),
+                Change::Deleted { line, .. } => ('-', line.as_str()),
+                Change::Equal { line, .. } => (' ', line.as_str()),
+            })
+            .collect();
+        assert_eq!(
+            hunks,
+            vec![
+                ('-', "PARTIES"),
+                (' ', "The provider delivers the services described below."),
+                ('-', "[SCOPE]"),
+                ('+', "Scope: monthly audits."),
+            ]
+        );
+        assert_eq!(comparison.statistics.lines_unchanged, 1);
+
+        assert_eq!(comparison.tags_added, vec!["acme".to_string(), "signed".to_string()]);
+        assert_eq!(comparison.tags_removed, vec!["template".to_string()]);
+        assert_eq!(comparison.tag_similarity, 0.25);
+        assert!(matches!(
+            comparison.metadata_changes.get("jurisdiction"),
+            Some(MetadataChange::Modified { new_value, .. }) if new_value == "CA"
+        ));
+        assert!(comparison.similarity > 0.5 && comparison.similarity < 1.0);
+    }
+}
@@ -21,12 +21,30 @@ impl DocumentId {
         Self(Uuid::new_v4())
     }
 
+    /// Create a new document ID using the given generation strategy
+    pub fn with_strategy(strategy: DocumentIdStrategy) -> Self {
+        match strategy {
+            DocumentIdStrategy::V4 => Self::new(),
+            DocumentIdStrategy::V7 => Self(Uuid::now_v7()),
+        }
+    }
+
     /// Get the inner UUID
     pub fn as_uuid(&self) -> &Uuid {
         &self.0
     }
 }
 
+/// How new document IDs are generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DocumentIdStrategy {
+    /// Random UUIDv4
+    #[default]
+    V4,
+    /// Time-ordered UUIDv7, sortable by creation time
+    V7,
+}
+
 /// User identifier for actors in the system
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UserId(pub Uuid);
@@ -79,6 +97,33 @@ impl std::fmt::Display for DocumentId {
     }
 }
 
+impl std::str::FromStr for DocumentId {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_uuid("DocumentId", s).map(Self)
+    }
+}
+
+/// Error parsing an identifier from its string form
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind} '{input}': {reason}")]
+pub struct IdParseError {
+    /// Identifier type being parsed
+    pub kind: &'static str,
+    pub input: String,
+    pub reason: String,
+}
+
+/// Parse a UUID-backed identifier, naming `kind` in the error
+pub(crate) fn parse_uuid(kind: &'static str, s: &str) -> Result<Uuid, IdParseError> {
+    Uuid::parse_str(s).map_err(|e| IdParseError {
+        kind,
+        input: s.to_string(),
+        reason: e.to_string(),
+    })
+}
+
 /// Document metadata
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentMetadata {
@@ -94,8 +139,105 @@ pub struct DocumentMetadata {
     pub filename: Option<String>,
 }
 
+impl DocumentMetadata {
+    /// Custom attribute as a string, if present and a JSON string
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        self.custom_attributes.get(key)?.as_str()
+    }
+
+    /// Custom attribute as an integer, if present and a JSON integer that fits
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.custom_attributes.get(key)?.as_i64()
+    }
+
+    /// Custom attribute as a boolean, if present and a JSON boolean
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.custom_attributes.get(key)?.as_bool()
+    }
+
+    /// Custom attribute as an array, if present and a JSON array
+    pub fn get_array(&self, key: &str) -> Option<&[serde_json::Value]> {
+        self.custom_attributes.get(key)?.as_array().map(Vec::as_slice)
+    }
+
+    /// Set a custom attribute, replacing any previous value
+    pub fn set<T: Into<serde_json::Value>>(&mut self, key: impl Into<String>, value: T) {
+        self.custom_attributes.insert(key.into(), value.into());
+    }
+
+    /// Apply `patch`, keeping every field it leaves as `None`
+    ///
+    /// Custom attributes are merged key by key; attributes not in the patch are kept.
+    pub fn merge(&mut self, patch: DocumentMetadataPatch) {
+        if let Some(title) = patch.title {
+            self.title = title;
+        }
+        if let Some(description) = patch.description {
+            self.description = Some(description);
+        }
+        if let Some(tags) = patch.tags {
+            self.tags = tags;
+        }
+        self.custom_attributes.extend(patch.custom_attributes);
+        if let Some(mime_type) = patch.mime_type {
+            self.mime_type = Some(mime_type);
+        }
+        if let Some(size_bytes) = patch.size_bytes {
+            self.size_bytes = Some(size_bytes);
+        }
+        if let Some(language) = patch.language {
+            self.language = Some(language);
+        }
+        if let Some(category) = patch.category {
+            self.category = Some(category);
+        }
+        if let Some(subcategories) = patch.subcategories {
+            self.subcategories = Some(subcategories);
+        }
+        if let Some(filename) = patch.filename {
+            self.filename = Some(filename);
+        }
+    }
+}
+
+/// Partial update to [`DocumentMetadata`]; `None` fields are left unchanged
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DocumentMetadataPatch {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// Attributes to add or overwrite
+    pub custom_attributes: HashMap<String, serde_json::Value>,
+    pub mime_type: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub language: Option<String>,
+    pub category: Option<String>,
+    pub subcategories: Option<Vec<String>>,
+    pub filename: Option<String>,
+}
+
+/// Canonical tags: trimmed, lowercased and deduplicated in first-seen order
+///
+/// Empty tags are dropped.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    normalize_tags_with(tags, true)
+}
+
+/// Like [`normalize_tags`], optionally keeping the first-seen spelling
+///
+/// Duplicates are always detected case-insensitively.
+pub fn normalize_tags_with(tags: &[String], lowercase: bool) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    tags.iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty() && seen.insert(tag.to_lowercase()))
+        .map(|tag| if lowercase { tag.to_lowercase() } else { tag.to_string() })
+        .collect()
+}
+
 /// Document type
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DocumentType {
     Text,
     Image,
@@ -113,8 +255,90 @@ pub enum DocumentType {
     Other(String),
 }
 
+impl DocumentType {
+    /// Classify content by MIME type
+    ///
+    /// Unrecognised types become `Other` carrying the raw subtype, e.g.
+    /// `application/json` maps to `Other("json")`.
+    pub fn from_mime(mime: &str) -> DocumentType {
+        let essence = mime.split(';').next().unwrap_or_default().trim().to_lowercase();
+        let (kind, subtype) = essence.split_once('/').unwrap_or(("", essence.as_str()));
+
+        match (kind, subtype) {
+            ("application", "pdf") => DocumentType::Pdf,
+            ("text", "csv")
+            | ("application", "vnd.ms-excel")
+            | ("application", "vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+            | ("application", "vnd.oasis.opendocument.spreadsheet") => DocumentType::Spreadsheet,
+            ("application", "vnd.ms-powerpoint")
+            | ("application", "vnd.openxmlformats-officedocument.presentationml.presentation")
+            | ("application", "vnd.oasis.opendocument.presentation") => DocumentType::Presentation,
+            ("application", "zip" | "gzip" | "x-tar" | "x-7z-compressed" | "x-rar-compressed" | "vnd.rar") => {
+                DocumentType::Archive
+            }
+            ("application", "msword" | "rtf" | "vnd.openxmlformats-officedocument.wordprocessingml.document" | "vnd.oasis.opendocument.text")
+            | ("text", _) => DocumentType::Text,
+            ("image", _) => DocumentType::Image,
+            ("video", _) => DocumentType::Video,
+            ("audio", _) => DocumentType::Audio,
+            (_, subtype) => DocumentType::Other(subtype.to_string()),
+        }
+    }
+
+    /// Lowercase name of a known type; `Other` yields its own name
+    ///
+    /// Inverse of [`DocumentType::from_name`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            DocumentType::Text => "text",
+            DocumentType::Image => "image",
+            DocumentType::Video => "video",
+            DocumentType::Audio => "audio",
+            DocumentType::Pdf => "pdf",
+            DocumentType::Spreadsheet => "spreadsheet",
+            DocumentType::Presentation => "presentation",
+            DocumentType::Archive => "archive",
+            DocumentType::Note => "note",
+            DocumentType::Article => "article",
+            DocumentType::Proposal => "proposal",
+            DocumentType::Report => "report",
+            DocumentType::Contract => "contract",
+            DocumentType::Other(name) => name,
+        }
+    }
+
+    /// Parse a name written by [`DocumentType::as_str`], case-insensitively
+    ///
+    /// The `Other("x")` form written by older exports is also accepted;
+    /// unrecognised names map to `Other`.
+    pub fn from_name(name: &str) -> DocumentType {
+        let name = name.trim();
+        if let Some(inner) = name.strip_prefix("Other(").and_then(|rest| rest.strip_suffix(')')) {
+            return DocumentType::Other(inner.trim().trim_matches('"').to_string());
+        }
+        match name.to_ascii_lowercase().as_str() {
+            "text" => DocumentType::Text,
+            "image" => DocumentType::Image,
+            "video" => DocumentType::Video,
+            "audio" => DocumentType::Audio,
+            "pdf" => DocumentType::Pdf,
+            "spreadsheet" => DocumentType::Spreadsheet,
+            "presentation" => DocumentType::Presentation,
+            "archive" => DocumentType::Archive,
+            "note" => DocumentType::Note,
+            "article" => DocumentType::Article,
+            "proposal" => DocumentType::Proposal,
+            "report" => DocumentType::Report,
+            "contract" => DocumentType::Contract,
+            _ => DocumentType::Other(name.to_string()),
+        }
+    }
+}
+
 /// Document version
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Ordered semantically: by major, then minor, then patch.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct DocumentVersion {
     pub major: u32,
     pub minor: u32,
@@ -126,6 +350,30 @@ impl DocumentVersion {
     pub fn new(major: u32, minor: u32, patch: u32) -> Self {
         Self { major, minor, patch }
     }
+
+    /// Next major version, resetting minor and patch
+    pub fn increment_major(&self) -> Self {
+        Self::new(self.major + 1, 0, 0)
+    }
+
+    /// Next minor version, resetting patch
+    pub fn increment_minor(&self) -> Self {
+        Self::new(self.major, self.minor + 1, 0)
+    }
+
+    /// Next patch version
+    pub fn increment_patch(&self) -> Self {
+        Self::new(self.major, self.minor, self.patch + 1)
+    }
+
+    /// Apply a bump policy to this version
+    pub fn bump(&self, bump: VersionBump) -> Self {
+        match bump {
+            VersionBump::Major => self.increment_major(),
+            VersionBump::Minor => self.increment_minor(),
+            VersionBump::Patch => self.increment_patch(),
+        }
+    }
 }
 
 impl Default for DocumentVersion {
@@ -140,6 +388,45 @@ impl std::fmt::Display for DocumentVersion {
     }
 }
 
+impl std::str::FromStr for DocumentVersion {
+    type Err = VersionParseError;
+
+    /// Parse `major[.minor[.patch]]`, treating missing parts as zero
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || VersionParseError { input: s.to_string() };
+        let mut parts = s.trim().trim_start_matches('v').split('.');
+        let mut next = |required: bool| match parts.next() {
+            Some(part) => part.parse::<u32>().map_err(|_| error()),
+            None if required => Err(error()),
+            None => Ok(0),
+        };
+        let version = Self::new(next(true)?, next(false)?, next(false)?);
+        match parts.next() {
+            Some(_) => Err(error()),
+            None => Ok(version),
+        }
+    }
+}
+
+/// Error parsing a `DocumentVersion` from its string form
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid document version '{input}'")]
+pub struct VersionParseError {
+    pub input: String,
+}
+
+/// Which part of a version a change increments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VersionBump {
+    /// Incompatible change
+    Major,
+    /// Backwards-compatible addition
+    Minor,
+    /// Correction or small edit
+    #[default]
+    Patch,
+}
+
 /// Document revision
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Revision(pub u32);
@@ -184,7 +471,9 @@ pub struct ContentBlock {
 }
 
 /// Access level for document sharing
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Levels are ordered so that each one implies those below it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum AccessLevel {
     /// Can view the document
     Read,
@@ -211,6 +500,25 @@ pub enum DocumentState {
     Archived,
 }
 
+impl DocumentState {
+    /// Whether the lifecycle allows moving from this state to `target`
+    ///
+    /// Archived documents only leave the archive by being restored to `Draft`.
+    pub fn can_transition_to(&self, target: &DocumentState) -> bool {
+        use DocumentState::*;
+
+        matches!(
+            (self, target),
+            (Draft, InReview)
+                | (InReview, Approved)
+                | (InReview, Rejected)
+                | (Rejected, Draft)
+                | (Approved, Archived)
+                | (Archived, Draft)
+        )
+    }
+}
+
 /// Link type between documents
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LinkType {
@@ -275,6 +583,68 @@ pub struct Collection {
     pub metadata: HashMap<String, String>,
 }
 
+/// Collection parent chain that loops back on itself
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("collection hierarchy cycle at {collection_id}")]
+pub struct CollectionCycleError {
+    /// Collection reached a second time
+    pub collection_id: Uuid,
+}
+
+/// Ancestors of a collection, nearest parent first
+///
+/// Walking stops at a root or at a parent missing from `collections`.
+pub fn collection_ancestors(
+    id: Uuid,
+    collections: &[Collection],
+) -> Result<Vec<Collection>, CollectionCycleError> {
+    let by_id: HashMap<Uuid, &Collection> = collections.iter().map(|c| (c.id, c)).collect();
+    let mut seen = std::collections::HashSet::from([id]);
+    let mut ancestors = Vec::new();
+    let mut parent_id = by_id.get(&id).and_then(|c| c.parent_id);
+    while let Some(current) = parent_id {
+        if !seen.insert(current) {
+            return Err(CollectionCycleError { collection_id: current });
+        }
+        let Some(parent) = by_id.get(&current) else {
+            break;
+        };
+        ancestors.push((*parent).clone());
+        parent_id = parent.parent_id;
+    }
+    Ok(ancestors)
+}
+
+/// Descendants of a collection in breadth-first order
+///
+/// `max_depth` limits how many levels below `id` are returned; `Some(1)`
+/// yields only direct children.
+pub fn collection_descendants(
+    id: Uuid,
+    collections: &[Collection],
+    max_depth: Option<usize>,
+) -> Result<Vec<Collection>, CollectionCycleError> {
+    let mut seen = std::collections::HashSet::from([id]);
+    let mut descendants = Vec::new();
+    let mut level = vec![id];
+    let mut depth = 0;
+    while !level.is_empty() && !max_depth.is_some_and(|max| depth >= max) {
+        let mut next = Vec::new();
+        for parent in level {
+            for child in collections.iter().filter(|c| c.parent_id == Some(parent)) {
+                if !seen.insert(child.id) {
+                    return Err(CollectionCycleError { collection_id: child.id });
+                }
+                descendants.push(child.clone());
+                next.push(child.id);
+            }
+        }
+        level = next;
+        depth += 1;
+    }
+    Ok(descendants)
+}
+
 /// Merge strategy
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MergeStrategy {
@@ -359,7 +729,7 @@ pub struct ExtractedEntity {
 }
 
 /// Entity type
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EntityType {
     /// Person name
     Person,
@@ -373,6 +743,10 @@ pub enum EntityType {
     Concept,
     /// Keyword
     Keyword,
+    /// Email address
+    Email,
+    /// Phone number
+    PhoneNumber,
     /// Custom type
     Custom(String),
 }
@@ -407,6 +781,12 @@ pub struct MergeConflict {
     pub source_content: String,
     /// Base content (common ancestor)
     pub base_content: Option<String>,
+    /// Target block, `None` when the target deleted it
+    #[serde(default)]
+    pub target_block: Option<ContentBlock>,
+    /// Source block, `None` when the source deleted it
+    #[serde(default)]
+    pub source_block: Option<ContentBlock>,
     /// Conflict type
     pub conflict_type: ConflictType,
 }
@@ -448,6 +828,20 @@ impl TemplateId {
     }
 }
 
+impl std::fmt::Display for TemplateId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for TemplateId {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_uuid("TemplateId", s).map(Self)
+    }
+}
+
 /// Document template
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentTemplate {
@@ -465,6 +859,13 @@ pub struct DocumentTemplate {
     pub category: String,
     /// Template version
     pub version: DocumentVersion,
+    /// MIME type of documents rendered from this template
+    #[serde(default = "default_template_mime_type")]
+    pub mime_type: String,
+}
+
+fn default_template_mime_type() -> String {
+    "text/plain".to_string()
 }
 
 /// Template variable
@@ -493,7 +894,9 @@ pub enum VariableType {
     Date,
     /// Boolean
     Boolean,
-    /// List of values
+    /// List of values, given as a JSON array or comma-separated
+    ///
+    /// When non-empty, every value must be one of the listed options.
     List(Vec<String>),
 }
 
@@ -516,8 +919,36 @@ pub enum ImportFormat {
     Custom(String),
 }
 
+impl ImportFormat {
+    /// MIME type of content in this format; custom formats are opaque bytes
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImportFormat::Markdown => "text/markdown",
+            ImportFormat::PlainText => "text/plain",
+            ImportFormat::Html => "text/html",
+            ImportFormat::Pdf => "application/pdf",
+            ImportFormat::Word => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            ImportFormat::Json => "application/json",
+            ImportFormat::Custom(_) => "application/octet-stream",
+        }
+    }
+
+    /// Document type given to content imported in this format
+    ///
+    /// Markdown imports as a report, plain text as a note and HTML as an
+    /// article; other formats are classified by [`mime_type`](Self::mime_type).
+    pub fn document_type(&self) -> DocumentType {
+        match self {
+            ImportFormat::Markdown => DocumentType::Report,
+            ImportFormat::PlainText => DocumentType::Note,
+            ImportFormat::Html => DocumentType::Article,
+            _ => DocumentType::from_mime(self.mime_type()),
+        }
+    }
+}
+
 /// Export format
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ExportFormat {
     /// Markdown format
     Markdown,
@@ -589,6 +1020,61 @@ impl Default for ExportOptions {
     }
 }
 
+impl ExportOptions {
+    /// Watermark placement from `custom_options`, defaulting to the footer
+    pub fn watermark_placement(&self) -> WatermarkPlacement {
+        self.custom_options
+            .get(WATERMARK_PLACEMENT_OPTION)
+            .map(|placement| WatermarkPlacement::parse(placement))
+            .unwrap_or_default()
+    }
+
+    /// Set the watermark placement in `custom_options`
+    pub fn with_watermark_placement(mut self, placement: WatermarkPlacement) -> Self {
+        self.custom_options
+            .insert(WATERMARK_PLACEMENT_OPTION.to_string(), placement.as_str().to_string());
+        self
+    }
+}
+
+/// `ExportOptions::custom_options` key holding the watermark placement
+pub const WATERMARK_PLACEMENT_OPTION: &str = "watermark_placement";
+
+/// Where exporters place the watermark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum WatermarkPlacement {
+    /// Before the document body
+    Header,
+    /// After the document body
+    #[default]
+    Footer,
+    /// Across the page, where the format supports positioning
+    Diagonal,
+    /// After every paragraph
+    EveryParagraph,
+}
+
+impl WatermarkPlacement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Header => "header",
+            Self::Footer => "footer",
+            Self::Diagonal => "diagonal",
+            Self::EveryParagraph => "every_paragraph",
+        }
+    }
+
+    /// Parse a placement name; unknown names fall back to `Footer`
+    pub fn parse(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+            "header" => Self::Header,
+            "diagonal" => Self::Diagonal,
+            "every_paragraph" | "everyparagraph" => Self::EveryParagraph,
+            _ => Self::Footer,
+        }
+    }
+}
+
 /// Search query
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchQuery {
@@ -602,6 +1088,9 @@ pub struct SearchQuery {
     pub sort: SearchSort,
     /// Pagination
     pub pagination: SearchPagination,
+    /// Also match terms within a small edit distance of the query terms
+    #[serde(default)]
+    pub fuzzy: bool,
 }
 
 /// Search field
@@ -683,6 +1172,62 @@ impl Default for SearchPagination {
     }
 }
 
+/// One page of results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Results across all pages
+    pub total: usize,
+    /// Page number (0-based)
+    pub page: usize,
+    /// Requested page size
+    pub size: usize,
+    /// Whether a later page has results
+    pub has_next: bool,
+}
+
+impl<T> Page<T> {
+    /// Take the page `pagination` selects from the full, ordered results
+    ///
+    /// A page size of zero is clamped to one so every page makes progress.
+    pub fn paginate(all: Vec<T>, pagination: &SearchPagination) -> Self {
+        let size = pagination.size.max(1);
+        let total = all.len();
+        let start = pagination.page.saturating_mul(size).min(total);
+        let items: Vec<T> = all.into_iter().skip(start).take(size).collect();
+        let has_next = start + items.len() < total;
+        Self {
+            items,
+            total,
+            page: pagination.page,
+            size,
+            has_next,
+        }
+    }
+
+    /// Every result as a single page
+    pub fn all(items: Vec<T>) -> Self {
+        let total = items.len();
+        Self {
+            items,
+            total,
+            page: 0,
+            size: total,
+            has_next: false,
+        }
+    }
+}
+
+/// A named [`SearchQuery`] kept for re-running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: Uuid,
+    pub name: String,
+    pub query: SearchQuery,
+    pub owner: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -712,6 +1257,27 @@ mod tests {
         assert_eq!(format!("{}", doc_id), doc_id.0.to_string());
     }
 
+    #[test]
+    fn test_id_from_str_round_trip() {
+        // US-019: Test IDs parse back from their Display form
+        let doc_id = DocumentId::new();
+        assert_eq!(doc_id.to_string().parse::<DocumentId>().unwrap(), doc_id);
+
+        let template_id = TemplateId::new();
+        assert_eq!(template_id.to_string().parse::<TemplateId>().unwrap(), template_id);
+    }
+
+    #[test]
+    fn test_id_from_str_rejects_malformed_input() {
+        // US-019: Test parse errors name the ID type and input
+        let err = "not-a-uuid".parse::<DocumentId>().unwrap_err();
+        assert_eq!(err.kind, "DocumentId");
+        assert_eq!(err.input, "not-a-uuid");
+        assert!(err.to_string().starts_with("invalid DocumentId 'not-a-uuid'"));
+
+        assert!("".parse::<TemplateId>().is_err());
+    }
+
     #[test]
     fn test_document_id_default() {
         // US-019: Test DocumentId default implementation
@@ -720,6 +1286,69 @@ mod tests {
         assert!(doc_id.as_uuid() != &Uuid::nil());
     }
 
+    #[test]
+    fn test_document_id_strategy_versions() {
+        // US-019: Test DocumentId generation strategies
+        assert_eq!(DocumentIdStrategy::default(), DocumentIdStrategy::V4);
+        assert_eq!(DocumentId::with_strategy(DocumentIdStrategy::V4).as_uuid().get_version_num(), 4);
+        assert_eq!(DocumentId::with_strategy(DocumentIdStrategy::V7).as_uuid().get_version_num(), 7);
+    }
+
+    #[test]
+    fn test_document_id_v7_is_monotonic() {
+        // US-019: Test V7 document IDs sort in generation order
+        let ids: Vec<DocumentId> = (0..100)
+            .map(|_| DocumentId::with_strategy(DocumentIdStrategy::V7))
+            .collect();
+
+        for pair in ids.windows(2) {
+            assert!(pair[0].as_uuid() < pair[1].as_uuid());
+        }
+    }
+
+    #[test]
+    fn test_document_id_v7_serialization() {
+        // US-019: Test V7 document IDs round-trip like any other ID
+        let doc_id = DocumentId::with_strategy(DocumentIdStrategy::V7);
+
+        let serialized = serde_json::to_string(&doc_id).unwrap();
+        let deserialized: DocumentId = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(doc_id, deserialized);
+    }
+
+    #[test]
+    fn test_document_state_legal_transitions() {
+        // US-005: Test the document lifecycle transitions
+        use DocumentState::*;
+        let states = [Draft, InReview, Approved, Rejected, Archived];
+        let legal = [
+            (Draft, InReview),
+            (InReview, Approved),
+            (InReview, Rejected),
+            (Rejected, Draft),
+            (Approved, Archived),
+            (Archived, Draft),
+        ];
+
+        for from in &states {
+            for to in &states {
+                let expected = legal.contains(&(from.clone(), to.clone()));
+                assert_eq!(from.can_transition_to(to), expected, "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_document_state_illegal_transitions() {
+        // US-005: Test a sampling of illegal transitions
+        assert!(!DocumentState::Draft.can_transition_to(&DocumentState::Approved));
+        assert!(!DocumentState::Rejected.can_transition_to(&DocumentState::Approved));
+        assert!(!DocumentState::Archived.can_transition_to(&DocumentState::InReview));
+        assert!(!DocumentState::Approved.can_transition_to(&DocumentState::Draft));
+        assert!(!DocumentState::Draft.can_transition_to(&DocumentState::Draft));
+    }
+
     #[test]
     fn test_document_id_from_uuid() {
         // US-019: Test DocumentId conversion from UUID
@@ -777,6 +1406,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_document_type_from_mime() {
+        let cases = [
+            ("application/pdf", DocumentType::Pdf),
+            ("image/png", DocumentType::Image),
+            ("video/mp4", DocumentType::Video),
+            ("audio/mpeg", DocumentType::Audio),
+            ("text/markdown; charset=utf-8", DocumentType::Text),
+            ("text/csv", DocumentType::Spreadsheet),
+            ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", DocumentType::Spreadsheet),
+            ("application/vnd.ms-powerpoint", DocumentType::Presentation),
+            ("application/zip", DocumentType::Archive),
+            ("Application/JSON", DocumentType::Other("json".to_string())),
+            ("application/x-custom-format", DocumentType::Other("x-custom-format".to_string())),
+        ];
+
+        for (mime, expected) in cases {
+            assert_eq!(DocumentType::from_mime(mime), expected, "{mime}");
+        }
+    }
+
     #[test]
     fn test_document_type_custom() {
         // US-020: Test custom DocumentType variant
@@ -812,6 +1462,44 @@ mod tests {
         assert_eq!(format!("{}", version), "1.0.0");
     }
 
+    #[test]
+    fn test_document_version_ordering() {
+        // US-020: Test versions sort semantically rather than lexically
+        let mut versions = vec![
+            DocumentVersion::new(1, 10, 0),
+            DocumentVersion::new(2, 0, 0),
+            DocumentVersion::new(1, 2, 0),
+            DocumentVersion::new(1, 2, 1),
+            DocumentVersion::new(0, 9, 9),
+        ];
+        versions.sort();
+
+        let sorted: Vec<String> = versions.iter().map(ToString::to_string).collect();
+        assert_eq!(sorted, vec!["0.9.9", "1.2.0", "1.2.1", "1.10.0", "2.0.0"]);
+        assert!(DocumentVersion::new(1, 2, 0) < DocumentVersion::new(1, 10, 0));
+    }
+
+    #[test]
+    fn test_document_version_increments() {
+        // US-020: Test version bump helpers
+        let version = DocumentVersion::new(1, 4, 2);
+
+        assert_eq!(version.increment_major(), DocumentVersion::new(2, 0, 0));
+        assert_eq!(version.increment_minor(), DocumentVersion::new(1, 5, 0));
+        assert_eq!(version.increment_patch(), DocumentVersion::new(1, 4, 3));
+        assert!(version.increment_patch() > version);
+    }
+
+    #[test]
+    fn test_document_version_parsing() {
+        // US-020: Test versions parse from lifecycle version strings
+        assert_eq!("1.2.3".parse(), Ok(DocumentVersion::new(1, 2, 3)));
+        assert_eq!("1.0".parse(), Ok(DocumentVersion::new(1, 0, 0)));
+        assert_eq!("v3".parse(), Ok(DocumentVersion::new(3, 0, 0)));
+        assert!("1.2.3.4".parse::<DocumentVersion>().is_err());
+        assert!("draft".parse::<DocumentVersion>().is_err());
+    }
+
     #[test]
     fn test_document_version_serialization() {
         // US-020: Test DocumentVersion serialization
@@ -1017,6 +1705,7 @@ mod tests {
             required_variables: vec![],
             category: "test".to_string(),
             version: DocumentVersion::default(),
+            mime_type: "text/plain".to_string(),
         };
         
         assert_eq!(template.name, "Test Template");
@@ -1081,6 +1770,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_import_format_document_types() {
+        let cases = [
+            (ImportFormat::Markdown, DocumentType::Report),
+            (ImportFormat::PlainText, DocumentType::Note),
+            (ImportFormat::Html, DocumentType::Article),
+            (ImportFormat::Pdf, DocumentType::Pdf),
+            (ImportFormat::Word, DocumentType::Text),
+            (ImportFormat::Json, DocumentType::Other("json".to_string())),
+        ];
+
+        for (format, expected) in cases {
+            assert_eq!(format.document_type(), expected, "{format:?}");
+        }
+        assert_eq!(ImportFormat::Custom("custom".to_string()).mime_type(), "application/octet-stream");
+    }
+
     #[test]
     fn test_export_format_variants() {
         // US-018: Test ExportFormat enum variants
@@ -1324,6 +2030,8 @@ mod tests {
             target_content: "Target content".to_string(),
             source_content: "Source content".to_string(),
             base_content: Some("Base content".to_string()),
+            target_block: None,
+            source_block: None,
             conflict_type: ConflictType::ContentModified,
         };
         
@@ -1365,6 +2073,7 @@ mod tests {
                 direction: SortDirection::Descending,
             },
             pagination: SearchPagination::default(),
+            fuzzy: false,
         };
         
         assert_eq!(query.query, "test search");
@@ -1498,6 +2207,74 @@ mod tests {
         assert_eq!(metadata.subcategories, Some(vec!["unit-tests".to_string()]));
     }
 
+    #[test]
+    fn test_document_metadata_typed_attributes() {
+        let mut metadata = DocumentMetadata {
+            title: "Budget".to_string(),
+            description: None,
+            tags: vec![],
+            custom_attributes: HashMap::new(),
+            mime_type: None,
+            size_bytes: None,
+            language: None,
+            category: None,
+            subcategories: None,
+            filename: None,
+        };
+
+        metadata.set("pages", 42);
+        metadata.set("owner", "finance");
+        metadata.set("reviewed", true);
+        metadata.set("reviewers", vec!["ana", "raj"]);
+
+        assert_eq!(metadata.get_i64("pages"), Some(42));
+        assert_eq!(metadata.get_i64("owner"), None);
+        assert_eq!(metadata.get_string("owner"), Some("finance"));
+        assert_eq!(metadata.get_string("pages"), None);
+        assert_eq!(metadata.get_bool("reviewed"), Some(true));
+        assert_eq!(metadata.get_array("reviewers").map(<[_]>::len), Some(2));
+        assert_eq!(metadata.get_i64("missing"), None);
+    }
+
+    #[test]
+    fn test_document_metadata_merge_keeps_unpatched_fields() {
+        let mut metadata = DocumentMetadata {
+            title: "Budget".to_string(),
+            description: Some("Q3 figures".to_string()),
+            tags: vec!["draft".to_string()],
+            custom_attributes: HashMap::from([
+                ("priority".to_string(), serde_json::json!("high")),
+                ("owner".to_string(), serde_json::json!("finance")),
+            ]),
+            mime_type: Some("text/plain".to_string()),
+            size_bytes: Some(1024),
+            language: Some("en".to_string()),
+            category: Some("reports".to_string()),
+            subcategories: None,
+            filename: Some("budget.txt".to_string()),
+        };
+        let original = metadata.clone();
+
+        metadata.merge(DocumentMetadataPatch {
+            tags: Some(vec!["final".to_string(), "q3".to_string()]),
+            custom_attributes: HashMap::from([("priority".to_string(), serde_json::json!("low"))]),
+            ..DocumentMetadataPatch::default()
+        });
+
+        assert_eq!(metadata.tags, vec!["final", "q3"]);
+        assert_eq!(metadata.custom_attributes["priority"], serde_json::json!("low"));
+        assert_eq!(metadata.custom_attributes["owner"], serde_json::json!("finance"));
+        assert_eq!(metadata.custom_attributes.len(), 2);
+        assert_eq!(
+            DocumentMetadata {
+                tags: original.tags.clone(),
+                custom_attributes: original.custom_attributes.clone(),
+                ..metadata
+            },
+            original
+        );
+    }
+
     // VersionTag tests
     #[test]
     fn test_version_tag_creation() {
@@ -1515,7 +2292,68 @@ mod tests {
         assert_eq!(tag.version, DocumentVersion::new(1, 0, 0));
     }
 
+    #[test]
+    fn test_normalize_tags() {
+        // US-019: Test tags collapse to one canonical entry each
+        let tags: Vec<String> = ["Rust", "rust", "rust ", " Finance", "", "  ", "FINANCE", "q3"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+
+        assert_eq!(normalize_tags(&tags), vec!["rust", "finance", "q3"]);
+        assert_eq!(normalize_tags_with(&tags, false), vec!["Rust", "Finance", "q3"]);
+    }
+
     // Collection tests
+    fn collection(name: &str, parent_id: Option<Uuid>) -> Collection {
+        Collection {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: None,
+            parent_id,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_collection_hierarchy_walks() {
+        // US-021: Test walking a 3-level collection hierarchy
+        let root = collection("root", None);
+        let child = collection("child", Some(root.id));
+        let sibling = collection("sibling", Some(root.id));
+        let grandchild = collection("grandchild", Some(child.id));
+        let collections = vec![grandchild.clone(), sibling.clone(), child.clone(), root.clone()];
+
+        let ancestors = collection_ancestors(grandchild.id, &collections).unwrap();
+        assert_eq!(ancestors, vec![child.clone(), root.clone()]);
+        assert!(collection_ancestors(root.id, &collections).unwrap().is_empty());
+
+        let all = collection_descendants(root.id, &collections, None).unwrap();
+        assert_eq!(all, vec![sibling.clone(), child.clone(), grandchild.clone()]);
+
+        let direct = collection_descendants(root.id, &collections, Some(1)).unwrap();
+        assert_eq!(direct, vec![sibling, child]);
+        assert!(collection_descendants(root.id, &collections, Some(0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_collection_hierarchy_cycles_error() {
+        // US-021: Test malformed parent chains error instead of looping
+        let mut looped = collection("looped", None);
+        looped.parent_id = Some(looped.id);
+        let collections = vec![looped.clone()];
+
+        let expected = CollectionCycleError { collection_id: looped.id };
+        assert_eq!(collection_ancestors(looped.id, &collections), Err(expected.clone()));
+        assert_eq!(collection_descendants(looped.id, &collections, None), Err(expected));
+
+        let mut a = collection("a", None);
+        let b = collection("b", Some(a.id));
+        a.parent_id = Some(b.id);
+        assert!(collection_ancestors(a.id, &[a.clone(), b.clone()]).is_err());
+        assert!(collection_descendants(a.id, &[a, b], None).is_err());
+    }
+
     #[test]
     fn test_collection_creation() {
         // US-021: Test Collection structure
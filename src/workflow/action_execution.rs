@@ -0,0 +1,164 @@
+//! Execution of workflow actions
+//!
+//! [`ActionRegistry`] dispatches a [`WorkflowAction`] to the executor
+//! registered for its `action_type`. Integrators register their own
+//! executors (e.g. "notify", "assign"); "set_variable" is built in.
+
+use super::*;
+use std::sync::Arc;
+
+/// Something that can carry out a workflow action
+pub trait ActionExecutor: Send + Sync {
+    fn execute(&self, action: &WorkflowAction, ctx: &mut WorkflowContext) -> WorkflowResult<ActionResult>;
+}
+
+impl<F> ActionExecutor for F
+where
+    F: Fn(&WorkflowAction, &mut WorkflowContext) -> WorkflowResult<ActionResult> + Send + Sync,
+{
+    fn execute(&self, action: &WorkflowAction, ctx: &mut WorkflowContext) -> WorkflowResult<ActionResult> {
+        self(action, ctx)
+    }
+}
+
+/// Built-in "set_variable" action: sets `parameters["name"]` to `parameters["value"]`
+pub struct SetVariableExecutor;
+
+impl ActionExecutor for SetVariableExecutor {
+    fn execute(&self, action: &WorkflowAction, ctx: &mut WorkflowContext) -> WorkflowResult<ActionResult> {
+        let name = action
+            .parameters
+            .get("name")
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| WorkflowError::ActionFailed {
+                action: action.action_type.clone(),
+                error: "Missing string parameter 'name'".to_string(),
+            })?;
+        let value = action
+            .parameters
+            .get("value")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+
+        ctx.set_variable(name.to_string(), value.clone());
+        Ok(ActionResult::Success {
+            output: HashMap::from([(name.to_string(), value)]),
+        })
+    }
+}
+
+/// Executors keyed on action type
+#[derive(Clone)]
+pub struct ActionRegistry {
+    executors: HashMap<String, Arc<dyn ActionExecutor>>,
+}
+
+impl ActionRegistry {
+    /// Registry with the built-in "set_variable" executor
+    pub fn new() -> Self {
+        let mut registry = Self {
+            executors: HashMap::new(),
+        };
+        registry.register("set_variable", SetVariableExecutor);
+        registry
+    }
+
+    /// Register (or replace) the executor for `action_type`
+    pub fn register(&mut self, action_type: impl Into<String>, executor: impl ActionExecutor + 'static) {
+        self.executors.insert(action_type.into(), Arc::new(executor));
+    }
+
+    pub fn is_registered(&self, action_type: &str) -> bool {
+        self.executors.contains_key(action_type)
+    }
+}
+
+impl Default for ActionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ActionRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut action_types: Vec<&String> = self.executors.keys().collect();
+        action_types.sort();
+        f.debug_struct("ActionRegistry")
+            .field("action_types", &action_types)
+            .finish()
+    }
+}
+
+impl ActionExecutor for ActionRegistry {
+    fn execute(&self, action: &WorkflowAction, ctx: &mut WorkflowContext) -> WorkflowResult<ActionResult> {
+        let executor = self
+            .executors
+            .get(&action.action_type)
+            .ok_or_else(|| WorkflowError::ActionFailed {
+                action: action.action_type.clone(),
+                error: "No executor registered".to_string(),
+            })?;
+        executor.execute(action, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn action(action_type: &str, parameters: serde_json::Value) -> WorkflowAction {
+        WorkflowAction {
+            action_type: action_type.to_string(),
+            parameters: serde_json::from_value(parameters).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_set_variable_is_built_in() {
+        let registry = ActionRegistry::new();
+        let mut ctx = WorkflowContext::new();
+
+        let result = registry
+            .execute(&action("set_variable", serde_json::json!({"name": "approved", "value": true})), &mut ctx)
+            .unwrap();
+
+        assert_eq!(ctx.get_variable("approved"), Some(&serde_json::json!(true)));
+        assert!(matches!(result, ActionResult::Success { output } if output["approved"] == serde_json::json!(true)));
+    }
+
+    #[test]
+    fn test_custom_action_runs() {
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = ActionRegistry::new();
+        let log = notified.clone();
+        registry.register("notify", move |action: &WorkflowAction, ctx: &mut WorkflowContext| {
+            let recipient = action.parameters["to"].clone();
+            log.lock().unwrap().push(recipient.clone());
+            ctx.set_variable("last_notified".to_string(), recipient.clone());
+            Ok(ActionResult::Success {
+                output: HashMap::from([("notified".to_string(), recipient)]),
+            })
+        });
+        let mut ctx = WorkflowContext::new();
+
+        let result = registry
+            .execute(&action("notify", serde_json::json!({"to": "reviewer"})), &mut ctx)
+            .unwrap();
+
+        assert_eq!(*notified.lock().unwrap(), vec![serde_json::json!("reviewer")]);
+        assert_eq!(ctx.get_variable("last_notified"), Some(&serde_json::json!("reviewer")));
+        assert!(matches!(result, ActionResult::Success { output } if output["notified"] == "reviewer"));
+    }
+
+    #[test]
+    fn test_unknown_action_fails() {
+        let registry = ActionRegistry::new();
+        let mut ctx = WorkflowContext::new();
+
+        let result = registry.execute(&action("assign", serde_json::json!({})), &mut ctx);
+
+        assert!(matches!(result, Err(WorkflowError::ActionFailed { action, .. }) if action == "assign"));
+        assert!(!registry.is_registered("assign"));
+    }
+}
@@ -649,14 +649,12 @@ mod tests {
 
     fn create_test_document() -> Document {
         let mut doc = Document::new(DocumentId::new());
-        let info = DocumentInfoComponent {
-            title: "Test Document".to_string(),
-            description: None,
-            mime_type: "text/plain".to_string(),
-            filename: None,
-            size_bytes: 1024,
-            language: None,
-        };
+        let info = DocumentInfoComponent::builder()
+            .with_title("Test Document")
+            .with_mime_type("text/plain")
+            .with_size_bytes(1024)
+            .build()
+            .unwrap();
         doc.add_component(info, &Uuid::new_v4(), None).unwrap();
         doc
     }
@@ -55,45 +55,76 @@ impl WorkflowDefinition {
         }
     }
     
-    /// Validate workflow definition for correctness
+    /// Validate workflow definition, failing with the first problem
+    /// [`validate_definition`] reports
     pub fn validate(&self) -> WorkflowResult<()> {
-        self.graph.validate()?;
-        
-        // Ensure at least one start node
-        if self.graph.start_nodes.is_empty() {
-            return Err(WorkflowError::InvalidDefinition {
-                reason: "Workflow must have at least one start node".to_string(),
-            });
-        }
-        
-        // Ensure at least one end node
-        if self.graph.end_nodes.is_empty() {
-            return Err(WorkflowError::InvalidDefinition {
-                reason: "Workflow must have at least one end node".to_string(),
-            });
-        }
-        
-        Ok(())
+        validate_definition(self).map_err(first_problem)
     }
     
     /// Get all nodes that can be reached from start nodes
     pub fn get_reachable_nodes(&self) -> HashSet<NodeId> {
-        let mut reachable = HashSet::new();
-        let mut to_visit = self.graph.start_nodes.clone();
-        
-        while let Some(node_id) = to_visit.pop() {
-            if reachable.insert(node_id.clone()) {
-                // Add all nodes reachable from this node
-                for edge in self.graph.edges.values() {
-                    if edge.from_node == node_id {
-                        to_visit.push(edge.to_node.clone());
-                    }
-                }
+        self.graph.reachable_nodes()
+    }
+}
+
+/// Check a definition for structural problems, reporting all of them
+///
+/// A valid definition has exactly one start node, at least one end node,
+/// every node reachable from the start, and edges only between known nodes.
+pub fn validate_definition(definition: &WorkflowDefinition) -> Result<(), Vec<WorkflowError>> {
+    validate_graph(&definition.graph)
+}
+
+fn validate_graph(graph: &WorkflowGraph) -> Result<(), Vec<WorkflowError>> {
+    let invalid = |reason: String| WorkflowError::InvalidDefinition { reason };
+    let mut errors = Vec::new();
+
+    if graph.start_nodes.len() != 1 {
+        errors.push(invalid(format!(
+            "Workflow must have exactly one start node, found {}",
+            graph.start_nodes.len()
+        )));
+    }
+    if graph.end_nodes.is_empty() {
+        errors.push(invalid("Workflow must have at least one end node".to_string()));
+    }
+
+    let mut edges: Vec<&WorkflowEdge> = graph.edges.values().collect();
+    edges.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+    for edge in edges {
+        for endpoint in [&edge.from_node, &edge.to_node] {
+            if !graph.nodes.contains_key(endpoint) {
+                errors.push(invalid(format!(
+                    "Edge {} references non-existent node {}",
+                    edge.id.as_str(),
+                    endpoint.as_str()
+                )));
             }
         }
-        
-        reachable
     }
+
+    if !graph.start_nodes.is_empty() {
+        let reachable = graph.reachable_nodes();
+        let mut unreachable: Vec<&NodeId> = graph
+            .nodes
+            .keys()
+            .filter(|node_id| !reachable.contains(*node_id))
+            .collect();
+        unreachable.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        errors.extend(unreachable.into_iter().map(|node_id| {
+            invalid(format!("Node {} is not reachable from the start node", node_id.as_str()))
+        }));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn first_problem(mut errors: Vec<WorkflowError>) -> WorkflowError {
+    errors.remove(0)
 }
 
 /// Graph structure representing workflow flow
@@ -160,53 +191,24 @@ impl WorkflowGraph {
             .collect()
     }
     
-    /// Validate graph structure for correctness
+    /// Validate graph structure, failing with the first problem
+    /// [`validate_definition`] reports
     pub fn validate(&self) -> WorkflowResult<()> {
-        // Check all edges reference valid nodes
-        for edge in self.edges.values() {
-            if !self.nodes.contains_key(&edge.from_node) {
-                return Err(WorkflowError::InvalidDefinition {
-                    reason: format!("Edge references non-existent from_node: {}", edge.from_node.as_str()),
-                });
-            }
-            if !self.nodes.contains_key(&edge.to_node) {
-                return Err(WorkflowError::InvalidDefinition {
-                    reason: format!("Edge references non-existent to_node: {}", edge.to_node.as_str()),
-                });
-            }
-        }
+        validate_graph(self).map_err(first_problem)
+    }
+    
+    /// All nodes that can be reached from the start nodes
+    pub fn reachable_nodes(&self) -> HashSet<NodeId> {
+        let mut reachable = HashSet::new();
+        let mut to_visit = self.start_nodes.clone();
         
-        // Check for isolated nodes (except start/end nodes)
-        for (node_id, node) in &self.nodes {
-            let has_incoming = self.get_incoming_edges(node_id).len() > 0;
-            let has_outgoing = self.get_outgoing_edges(node_id).len() > 0;
-            
-            match node {
-                WorkflowNode::Start(_) => {
-                    if !has_outgoing {
-                        return Err(WorkflowError::InvalidDefinition {
-                            reason: format!("Start node {} has no outgoing edges", node_id.as_str()),
-                        });
-                    }
-                }
-                WorkflowNode::End(_) => {
-                    if !has_incoming {
-                        return Err(WorkflowError::InvalidDefinition {
-                            reason: format!("End node {} has no incoming edges", node_id.as_str()),
-                        });
-                    }
-                }
-                _ => {
-                    if !has_incoming && !has_outgoing {
-                        return Err(WorkflowError::InvalidDefinition {
-                            reason: format!("Node {} is isolated (no incoming or outgoing edges)", node_id.as_str()),
-                        });
-                    }
-                }
+        while let Some(node_id) = to_visit.pop() {
+            if reachable.insert(node_id.clone()) {
+                to_visit.extend(self.get_outgoing_edges(&node_id).into_iter().map(|edge| edge.to_node.clone()));
             }
         }
         
-        Ok(())
+        reachable
     }
 }
 
@@ -545,6 +547,51 @@ fn extract_variables(expression: &str) -> Vec<String> {
     variables
 }
 
+/// Draft → review → approved workflow shared by the workflow tests
+///
+/// Leaving `review` requires the `reviewed` guard and the `approve` edge
+/// requires the `approved` condition.
+#[cfg(test)]
+pub(crate) fn review_definition() -> WorkflowDefinition {
+    let mut definition = WorkflowDefinition::new(
+        "Review".to_string(),
+        "Draft, review, approve".to_string(),
+        Uuid::new_v4(),
+    );
+    let graph = &mut definition.graph;
+    graph.add_node(NodeId::new("draft"), WorkflowNode::Start(StartNode {
+        id: NodeId::new("draft"),
+        name: "Draft".to_string(),
+        actions: vec![],
+        metadata: HashMap::new(),
+    }));
+    graph.add_node(NodeId::new("review"), WorkflowNode::Task(TaskNode {
+        id: NodeId::new("review"),
+        name: "Review".to_string(),
+        task_type: TaskType::Manual,
+        assignees: vec![],
+        duration_sla: None,
+        guards: vec![Guard { condition: "reviewed".to_string() }],
+        actions: vec![],
+        metadata: HashMap::new(),
+    }));
+    graph.add_node(NodeId::new("approved"), WorkflowNode::End(EndNode {
+        id: NodeId::new("approved"),
+        name: "Approved".to_string(),
+        actions: vec![],
+        completion_status: CompletionStatus::Success,
+        metadata: HashMap::new(),
+    }));
+    graph.add_edge(EdgeId::new("submit"), NodeId::new("draft"), NodeId::new("review"), None);
+    graph.add_edge(
+        EdgeId::new("approve"),
+        NodeId::new("review"),
+        NodeId::new("approved"),
+        Some(Condition::boolean("approved".to_string())),
+    );
+    definition
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -643,4 +690,77 @@ mod tests {
         
         assert!(graph.validate().is_err());
     }
-}
\ No newline at end of file
+
+    fn task_node(id: &str) -> WorkflowNode {
+        WorkflowNode::Task(TaskNode {
+            id: NodeId::new(id),
+            name: id.to_string(),
+            task_type: TaskType::Manual,
+            assignees: vec![],
+            duration_sla: None,
+            guards: vec![],
+            actions: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_validate_definition_accepts_review_workflow() {
+        let definition = review_definition();
+        assert!(validate_definition(&definition).is_ok());
+        assert!(definition.validate().is_ok());
+        assert!(definition.graph.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_methods_report_the_first_definition_problem() {
+        let mut definition = review_definition();
+        definition.graph.add_node(NodeId::new("orphan"), task_node("orphan"));
+
+        let first = validate_definition(&definition).unwrap_err().remove(0).to_string();
+
+        assert_eq!(definition.validate().unwrap_err().to_string(), first);
+        assert_eq!(definition.graph.validate().unwrap_err().to_string(), first);
+    }
+
+    #[test]
+    fn test_validate_definition_rejects_orphan_node() {
+        let mut definition = review_definition();
+        definition.graph.add_node(NodeId::new("orphan"), task_node("orphan"));
+        definition.graph.add_edge(
+            EdgeId::new("dangling"),
+            NodeId::new("orphan"),
+            NodeId::new("missing"),
+            None,
+        );
+
+        let errors = validate_definition(&definition).unwrap_err();
+
+        let reasons: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors.len(), 2, "{reasons:?}");
+        assert!(reasons.iter().any(|r| r.contains("non-existent node missing")));
+        assert!(reasons.iter().any(|r| r.contains("orphan is not reachable")));
+    }
+
+    #[test]
+    fn test_validate_definition_rejects_missing_terminal() {
+        let mut definition = review_definition();
+        definition.graph.nodes.insert(NodeId::new("approved"), task_node("approved"));
+        definition.graph.end_nodes.clear();
+        definition.graph.add_node(NodeId::new("second_start"), WorkflowNode::Start(StartNode {
+            id: NodeId::new("second_start"),
+            name: "Second start".to_string(),
+            actions: vec![],
+            metadata: HashMap::new(),
+        }));
+
+        let errors = validate_definition(&definition).unwrap_err();
+
+        let reasons: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(reasons.iter().any(|r| r.contains("at least one end node")), "{reasons:?}");
+        assert!(reasons.iter().any(|r| r.contains("exactly one start node")), "{reasons:?}");
+        assert!(errors
+            .iter()
+            .all(|e| matches!(e, WorkflowError::InvalidDefinition { .. })));
+    }
+}
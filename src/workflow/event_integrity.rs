@@ -10,6 +10,7 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use std::collections::HashMap;
 
+use crate::aggregate::{compute_cid, CidCodec, CidHash};
 use crate::value_objects::{DocumentId, CidChain, CidChainLink, ChainError};
 use crate::workflow::{WorkflowId, WorkflowInstanceId, WorkflowNodeId};
 use crate::nats::{ActorId, MessageIdentity};
@@ -80,6 +81,9 @@ pub struct WorkflowEventLink {
     pub created_by: ActorId,
     /// Verification metadata
     pub integrity: WorkflowEventIntegrity,
+    /// Event payload the CID was computed over, when retained
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
 }
 
 /// Type of workflow event for chain tracking
@@ -268,28 +272,7 @@ impl DefaultWorkflowIntegrityService {
     
     /// Create CID from event content
     async fn create_event_cid(&self, content: &[u8]) -> Result<Cid, IntegrityError> {
-        use sha2::{Sha256, Digest};
-        
-        // Create SHA-256 hash of content
-        let mut hasher = Sha256::new();
-        hasher.update(content);
-        let hash_bytes = hasher.finalize();
-        
-        // Create a simple CID v1 with raw codec and SHA-256 hash
-        // This is a simplified approach - in production you'd want proper IPLD
-        let cid_string = format!("bafkreig{}", hex::encode(&hash_bytes[..20])); // Use first 20 bytes for valid CID
-        
-        // Fallback to a known valid CID format if parsing fails
-        match Cid::try_from(cid_string.as_str()) {
-            Ok(cid) => Ok(cid),
-            Err(_) => {
-                // Create a deterministic but valid CID from hash
-                let test_cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
-                Cid::try_from(test_cid).map_err(|e| IntegrityError::CidComputationFailed {
-                    reason: format!("Failed to create fallback CID: {}", e),
-                })
-            }
-        }
+        Ok(compute_cid(content, CidCodec::Raw, CidHash::Sha2_256))
     }
 }
 
@@ -305,12 +288,12 @@ impl WorkflowIntegrityService for DefaultWorkflowIntegrityService {
         event_payload: &[u8],
         predecessor_cid: Option<Cid>,
         actor: &ActorId,
-        node_id: &WorkflowNodeId,
-        event_type: WorkflowEventType,
+        _node_id: &WorkflowNodeId,
+        _event_type: WorkflowEventType,
     ) -> Result<WorkflowEventIntegrity, IntegrityError> {
-        // Create deterministic CID that includes node and event type for uniqueness
-        let enriched_payload = format!("{:?}:{:?}:{}", node_id, event_type, std::str::from_utf8(event_payload).unwrap_or("binary"));
-        let event_cid = self.create_event_cid(enriched_payload.as_bytes()).await?;
+        // Address the payload itself so verify_event_integrity can recompute it;
+        // node and event type are bound in by the link CID extend_event_chain computes
+        let event_cid = self.create_event_cid(event_payload).await?;
         let content_hash = self.compute_content_hash(event_payload);
         
         // Sequence number is 0 for genesis, otherwise increment
@@ -412,10 +395,11 @@ impl WorkflowIntegrityService for DefaultWorkflowIntegrityService {
         event_type: WorkflowEventType,
         actor: &ActorId,
     ) -> Result<(), IntegrityError> {
-        // Verify predecessor matches current head
-        if event_integrity.predecessor_cid != Some(chain.head_cid.clone()) {
+        // Verify predecessor matches the last link (none for the genesis event)
+        let expected_predecessor = chain.event_links.last().map(|link| link.event_cid);
+        if event_integrity.predecessor_cid != expected_predecessor {
             return Err(IntegrityError::InvalidPredecessor {
-                expected: chain.head_cid.clone(),
+                expected: expected_predecessor.unwrap_or(chain.head_cid),
                 actual: event_integrity.predecessor_cid.unwrap_or_else(|| {
                     // Create a dummy CID for error reporting
                     Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap()
@@ -423,20 +407,25 @@ impl WorkflowIntegrityService for DefaultWorkflowIntegrityService {
             });
         }
         
-        // Create new link
-        let link = WorkflowEventLink {
-            predecessor_cid: event_integrity.predecessor_cid.clone(),
-            event_cid: event_integrity.event_cid.clone(),
+        // The service only sees the payload's hash, so the link commits to that
+        let mut link = WorkflowEventLink {
+            predecessor_cid: event_integrity.predecessor_cid,
+            event_cid: event_integrity.event_cid,
             node_id,
             event_type,
             created_at: Utc::now(),
             created_by: actor.clone(),
+            payload: Some(serde_json::Value::String(event_integrity.content_hash.clone())),
             integrity: event_integrity,
         };
+        link.event_cid = link_cid(&link, link.payload.as_ref().unwrap());
         
         // Add to chain
+        if chain.event_links.is_empty() {
+            chain.genesis_cid = link.event_cid;
+        }
+        chain.head_cid = link.event_cid;
         chain.event_links.push(link);
-        chain.head_cid = chain.event_links.last().unwrap().event_cid.clone();
         chain.last_verified = Utc::now();
         
         // Update integrity status
@@ -489,6 +478,174 @@ pub fn create_workflow_event_chain(
     }
 }
 
+/// A workflow event to be appended to an event chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainedWorkflowEvent {
+    pub node_id: WorkflowNodeId,
+    pub event_type: WorkflowEventType,
+    pub created_by: ActorId,
+    pub payload: serde_json::Value,
+}
+
+/// Fields of a link covered by its CID, in a fixed order
+#[derive(Serialize)]
+struct CanonicalLink<'a> {
+    predecessor_cid: Option<String>,
+    sequence_number: u64,
+    node_id: &'a WorkflowNodeId,
+    event_type: &'a WorkflowEventType,
+    created_by: &'a ActorId,
+    created_at: &'a DateTime<Utc>,
+    payload: &'a serde_json::Value,
+}
+
+impl CanonicalLink<'_> {
+    /// CIDv1 (raw, SHA2-256) over the JSON form of the link
+    fn cid(&self) -> Cid {
+        let bytes = serde_json::to_vec(self).expect("canonical link serializes to JSON");
+        compute_cid(&bytes, CidCodec::Raw, CidHash::Sha2_256)
+    }
+}
+
+/// CID of `link` recomputed from its fields and `payload`
+fn link_cid(link: &WorkflowEventLink, payload: &serde_json::Value) -> Cid {
+    CanonicalLink {
+        predecessor_cid: link.predecessor_cid.map(|cid| cid.to_string()),
+        sequence_number: link.integrity.chain_metadata.sequence_number,
+        node_id: &link.node_id,
+        event_type: &link.event_type,
+        created_by: &link.created_by,
+        created_at: &link.created_at,
+        payload,
+    }
+    .cid()
+}
+
+/// Append `event` to `chain`, linking it to the current last event
+///
+/// The new link's CID covers the predecessor's CID, the link's own fields and
+/// the event payload, so altering any earlier link invalidates every later one.
+pub fn append_event(chain: &mut WorkflowEventChain, event: ChainedWorkflowEvent) -> WorkflowEventLink {
+    use sha2::{Digest, Sha256};
+
+    let predecessor_cid = chain.event_links.last().map(|link| link.event_cid);
+    let sequence_number = chain.event_links.len() as u64;
+    let now = Utc::now();
+    let event_cid = CanonicalLink {
+        predecessor_cid: predecessor_cid.map(|cid| cid.to_string()),
+        sequence_number,
+        node_id: &event.node_id,
+        event_type: &event.event_type,
+        created_by: &event.created_by,
+        created_at: &now,
+        payload: &event.payload,
+    }
+    .cid();
+
+    let link = WorkflowEventLink {
+        predecessor_cid,
+        event_cid,
+        node_id: event.node_id,
+        event_type: event.event_type,
+        created_at: now,
+        created_by: event.created_by.clone(),
+        integrity: WorkflowEventIntegrity {
+            event_cid,
+            predecessor_cid,
+            chain_metadata: ChainVerificationMetadata {
+                hash_algorithm: "sha2-256".to_string(),
+                sequence_number,
+                chain_length_at_creation: sequence_number + 1,
+                created_by: event.created_by,
+                computed_at: now,
+            },
+            content_hash: hex::encode(Sha256::digest(event.payload.to_string().as_bytes())),
+            digital_signature: None,
+        },
+        payload: Some(event.payload),
+    };
+
+    if chain.event_links.is_empty() {
+        chain.genesis_cid = event_cid;
+    }
+    chain.head_cid = event_cid;
+    chain.event_links.push(link.clone());
+    link
+}
+
+/// Verify every link of `chain`, reporting the first problem found
+///
+/// Checks sequence numbers (gaps and reordering), predecessor links,
+/// timestamps and the CID recomputed from each link. A link without its
+/// payload cannot be recomputed and is reported as a content mismatch.
+pub fn verify(chain: &WorkflowEventChain) -> ChainIntegrityStatus {
+    let mut previous: Option<&WorkflowEventLink> = None;
+
+    for (index, link) in chain.event_links.iter().enumerate() {
+        let position = index as u64;
+        let issue = |issue_type, severity, description: String| ChainIntegrityStatus::Corrupted {
+            issues: vec![IntegrityIssue {
+                issue_type,
+                position,
+                event_cid: link.event_cid,
+                description,
+                severity,
+            }],
+        };
+
+        let sequence_number = link.integrity.chain_metadata.sequence_number;
+        if sequence_number != position {
+            return issue(
+                IntegrityIssueType::InvalidSequence,
+                IssueSeverity::Major,
+                format!("Expected sequence number {position}, found {sequence_number}"),
+            );
+        }
+
+        let expected_predecessor = previous.map(|prev| prev.event_cid);
+        if link.predecessor_cid != expected_predecessor {
+            return issue(
+                IntegrityIssueType::BrokenLink,
+                IssueSeverity::Critical,
+                format!(
+                    "Expected predecessor {:?}, found {:?}",
+                    expected_predecessor, link.predecessor_cid
+                ),
+            );
+        }
+
+        if let Some(prev) = previous {
+            if link.created_at < prev.created_at {
+                return issue(
+                    IntegrityIssueType::TemporalViolation,
+                    IssueSeverity::Major,
+                    format!("Created at {} before its predecessor ({})", link.created_at, prev.created_at),
+                );
+            }
+        }
+
+        let Some(payload) = &link.payload else {
+            return issue(
+                IntegrityIssueType::ContentMismatch,
+                IssueSeverity::Critical,
+                "Payload missing, so the link cannot be verified".to_string(),
+            );
+        };
+        let computed = link_cid(link, payload);
+        if computed != link.event_cid {
+            return issue(
+                IntegrityIssueType::ContentMismatch,
+                IssueSeverity::Critical,
+                format!("Link hashes to {computed}, recorded {}", link.event_cid),
+            );
+        }
+
+        previous = Some(link);
+    }
+
+    ChainIntegrityStatus::Valid
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,6 +676,7 @@ mod tests {
         ).await.unwrap();
 
         assert_eq!(integrity.predecessor_cid, None);
+        assert_eq!(integrity.event_cid, compute_cid(payload, CidCodec::Raw, CidHash::Sha2_256));
         assert!(integrity.content_hash.len() > 0);
         assert_eq!(integrity.chain_metadata.sequence_number, 0);
         assert_eq!(integrity.chain_metadata.created_by, actor);
@@ -605,6 +763,8 @@ mod tests {
 
         assert_eq!(chain.event_links.len(), 2);
         assert!(matches!(chain.integrity_status, ChainIntegrityStatus::Valid));
+        assert_eq!(chain.genesis_cid, chain.event_links[0].event_cid);
+        assert_eq!(verify(&chain), ChainIntegrityStatus::Valid);
     }
 
     #[tokio::test]
@@ -641,6 +801,7 @@ mod tests {
             created_at: Utc::now(),
             created_by: actor,
             integrity: broken_integrity,
+            payload: None,
         };
 
         chain.event_links.push(broken_link);
@@ -655,4 +816,106 @@ mod tests {
             _ => panic!("Expected corrupted chain status"),
         }
     }
-}
\ No newline at end of file
+
+    fn four_event_chain() -> WorkflowEventChain {
+        let placeholder = Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
+        let mut chain = create_workflow_event_chain(WorkflowInstanceId::new(), DocumentId::new(), placeholder);
+        let actor = create_test_actor();
+        let node_id = create_test_node_id();
+
+        for (event_type, step) in [
+            (WorkflowEventType::Started, "draft"),
+            (WorkflowEventType::Transitioned, "review"),
+            (WorkflowEventType::Transitioned, "approve"),
+            (WorkflowEventType::Completed, "done"),
+        ] {
+            append_event(&mut chain, ChainedWorkflowEvent {
+                node_id: node_id.clone(),
+                event_type,
+                created_by: actor.clone(),
+                payload: serde_json::json!({ "step": step }),
+            });
+        }
+        chain
+    }
+
+    #[test]
+    fn test_append_event_links_each_event_to_its_predecessor() {
+        let chain = four_event_chain();
+
+        assert_eq!(chain.event_links.len(), 4);
+        assert_eq!(chain.genesis_cid, chain.event_links[0].event_cid);
+        assert_eq!(chain.head_cid, chain.event_links[3].event_cid);
+        assert_eq!(chain.event_links[0].predecessor_cid, None);
+        for pair in chain.event_links.windows(2) {
+            assert_eq!(pair[1].predecessor_cid, Some(pair[0].event_cid));
+        }
+        assert_eq!(verify(&chain), ChainIntegrityStatus::Valid);
+    }
+
+    #[test]
+    fn test_verify_detects_tampered_payload() {
+        let mut chain = four_event_chain();
+        chain.event_links[2].payload = Some(serde_json::json!({ "step": "rejected" }));
+
+        match verify(&chain) {
+            ChainIntegrityStatus::Corrupted { issues } => {
+                assert_eq!(issues.len(), 1);
+                assert_eq!(issues[0].issue_type, IntegrityIssueType::ContentMismatch);
+                assert_eq!(issues[0].position, 2);
+                assert_eq!(issues[0].event_cid, chain.event_links[2].event_cid);
+            }
+            status => panic!("Expected corrupted chain, got {status:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_detects_tampered_link_fields() {
+        let tamperings: [fn(&mut WorkflowEventLink); 4] = [
+            |link| link.node_id = WorkflowNodeId::new(),
+            |link| link.event_type = WorkflowEventType::Failed,
+            |link| link.created_by = ActorId::User(Uuid::new_v4()),
+            |link| link.created_at += chrono::Duration::milliseconds(1),
+        ];
+
+        for tamper in tamperings {
+            let mut chain = four_event_chain();
+            tamper(&mut chain.event_links[3]);
+            match verify(&chain) {
+                ChainIntegrityStatus::Corrupted { issues } => {
+                    assert_eq!(issues[0].issue_type, IntegrityIssueType::ContentMismatch);
+                    assert_eq!(issues[0].position, 3);
+                }
+                status => panic!("Expected corrupted chain, got {status:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_stripped_payload() {
+        let mut chain = four_event_chain();
+        chain.event_links[1].payload = None;
+
+        match verify(&chain) {
+            ChainIntegrityStatus::Corrupted { issues } => {
+                assert_eq!(issues[0].issue_type, IntegrityIssueType::ContentMismatch);
+                assert_eq!(issues[0].position, 1);
+            }
+            status => panic!("Expected corrupted chain, got {status:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_detects_reordering() {
+        let mut chain = four_event_chain();
+        chain.event_links.swap(1, 2);
+
+        match verify(&chain) {
+            ChainIntegrityStatus::Corrupted { issues } => {
+                assert_eq!(issues[0].issue_type, IntegrityIssueType::InvalidSequence);
+                assert_eq!(issues[0].position, 1);
+            }
+            status => panic!("Expected corrupted chain, got {status:?}"),
+        }
+    }
+}
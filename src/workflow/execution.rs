@@ -0,0 +1,190 @@
+//! Minimal execution of definition-based workflows
+//!
+//! Runs a [`WorkflowDefinition`] graph one edge at a time, checking guards
+//! against the [`WorkflowContext`] and recording every transition.
+
+use super::*;
+use super::definitions::{WorkflowGraph, WorkflowNode};
+use super::guard_evaluation::evaluate_guard;
+
+/// A running instance of a [`WorkflowDefinition`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowExecution {
+    pub id: WorkflowInstanceId,
+    pub workflow_id: WorkflowId,
+    pub document_id: DocumentId,
+    /// Graph being executed
+    pub graph: WorkflowGraph,
+    /// Node the instance currently sits at
+    pub current_node: NodeId,
+    pub node_statuses: HashMap<NodeId, NodeStatus>,
+    /// Transitions taken, oldest first
+    pub transitions: Vec<WorkflowTransition>,
+    pub status: WorkflowStatus,
+}
+
+impl WorkflowExecution {
+    /// Start executing `definition` at its start node
+    pub fn start(definition: &WorkflowDefinition, document_id: DocumentId) -> WorkflowResult<Self> {
+        let start = match definition.graph.start_nodes.as_slice() {
+            [start] => start.clone(),
+            starts => {
+                return Err(WorkflowError::InvalidDefinition {
+                    reason: format!("Expected one start node, found {}", starts.len()),
+                })
+            }
+        };
+
+        let mut node_statuses: HashMap<NodeId, NodeStatus> = definition
+            .graph
+            .nodes
+            .keys()
+            .map(|id| (id.clone(), NodeStatus::Pending))
+            .collect();
+        node_statuses.insert(start.clone(), NodeStatus::Active);
+
+        Ok(Self {
+            id: WorkflowInstanceId::new(),
+            workflow_id: definition.id.clone(),
+            document_id,
+            graph: definition.graph.clone(),
+            current_node: start,
+            node_statuses,
+            transitions: Vec::new(),
+            status: WorkflowStatus::Running,
+        })
+    }
+
+    /// Whether the instance is still running, suspended or escalated
+    pub fn is_active(&self) -> bool {
+        self.status.is_active()
+    }
+
+    /// Status of a node in this instance
+    pub fn node_status(&self, node_id: &NodeId) -> Option<&NodeStatus> {
+        self.node_statuses.get(node_id)
+    }
+}
+
+/// Follow `edge_id` out of the instance's current node
+///
+/// The edge's condition and the guards of the node being left must hold in
+/// `ctx`. On success the source node is completed, the target activated (or
+/// completed, for an end node) and the transition recorded.
+pub fn execute_transition(
+    instance: &mut WorkflowExecution,
+    edge_id: &EdgeId,
+    by: Uuid,
+    ctx: &WorkflowContext,
+) -> WorkflowResult<WorkflowTransition> {
+    let invalid = |to: &str, reason: &str| WorkflowError::InvalidTransition {
+        from: instance.current_node.as_str().to_string(),
+        to: to.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let edge = instance
+        .graph
+        .edges
+        .get(edge_id)
+        .ok_or_else(|| invalid(edge_id.as_str(), "Unknown edge"))?;
+    if instance.status != WorkflowStatus::Running {
+        return Err(invalid(edge.to_node.as_str(), "Workflow is not running"));
+    }
+    if edge.from_node != instance.current_node {
+        return Err(invalid(edge.to_node.as_str(), "Edge does not leave the current node"));
+    }
+
+    let node_guards = match instance.graph.nodes.get(&edge.from_node) {
+        Some(WorkflowNode::Task(task)) => task.guards.clone(),
+        _ => Vec::new(),
+    };
+    let edge_guard = edge.condition.as_ref().map(|condition| Guard {
+        condition: condition.expression.clone(),
+    });
+    for guard in node_guards.iter().chain(edge_guard.as_ref()) {
+        if !evaluate_guard(guard, ctx)? {
+            return Err(WorkflowError::GuardFailed {
+                guard: guard.condition.clone(),
+                reason: format!("Not satisfied leaving {}", edge.from_node.as_str()),
+            });
+        }
+    }
+
+    let from_node = edge.from_node.clone();
+    let to_node = edge.to_node.clone();
+    let is_end = instance.graph.end_nodes.contains(&to_node);
+
+    instance.node_statuses.insert(from_node.clone(), NodeStatus::Completed);
+    if is_end {
+        instance.node_statuses.insert(to_node.clone(), NodeStatus::Completed);
+        instance.status = WorkflowStatus::Completed;
+    } else {
+        instance.node_statuses.insert(to_node.clone(), NodeStatus::Active);
+    }
+    instance.current_node = to_node.clone();
+
+    let transition = WorkflowTransition {
+        id: Uuid::new_v4(),
+        from_node,
+        to_node,
+        transitioned_at: Utc::now(),
+        transitioned_by: by,
+        reason: None,
+        data: HashMap::from([(
+            "edge_id".to_string(),
+            serde_json::Value::String(edge_id.as_str().to_string()),
+        )]),
+    };
+    instance.transitions.push(transition.clone());
+    Ok(transition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::definitions::review_definition;
+
+    #[test]
+    fn test_draft_review_approved() {
+        let reviewer = Uuid::new_v4();
+        let mut instance = WorkflowExecution::start(&review_definition(), DocumentId::new()).unwrap();
+        let mut ctx = WorkflowContext::new();
+
+        execute_transition(&mut instance, &EdgeId::new("submit"), reviewer, &ctx).unwrap();
+        assert_eq!(instance.node_status(&NodeId::new("draft")), Some(&NodeStatus::Completed));
+        assert_eq!(instance.node_status(&NodeId::new("review")), Some(&NodeStatus::Active));
+
+        ctx.set_variable("reviewed".to_string(), serde_json::json!(true));
+        ctx.set_variable("approved".to_string(), serde_json::json!(true));
+        execute_transition(&mut instance, &EdgeId::new("approve"), reviewer, &ctx).unwrap();
+
+        let path: Vec<(&str, &str)> = instance
+            .transitions
+            .iter()
+            .map(|t| (t.from_node.as_str(), t.to_node.as_str()))
+            .collect();
+        assert_eq!(path, vec![("draft", "review"), ("review", "approved")]);
+        assert!(instance.transitions.iter().all(|t| t.transitioned_by == reviewer));
+        assert_eq!(instance.node_status(&NodeId::new("approved")), Some(&NodeStatus::Completed));
+        assert_eq!(instance.status, WorkflowStatus::Completed);
+    }
+
+    #[test]
+    fn test_invalid_transitions_are_rejected() {
+        let by = Uuid::new_v4();
+        let mut instance = WorkflowExecution::start(&review_definition(), DocumentId::new()).unwrap();
+        let ctx = WorkflowContext::new();
+
+        let skip = execute_transition(&mut instance, &EdgeId::new("approve"), by, &ctx);
+        assert!(matches!(skip, Err(WorkflowError::InvalidTransition { .. })));
+        let unknown = execute_transition(&mut instance, &EdgeId::new("publish"), by, &ctx);
+        assert!(matches!(unknown, Err(WorkflowError::InvalidTransition { .. })));
+
+        execute_transition(&mut instance, &EdgeId::new("submit"), by, &ctx).unwrap();
+        let unguarded = execute_transition(&mut instance, &EdgeId::new("approve"), by, &ctx);
+        assert!(matches!(unguarded, Err(WorkflowError::GuardFailed { .. })));
+        assert_eq!(instance.current_node, NodeId::new("review"));
+        assert_eq!(instance.transitions.len(), 1);
+    }
+}
@@ -0,0 +1,374 @@
+//! Evaluation of guard conditions against workflow context variables
+//!
+//! Conditions are small boolean expressions such as
+//! `amount > 1000 and status == 'approved'` or `role in ['reviewer', 'admin']`.
+//! A bare variable name holds when the variable is `true`.
+
+use super::*;
+use serde_json::Value;
+
+/// Evaluate `guard.condition` against the variables in `ctx`
+///
+/// An empty condition always holds. Unknown variables, type mismatches and
+/// malformed expressions are reported as [`WorkflowError::GuardFailed`].
+pub fn evaluate_guard(guard: &Guard, ctx: &WorkflowContext) -> WorkflowResult<bool> {
+    let failed = |reason: String| WorkflowError::GuardFailed {
+        guard: guard.condition.clone(),
+        reason,
+    };
+
+    let tokens = tokenize(&guard.condition).map_err(failed)?;
+    if tokens.is_empty() {
+        return Ok(true);
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or().map_err(failed)?;
+    if let Some(token) = parser.peek() {
+        return Err(failed(format!("Unexpected {token:?}")));
+    }
+    expr.evaluate(ctx).map_err(failed)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Op(&'static str),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | '[' | ']' | ',' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    _ => Token::Comma,
+                });
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(ch) if ch == c => break,
+                        Some(ch) => value.push(ch),
+                        None => return Err("Unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '=' | '!' | '<' | '>' | '&' | '|' => {
+                chars.next();
+                let next = chars.peek().copied();
+                let (op, two_chars) = match (c, next) {
+                    ('=', Some('=')) => ("==", true),
+                    ('!', Some('=')) => ("!=", true),
+                    ('<', Some('=')) => ("<=", true),
+                    ('>', Some('=')) => (">=", true),
+                    ('&', Some('&')) => ("and", true),
+                    ('|', Some('|')) => ("or", true),
+                    ('!', _) => ("not", false),
+                    ('<', _) => ("<", false),
+                    ('>', _) => (">", false),
+                    _ => return Err(format!("Unexpected '{c}'")),
+                };
+                if two_chars {
+                    chars.next();
+                }
+                tokens.push(Token::Op(op));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_ascii_digit() || ch == '.' || (ch == '-' && number.is_empty()) {
+                        number.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let value = number
+                    .parse()
+                    .map_err(|_| format!("Invalid number '{number}'"))?;
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_alphanumeric() || ch == '_' || ch == '.' {
+                        ident.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(match ident.as_str() {
+                    "and" => Token::Op("and"),
+                    "or" => Token::Op("or"),
+                    "not" => Token::Op("not"),
+                    "in" => Token::Op("in"),
+                    _ => Token::Ident(ident),
+                });
+            }
+            c => return Err(format!("Unexpected '{c}'")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Expr {
+    Literal(bool),
+    Variable(String),
+    Compare {
+        variable: String,
+        op: &'static str,
+        value: Value,
+    },
+    In {
+        variable: String,
+        values: Vec<Value>,
+    },
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Op(o)) if *o == op);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_and()?;
+        while self.eat_op("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_term()?;
+        while self.eat_op("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_term()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        if self.eat_op("not") {
+            return Ok(Expr::Not(Box::new(self.parse_term()?)));
+        }
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("Expected ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(name)) => self.parse_comparison(name),
+            Some(token) => Err(format!("Unexpected {token:?}")),
+            None => Err("Unexpected end of condition".to_string()),
+        }
+    }
+
+    fn parse_comparison(&mut self, name: String) -> Result<Expr, String> {
+        let op = match self.peek() {
+            Some(Token::Op(op)) if !matches!(*op, "and" | "or" | "not") => *op,
+            _ => {
+                return Ok(match name.as_str() {
+                    "true" => Expr::Literal(true),
+                    "false" => Expr::Literal(false),
+                    _ => Expr::Variable(name),
+                })
+            }
+        };
+        self.pos += 1;
+
+        if op == "in" {
+            if self.next() != Some(Token::LBracket) {
+                return Err("Expected '[' after 'in'".to_string());
+            }
+            let mut values = Vec::new();
+            if self.peek() == Some(&Token::RBracket) {
+                self.pos += 1;
+            } else {
+                loop {
+                    values.push(self.parse_value()?);
+                    match self.next() {
+                        Some(Token::Comma) => continue,
+                        Some(Token::RBracket) => break,
+                        _ => return Err("Expected ',' or ']'".to_string()),
+                    }
+                }
+            }
+            return Ok(Expr::In { variable: name, values });
+        }
+
+        let value = self.parse_value()?;
+        Ok(Expr::Compare { variable: name, op, value })
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(Value::String(s)),
+            Some(Token::Number(n)) => Ok(serde_json::json!(n)),
+            Some(Token::Ident(ident)) if ident == "true" => Ok(Value::Bool(true)),
+            Some(Token::Ident(ident)) if ident == "false" => Ok(Value::Bool(false)),
+            Some(token) => Err(format!("Expected a literal, found {token:?}")),
+            None => Err("Expected a literal".to_string()),
+        }
+    }
+}
+
+impl Expr {
+    fn evaluate(&self, ctx: &WorkflowContext) -> Result<bool, String> {
+        let lookup = |name: &str| {
+            ctx.get_variable(name)
+                .ok_or_else(|| format!("Unknown variable '{name}'"))
+        };
+
+        match self {
+            Expr::Literal(value) => Ok(*value),
+            Expr::Variable(name) => match lookup(name)? {
+                Value::Bool(value) => Ok(*value),
+                other => Err(format!("Variable '{name}' is not a boolean: {other}")),
+            },
+            Expr::Compare { variable, op, value } => {
+                let actual = lookup(variable)?;
+                match *op {
+                    "==" => Ok(values_equal(actual, value)),
+                    "!=" => Ok(!values_equal(actual, value)),
+                    _ => {
+                        let (Some(left), Some(right)) = (actual.as_f64(), value.as_f64()) else {
+                            return Err(format!("Cannot compare '{variable}' ({actual}) with {value} using {op}"));
+                        };
+                        Ok(match *op {
+                            ">" => left > right,
+                            ">=" => left >= right,
+                            "<" => left < right,
+                            "<=" => left <= right,
+                            _ => return Err(format!("Unknown operator {op}")),
+                        })
+                    }
+                }
+            }
+            Expr::In { variable, values } => {
+                let actual = lookup(variable)?;
+                Ok(values.iter().any(|value| values_equal(actual, value)))
+            }
+            Expr::Not(expr) => Ok(!expr.evaluate(ctx)?),
+            Expr::And(left, right) => Ok(left.evaluate(ctx)? && right.evaluate(ctx)?),
+            Expr::Or(left, right) => Ok(left.evaluate(ctx)? || right.evaluate(ctx)?),
+        }
+    }
+}
+
+/// Equality treating integer and float JSON numbers alike
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left.as_f64(), right.as_f64()) {
+        (Some(l), Some(r)) => l == r,
+        _ => left == right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard(condition: &str) -> Guard {
+        Guard { condition: condition.to_string() }
+    }
+
+    fn context() -> WorkflowContext {
+        let mut ctx = WorkflowContext::new();
+        ctx.set_variable("amount".to_string(), serde_json::json!(1500));
+        ctx.set_variable("status".to_string(), serde_json::json!("approved"));
+        ctx.set_variable("role".to_string(), serde_json::json!("reviewer"));
+        ctx.set_variable("urgent".to_string(), serde_json::json!(false));
+        ctx
+    }
+
+    #[test]
+    fn test_numeric_comparisons() {
+        let ctx = context();
+        assert!(evaluate_guard(&guard("amount > 1000"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("amount >= 1500"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("amount == 1500.0"), &ctx).unwrap());
+        assert!(!evaluate_guard(&guard("amount < 1000"), &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_string_equality() {
+        let ctx = context();
+        assert!(evaluate_guard(&guard("status == 'approved'"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("status != \"rejected\""), &ctx).unwrap());
+        assert!(!evaluate_guard(&guard("status == 'draft'"), &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_membership() {
+        let ctx = context();
+        assert!(evaluate_guard(&guard("role in ['reviewer','admin']"), &ctx).unwrap());
+        assert!(!evaluate_guard(&guard("role in ['admin']"), &ctx).unwrap());
+        assert!(!evaluate_guard(&guard("role in []"), &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_compound_conditions() {
+        let ctx = context();
+        assert!(evaluate_guard(&guard("amount > 1000 and status == 'approved'"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("urgent or role in ['reviewer']"), &ctx).unwrap());
+        assert!(!evaluate_guard(&guard("amount > 1000 and urgent"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("not urgent and (amount < 10 or status == 'approved')"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard("!urgent && amount > 1000"), &ctx).unwrap());
+        assert!(evaluate_guard(&guard(""), &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_unknown_variable_fails() {
+        let ctx = context();
+        let result = evaluate_guard(&guard("budget > 1000"), &ctx);
+        assert!(matches!(result, Err(WorkflowError::GuardFailed { reason, .. }) if reason.contains("budget")));
+
+        let malformed = evaluate_guard(&guard("amount >"), &ctx);
+        assert!(matches!(malformed, Err(WorkflowError::GuardFailed { .. })));
+    }
+}
@@ -12,6 +12,10 @@ pub mod manager;
 pub mod cim_events;
 pub mod cim_engine;
 pub mod event_integrity;
+pub mod execution;
+pub mod guard_evaluation;
+pub mod sla;
+pub mod action_execution;
 // TODO: Re-enable complex modules after simplification
 // pub mod engine;
 // pub mod guards; 
@@ -19,7 +23,7 @@ pub mod event_integrity;
 // pub mod templates;
 
 // Import definitions types with specific names
-pub use definitions::{WorkflowDefinition, WorkflowGraph as ComplexWorkflowGraph, WorkflowNode as ComplexWorkflowNode, WorkflowEdge as ComplexWorkflowEdge};
+pub use definitions::{WorkflowDefinition, validate_definition, WorkflowGraph as ComplexWorkflowGraph, WorkflowNode as ComplexWorkflowNode, WorkflowEdge as ComplexWorkflowEdge};
 // Import simple workflow types
 pub use simple_workflow::*;
 pub use event_integration::*;
@@ -37,14 +41,19 @@ pub use cim_engine::*;
 pub use event_integrity::{
     WorkflowEventIntegrity, WorkflowEventChain, WorkflowEventLink,
     ChainIntegrityStatus, IntegrityIssue, WorkflowIntegrityService,
-    DefaultWorkflowIntegrityService, IntegrityError,
+    DefaultWorkflowIntegrityService, IntegrityError, ChainedWorkflowEvent, append_event, verify,
 };
+// Guarded execution of definition-based workflows
+pub use execution::{WorkflowExecution, execute_transition};
+pub use guard_evaluation::evaluate_guard;
+pub use sla::{check_sla, fire_escalations};
+pub use action_execution::{ActionExecutor, ActionRegistry, SetVariableExecutor};
 
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc, Duration};
 use std::collections::HashMap;
-use crate::value_objects::DocumentId;
+use crate::value_objects::{parse_uuid, DocumentId, IdParseError};
 
 /// Unique identifier for workflow definitions
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -93,6 +102,20 @@ impl Default for WorkflowId {
     }
 }
 
+impl std::fmt::Display for WorkflowId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for WorkflowId {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_uuid("WorkflowId", s).map(Self)
+    }
+}
+
 /// Unique identifier for workflow instances
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WorkflowInstanceId(Uuid);
@@ -117,6 +140,20 @@ impl Default for WorkflowInstanceId {
     }
 }
 
+impl std::fmt::Display for WorkflowInstanceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for WorkflowInstanceId {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_uuid("WorkflowInstanceId", s).map(Self)
+    }
+}
+
 /// Unique identifier for workflow nodes
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NodeId(String);
@@ -186,6 +223,13 @@ pub enum WorkflowStatus {
     Escalated,
 }
 
+impl WorkflowStatus {
+    /// Running, suspended or escalated (i.e. not yet finished)
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Running | Self::Suspended | Self::Escalated)
+    }
+}
+
 /// Workflow execution context containing runtime data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowContext {
@@ -197,6 +241,12 @@ pub struct WorkflowContext {
     pub sla_deadlines: HashMap<NodeId, DateTime<Utc>>,
     /// Active escalation rules
     pub escalation_rules: Vec<EscalationRule>,
+    /// How long before its deadline a node counts as at risk
+    #[serde(default = "sla::default_at_risk_window")]
+    pub sla_at_risk_window: Duration,
+    /// Last firing of each (node, escalation rule) pair
+    #[serde(default)]
+    pub escalations_fired: Vec<FiredEscalation>,
 }
 
 impl WorkflowContext {
@@ -206,6 +256,8 @@ impl WorkflowContext {
             permissions: HashMap::new(),
             sla_deadlines: HashMap::new(),
             escalation_rules: Vec::new(),
+            sla_at_risk_window: sla::default_at_risk_window(),
+            escalations_fired: Vec::new(),
         }
     }
     
@@ -268,6 +320,15 @@ pub struct EscalationRule {
     pub repeat_interval: Option<Duration>,
 }
 
+/// When an escalation rule last fired for a node
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FiredEscalation {
+    pub node_id: NodeId,
+    /// Index into [`WorkflowContext::escalation_rules`]
+    pub rule: usize,
+    pub fired_at: DateTime<Utc>,
+}
+
 /// Record of a workflow state transition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowTransition {
@@ -376,6 +437,23 @@ pub struct NodeInfo {
     pub sla_status: SLAStatus,
 }
 
+/// Binding between a document and a workflow instance driving it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentWorkflowBinding {
+    pub document_id: DocumentId,
+    pub instance_id: WorkflowInstanceId,
+    pub workflow_id: WorkflowId,
+    pub status: WorkflowStatus,
+    pub current_node: NodeInfo,
+}
+
+impl DocumentWorkflowBinding {
+    /// Whether the bound instance is still in progress
+    pub fn is_active(&self) -> bool {
+        self.status.is_active()
+    }
+}
+
 /// Status of individual workflow nodes
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NodeStatus {
@@ -457,6 +535,18 @@ mod tests {
         assert_eq!(id3.as_uuid(), &uuid);
     }
     
+    #[test]
+    fn test_workflow_ids_from_str() {
+        let id = WorkflowId::new();
+        assert_eq!(id.to_string().parse::<WorkflowId>().unwrap(), id);
+
+        let instance_id = WorkflowInstanceId::new();
+        assert_eq!(instance_id.to_string().parse::<WorkflowInstanceId>().unwrap(), instance_id);
+
+        let err = "workflow-1".parse::<WorkflowInstanceId>().unwrap_err();
+        assert_eq!(err.kind, "WorkflowInstanceId");
+    }
+    
     #[test]
     fn test_workflow_context() {
         let mut context = WorkflowContext::new();
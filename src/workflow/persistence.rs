@@ -116,6 +116,84 @@ impl WorkflowRepository for InMemoryWorkflowRepository {
     }
 }
 
+/// A running definition-based workflow together with its context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowInstanceRecord {
+    /// Current node, node statuses and transition history
+    pub instance: WorkflowExecution,
+    /// Variables, permissions and SLA state
+    pub context: WorkflowContext,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl WorkflowInstanceRecord {
+    pub fn new(instance: WorkflowExecution, context: WorkflowContext) -> Self {
+        Self {
+            instance,
+            context,
+            saved_at: Utc::now(),
+        }
+    }
+}
+
+/// Storage for running [`WorkflowExecution`]s and their contexts
+#[async_trait]
+pub trait WorkflowInstanceStore: Send + Sync {
+    /// Save (or overwrite) an instance
+    async fn save(&self, record: &WorkflowInstanceRecord) -> WorkflowResult<()>;
+
+    /// Load an instance by ID
+    async fn load(&self, instance_id: WorkflowInstanceId) -> WorkflowResult<Option<WorkflowInstanceRecord>>;
+
+    /// Instances that are still running, suspended or escalated
+    async fn list_active(&self) -> WorkflowResult<Vec<WorkflowInstanceRecord>>;
+}
+
+/// In-memory instance store keeping each instance serialized as JSON
+#[derive(Debug, Default)]
+pub struct InMemoryWorkflowInstanceStore {
+    records: tokio::sync::RwLock<HashMap<WorkflowInstanceId, StoredInstance>>,
+}
+
+/// Serialized record plus the fields `list_active` filters on, so finished
+/// instances never have to be deserialized
+#[derive(Debug)]
+struct StoredInstance {
+    status: WorkflowStatus,
+    json: String,
+}
+
+#[async_trait]
+impl WorkflowInstanceStore for InMemoryWorkflowInstanceStore {
+    async fn save(&self, record: &WorkflowInstanceRecord) -> WorkflowResult<()> {
+        let stored = StoredInstance {
+            status: record.instance.status.clone(),
+            json: serde_json::to_string(record)?,
+        };
+        self.records.write().await.insert(record.instance.id, stored);
+        Ok(())
+    }
+
+    async fn load(&self, instance_id: WorkflowInstanceId) -> WorkflowResult<Option<WorkflowInstanceRecord>> {
+        let records = self.records.read().await;
+        records
+            .get(&instance_id)
+            .map(|stored| serde_json::from_str(&stored.json).map_err(WorkflowError::from))
+            .transpose()
+    }
+
+    async fn list_active(&self) -> WorkflowResult<Vec<WorkflowInstanceRecord>> {
+        let records = self.records.read().await;
+        let mut active = records
+            .values()
+            .filter(|stored| stored.status.is_active())
+            .map(|stored| serde_json::from_str(&stored.json))
+            .collect::<Result<Vec<WorkflowInstanceRecord>, _>>()?;
+        active.sort_by_key(|record| record.saved_at);
+        Ok(active)
+    }
+}
+
 /// Persistent workflow engine that uses a repository for storage
 pub struct PersistentWorkflowEngine {
     repository: Box<dyn WorkflowRepository>,
@@ -403,6 +481,7 @@ impl WorkflowAuditService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workflow::definitions::review_definition;
 
     #[tokio::test]
     async fn test_in_memory_repository() {
@@ -477,4 +556,58 @@ mod tests {
         assert_eq!(trail.len(), 1);
         assert!(matches!(trail[0].event_type, WorkflowEventType::WorkflowStarted));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_instance_store_round_trip_mid_workflow() {
+        let store = InMemoryWorkflowInstanceStore::default();
+        let definition = review_definition();
+        let author = Uuid::new_v4();
+
+        let mut instance = WorkflowExecution::start(&definition, DocumentId::new()).unwrap();
+        let mut context = WorkflowContext::new();
+        context.set_variable("amount".to_string(), serde_json::json!(1200));
+        context.set_variable("reviewer".to_string(), serde_json::json!("alice"));
+        execute_transition(&mut instance, &EdgeId::new("submit"), author, &context).unwrap();
+        let instance_id = instance.id;
+
+        store
+            .save(&WorkflowInstanceRecord::new(instance, context))
+            .await
+            .unwrap();
+        let loaded = store.load(instance_id).await.unwrap().unwrap();
+
+        assert_eq!(loaded.instance.current_node, NodeId::new("review"));
+        assert_eq!(loaded.instance.status, WorkflowStatus::Running);
+        assert_eq!(loaded.instance.transitions.len(), 1);
+        assert_eq!(loaded.instance.transitions[0].transitioned_by, author);
+        assert_eq!(loaded.context.get_variable("amount"), Some(&serde_json::json!(1200)));
+        assert_eq!(loaded.context.get_variable("reviewer"), Some(&serde_json::json!("alice")));
+        assert!(store.load(WorkflowInstanceId::new()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_instance_store_lists_only_active() {
+        let store = InMemoryWorkflowInstanceStore::default();
+        let definition = review_definition();
+        let mut context = WorkflowContext::new();
+        context.set_variable("reviewed".to_string(), serde_json::json!(true));
+        context.set_variable("approved".to_string(), serde_json::json!(true));
+        let by = Uuid::new_v4();
+
+        let running = WorkflowExecution::start(&definition, DocumentId::new()).unwrap();
+        let mut finished = WorkflowExecution::start(&definition, DocumentId::new()).unwrap();
+        execute_transition(&mut finished, &EdgeId::new("submit"), by, &context).unwrap();
+        execute_transition(&mut finished, &EdgeId::new("approve"), by, &context).unwrap();
+
+        for instance in [running.clone(), finished] {
+            store
+                .save(&WorkflowInstanceRecord::new(instance, context.clone()))
+                .await
+                .unwrap();
+        }
+
+        let active = store.list_active().await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].instance.id, running.id);
+    }
+}
@@ -0,0 +1,208 @@
+//! SLA monitoring and escalation for workflow nodes
+//!
+//! Deadlines come from [`WorkflowContext::sla_deadlines`]; escalation rules
+//! are measured from a node's deadline and remembered in the context so
+//! repeats honour each rule's `repeat_interval`.
+
+use super::*;
+
+/// Default lead time before a deadline at which a node is at risk
+pub fn default_at_risk_window() -> Duration {
+    Duration::hours(4)
+}
+
+/// Classify every node with a deadline, ordered by node id
+///
+/// Nodes past their deadline are `Breached`; nodes within
+/// `ctx.sla_at_risk_window` of it are `AtRisk`.
+pub fn check_sla(ctx: &WorkflowContext, now: DateTime<Utc>) -> Vec<(NodeId, SLAStatus)> {
+    let mut statuses: Vec<(NodeId, SLAStatus)> = ctx
+        .sla_deadlines
+        .iter()
+        .map(|(node_id, deadline)| {
+            let status = if now >= *deadline {
+                SLAStatus::Breached
+            } else if *deadline - now <= ctx.sla_at_risk_window {
+                SLAStatus::AtRisk
+            } else {
+                SLAStatus::OnTrack
+            };
+            (node_id.clone(), status)
+        })
+        .collect();
+    statuses.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+    statuses
+}
+
+/// Actions of every escalation rule now due, recording when each fired
+///
+/// A rule is due for a node once `trigger_after` has elapsed past the node's
+/// deadline. It fires once, then again every `repeat_interval` if set.
+/// Nodes `instance` has already completed or skipped never escalate.
+/// Returned actions carry `node_id` and `escalate_to` parameters.
+pub fn fire_escalations(
+    instance: &WorkflowExecution,
+    ctx: &mut WorkflowContext,
+    now: DateTime<Utc>,
+) -> Vec<WorkflowAction> {
+    let mut deadlines: Vec<(NodeId, DateTime<Utc>)> = ctx
+        .sla_deadlines
+        .iter()
+        .filter(|(node_id, _)| {
+            !matches!(
+                instance.node_status(node_id),
+                Some(NodeStatus::Completed | NodeStatus::Skipped)
+            )
+        })
+        .map(|(node_id, deadline)| (node_id.clone(), *deadline))
+        .collect();
+    deadlines.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+    let mut actions = Vec::new();
+    for (node_id, deadline) in deadlines {
+        for (index, rule) in ctx.escalation_rules.iter().enumerate() {
+            if now < deadline + rule.trigger_after {
+                continue;
+            }
+            let fired = ctx
+                .escalations_fired
+                .iter_mut()
+                .find(|fired| fired.node_id == node_id && fired.rule == index);
+            let due = match (&fired, rule.repeat_interval) {
+                (None, _) => true,
+                (Some(last), Some(interval)) => now >= last.fired_at + interval,
+                (Some(_), None) => false,
+            };
+            if !due {
+                continue;
+            }
+            match fired {
+                Some(last) => last.fired_at = now,
+                None => ctx.escalations_fired.push(FiredEscalation {
+                    node_id: node_id.clone(),
+                    rule: index,
+                    fired_at: now,
+                }),
+            }
+
+            actions.extend(rule.actions.iter().cloned().map(|mut action| {
+                action.parameters.insert(
+                    "node_id".to_string(),
+                    serde_json::Value::String(node_id.as_str().to_string()),
+                );
+                action.parameters.insert(
+                    "escalate_to".to_string(),
+                    serde_json::json!(rule.escalate_to),
+                );
+                action
+            }));
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::definitions::review_definition;
+
+    fn notify() -> WorkflowAction {
+        WorkflowAction {
+            action_type: "notify".to_string(),
+            parameters: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_sla_classifies_nodes() {
+        let now = Utc::now();
+        let mut ctx = WorkflowContext::new();
+        ctx.sla_at_risk_window = Duration::hours(1);
+        ctx.sla_deadlines.insert(NodeId::new("approve"), now - Duration::minutes(5));
+        ctx.sla_deadlines.insert(NodeId::new("review"), now + Duration::minutes(30));
+        ctx.sla_deadlines.insert(NodeId::new("sign"), now + Duration::days(2));
+
+        assert_eq!(
+            check_sla(&ctx, now),
+            vec![
+                (NodeId::new("approve"), SLAStatus::Breached),
+                (NodeId::new("review"), SLAStatus::AtRisk),
+                (NodeId::new("sign"), SLAStatus::OnTrack),
+            ]
+        );
+
+        ctx.sla_at_risk_window = Duration::minutes(10);
+        assert!(check_sla(&ctx, now).contains(&(NodeId::new("review"), SLAStatus::OnTrack)));
+    }
+
+    #[test]
+    fn test_breached_deadline_fires_escalations() {
+        let deadline = Utc::now();
+        let manager = Uuid::new_v4();
+        let instance = WorkflowExecution::start(&review_definition(), DocumentId::new()).unwrap();
+        let mut ctx = WorkflowContext::new();
+        ctx.sla_deadlines.insert(NodeId::new("review"), deadline);
+        ctx.escalation_rules.push(EscalationRule {
+            trigger_after: Duration::hours(1),
+            escalate_to: vec![manager],
+            actions: vec![notify()],
+            repeat_interval: Some(Duration::hours(2)),
+        });
+        ctx.escalation_rules.push(EscalationRule {
+            trigger_after: Duration::hours(4),
+            escalate_to: vec![],
+            actions: vec![notify()],
+            repeat_interval: None,
+        });
+
+        assert!(fire_escalations(&instance, &mut ctx, deadline + Duration::minutes(30)).is_empty());
+
+        let now = deadline + Duration::hours(1);
+        assert_eq!(
+            check_sla(&ctx, now),
+            vec![(NodeId::new("review"), SLAStatus::Breached)]
+        );
+        let fired = fire_escalations(&instance, &mut ctx, now);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].action_type, "notify");
+        assert_eq!(fired[0].parameters["node_id"], serde_json::json!("review"));
+        assert_eq!(fired[0].parameters["escalate_to"], serde_json::json!([manager]));
+
+        // Not repeated before the interval elapses
+        assert!(fire_escalations(&instance, &mut ctx, deadline + Duration::hours(2)).is_empty());
+
+        // First rule repeats and the second, one-shot rule triggers
+        assert_eq!(fire_escalations(&instance, &mut ctx, deadline + Duration::hours(4)).len(), 2);
+        assert!(fire_escalations(&instance, &mut ctx, deadline + Duration::hours(5)).is_empty());
+        assert_eq!(fire_escalations(&instance, &mut ctx, deadline + Duration::hours(6)).len(), 1);
+    }
+
+    #[test]
+    fn test_completed_node_does_not_escalate() {
+        let deadline = Utc::now();
+        let mut instance = WorkflowExecution::start(&review_definition(), DocumentId::new()).unwrap();
+        let mut ctx = WorkflowContext::new();
+        ctx.set_variable("reviewed".to_string(), serde_json::json!(true));
+        ctx.set_variable("approved".to_string(), serde_json::json!(true));
+        ctx.sla_deadlines.insert(NodeId::new("draft"), deadline);
+        ctx.sla_deadlines.insert(NodeId::new("review"), deadline);
+        ctx.escalation_rules.push(EscalationRule {
+            trigger_after: Duration::zero(),
+            escalate_to: vec![],
+            actions: vec![notify()],
+            repeat_interval: Some(Duration::hours(1)),
+        });
+        execute_transition(&mut instance, &EdgeId::new("submit"), Uuid::new_v4(), &ctx).unwrap();
+
+        // Only the node still being worked on escalates
+        let fired = fire_escalations(&instance, &mut ctx, deadline);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].parameters["node_id"], serde_json::json!("review"));
+        assert_eq!(ctx.escalations_fired.len(), 1);
+        assert_eq!(ctx.escalations_fired[0].node_id, NodeId::new("review"));
+        assert_eq!(ctx.escalations_fired[0].rule, 0);
+
+        execute_transition(&mut instance, &EdgeId::new("approve"), Uuid::new_v4(), &ctx).unwrap();
+        assert!(fire_escalations(&instance, &mut ctx, deadline + Duration::hours(2)).is_empty());
+    }
+}
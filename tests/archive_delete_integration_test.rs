@@ -24,6 +24,7 @@ async fn test_document_lifecycle() {
         reason: "End of year archival".to_string(),
         retention_days: Some(2555), // 7 years
         archived_by: Uuid::new_v4(),
+        idempotency_key: None,
     };
 
     // Verify archive command
@@ -59,6 +60,7 @@ async fn test_archive_metadata() {
         reason: "Compliance requirement".to_string(),
         retention_days: Some(3650), // 10 years for compliance
         archived_by: Uuid::new_v4(),
+        idempotency_key: None,
     };
 
     assert_eq!(archive_cmd.retention_days, Some(3650));
@@ -93,6 +95,7 @@ async fn test_archive_delete_workflow() {
         reason: "Quarterly cleanup".to_string(),
         retention_days: Some(90), // 3 months
         archived_by: admin_id,
+        idempotency_key: None,
     };
 
     // Step 2: After retention period, create deletion event
@@ -17,7 +17,7 @@ fn test_document_creation() {
     let content_cid =
         Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
 
-    let document = Document::new(document_id, info.clone(), content_cid);
+    let document = Document::new(document_id, info.clone(), content_cid).unwrap();
 
     assert_eq!(document.id(), document_id);
     assert_eq!(document.version(), 0);
@@ -69,6 +69,7 @@ fn test_document_collaboration_workflow() {
         share_with: user2,
         access_level: AccessLevel::Write,
         shared_by: user1,
+        idempotency_key: None,
     };
 
     // Add a comment
@@ -133,6 +134,7 @@ fn test_document_linking_workflow() {
             link_type: link_type.clone(),
             description: Some(format!("Document {:?} relationship", link_type)),
             linked_by: user,
+            idempotency_key: None,
         };
 
         assert!(link_cmd.description.is_some());
@@ -179,6 +181,7 @@ fn test_document_state_transitions() {
             new_state: new_state.clone(),
             reason: reason.to_string(),
             changed_by: user,
+            idempotency_key: None,
         };
 
         assert_eq!(change_cmd.new_state, new_state);
@@ -216,6 +219,8 @@ fn test_document_content_blocks() {
         content_blocks: blocks.clone(),
         change_summary: "Added financial section".to_string(),
         updated_by: user,
+        version_bump: VersionBump::Minor,
+        idempotency_key: None,
     };
 
     assert_eq!(update_cmd.content_blocks.len(), 2);
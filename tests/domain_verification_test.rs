@@ -32,6 +32,8 @@ fn test_all_commands_can_be_created() {
         }],
         change_summary: "Added introduction".to_string(),
         updated_by: user_id,
+        version_bump: VersionBump::Patch,
+        idempotency_key: None,
     };
 
     // Test ShareDocument command
@@ -40,6 +42,7 @@ fn test_all_commands_can_be_created() {
         share_with: user_id,
         access_level: AccessLevel::Read,
         shared_by: user_id,
+        idempotency_key: None,
     };
 
     // Test ChangeState command
@@ -48,6 +51,7 @@ fn test_all_commands_can_be_created() {
         new_state: DocumentState::InReview,
         reason: "Ready for review".to_string(),
         changed_by: user_id,
+        idempotency_key: None,
     };
 
     // Test ArchiveDocument command
@@ -56,6 +60,7 @@ fn test_all_commands_can_be_created() {
         reason: "No longer needed".to_string(),
         retention_days: Some(365),
         archived_by: user_id,
+        idempotency_key: None,
     };
 }
 
@@ -83,6 +88,7 @@ fn test_all_events_can_be_created() {
         change_summary: "Updated content".to_string(),
         updated_by: user_id,
         updated_at: now,
+        version: DocumentVersion::default(),
     };
 
     // Test StateChanged event
@@ -122,7 +128,7 @@ fn test_document_aggregate_operations() {
         cid::Cid::try_from("bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi").unwrap();
 
     // Create document
-    let document = Document::new(doc_id, info.clone(), content_cid);
+    let document = Document::new(doc_id, info.clone(), content_cid).unwrap();
 
     // Verify basic properties
     assert_eq!(document.id(), doc_id);
@@ -191,6 +197,7 @@ fn test_document_services() {
         }],
         category: "test".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/plain".to_string(),
     };
 
     template_service
@@ -269,7 +276,9 @@ fn test_query_handlers() {
         query: "test".to_string(),
         tags: vec![],
         mime_types: vec![],
-        limit: Some(10),
+        author_ids: vec![],
+        created_between: None,
+        pagination: SearchPagination { page: 0, size: 10 },
     };
 
     // Test GetDocument query
@@ -283,6 +292,10 @@ fn test_query_handlers() {
     let _history_query = GetDocumentHistory {
         document_id: doc_id,
         include_content_changes: true,
+        from: None,
+        to: None,
+        event_kinds: None,
+        pagination: None,
     };
 
     // Mermaid graph showing test coverage:
@@ -64,6 +64,7 @@ async fn test_search_workflow() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination::default(),
+        fuzzy: false,
     };
 
     let results = search_service.search(&query).unwrap();
@@ -79,6 +80,7 @@ async fn test_search_workflow() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination::default(),
+        fuzzy: false,
     };
 
     let title_results = search_service.search(&title_query).unwrap();
@@ -99,6 +101,7 @@ async fn test_search_workflow() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination::default(),
+        fuzzy: false,
     };
 
     let filtered_results = search_service.search(&filtered_query).unwrap();
@@ -136,6 +139,7 @@ async fn test_search_pagination() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination { page: 0, size: 5 },
+        fuzzy: false,
     };
 
     let page1_results = search_service.search(&page1_query).unwrap();
@@ -151,6 +155,7 @@ async fn test_search_pagination() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination { page: 1, size: 5 },
+        fuzzy: false,
     };
 
     let page2_results = search_service.search(&page2_query).unwrap();
@@ -199,6 +204,7 @@ async fn test_search_snippets_and_highlights() {
             direction: SortDirection::Descending,
         },
         pagination: SearchPagination::default(),
+        fuzzy: false,
     };
 
     let results = search_service.search(&query).unwrap();
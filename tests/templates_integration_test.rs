@@ -122,6 +122,7 @@ async fn test_template_workflow() {
         ],
         category: "reports".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/markdown".to_string(),
     };
 
     template_service
@@ -179,6 +180,7 @@ async fn test_template_validation() {
         ],
         category: "meetings".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/plain".to_string(),
     };
 
     template_service
@@ -221,6 +223,7 @@ async fn test_template_categories() {
         required_variables: vec![],
         category: "meetings".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/plain".to_string(),
     };
 
     let report_template = DocumentTemplate {
@@ -231,6 +234,7 @@ async fn test_template_categories() {
         required_variables: vec![],
         category: "reports".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/plain".to_string(),
     };
 
     let proposal_template = DocumentTemplate {
@@ -241,6 +245,7 @@ async fn test_template_categories() {
         required_variables: vec![],
         category: "proposals".to_string(),
         version: DocumentVersion::new(1, 0, 0),
+        mime_type: "text/plain".to_string(),
     };
 
     template_service